@@ -4,19 +4,62 @@ use std::sync::Arc;
 use dashmap::DashMap;
 use your_crate::ingest::birdeye_ws::{LiveTokenMetrics, MetricsMap};
 use your_crate::ingest::birdeye_ws::handle_payload as handle;
+use your_crate::universal::backtest::{backtest, read_inputs};
+use your_crate::universal::executor::{SimBackend, SimConfig, SimulationAction};
+use your_crate::universal::metrics::clock::{event_time, install_event_clock, replay_use_event_time, EventClock};
 
 fn metrics() -> MetricsMap { Arc::new(DashMap::new()) }
 
+/// Backtest the simulation gates over BACKTEST_INPUT_PATH (JSONL of
+/// TradeContext rows) and write the report to BACKTEST_REPORT_PATH
+/// (`.csv` for per-row CSV, anything else for JSON).
+fn run_backtest(input: &str) {
+    let inputs = read_inputs(input).expect("read backtest inputs");
+    let backend = SimBackend::new(SimConfig::from_env());
+    let report = backtest(&backend, &inputs, SimulationAction::Buy);
+    let out = std::env::var("BACKTEST_REPORT_PATH").unwrap_or_else(|_| "./backtest_report.json".into());
+    report.write_to(&out).expect("write backtest report");
+    let s = &report.summary;
+    println!(
+        "backtest complete; {} inputs, {} passed, {} rejected {:?}, filled {:.2} orders (${:.2}), est pnl ${:.2}; report at {}",
+        s.total, s.passed, s.rejected, s.rejected_by_gate, s.filled_orders, s.filled_usd, s.est_pnl_usd, out
+    );
+}
+
 fn main() {
     let path = std::env::var("REPLAY_WS_PATH").unwrap_or_else(|_| "./ws_feed.jsonl".into());
     let file = File::open(&path).expect("open replay file");
     let reader = BufReader::new(file);
     let m = metrics();
     let roll = Arc::new(DashMap::new());
+
+    // With REPLAY_USE_EVENT_TIME=1 the metrics layer reads time from this
+    // clock: each frame moves it to the frame's own timestamp, and frames
+    // without one are assigned REPLAY_STEP_SECS after the previous frame.
+    let clock = if replay_use_event_time() {
+        let start = std::env::var("REPLAY_START_TS").ok().and_then(|v| v.parse::<i64>().ok()).unwrap_or(0);
+        let clock = Arc::new(EventClock::new(start));
+        install_event_clock(clock.clone());
+        Some(clock)
+    } else {
+        None
+    };
+    let step = std::env::var("REPLAY_STEP_SECS").ok().and_then(|v| v.parse::<i64>().ok()).unwrap_or(1);
+
     for line in reader.lines() {
         if let Ok(txt) = line {
+            if let Some(clock) = &clock {
+                match event_time(&txt) {
+                    Some(ts) => clock.observe(ts),
+                    None => clock.advance(step),
+                };
+            }
             let _ = handle(&txt, &m, &roll);
         }
     }
     println!("replay complete; {} tokens cached", m.len());
-}
\ No newline at end of file
+
+    if let Ok(input) = std::env::var("BACKTEST_INPUT_PATH") {
+        run_backtest(&input);
+    }
+}
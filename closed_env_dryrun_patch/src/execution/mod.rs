@@ -1,22 +1,15 @@
 // src/execution/mod.rs
-use serde::Serialize;
+use std::sync::Arc;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum ExecutionMode {
-    DryRun,
-    Simulate,
-    Live,
-}
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::RpcSendTransactionConfig;
+use serde::Serialize;
+use solana_sdk::transaction::VersionedTransaction;
+use crate::universal::dry_run::{dry_run_buy, dry_run_sell};
+use crate::universal::gates::TradeContext;
 
-impl ExecutionMode {
-    pub fn from_env() -> Self {
-        match std::env::var("EXECUTION_MODE").unwrap_or_else(|_| "DRY_RUN".into()).to_uppercase().as_str() {
-            "LIVE" => ExecutionMode::Live,
-            "SIMULATE" => ExecutionMode::Simulate,
-            _ => ExecutionMode::DryRun,
-        }
-    }
-}
+// Shared with the rest of the crate (e.g. the withdrawal scheduler).
+pub use crate::universal::dry_run::ExecutionMode;
 
 #[derive(Debug, Serialize)]
 pub struct ExecResult {
@@ -39,26 +32,158 @@ impl TradeExecutor for DryRunExecutor {
     }
 }
 
+impl DryRunExecutor {
+    /// Paper buy: run every gate for `ctx` and, on a pass, record a simulated
+    /// position for `wallet` so `/positions` shows what the bot would hold.
+    /// Nothing is sent.
+    pub fn paper_buy(&self, wallet: &str, ctx: &TradeContext) -> ExecResult {
+        let sim = dry_run_buy(wallet, ctx);
+        let note = if sim.passed {
+            format!("paper-open fill={:.2} est_pnl={:?}", sim.est_fill_pct, sim.est_pnl_usd)
+        } else {
+            format!("rejected {:?}", sim.gate_reasons)
+        };
+        ExecResult { mode: "DRY_RUN", ok: sim.passed, tx_sig_or_reason: note }
+    }
+
+    /// Paper sell: close the simulated position for (wallet, mint).
+    pub fn paper_sell(&self, wallet: &str, mint: &str) -> ExecResult {
+        match dry_run_sell(wallet, mint) {
+            Ok(()) => ExecResult { mode: "DRY_RUN", ok: true, tx_sig_or_reason: "paper-close".into() },
+            Err(e) => ExecResult { mode: "DRY_RUN", ok: false, tx_sig_or_reason: e.to_string() },
+        }
+    }
+}
+
+/// Runs `simulateTransaction` on the serialized `VersionedTransaction`;
+/// nothing is sent.
 pub struct SimExecutor {
-    // pub rpc: solana_client::nonblocking::rpc_client::RpcClient,
+    pub rpc: Arc<RpcClient>,
 }
 #[async_trait::async_trait]
 impl TradeExecutor for SimExecutor {
     async fn execute(&self, tx_bytes: Vec<u8>) -> anyhow::Result<ExecResult> {
-        // TODO: build solana_sdk::transaction::VersionedTransaction from bytes
-        // and call rpc.simulate_transaction() here. For now, just declare as simulated.
-        let _ = tx_bytes;
-        Ok(ExecResult { mode: "SIMULATE", ok: true, tx_sig_or_reason: "simulated-ok".into() })
+        let tx: VersionedTransaction = bincode::deserialize(&tx_bytes)?;
+        let sim = self.rpc.simulate_transaction(&tx).await?.value;
+        let (ok, note) = match sim.err {
+            None => (true, format!("simulated-ok units={:?}", sim.units_consumed)),
+            Some(err) => (false, format!("simulation failed: {} logs={:?}", err, sim.logs.unwrap_or_default())),
+        };
+        Ok(ExecResult { mode: "SIMULATE", ok, tx_sig_or_reason: note })
     }
 }
 
+/// Sends the serialized `VersionedTransaction` with preflight checks on.
 pub struct LiveExecutor {
-    // pub rpc: solana_client::nonblocking::rpc_client::RpcClient,
+    pub rpc: Arc<RpcClient>,
 }
 #[async_trait::async_trait]
 impl TradeExecutor for LiveExecutor {
-    async fn execute(&self, _tx_bytes: Vec<u8>) -> anyhow::Result<ExecResult> {
-        // TODO: send via rpc.send_transaction(). Keep skip_preflight=false.
-        Ok(ExecResult { mode: "LIVE", ok: false, tx_sig_or_reason: "NOT-WIRED".into() })
+    async fn execute(&self, tx_bytes: Vec<u8>) -> anyhow::Result<ExecResult> {
+        let tx: VersionedTransaction = bincode::deserialize(&tx_bytes)?;
+        let config = RpcSendTransactionConfig { skip_preflight: false, ..RpcSendTransactionConfig::default() };
+        let sig = self.rpc.send_transaction_with_config(&tx, config).await?;
+        Ok(ExecResult { mode: "LIVE", ok: true, tx_sig_or_reason: sig.to_string() })
+    }
+}
+
+/// Both halves of a [`SimulateThenSendExecutor`] run; `live` is `None` when
+/// the simulation failed and nothing was sent.
+#[derive(Debug, Serialize)]
+pub struct SimThenSendResult {
+    pub simulation: ExecResult,
+    pub live: Option<ExecResult>,
+}
+
+/// Simulates every transaction and only forwards the same bytes to the live
+/// executor when the simulation passed, so txs that would fail never burn
+/// fees. A failed simulation is returned as-is and never falls back to send.
+pub struct SimulateThenSendExecutor<S = SimExecutor, L = LiveExecutor> {
+    pub sim: S,
+    pub live: L,
+}
+
+impl<S: TradeExecutor, L: TradeExecutor> SimulateThenSendExecutor<S, L> {
+    pub fn new(sim: S, live: L) -> Self {
+        Self { sim, live }
+    }
+
+    /// Simulate `tx_bytes`, then send them if the simulation passed.
+    pub async fn execute_both(&self, tx_bytes: Vec<u8>) -> anyhow::Result<SimThenSendResult> {
+        let simulation = self.sim.execute(tx_bytes.clone()).await?;
+        if !simulation.ok {
+            return Ok(SimThenSendResult { simulation, live: None });
+        }
+        let live = self.live.execute(tx_bytes).await?;
+        Ok(SimThenSendResult { simulation, live: Some(live) })
+    }
+}
+
+#[async_trait::async_trait]
+impl<S: TradeExecutor, L: TradeExecutor> TradeExecutor for SimulateThenSendExecutor<S, L> {
+    async fn execute(&self, tx_bytes: Vec<u8>) -> anyhow::Result<ExecResult> {
+        let res = self.execute_both(tx_bytes).await?;
+        Ok(match res.live {
+            Some(live) => ExecResult {
+                mode: "SIMULATE_THEN_LIVE",
+                ok: live.ok,
+                tx_sig_or_reason: format!("sim: {}; live: {}", res.simulation.tx_sig_or_reason, live.tx_sig_or_reason),
+            },
+            None => ExecResult {
+                mode: "SIMULATE_THEN_LIVE",
+                ok: false,
+                tx_sig_or_reason: format!("sim: {}; live: not-sent", res.simulation.tx_sig_or_reason),
+            },
+        })
+    }
+}
+
+// This module is dropped into the host crate rather than built here, so its
+// tests travel with it.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct StubExecutor {
+        ok: bool,
+        calls: AtomicUsize,
     }
-}
\ No newline at end of file
+
+    impl StubExecutor {
+        fn new(ok: bool) -> Self {
+            Self { ok, calls: AtomicUsize::new(0) }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl TradeExecutor for StubExecutor {
+        async fn execute(&self, _tx_bytes: Vec<u8>) -> anyhow::Result<ExecResult> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ExecResult { mode: "STUB", ok: self.ok, tx_sig_or_reason: format!("stub ok={}", self.ok) })
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_simulation_skips_live_send() {
+        let exec = SimulateThenSendExecutor::new(StubExecutor::new(false), StubExecutor::new(true));
+        let res = exec.execute_both(vec![1, 2, 3]).await.unwrap();
+        assert!(!res.simulation.ok);
+        assert!(res.live.is_none());
+        assert_eq!(exec.sim.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(exec.live.calls.load(Ordering::SeqCst), 0);
+
+        let summary = exec.execute(vec![1, 2, 3]).await.unwrap();
+        assert!(!summary.ok);
+        assert_eq!(exec.live.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn passed_simulation_forwards_to_live() {
+        let exec = SimulateThenSendExecutor::new(StubExecutor::new(true), StubExecutor::new(true));
+        let res = exec.execute_both(vec![1, 2, 3]).await.unwrap();
+        assert!(res.simulation.ok);
+        assert!(res.live.map(|live| live.ok).unwrap_or(false));
+        assert_eq!(exec.live.calls.load(Ordering::SeqCst), 1);
+    }
+}
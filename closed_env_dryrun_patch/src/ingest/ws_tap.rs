@@ -1,22 +1,179 @@
 // src/ingest/ws_tap.rs
-use std::fs::OpenOptions;
-use std::io::Write;
+//
+// Records raw WS frames to WS_RECORD_PATH (JSONL). The file is rotated when
+// it would exceed WS_RECORD_MAX_MB and, with WS_RECORD_DAILY=1, when the UTC
+// date changes. Rotated files are renamed to `<path>.<YYYYmmdd-HHMMSS>` and,
+// with WS_RECORD_GZIP=1, compressed in the background with the system `gzip`.
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+
+use chrono::{DateTime, NaiveDate, Utc};
 use once_cell::sync::Lazy;
 
-static REC_PATH: Lazy<String> = Lazy::new(|| std::env::var("WS_RECORD_PATH").unwrap_or_else(|_| "./ws_feed.jsonl".into()));
 static RECORD_WS: Lazy<bool> = Lazy::new(|| std::env::var("RECORD_WS").map(|s| s != "0").unwrap_or(true));
-static FILE: Lazy<Mutex<Option<std::fs::File>>> = Lazy::new(|| Mutex::new(None));
+static RECORDER: Lazy<Mutex<RotatingRecorder>> = Lazy::new(|| Mutex::new(RotatingRecorder::from_env()));
+
+fn env_flag(key: &str) -> bool {
+    std::env::var(key).map(|v| v == "1" || v.eq_ignore_ascii_case("true")).unwrap_or(false)
+}
+
+/// Append-only JSONL writer with size / daily rotation.
+pub struct RotatingRecorder {
+    path: PathBuf,
+    /// Rotate before a write would push the file past this many bytes.
+    max_bytes: Option<u64>,
+    /// Rotate when the UTC date changes.
+    daily: bool,
+    /// Gzip rotated files in the background.
+    gzip: bool,
+    file: Option<File>,
+    written: u64,
+    day: Option<NaiveDate>,
+}
+
+impl RotatingRecorder {
+    pub fn new<P: AsRef<Path>>(path: P, max_bytes: Option<u64>, daily: bool, gzip: bool) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            max_bytes: max_bytes.filter(|b| *b > 0),
+            daily,
+            gzip,
+            file: None,
+            written: 0,
+            day: None,
+        }
+    }
+
+    /// WS_RECORD_PATH (default ./ws_feed.jsonl), WS_RECORD_MAX_MB (unset or 0
+    /// = no size limit), WS_RECORD_DAILY and WS_RECORD_GZIP (default off).
+    pub fn from_env() -> Self {
+        let path = std::env::var("WS_RECORD_PATH").unwrap_or_else(|_| "./ws_feed.jsonl".into());
+        let mut max_bytes = None;
+        if let Ok(v) = std::env::var("WS_RECORD_MAX_MB") {
+            if let Ok(mb) = v.parse::<f64>() {
+                if mb > 0.0 {
+                    max_bytes = Some((mb * 1024.0 * 1024.0) as u64);
+                }
+            }
+        }
+        Self::new(path, max_bytes, env_flag("WS_RECORD_DAILY"), env_flag("WS_RECORD_GZIP"))
+    }
+
+    fn open(&mut self, now: DateTime<Utc>) -> io::Result<()> {
+        let file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        self.file = Some(file);
+        self.day = Some(now.date_naive());
+        Ok(())
+    }
+
+    /// Close the current file and move it aside with a timestamp suffix.
+    fn rotate(&mut self, now: DateTime<Utc>) -> io::Result<()> {
+        self.file = None;
+        let stamp = now.format("%Y%m%d-%H%M%S").to_string();
+        let mut rotated = PathBuf::from(format!("{}.{}", self.path.display(), stamp));
+        let mut n = 1;
+        while rotated.exists() {
+            rotated = PathBuf::from(format!("{}.{}-{}", self.path.display(), stamp, n));
+            n += 1;
+        }
+        std::fs::rename(&self.path, &rotated)?;
+        if self.gzip {
+            std::thread::spawn(move || {
+                if let Err(e) = std::process::Command::new("gzip").arg(&rotated).status() {
+                    eprintln!("ws_tap: gzip {} failed: {}", rotated.display(), e);
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Append one line at `now`, rotating first if the size limit or the
+    /// day boundary would be crossed. A single oversized line still goes
+    /// into a fresh file on its own.
+    pub fn write_line_at(&mut self, line: &str, now: DateTime<Utc>) -> io::Result<()> {
+        if self.file.is_none() {
+            self.open(now)?;
+        }
+        let len = line.len() as u64 + 1;
+        let over_size = self.max_bytes.map(|max| self.written > 0 && self.written + len > max).unwrap_or(false);
+        let new_day = self.daily && self.day != Some(now.date_naive());
+        if over_size || new_day {
+            self.rotate(now)?;
+            self.open(now)?;
+        }
+        if let Some(f) = self.file.as_mut() {
+            writeln!(f, "{}", line)?;
+            self.written += len;
+        }
+        Ok(())
+    }
+
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        self.write_line_at(line, Utc::now())
+    }
+}
 
 pub fn record_line(line: &str) {
     if !*RECORD_WS { return; }
-    let mut guard = FILE.lock().unwrap();
-    if guard.is_none() {
-        if let Ok(f) = OpenOptions::new().create(true).append(true).open(&*REC_PATH) {
-            *guard = Some(f);
-        } else { return; }
+    let mut guard = match RECORDER.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Err(e) = guard.write_line(line) {
+        eprintln!("ws_tap: failed to record frame: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ws_tap_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
     }
-    if let Some(f) = guard.as_mut() {
-        let _ = writeln!(f, "{}", line);
+
+    fn files_in(dir: &Path) -> usize {
+        std::fs::read_dir(dir).unwrap().count()
+    }
+
+    #[test]
+    fn writing_past_size_limit_creates_second_file() {
+        let dir = temp_dir("size");
+        let path = dir.join("ws_feed.jsonl");
+        let mut rec = RotatingRecorder::new(&path, Some(64), false, false);
+        let now = Utc::now();
+        let line = "x".repeat(40);
+        rec.write_line_at(&line, now).unwrap();
+        assert_eq!(files_in(&dir), 1);
+        rec.write_line_at(&line, now).unwrap();
+        assert_eq!(files_in(&dir), 2);
+        // The live file only holds the line written after rotation.
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), format!("{}\n", line));
+        // Same-second rotations don't clobber each other.
+        rec.write_line_at(&line, now).unwrap();
+        assert_eq!(files_in(&dir), 3);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn daily_rotation_starts_a_new_file_at_midnight() {
+        let dir = temp_dir("daily");
+        let path = dir.join("ws_feed.jsonl");
+        let mut rec = RotatingRecorder::new(&path, None, true, false);
+        let day1 = "2024-05-01T23:59:59Z".parse::<DateTime<Utc>>().unwrap();
+        let day2 = "2024-05-02T00:00:01Z".parse::<DateTime<Utc>>().unwrap();
+        rec.write_line_at("a", day1).unwrap();
+        rec.write_line_at("b", day1).unwrap();
+        assert_eq!(files_in(&dir), 1);
+        rec.write_line_at("c", day2).unwrap();
+        assert_eq!(files_in(&dir), 2);
+        assert!(dir.join("ws_feed.jsonl.20240502-000001").exists());
+        std::fs::remove_dir_all(&dir).ok();
     }
-}
\ No newline at end of file
+}
@@ -1,2 +1,5 @@
+pub mod send_serializer;
 pub mod token;
 pub mod tx;
+
+pub use send_serializer::{SendSerializer, GLOBAL_SEND_SERIALIZER};
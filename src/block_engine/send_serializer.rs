@@ -0,0 +1,68 @@
+//! Per-wallet send ordering.
+//!
+//! Two transactions signed by the same wallet and sent at once race each
+//! other for the blockhash and the fee payer's state, and one of them tends
+//! to come back "already processed" or fail on a stale nonce. The
+//! [`SendSerializer`] hands out one async lock per signer so sends for a
+//! wallet go out one at a time, in the order they asked, while sends for
+//! different wallets still run in parallel.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use once_cell::sync::Lazy;
+use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
+
+/// Serializer shared by the landing path.
+pub static GLOBAL_SEND_SERIALIZER: Lazy<SendSerializer> = Lazy::new(SendSerializer::new);
+
+/// Keyed map of per-wallet async locks; see the module docs.
+#[derive(Default)]
+pub struct SendSerializer {
+    locks: Mutex<HashMap<Pubkey, Arc<AsyncMutex<()>>>>,
+}
+
+impl SendSerializer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for `wallet`'s turn to send. Other sends for the wallet wait
+    /// until the returned guard is dropped; waiters are served in order.
+    pub async fn lock(&self, wallet: &Pubkey) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = match self.locks.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            // Nobody holds or waits on a lock only the map references, so
+            // it can go; the map stays as small as the set of busy wallets.
+            locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+            locks.entry(*wallet).or_default().clone()
+        };
+        lock.lock_owned().await
+    }
+
+    /// Run `send` once no other send for `wallet` is in progress.
+    pub async fn run<F, T>(&self, wallet: &Pubkey, send: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        let _turn = self.lock(wallet).await;
+        send.await
+    }
+
+    /// Wallets with a send in progress or waiting.
+    pub fn busy_wallets(&self) -> usize {
+        let locks = match self.locks.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        locks
+            .values()
+            .filter(|lock| Arc::strong_count(lock) > 1)
+            .count()
+    }
+}
@@ -3,11 +3,13 @@ use std::time::Instant;
 // Quiet planned scaffolding in minimal builds;
 // when features are enabled, clippy will check normally.
 use crate::{
+    block_engine::GLOBAL_SEND_SERIALIZER,
     common::{logger::Logger},
+    library::blockhash_cache::{BlockhashSource, GLOBAL_BLOCKHASH_CACHE},
     library::zeroslot::{self, ZeroSlotClient},
 };
 // use anchor_client::solana_client::nonblocking::rpc_client::RpcClient; // unused now
-// use anchor_client::solana_sdk::pubkey::Pubkey; // unused now
+use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::{
     instruction::Instruction, signature::Keypair, system_instruction, transaction::Transaction,
 };
@@ -45,6 +47,171 @@ fn get_unit_limit() -> u32 {
         .unwrap_or(200_000)
 }
 
+fn dynamic_priority_fee_enabled() -> bool {
+    env::var("DYNAMIC_PRIORITY_FEE")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+fn get_priority_fee_percentile() -> f64 {
+    env::var("PRIORITY_FEE_PERCENTILE")
+        .ok()
+        .and_then(|v| f64::from_str(&v).ok())
+        .map(|p| p.clamp(0.0, 100.0))
+        .unwrap_or(75.0)
+}
+
+fn get_unit_price_max() -> u64 {
+    env::var("UNIT_PRICE_MAX")
+        .ok()
+        .and_then(|v| u64::from_str(&v).ok())
+        .unwrap_or(2_000_000)
+}
+
+fn get_priority_fee_cache_ttl() -> std::time::Duration {
+    let ms = env::var("PRIORITY_FEE_CACHE_MS")
+        .ok()
+        .and_then(|v| u64::from_str(&v).ok())
+        .unwrap_or(2_000);
+    std::time::Duration::from_millis(ms)
+}
+
+/// Dynamic unit prices by writable-account set. Fees are local to the
+/// accounts a transaction locks, so a price sampled for one pool is not
+/// reused for another.
+#[derive(Debug, Default)]
+pub struct PriorityFeeCache {
+    entries: std::sync::Mutex<std::collections::HashMap<Vec<Pubkey>, (std::time::Instant, u64)>>,
+}
+
+impl PriorityFeeCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(accounts: &[Pubkey]) -> Vec<Pubkey> {
+        let mut key = accounts.to_vec();
+        key.sort_unstable();
+        key
+    }
+
+    /// Price cached for this account set within the last `ttl`.
+    pub fn get(&self, accounts: &[Pubkey], ttl: std::time::Duration) -> Option<u64> {
+        let guard = self.entries.lock().ok()?;
+        match guard.get(&Self::key(accounts)) {
+            Some((at, price)) if at.elapsed() < ttl => Some(*price),
+            _ => None,
+        }
+    }
+
+    /// Cache `price` for this account set, dropping entries older than `ttl`.
+    pub fn insert(&self, accounts: &[Pubkey], price: u64, ttl: std::time::Duration) {
+        if let Ok(mut guard) = self.entries.lock() {
+            guard.retain(|_, (at, _)| at.elapsed() < ttl);
+            guard.insert(Self::key(accounts), (std::time::Instant::now(), price));
+        }
+    }
+}
+
+// Last computed dynamic unit prices, shared across sends.
+static PRIORITY_FEE_CACHE: Lazy<PriorityFeeCache> = Lazy::new(PriorityFeeCache::new);
+
+// getRecentPrioritizationFees accepts at most 128 accounts.
+const MAX_PRIORITY_FEE_ACCOUNTS: usize = 128;
+
+/// Collect the distinct writable accounts referenced by an instruction set.
+pub fn writable_accounts(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let mut accounts: Vec<Pubkey> = Vec::new();
+    for meta in instructions.iter().flat_map(|ix| ix.accounts.iter()) {
+        if meta.is_writable && !accounts.contains(&meta.pubkey) {
+            accounts.push(meta.pubkey);
+            if accounts.len() == MAX_PRIORITY_FEE_ACCOUNTS {
+                break;
+            }
+        }
+    }
+    accounts
+}
+
+/// Nearest-rank percentile of the given fees, or None if there are none.
+pub fn percentile_fee(mut fees: Vec<u64>, percentile: f64) -> Option<u64> {
+    if fees.is_empty() {
+        return None;
+    }
+    fees.sort_unstable();
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * fees.len() as f64).ceil() as usize;
+    Some(fees[rank.saturating_sub(1).min(fees.len() - 1)])
+}
+
+/// Compute-unit price derived from recent prioritization fees for `accounts`.
+///
+/// Returns the configured percentile (PRIORITY_FEE_PERCENTILE, default p75)
+/// clamped by UNIT_PRICE_MAX. The value is cached per account set for
+/// PRIORITY_FEE_CACHE_MS so we don't pay an RPC round-trip per trade. Falls
+/// back to the static UNIT_PRICE when the RPC call fails or returns no samples.
+pub async fn dynamic_unit_price(
+    rpc_client: &anchor_client::solana_client::nonblocking::rpc_client::RpcClient,
+    accounts: &[Pubkey],
+) -> u64 {
+    let ttl = get_priority_fee_cache_ttl();
+    if let Some(price) = PRIORITY_FEE_CACHE.get(accounts, ttl) {
+        return price;
+    }
+
+    let max = get_unit_price_max();
+    let price = match rpc_client.get_recent_prioritization_fees(accounts).await {
+        Ok(fees) => {
+            let samples: Vec<u64> = fees.iter().map(|f| f.prioritization_fee).collect();
+            percentile_fee(samples, get_priority_fee_percentile()).unwrap_or_else(get_unit_price)
+        }
+        Err(_) => get_unit_price(),
+    }
+    .min(max);
+
+    PRIORITY_FEE_CACHE.insert(accounts, price, ttl);
+    price
+}
+
+/// Unit price for a send: dynamic when DYNAMIC_PRIORITY_FEE=1, static otherwise.
+async fn resolve_unit_price(
+    rpc_client: &anchor_client::solana_client::nonblocking::rpc_client::RpcClient,
+    instructions: &[Instruction],
+) -> u64 {
+    if !dynamic_priority_fee_enabled() {
+        return get_unit_price();
+    }
+    dynamic_unit_price(rpc_client, &writable_accounts(instructions)).await
+}
+
+/// Clamp a compute-unit price to MEV_CU_PRICE_MAX_LAMPORTS, logging when the
+/// cap applies.
+fn capped_unit_price(price: u64, logger: &Logger) -> u64 {
+    let capped = crate::universal::mev_submit::MEV_CONFIG.clamp_cu_price(price);
+    if capped < price {
+        logger.log(
+            format!("[MEV] compute-unit price {} clamped to cap {}", price, capped)
+                .yellow()
+                .to_string(),
+        );
+    }
+    capped
+}
+
+/// Clamp a landing tip (lamports) to MEV_TIP_CAP_LAMPORTS, logging when the
+/// cap applies.
+#[cfg(feature = "zeroslot")]
+fn capped_tip_lamports(lamports: u64, logger: &Logger) -> u64 {
+    let capped = crate::universal::mev_submit::MEV_CONFIG.clamp_tip_lamports(lamports);
+    if capped < lamports {
+        logger.log(
+            format!("[MEV] tip {} lamports clamped to cap {}", lamports, capped)
+                .yellow()
+                .to_string(),
+        );
+    }
+    capped
+}
+
 // Cache the FlashBlock API key
 #[cfg(feature="zeroslot")] static FLASHBLOCK_API_KEY: Lazy<String> = Lazy::new(|| {
     std::env::var("FLASHBLOCK_API_KEY")
@@ -57,27 +224,36 @@ static HTTP_CLIENT: Lazy<Client> = Lazy::new(|| {
     let client = reqwest::Client::new();
     client
 });
+/// Send through zeroslot with a tip; the priority fee is sampled over the
+/// normal RPC client.
 #[cfg(feature = "zeroslot")]
 pub async fn new_signed_and_send_zeroslot(
-    zeroslot_rpc_client: Arc<crate::library::zeroslot::ZeroSlotClient>,
+    app_state: &crate::common::config::AppState,
     recent_blockhash: solana_sdk::hash::Hash,
     keypair: &Keypair,
-     instructions: Vec<Instruction>,
+    mut instructions: Vec<Instruction>,
     logger: &Logger,
 ) -> Result<Vec<String>> {
+    let zeroslot_rpc_client = app_state.zeroslot_rpc_client.clone();
     let tip_account = zeroslot::get_tip_account()?;
     let start_time = Instant::now();
     let mut txs: Vec<String> = vec![];
 
     // zeroslot tip, the upper limit is 0.1
     let tip = zeroslot::get_tip_value().await?;
-    let _tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
+    let _tip_lamports = capped_tip_lamports(
+        ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS),
+        logger,
+    );
 
     let zeroslot_tip_instruction =
         system_instruction::transfer(&keypair.pubkey(), &tip_account, _tip_lamports);
 
     let unit_limit = get_unit_limit(); // TODO: update in mev boost
-    let unit_price = get_unit_price(); // TODO: update in mev boost
+    let unit_price = capped_unit_price(
+        resolve_unit_price(&app_state.rpc_nonblocking_client, &instructions).await,
+        logger,
+    ); // TODO: update in mev boost
     let modify_compute_units =
         solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(unit_limit);
     let add_priority_fee =
@@ -120,6 +296,7 @@ pub async fn new_signed_and_send_zeroslot_fast(
     _compute_unit_price: u64,
     _tip_lamports: u64,
     zeroslot_rpc_client: Arc<crate::library::zeroslot::ZeroSlotClient>,
+    rpc_client: &anchor_client::solana_client::nonblocking::rpc_client::RpcClient,
     recent_blockhash: solana_sdk::hash::Hash,
     keypair: &Keypair,
     mut instructions: Vec<Instruction>,
@@ -131,13 +308,17 @@ pub async fn new_signed_and_send_zeroslot_fast(
 
     // zeroslot tip, the upper limit is 0.1
     let tip = zeroslot::get_tip_value().await?;
-    let _tip_lamports = ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS);
+    let _tip_lamports = capped_tip_lamports(
+        ui_amount_to_amount(tip, spl_token::native_mint::DECIMALS),
+        logger,
+    );
 
     let zeroslot_tip_instruction =
         system_instruction::transfer(&keypair.pubkey(), &tip_account, _tip_lamports);
 
     let unit_limit = get_unit_limit(); // TODO: update in mev boost
-    let unit_price = get_unit_price(); // TODO: update in mev boost
+    let unit_price =
+        capped_unit_price(resolve_unit_price(rpc_client, &instructions).await, logger); // TODO: update in mev boost
     let modify_compute_units =
         solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_limit(unit_limit);
     let add_priority_fee =
@@ -174,12 +355,14 @@ pub async fn new_signed_and_send_zeroslot_fast(
     Ok(txs)
 }
 
-/// Send transaction using normal RPC without any service or tips
+/// Send transaction using normal RPC without any service or tips.
+/// Preflight runs at `commitment` (see `config::commitment_from_env`).
 pub async fn new_signed_and_send_normal(
     rpc_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
     recent_blockhash: anchor_client::solana_sdk::hash::Hash,
     keypair: &Keypair,
     mut instructions: Vec<Instruction>,
+    commitment: anchor_client::solana_sdk::commitment_config::CommitmentConfig,
     logger: &Logger,
 ) -> Result<Vec<String>> {
     let start_time = Instant::now();
@@ -194,6 +377,17 @@ pub async fn new_signed_and_send_normal(
     // instructions.insert(0, modify_compute_units);
     // instructions.insert(1, add_priority_fee);
 
+    if dynamic_priority_fee_enabled() {
+        let unit_price = capped_unit_price(
+            resolve_unit_price(&rpc_client, &instructions).await,
+            logger,
+        );
+        instructions.insert(
+            0,
+            solana_sdk::compute_budget::ComputeBudgetInstruction::set_compute_unit_price(unit_price),
+        );
+    }
+
     // Create and send transaction
     let txn = Transaction::new_signed_with_payer(
         &instructions,
@@ -202,7 +396,11 @@ pub async fn new_signed_and_send_normal(
         recent_blockhash,
     );
 
-    match rpc_client.send_transaction(&txn).await {
+    let config = anchor_client::solana_client::rpc_config::RpcSendTransactionConfig {
+        preflight_commitment: Some(commitment.commitment),
+        ..Default::default()
+    };
+    match rpc_client.send_transaction_with_config(&txn, config).await {
         Ok(signature) => {
             logger.log(
                 format!("[TXN-ELAPSED(NORMAL)]: {:?}", start_time.elapsed())
@@ -215,28 +413,160 @@ pub async fn new_signed_and_send_normal(
     }
 }
 
-/// Universal transaction landing function that routes to the appropriate service
+fn get_send_max_retries() -> usize {
+    env::var("SEND_MAX_RETRIES")
+        .ok()
+        .and_then(|v| usize::from_str(&v).ok())
+        .unwrap_or(2)
+}
+
+/// Whether a send error means the transaction's blockhash was unknown or
+/// expired, so re-signing with a fresh blockhash can succeed. Anything else
+/// (insufficient funds, program errors, ...) fails the same way on retry.
+pub fn is_blockhash_expired_error(err: &str) -> bool {
+    let err = err.to_ascii_lowercase();
+    err.contains("blockhashnotfound")
+        || err.contains("blockhash not found")
+        || err.contains("block height exceeded")
+        || err.contains("transaction expired")
+}
+
+/// Call `send` with `recent_blockhash`, and while it fails with a blockhash
+/// expiry error, with a fresh blockhash from `fresh_blockhash`, up to
+/// `max_retries` more times. Other errors are returned immediately.
+pub async fn send_with_blockhash_retry<S, SFut, B, BFut>(
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    max_retries: usize,
+    mut fresh_blockhash: B,
+    mut send: S,
+) -> Result<Vec<String>>
+where
+    S: FnMut(anchor_client::solana_sdk::hash::Hash) -> SFut,
+    SFut: std::future::Future<Output = Result<Vec<String>>>,
+    B: FnMut() -> BFut,
+    BFut: std::future::Future<Output = Result<anchor_client::solana_sdk::hash::Hash>>,
+{
+    let mut blockhash = recent_blockhash;
+    let mut retries = 0;
+    loop {
+        match send(blockhash).await {
+            Ok(signatures) => return Ok(signatures),
+            Err(e) if retries < max_retries && is_blockhash_expired_error(&e.to_string()) => {
+                retries += 1;
+                blockhash = fresh_blockhash().await?;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// [`new_signed_and_send_normal`], re-signed with a freshly fetched blockhash
+/// and resent up to SEND_MAX_RETRIES (default 2) times when the blockhash is
+/// rejected as unknown or expired.
+pub async fn new_signed_and_send_normal_with_retry(
+    rpc_client: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &Keypair,
+    instructions: Vec<Instruction>,
+    commitment: anchor_client::solana_sdk::commitment_config::CommitmentConfig,
+    logger: &Logger,
+) -> Result<Vec<String>> {
+    let rpc = &rpc_client;
+    send_with_blockhash_retry(
+        recent_blockhash,
+        get_send_max_retries(),
+        || async move {
+            logger.log("Blockhash expired; re-signing with a fresh one".yellow().to_string());
+            // Bypass the cache: the cached hash may be the one that just expired.
+            let info = rpc
+                .fetch_blockhash()
+                .await
+                .map_err(|e| anyhow!("Failed to refresh blockhash: {}", e))?;
+            GLOBAL_BLOCKHASH_CACHE.record(info);
+            Ok(info.hash)
+        },
+        |blockhash| {
+            new_signed_and_send_normal(
+                rpc.clone(),
+                blockhash,
+                keypair,
+                instructions.clone(),
+                commitment,
+                logger,
+            )
+        },
+    )
+    .await
+}
+
+/// Which landing path a transaction went through, where, and how long it took.
+#[derive(Clone, Debug)]
+pub struct LandingOutcome {
+    /// Path actually used (may differ from the requested mode, e.g. when
+    /// zeroslot is compiled out).
+    pub mode: crate::common::config::TransactionLandingMode,
+    pub signatures: Vec<String>,
+    pub elapsed: std::time::Duration,
+    pub endpoint: String,
+}
+
+/// Await `send` and record its signatures and timing under `mode` / `endpoint`.
+pub async fn record_landing<F>(
+    mode: crate::common::config::TransactionLandingMode,
+    endpoint: String,
+    send: F,
+) -> anyhow::Result<LandingOutcome>
+where
+    F: std::future::Future<Output = anyhow::Result<Vec<String>>>,
+{
+    let started = Instant::now();
+    // A graceful shutdown waits for this send before exiting
+    let in_flight = crate::universal::shutdown::track_send();
+    let result = send.await;
+    drop(in_flight);
+    // Consecutive failed sends trip the circuit breaker
+    crate::universal::control::record_send_result(result.is_ok());
+    let signatures = result?;
+    Ok(LandingOutcome {
+        mode,
+        signatures,
+        elapsed: started.elapsed(),
+        endpoint,
+    })
+}
+
+/// Universal transaction landing function that routes to the appropriate
+/// service and reports which path was used.
 #[cfg(feature="zeroslot")]
-pub async fn new_signed_and_send_with_landing_mode(
+pub async fn new_signed_and_send_with_landing_outcome(
     transaction_landing_mode: TransactionLandingMode,
     app_state: &crate::common::config::AppState,
     recent_blockhash: anchor_client::solana_sdk::hash::Hash,
     keypair: &Keypair,
     instructions: Vec<Instruction>,
     logger: &Logger,
-) -> Result<Vec<String>> {
+) -> Result<LandingOutcome> {
+    // Sends signed by the same wallet go out one at a time
+    let _turn = GLOBAL_SEND_SERIALIZER.lock(&keypair.pubkey()).await;
     // Route to the appropriate service
     match transaction_landing_mode {
         TransactionLandingMode::Zeroslot => {
             logger.log("Using Zeroslot for transaction landing".green().to_string());
-            new_signed_and_send_zeroslot(
-                &app_state,
-                recent_blockhash,
-                keypair,
-                instructions,
-                logger,
+            let outcome = record_landing(
+                TransactionLandingMode::Zeroslot,
+                zeroslot::ZERO_SLOT_URL.to_string(),
+                new_signed_and_send_zeroslot(
+                    &app_state,
+                    recent_blockhash,
+                    keypair,
+                    instructions,
+                    logger,
+                ),
             )
-            .await
+            .await;
+            // Feed the adaptive tip with whether the tipped send went through
+            crate::universal::mev::record_land_outcome(outcome.is_ok());
+            outcome
         }
         TransactionLandingMode::Normal => {
             logger.log(
@@ -244,12 +574,17 @@ pub async fn new_signed_and_send_with_landing_mode(
                     .green()
                     .to_string(),
             );
-            new_signed_and_send_normal(
-                app_state.rpc_nonblocking_client.clone(),
-                recent_blockhash,
-                keypair,
-                instructions,
-                logger,
+            record_landing(
+                TransactionLandingMode::Normal,
+                app_state.rpc_nonblocking_client.url(),
+                new_signed_and_send_normal_with_retry(
+                    app_state.rpc_nonblocking_client.clone(),
+                    recent_blockhash,
+                    keypair,
+                    instructions,
+                    app_state.rpc_nonblocking_client.commitment(),
+                    logger,
+                ),
             )
             .await
         }
@@ -257,22 +592,55 @@ pub async fn new_signed_and_send_with_landing_mode(
 }
 
 #[cfg(not(feature = "zeroslot"))]
-pub async fn new_signed_and_send_with_landing_mode(
+pub async fn new_signed_and_send_with_landing_outcome(
     _transaction_landing_mode: crate::common::config::TransactionLandingMode,
     app_state: &crate::common::config::AppState,
     recent_blockhash: anchor_client::solana_sdk::hash::Hash,
     keypair: &anchor_client::solana_sdk::signature::Keypair,
     instructions: Vec<anchor_client::solana_sdk::instruction::Instruction>,
     logger: &crate::common::logger::Logger,
-) -> anyhow::Result<Vec<String>> {
+) -> anyhow::Result<LandingOutcome> {
     logger.log("Zeroslot disabled; using normal RPC landing".to_string());
-    new_signed_and_send_normal(
-        app_state.rpc_nonblocking_client.clone(),
+    // Sends signed by the same wallet go out one at a time
+    let _turn = GLOBAL_SEND_SERIALIZER.lock(&keypair.pubkey()).await;
+    record_landing(
+        crate::common::config::TransactionLandingMode::Normal,
+        app_state.rpc_nonblocking_client.url(),
+        new_signed_and_send_normal_with_retry(
+            app_state.rpc_nonblocking_client.clone(),
+            recent_blockhash,
+            keypair,
+            instructions,
+            app_state.rpc_nonblocking_client.commitment(),
+            logger,
+        ),
+    ).await
+}
+
+/// Thin wrapper over [`new_signed_and_send_with_landing_outcome`] for callers
+/// that only need the signatures.
+pub async fn new_signed_and_send_with_landing_mode(
+    transaction_landing_mode: crate::common::config::TransactionLandingMode,
+    app_state: &crate::common::config::AppState,
+    recent_blockhash: anchor_client::solana_sdk::hash::Hash,
+    keypair: &anchor_client::solana_sdk::signature::Keypair,
+    instructions: Vec<anchor_client::solana_sdk::instruction::Instruction>,
+    logger: &crate::common::logger::Logger,
+) -> anyhow::Result<Vec<String>> {
+    let outcome = new_signed_and_send_with_landing_outcome(
+        transaction_landing_mode,
+        app_state,
         recent_blockhash,
         keypair,
         instructions,
         logger,
-    ).await
+    )
+    .await?;
+    logger.log(format!(
+        "[LANDING] mode={:?} elapsed={:?}",
+        outcome.mode, outcome.elapsed
+    ));
+    Ok(outcome.signatures)
 }
 
 #[cfg(not(feature = "zeroslot"))]
@@ -289,6 +657,7 @@ pub async fn new_signed_and_send_zeroslot(
         recent_blockhash,
         keypair,
         instructions,
+        _app_state.rpc_nonblocking_client.commitment(),
         logger,
     ).await
 }
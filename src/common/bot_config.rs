@@ -0,0 +1,287 @@
+//! Unified bot configuration: environment, TOML file and defaults.
+//!
+//! Settings can come from a top-level TOML file at BOT_CONFIG_PATH as well
+//! as from the environment. Precedence is **env > file > default**: an env
+//! var always wins, a key in the file is used when the env var is unset, and
+//! each subsystem's own default applies when neither is set.
+//!
+//! The file has one section per subsystem. Keys are the env var names in
+//! lower case, without the section prefix for `[withdraw]` (WITHDRAW_) and
+//! `[rpc]` (RPC_); arrays are joined with commas:
+//!
+//! ```toml
+//! [gates]
+//! liq_5m_min_usd = 20000
+//! cooldown_secs = 30
+//!
+//! [mev]
+//! mev_route = "jito"
+//!
+//! [withdraw]
+//! destination = "<vault pubkey>"
+//! threshold_usd = 250
+//!
+//! [rpc]
+//! endpoints = ["us|https://a.example", "eu|https://b.example"]
+//!
+//! [wallets.<pubkey>]
+//! slippage = 0.02
+//! ```
+//!
+//! Unknown sections or keys are rejected, so a typo fails loudly instead of
+//! silently falling back to a default. `[wallets]` has the wallet-config
+//! shape and is used when neither WALLET_CONFIG_TOML nor WALLET_CONFIG_PATH
+//! is set.
+//!
+//! This module only resolves names to values. The subsystems' `from_env`
+//! constructors read through [`var`], so they see the file too; those that
+//! can reject a bad setting also expose a strict `from_config`. [`load`]
+//! installs the file up front.
+
+use std::collections::HashMap;
+use std::env::{self, VarError};
+use std::fs;
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+
+/// File sections, the prefix their keys drop and the env vars they may set.
+const SECTIONS: [(&str, &str, &[&str]); 4] = [
+    (
+        "gates",
+        "",
+        &[
+            "LIQ_5M_MIN_USD",
+            "LIQ_15M_MIN_USD",
+            "MIN_MCAP_USD",
+            "MCAP_MIN_USD",
+            "AMIHUD_MAX",
+            "RANGE_EFF_MIN",
+            "METRICS_MAX_AGE_SECS",
+            "MAX_SIZE_OVER_LIQ",
+            "MAX_IMPACT_BPS",
+            "LIQ_SCORE_MIN_USD",
+            "LIQ_SCORE_W5M",
+            "LIQ_SCORE_W15M",
+            "MIN_VOL_LIQ_RATIO",
+            "DEPTH_MULT",
+            "VOL_MAX_PCT",
+            "EXCLUDE_NON_MIGRATED",
+            "DECISION_DEADLINE_MS",
+            "COOLDOWN_SECS",
+            "MAX_POSITIONS_PER_MINT",
+            "MAX_OPEN_POSITIONS",
+            "MAX_DAILY_LOSS_USD",
+            "MIN_TOKEN_AGE_SECS",
+            "CURVE_PROGRESS_MIN_PCT",
+            "CURVE_PROGRESS_MAX_PCT",
+            "DUP_POSITION_BLOCK",
+            "REQUIRE_RENOUNCED",
+            "MAX_TOP10_HOLDER_PCT",
+            "MAX_BUYS_PER_MIN",
+            "TRANSFER_HOOK_ALLOWLIST",
+            "LIQUIDITY_GATE_ENABLED",
+            "MCAP_GATE_ENABLED",
+            "VOLATILITY_GATE_ENABLED",
+            "PUMPFUN_MIGRATION_GATE_ENABLED",
+            "CURVE_PROGRESS_GATE_ENABLED",
+            "TRANSFER_HOOK_GATE_ENABLED",
+            "BLACKLIST_GATE_ENABLED",
+            "COOLDOWN_GATE_ENABLED",
+            "MINT_CONCURRENCY_GATE_ENABLED",
+            "DUPLICATE_POSITION_GATE_ENABLED",
+            "MIN_AGE_GATE_ENABLED",
+            "HOLDER_CONCENTRATION_GATE_ENABLED",
+            "MINT_AUTHORITY_GATE_ENABLED",
+            "DAILY_LOSS_GATE_ENABLED",
+            "BUY_RATE_GATE_ENABLED",
+        ],
+    ),
+    (
+        "mev",
+        "",
+        &[
+            "MEV_ROUTE",
+            "MEV_CU_PRICE_MAX_LAMPORTS",
+            "MEV_TIP_CAP_LAMPORTS",
+            "PRIVATE_RPC_URL",
+            "JITO_BLOCK_ENGINE_URL",
+            "SANDWICH_DETECT",
+            "SANDWICH_MIN_ADVERSE_BPS",
+            "SANDWICH_ESCALATE_AFTER",
+            "ADAPTIVE_TIP",
+            "ADAPTIVE_TIP_WINDOW",
+            "ADAPTIVE_TIP_MIN_SAMPLES",
+            "ADAPTIVE_TIP_TARGET_RATE",
+            "ADAPTIVE_TIP_RELAX_RATE",
+            "ADAPTIVE_TIP_STEP_PCT",
+            "ADAPTIVE_TIP_MIN",
+            "MEV_TIP_CAP",
+        ],
+    ),
+    (
+        "withdraw",
+        "WITHDRAW_",
+        &[
+            "WITHDRAW_DESTINATION",
+            "WITHDRAW_THRESHOLD_USD",
+            "WITHDRAW_RETAIN_USD",
+            "WITHDRAW_INTERVAL_SECS",
+            "WITHDRAW_OUTPUT",
+            "WITHDRAW_SLIPPAGE_BPS",
+        ],
+    ),
+    (
+        "rpc",
+        "RPC_",
+        &[
+            "RPC_ENDPOINTS",
+            "RPC_HTTP",
+            "RPC_LOCAL_REGION",
+            "RPC_RETRY_ATTEMPTS",
+            "RPC_TIMEOUT_SECONDS",
+            "RPC_COMMITMENT",
+            "RPC_BACKOFF_BASE_MS",
+            "RPC_BACKOFF_MAX_MS",
+            "RPC_BACKOFF_BUDGET_MS",
+        ],
+    ),
+];
+
+/// Settings read from a bot config file, keyed by env var name.
+#[derive(Debug, Clone, Default)]
+pub struct FileSettings {
+    vars: HashMap<String, String>,
+    /// The `[wallets]` section, re-serialized in the wallet-config shape.
+    wallets_toml: Option<String>,
+}
+
+impl FileSettings {
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Parse a bot config file. Fails on invalid TOML and on unknown
+    /// sections or keys.
+    pub fn from_toml_str(raw: &str) -> Result<Self, String> {
+        let table = raw
+            .parse::<toml::Table>()
+            .map_err(|e| format!("invalid bot config TOML: {}", e))?;
+        let mut settings = Self::empty();
+        for (section, value) in table {
+            if section == "wallets" {
+                let mut wrapper = toml::Table::new();
+                wrapper.insert(section, value);
+                let raw = toml::to_string(&wrapper)
+                    .map_err(|e| format!("bot config [wallets]: {}", e))?;
+                settings.wallets_toml = Some(raw);
+                continue;
+            }
+            let (_, prefix, names) = SECTIONS
+                .iter()
+                .find(|(name, _, _)| *name == section)
+                .ok_or_else(|| format!("bot config: unknown section [{}]", section))?;
+            let entries = match value {
+                toml::Value::Table(entries) => entries,
+                _ => return Err(format!("bot config: [{}] must be a table", section)),
+            };
+            for (key, value) in entries {
+                let name = format!("{}{}", prefix, key.to_ascii_uppercase());
+                if !names.contains(&name.as_str()) {
+                    return Err(format!("bot config: unknown key {}.{}", section, key));
+                }
+                let value = scalar(&value).ok_or_else(|| {
+                    format!("bot config: {}.{} must be a scalar or array", section, key)
+                })?;
+                settings.vars.insert(name, value);
+            }
+        }
+        Ok(settings)
+    }
+
+    /// Read and parse the file at `path`.
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        let raw = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read BOT_CONFIG_PATH {}: {}", path, e))?;
+        Self::from_toml_str(&raw)
+    }
+
+    /// The file named by BOT_CONFIG_PATH; empty when it is unset.
+    pub fn from_env_path() -> Result<Self, String> {
+        match env::var("BOT_CONFIG_PATH") {
+            Ok(path) if !path.trim().is_empty() => Self::from_path(path.trim()),
+            _ => Ok(Self::empty()),
+        }
+    }
+
+    /// Value the file sets for env var `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    /// The `[wallets]` section as wallet-config TOML, if present.
+    pub fn wallets_toml(&self) -> Option<&str> {
+        self.wallets_toml.as_deref()
+    }
+}
+
+/// A TOML value as the string its env var would hold.
+fn scalar(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(n) => Some(n.to_string()),
+        toml::Value::Float(f) => Some(f.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        toml::Value::Array(items) => {
+            let parts: Option<Vec<String>> = items.iter().map(scalar).collect();
+            parts.map(|p| p.join(","))
+        }
+        _ => None,
+    }
+}
+
+/// File settings consulted by [`var`], loaded from BOT_CONFIG_PATH on first
+/// use. A missing or invalid file is logged and treated as empty.
+static FILE_SETTINGS: Lazy<RwLock<Arc<FileSettings>>> = Lazy::new(|| {
+    let settings = FileSettings::from_env_path().unwrap_or_else(|e| {
+        eprintln!("{}; ignoring bot config file", e);
+        FileSettings::empty()
+    });
+    RwLock::new(Arc::new(settings))
+});
+
+/// Replace the file settings [`var`] falls back to.
+pub fn install_file_settings(settings: FileSettings) {
+    let settings = Arc::new(settings);
+    match FILE_SETTINGS.write() {
+        Ok(mut guard) => *guard = settings,
+        Err(poisoned) => *poisoned.into_inner() = settings,
+    }
+}
+
+/// The file settings currently in effect.
+pub fn file_settings() -> Arc<FileSettings> {
+    match FILE_SETTINGS.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Drop-in for [`std::env::var`] that falls back to the bot config file:
+/// the env var if set, else the file's value, else `Err(NotPresent)`.
+pub fn var(name: &str) -> Result<String, VarError> {
+    match env::var(name) {
+        Err(VarError::NotPresent) => file_settings()
+            .get(name)
+            .map(String::from)
+            .ok_or(VarError::NotPresent),
+        other => other,
+    }
+}
+
+/// Load BOT_CONFIG_PATH (if set) and install it for [`var`]. Unlike the
+/// lenient lazy load, an unreadable or invalid file is an error here.
+pub fn load() -> Result<Arc<FileSettings>, String> {
+    install_file_settings(FileSettings::from_env_path()?);
+    Ok(file_settings())
+}
@@ -1,6 +1,8 @@
+use anchor_client::solana_client::rpc_client::RpcClient;
 use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
 use lazy_static::lazy_static;
-use spl_token_2022::extension::StateWithExtensionsOwned;
+use spl_token_2022::extension::{StateWithExtensions, StateWithExtensionsOwned};
 use spl_token_2022::state::{Account, Mint};
 use std::collections::{HashMap, HashSet};
 use std::sync::RwLock;
@@ -118,6 +120,120 @@ impl TokenMintCache {
     }
 }
 
+/// Decimals and owning token program of a mint.
+///
+/// Everything that mixes raw (base-unit) amounts with UI amounts should go
+/// through these helpers rather than assuming 9 decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintInfo {
+    pub decimals: u8,
+    /// SPL Token or Token-2022 program that owns the mint.
+    pub program: Pubkey,
+}
+
+impl MintInfo {
+    pub fn new(decimals: u8, program: Pubkey) -> Self {
+        Self { decimals, program }
+    }
+
+    /// Parse a mint account owned by `program` (legacy SPL or Token-2022).
+    pub fn from_account(program: Pubkey, data: &[u8]) -> Option<Self> {
+        let state = StateWithExtensions::<Mint>::unpack(data).ok()?;
+        Some(Self::new(state.base.decimals, program))
+    }
+
+    fn scale(&self) -> f64 {
+        10f64.powi(self.decimals as i32)
+    }
+
+    /// Raw base units to UI amount.
+    pub fn ui_amount(&self, raw: u64) -> f64 {
+        raw as f64 / self.scale()
+    }
+
+    /// UI amount to raw base units (rounded down, saturating).
+    pub fn raw_amount(&self, ui: f64) -> u64 {
+        if !ui.is_finite() || ui <= 0.0 {
+            return 0;
+        }
+        (ui * self.scale()).floor() as u64
+    }
+
+    /// Price per UI token when `quote_amount` (already in quote UI units)
+    /// buys `raw_token_amount` base units. `None` for a zero amount.
+    pub fn ui_price(&self, quote_amount: f64, raw_token_amount: u64) -> Option<f64> {
+        if raw_token_amount == 0 {
+            return None;
+        }
+        Some(quote_amount / self.ui_amount(raw_token_amount))
+    }
+}
+
+/// Mint decimals/program cache. Decimals never change after a mint is
+/// created, so entries are fetched once and kept for the life of the process.
+pub struct MintInfoCache {
+    mints: RwLock<HashMap<Pubkey, MintInfo>>,
+}
+
+impl Default for MintInfoCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MintInfoCache {
+    pub fn new() -> Self {
+        Self {
+            mints: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, mint: &Pubkey) -> Option<MintInfo> {
+        match self.mints.read() {
+            Ok(mints) => mints.get(mint).copied(),
+            Err(poisoned) => poisoned.into_inner().get(mint).copied(),
+        }
+    }
+
+    pub fn insert(&self, mint: Pubkey, info: MintInfo) {
+        match self.mints.write() {
+            Ok(mut mints) => mints.insert(mint, info),
+            Err(poisoned) => poisoned.into_inner().insert(mint, info),
+        };
+    }
+
+    /// Cached info for `mint`, calling `fetch` only on a miss.
+    pub fn get_or_insert_with<F>(&self, mint: &Pubkey, fetch: F) -> Result<MintInfo>
+    where
+        F: FnOnce() -> Result<MintInfo>,
+    {
+        if let Some(info) = self.get(mint) {
+            return Ok(info);
+        }
+        let info = fetch()?;
+        self.insert(*mint, info);
+        Ok(info)
+    }
+
+    /// Cached info for `mint`, reading the mint account over RPC on a miss.
+    pub fn get_or_fetch(&self, rpc_client: &RpcClient, mint: &Pubkey) -> Result<MintInfo> {
+        self.get_or_insert_with(mint, || {
+            let account = rpc_client
+                .get_account(mint)
+                .map_err(|e| anyhow!("Failed to get mint account {}: {}", mint, e))?;
+            MintInfo::from_account(account.owner, &account.data)
+                .ok_or_else(|| anyhow!("Failed to parse mint account {}", mint))
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        match self.mints.read() {
+            Ok(mints) => mints.len(),
+            Err(poisoned) => poisoned.into_inner().len(),
+        }
+    }
+}
+
 /// Simple wallet token account tracker
 pub struct WalletTokenAccounts {
     accounts: RwLock<HashSet<Pubkey>>,
@@ -166,4 +282,5 @@ lazy_static! {
     pub static ref TOKEN_ACCOUNT_CACHE: TokenAccountCache = TokenAccountCache::new(60); // 60 seconds TTL
     pub static ref TOKEN_MINT_CACHE: TokenMintCache = TokenMintCache::new(300); // 5 minutes TTL
     pub static ref WALLET_TOKEN_ACCOUNTS: WalletTokenAccounts = WalletTokenAccounts::new();
+    pub static ref MINT_INFO_CACHE: MintInfoCache = MintInfoCache::new(); // never expires
 }
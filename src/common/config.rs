@@ -59,6 +59,27 @@ impl FromStr for TransactionLandingMode {
 
 use std::str::FromStr;
 
+/// Landing mode for copies of `wallet`: its wallet-config `landing_mode`
+/// override if set and valid, else `default`. Once our buys have been
+/// sandwiched SANDWICH_ESCALATE_AFTER times, every copy goes through zeroslot.
+pub fn landing_mode_for_wallet(
+    wallet: Option<&str>,
+    default: TransactionLandingMode,
+) -> TransactionLandingMode {
+    if crate::universal::mev::SANDWICH_DETECTOR.prefer_private_landing() {
+        return TransactionLandingMode::Zeroslot;
+    }
+    let configured = wallet.and_then(|w| {
+        crate::universal::wallet_config::get_wallet_config_map()
+            .get(w)
+            .and_then(|cfg| cfg.landing_mode.clone())
+    });
+    match configured {
+        Some(mode) => mode.parse().unwrap_or(default),
+        None => default,
+    }
+}
+
 
 #[derive(Clone, Debug)]
 pub struct SimulationConfig {
@@ -191,6 +212,7 @@ impl Config {
                     slippage_input
                 };
                 let solana_price = create_coingecko_proxy().await.unwrap_or(200_f64);
+                crate::common::quote::set_sol_usd_price(solana_price);
                 let rpc_client = create_rpc_client().unwrap();
                 let rpc_nonblocking_client = create_nonblocking_rpc_client().await.unwrap();
                 let zeroslot_rpc_client = create_zeroslot_rpc_client().await.unwrap();
@@ -407,6 +429,29 @@ pub fn get_zero_slot_health_url() -> String {
     })
 }
 
+/// Parse a commitment level (processed | confirmed | finalized).
+pub fn parse_commitment(s: &str) -> Option<CommitmentConfig> {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "processed" => Some(CommitmentConfig::processed()),
+        "confirmed" => Some(CommitmentConfig::confirmed()),
+        "finalized" => Some(CommitmentConfig::finalized()),
+        _ => None,
+    }
+}
+
+/// Commitment for the RPC clients and the normal send path, from COMMITMENT
+/// (processed | confirmed | finalized, default confirmed). `processed` lands
+/// faster but may be rolled back; `finalized` is safest and slowest.
+pub fn commitment_from_env() -> CommitmentConfig {
+    if let Ok(v) = env::var("COMMITMENT") {
+        match parse_commitment(&v) {
+            Some(commitment) => return commitment,
+            None => eprintln!("Invalid COMMITMENT: {}, using confirmed", v),
+        }
+    }
+    CommitmentConfig::confirmed()
+}
+
 pub fn create_rpc_client() -> Result<Arc<anchor_client::solana_client::rpc_client::RpcClient>> {
     let rpc_http = import_env_var("RPC_HTTP");
     let timeout = Duration::from_secs(30); // 30 second timeout
@@ -414,7 +459,7 @@ pub fn create_rpc_client() -> Result<Arc<anchor_client::solana_client::rpc_clien
         anchor_client::solana_client::rpc_client::RpcClient::new_with_timeout_and_commitment(
             rpc_http,
             timeout,
-            CommitmentConfig::processed(),
+            commitment_from_env(),
         );
     Ok(Arc::new(rpc_client))
 }
@@ -426,7 +471,7 @@ pub async fn create_nonblocking_rpc_client(
     let rpc_client = anchor_client::solana_client::nonblocking::rpc_client::RpcClient::new_with_timeout_and_commitment(
         rpc_http,
         timeout,
-        CommitmentConfig::processed(),
+        commitment_from_env(),
     );
     Ok(Arc::new(rpc_client))
 }
@@ -1,5 +1,9 @@
+pub mod bot_config;
 pub mod cache;
 pub mod config;
 pub mod constants;
 pub mod logger;
+pub mod price_oracle;
+pub mod quote;
+pub mod rpc_failover;
 pub mod timeseries;
@@ -0,0 +1,357 @@
+//! SOL/USD price oracle for USD conversions.
+//!
+//! Liquidity, market cap and withdrawal thresholds are in USD, but pool
+//! prices and balances are measured in SOL. [`SOL_USD_ORACLE`] is the one
+//! place converting between them: it polls a [`PriceFeed`] (Pyth Hermes by
+//! default, or Birdeye), caches the price for SOL_USD_TTL_SECS and serves it
+//! until it is older than SOL_USD_MAX_STALENESS_SECS. A stale or missing
+//! price falls back to SOL_USD_FALLBACK when that is set.
+//!
+//! [`crate::common::quote::sol_usd_price`] reads through the oracle, so
+//! quote conversions and the gates see the same price.
+
+use std::env;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::common::quote::WSOL_MINT;
+
+/// Pyth price feed id of SOL/USD.
+pub const PYTH_SOL_USD_FEED_ID: &str =
+    "ef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
+const PYTH_LATEST_URL: &str = "https://hermes.pyth.network/v2/updates/price/latest";
+const BIRDEYE_PRICE_URL: &str = "https://public-api.birdeye.so/defi/price";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default time a fetched price is reused before refetching.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(10);
+/// Default age after which a cached price is no longer served.
+pub const DEFAULT_MAX_STALENESS: Duration = Duration::from_secs(120);
+
+/// A source of the SOL/USD price.
+pub trait PriceFeed: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn fetch(&self) -> BoxFuture<'_, Result<f64>>;
+}
+
+#[derive(Debug, Deserialize)]
+struct PythResponse {
+    #[serde(default)]
+    parsed: Vec<PythUpdate>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PythUpdate {
+    price: PythPrice,
+}
+
+#[derive(Debug, Deserialize)]
+struct PythPrice {
+    price: String,
+    expo: i32,
+}
+
+/// Price from a Hermes `/v2/updates/price/latest` body (`price * 10^expo`).
+pub fn parse_pyth_price(body: &str) -> Result<f64> {
+    let resp: PythResponse =
+        serde_json::from_str(body).map_err(|e| anyhow!("pyth: invalid body: {}", e))?;
+    let update = resp
+        .parsed
+        .first()
+        .ok_or_else(|| anyhow!("pyth: no parsed price"))?;
+    let raw = update
+        .price
+        .price
+        .parse::<i64>()
+        .map_err(|e| anyhow!("pyth: invalid price {:?}: {}", update.price.price, e))?;
+    valid_price(raw as f64 * 10f64.powi(update.price.expo), "pyth")
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyePriceResponse {
+    #[serde(default)]
+    success: bool,
+    data: Option<BirdeyePrice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyePrice {
+    value: Option<f64>,
+}
+
+/// Price from a Birdeye `/defi/price` body.
+pub fn parse_birdeye_price(body: &str) -> Result<f64> {
+    let resp: BirdeyePriceResponse =
+        serde_json::from_str(body).map_err(|e| anyhow!("birdeye: invalid price body: {}", e))?;
+    if !resp.success {
+        return Err(anyhow!("birdeye: price request failed"));
+    }
+    let value = resp
+        .data
+        .and_then(|d| d.value)
+        .ok_or_else(|| anyhow!("birdeye: no price value"))?;
+    valid_price(value, "birdeye")
+}
+
+fn valid_price(price: f64, source: &str) -> Result<f64> {
+    if price.is_finite() && price > 0.0 {
+        Ok(price)
+    } else {
+        Err(anyhow!("{}: invalid SOL/USD price {}", source, price))
+    }
+}
+
+static HTTP: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_else(|_| Client::new())
+});
+
+async fn get_body(request: reqwest::RequestBuilder, source: &str) -> Result<String> {
+    let resp = request
+        .send()
+        .await
+        .map_err(|e| anyhow!("{}: request failed: {}", source, e))?;
+    let status = resp.status();
+    let body = resp
+        .text()
+        .await
+        .map_err(|e| anyhow!("{}: reading response failed: {}", source, e))?;
+    if !status.is_success() {
+        return Err(anyhow!("{}: HTTP {}: {}", source, status, body));
+    }
+    Ok(body)
+}
+
+/// SOL/USD from the Pyth Hermes API.
+#[derive(Clone, Debug, Default)]
+pub struct PythFeed;
+
+impl PriceFeed for PythFeed {
+    fn name(&self) -> &'static str {
+        "pyth"
+    }
+
+    fn fetch(&self) -> BoxFuture<'_, Result<f64>> {
+        Box::pin(async move {
+            let request = HTTP
+                .get(PYTH_LATEST_URL)
+                .query(&[("ids[]", PYTH_SOL_USD_FEED_ID)]);
+            parse_pyth_price(&get_body(request, "pyth").await?)
+        })
+    }
+}
+
+/// SOL/USD from Birdeye's price endpoint (API key from BIRDEYE_API_KEY).
+#[derive(Clone, Debug)]
+pub struct BirdeyeFeed {
+    api_key: String,
+}
+
+impl BirdeyeFeed {
+    pub fn new(api_key: String) -> Self {
+        Self { api_key }
+    }
+}
+
+impl PriceFeed for BirdeyeFeed {
+    fn name(&self) -> &'static str {
+        "birdeye"
+    }
+
+    fn fetch(&self) -> BoxFuture<'_, Result<f64>> {
+        Box::pin(async move {
+            let request = HTTP
+                .get(BIRDEYE_PRICE_URL)
+                .query(&[("address", WSOL_MINT)])
+                .header("X-API-KEY", &self.api_key)
+                .header("x-chain", "solana")
+                .header("accept", "application/json");
+            parse_birdeye_price(&get_body(request, "birdeye").await?)
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Cached {
+    price: f64,
+    at: Instant,
+}
+
+/// Cached SOL/USD price with a refresh TTL, a staleness bound and an
+/// optional fallback.
+pub struct PriceOracle {
+    feed: Option<Arc<dyn PriceFeed>>,
+    ttl: Duration,
+    max_staleness: Duration,
+    fallback: Option<f64>,
+    cached: RwLock<Option<Cached>>,
+}
+
+impl PriceOracle {
+    /// An oracle polling `feed`; without a feed it only serves recorded
+    /// prices and the fallback.
+    pub fn new(
+        feed: Option<Arc<dyn PriceFeed>>,
+        ttl: Duration,
+        max_staleness: Duration,
+        fallback: Option<f64>,
+    ) -> Self {
+        Self {
+            feed,
+            ttl,
+            max_staleness,
+            fallback: fallback.filter(|p| p.is_finite() && *p > 0.0),
+            cached: RwLock::new(None),
+        }
+    }
+
+    /// Build from the environment:
+    ///
+    /// - SOL_USD_ORACLE: `pyth` (default), `birdeye` or `none`
+    /// - SOL_USD_TTL_SECS (default 10)
+    /// - SOL_USD_MAX_STALENESS_SECS (default 120)
+    /// - SOL_USD_FALLBACK: price used when the oracle is stale (unset = none)
+    pub fn from_env() -> Self {
+        let feed: Option<Arc<dyn PriceFeed>> = match env::var("SOL_USD_ORACLE")
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "none" | "off" => None,
+            "birdeye" => match env::var("BIRDEYE_API_KEY") {
+                Ok(key) if !key.trim().is_empty() => {
+                    Some(Arc::new(BirdeyeFeed::new(key.trim().to_string())))
+                }
+                _ => {
+                    eprintln!("SOL_USD_ORACLE=birdeye but BIRDEYE_API_KEY is missing; using pyth");
+                    Some(Arc::new(PythFeed))
+                }
+            },
+            _ => Some(Arc::new(PythFeed)),
+        };
+        let mut ttl = DEFAULT_TTL;
+        if let Ok(v) = env::var("SOL_USD_TTL_SECS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                ttl = Duration::from_secs(parsed);
+            }
+        }
+        let mut max_staleness = DEFAULT_MAX_STALENESS;
+        if let Ok(v) = env::var("SOL_USD_MAX_STALENESS_SECS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                max_staleness = Duration::from_secs(parsed);
+            }
+        }
+        let mut fallback = None;
+        if let Ok(v) = env::var("SOL_USD_FALLBACK") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                fallback = Some(parsed);
+            }
+        }
+        Self::new(feed, ttl, max_staleness, fallback)
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Record `price` as observed now. Non-positive or non-finite prices are
+    /// ignored.
+    pub fn record(&self, price: f64) {
+        self.record_at(price, Instant::now());
+    }
+
+    pub fn record_at(&self, price: f64, at: Instant) {
+        if !(price.is_finite() && price > 0.0) {
+            return;
+        }
+        let cached = Some(Cached { price, at });
+        match self.cached.write() {
+            Ok(mut guard) => *guard = cached,
+            Err(poisoned) => *poisoned.into_inner() = cached,
+        }
+    }
+
+    fn cached(&self) -> Option<Cached> {
+        match self.cached.read() {
+            Ok(guard) => *guard,
+            Err(poisoned) => *poisoned.into_inner(),
+        }
+    }
+
+    /// The cached price if it is within the staleness bound, else the
+    /// fallback.
+    pub fn price(&self) -> Option<f64> {
+        self.price_at(Instant::now())
+    }
+
+    pub fn price_at(&self, now: Instant) -> Option<f64> {
+        match self.cached() {
+            Some(c) if now.saturating_duration_since(c.at) <= self.max_staleness => Some(c.price),
+            _ => self.fallback,
+        }
+    }
+
+    /// Whether the cached price is missing or older than the TTL.
+    pub fn needs_refresh_at(&self, now: Instant) -> bool {
+        match self.cached() {
+            Some(c) => now.saturating_duration_since(c.at) >= self.ttl,
+            None => true,
+        }
+    }
+
+    /// Fetch a new price from the feed and cache it.
+    pub async fn refresh(&self) -> Result<f64> {
+        let feed = self
+            .feed
+            .as_ref()
+            .ok_or_else(|| anyhow!("SOL/USD oracle has no feed"))?;
+        let price = feed.fetch().await?;
+        self.record(price);
+        Ok(price)
+    }
+
+    /// The current price, refetching first when the cache is past its TTL.
+    /// A failed fetch is logged and the cached price or fallback is served.
+    pub async fn current(&self) -> Option<f64> {
+        if self.feed.is_some() && self.needs_refresh_at(Instant::now()) {
+            if let Err(e) = self.refresh().await {
+                eprintln!("[ORACLE] SOL/USD refresh failed: {}", e);
+            }
+        }
+        self.price()
+    }
+}
+
+/// Process-wide SOL/USD oracle, configured from the environment.
+pub static SOL_USD_ORACLE: Lazy<PriceOracle> = Lazy::new(PriceOracle::from_env);
+
+/// SOL/USD from [`SOL_USD_ORACLE`]: the cached price while fresh, else
+/// SOL_USD_FALLBACK, else `None`. Never blocks on the network.
+pub fn sol_usd_price() -> Option<f64> {
+    SOL_USD_ORACLE.price()
+}
+
+/// Convert `sol` to USD at the oracle price, if one is available.
+pub fn sol_to_usd(sol: f64) -> Option<f64> {
+    sol_usd_price().map(|price| sol * price)
+}
+
+/// Refresh [`SOL_USD_ORACLE`] every TTL in the background.
+pub fn spawn_sol_usd_refresher() -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(SOL_USD_ORACLE.ttl().max(Duration::from_secs(1)));
+        loop {
+            ticker.tick().await;
+            SOL_USD_ORACLE.current().await;
+        }
+    })
+}
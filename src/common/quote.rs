@@ -0,0 +1,117 @@
+use crate::common::price_oracle;
+
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/// The mint a pool is quoted in.
+///
+/// Most pools quote in SOL, but some PumpSwap / Launchpad pools quote in
+/// USDC. Everything downstream (gates, sizing, fee reserves) works in SOL,
+/// so quote amounts are converted through [`QuoteConverter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QuoteMint {
+    #[default]
+    Sol,
+    Usdc,
+}
+
+impl QuoteMint {
+    pub fn from_mint(mint: &str) -> Option<Self> {
+        match mint {
+            WSOL_MINT => Some(Self::Sol),
+            USDC_MINT => Some(Self::Usdc),
+            _ => None,
+        }
+    }
+
+    /// Detect the quote mint from the mints touched by a transaction.
+    /// USDC wins if present; otherwise the pool is assumed SOL-quoted.
+    pub fn detect<'a, I>(mints: I) -> Self
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        if mints.into_iter().any(|m| m == USDC_MINT) {
+            Self::Usdc
+        } else {
+            Self::Sol
+        }
+    }
+
+    pub fn mint(self) -> &'static str {
+        match self {
+            Self::Sol => WSOL_MINT,
+            Self::Usdc => USDC_MINT,
+        }
+    }
+
+    pub fn decimals(self) -> u8 {
+        match self {
+            Self::Sol => 9,
+            Self::Usdc => 6,
+        }
+    }
+
+    /// Whether `mint` is one of the known quote mints (i.e. not the traded token).
+    pub fn is_quote_mint(mint: &str) -> bool {
+        Self::from_mint(mint).is_some()
+    }
+}
+
+/// Converts quote-denominated amounts into SOL and USD.
+#[derive(Clone, Copy, Debug)]
+pub struct QuoteConverter {
+    pub sol_usd: f64,
+}
+
+impl QuoteConverter {
+    pub fn new(sol_usd: f64) -> Self {
+        Self { sol_usd }
+    }
+
+    /// Build from the globally published SOL price, if one is known.
+    pub fn current() -> Option<Self> {
+        sol_usd_price().map(Self::new)
+    }
+
+    /// Convert an amount (UI units of `quote`) into SOL.
+    pub fn to_sol(&self, amount: f64, quote: QuoteMint) -> f64 {
+        match quote {
+            QuoteMint::Sol => amount,
+            QuoteMint::Usdc => {
+                if self.sol_usd > 0.0 {
+                    amount / self.sol_usd
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Convert an amount (UI units of `quote`) into USD.
+    pub fn to_usd(&self, amount: f64, quote: QuoteMint) -> f64 {
+        match quote {
+            QuoteMint::Sol => amount * self.sol_usd,
+            QuoteMint::Usdc => amount,
+        }
+    }
+
+    /// Factor that turns a value decoded with SOL's 9 decimals into SOL
+    /// for a pool quoted in `quote`. The parser decodes every quote amount
+    /// as lamports; for USDC pools this corrects both decimals and price.
+    pub fn sol_rescale(&self, quote: QuoteMint) -> f64 {
+        let decimals_fix = 10f64.powi(9 - quote.decimals() as i32);
+        self.to_sol(decimals_fix, quote)
+    }
+}
+
+/// Publish the latest SOL/USD price for quote conversions. It is recorded
+/// in the [`SOL_USD_ORACLE`](crate::common::price_oracle::SOL_USD_ORACLE)
+/// and served while fresh.
+pub fn set_sol_usd_price(price: f64) {
+    price_oracle::SOL_USD_ORACLE.record(price);
+}
+
+/// Latest SOL/USD price from the oracle (or its fallback), if any.
+pub fn sol_usd_price() -> Option<f64> {
+    price_oracle::sol_usd_price()
+}
@@ -1,133 +1,726 @@
- url=https://github.com/rumhamdnmchickn/solana-copy-sniper-trading-bot/blob/feat/universal-gates-wiring/src/common/rpc_failover.rs
+use std::collections::HashSet;
+use std::fmt;
+use std::future::Future;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use std::{env, str::FromStr};
+use std::time::{Duration, Instant};
+use std::str::FromStr;
 
 use anchor_client::solana_client::rpc_request::TokenAccountsFilter;
-use anchor_client::solana_client::{rpc_request, rpc_response};
+use anchor_client::solana_client::rpc_response;
+use anchor_client::solana_sdk::account::Account;
 use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::signature::Signature;
 use anchor_client::solana_sdk::transaction::Transaction;
+use solana_client::nonblocking::rpc_client::RpcClient as NonblockingRpcClient;
 use solana_client::rpc_client::RpcClient;
-use solana_client::client_error::ClientError;
-use log::warn;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use futures::future::BoxFuture;
+use log::{info, warn};
+use rand::Rng;
+
+use crate::common::bot_config;
+use crate::library::blockhash_cache::{BlockhashInfo, BlockhashSource};
+use crate::universal::prometheus::COUNTERS;
+
+/// A single RPC endpoint, optionally tagged with the region it lives in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RpcEndpoint {
+    pub url: String,
+    pub region: Option<String>,
+}
+
+impl RpcEndpoint {
+    /// Parse `region|url` or a bare `url` (no region).
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return None;
+        }
+        match raw.split_once('|') {
+            Some((region, url)) if !url.trim().is_empty() => Some(Self {
+                url: url.trim().to_string(),
+                region: Some(region.trim().to_lowercase()).filter(|r| !r.is_empty()),
+            }),
+            Some(_) => None,
+            None => Some(Self {
+                url: raw.to_string(),
+                region: None,
+            }),
+        }
+    }
+}
+
+/// Error from an [`RpcFailover`] / [`AsyncRpcFailover`] call. `op` names
+/// the call (e.g. `get_account`); every message reads `<op> failed: ...`.
+#[derive(Debug)]
+pub enum RpcError {
+    /// The client has no endpoints to try.
+    NoEndpoints { op: &'static str },
+    /// Every endpoint failed its retries; `last` is the final attempt's error.
+    AllEndpointsFailed {
+        op: &'static str,
+        last: Option<Box<RpcError>>,
+    },
+    /// A request timed out. `source` is `None` when the call's retry budget
+    /// ran out before an endpoint answered.
+    Timeout {
+        op: &'static str,
+        source: Option<ClientError>,
+    },
+    /// The request or response could not be (de)serialized.
+    Serialization {
+        op: &'static str,
+        source: ClientError,
+    },
+    /// Any other error from the endpoint: an RPC error response, a rejected
+    /// transaction or a failed connection.
+    Upstream {
+        op: &'static str,
+        source: ClientError,
+    },
+}
+
+impl RpcError {
+    /// Classify one failed attempt of `op`.
+    pub fn from_client_error(op: &'static str, source: ClientError) -> Self {
+        let timed_out = match source.kind() {
+            ClientErrorKind::Reqwest(e) => e.is_timeout(),
+            ClientErrorKind::Io(e) => e.kind() == std::io::ErrorKind::TimedOut,
+            _ => false,
+        };
+        if timed_out {
+            RpcError::Timeout {
+                op,
+                source: Some(source),
+            }
+        } else if matches!(source.kind(), ClientErrorKind::SerdeJson(_)) {
+            RpcError::Serialization { op, source }
+        } else {
+            RpcError::Upstream { op, source }
+        }
+    }
+
+    /// The call that failed.
+    pub fn op(&self) -> &'static str {
+        match self {
+            RpcError::NoEndpoints { op }
+            | RpcError::AllEndpointsFailed { op, .. }
+            | RpcError::Timeout { op, .. }
+            | RpcError::Serialization { op, .. }
+            | RpcError::Upstream { op, .. } => op,
+        }
+    }
+
+    /// Whether trying the call again later may succeed: timeouts and
+    /// connection failures are, RPC error responses, rejected transactions
+    /// and bad payloads are not. [`RpcError::AllEndpointsFailed`] follows
+    /// its last attempt.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RpcError::NoEndpoints { .. } | RpcError::Serialization { .. } => false,
+            RpcError::AllEndpointsFailed { last, .. } => {
+                last.as_ref().map_or(true, |e| e.is_retryable())
+            }
+            RpcError::Timeout { .. } => true,
+            RpcError::Upstream { source, .. } => matches!(
+                source.kind(),
+                ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_)
+            ),
+        }
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed: ", self.op())?;
+        match self {
+            RpcError::NoEndpoints { .. } => write!(f, "no rpc endpoints configured"),
+            RpcError::AllEndpointsFailed { .. } => {
+                write!(f, "all rpc endpoints failed after retries")
+            }
+            RpcError::Timeout { source: None, .. } => write!(f, "rpc retry budget exhausted"),
+            RpcError::Timeout {
+                source: Some(e), ..
+            }
+            | RpcError::Serialization { source: e, .. }
+            | RpcError::Upstream { source: e, .. } => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RpcError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RpcError::NoEndpoints { .. } | RpcError::Timeout { source: None, .. } => None,
+            RpcError::AllEndpointsFailed { last, .. } => last
+                .as_deref()
+                .map(|e| e as &(dyn std::error::Error + 'static)),
+            RpcError::Timeout {
+                source: Some(e), ..
+            }
+            | RpcError::Serialization { source: e, .. }
+            | RpcError::Upstream { source: e, .. } => Some(e),
+        }
+    }
+}
+
+/// Exponential backoff with full jitter for the retries of one failover call.
+///
+/// Retry `n` sleeps a uniformly random time in `[0, min(max, base * 2^n)]`,
+/// so concurrent callers spread out instead of retrying in lockstep. The
+/// total sleep is capped by `budget`; once it is spent the call gives up.
+#[derive(Clone, Debug)]
+pub struct RetryBackoff {
+    base_ms: u64,
+    max_ms: u64,
+    budget: Duration,
+    attempt: u32,
+    slept: Duration,
+}
+
+impl RetryBackoff {
+    pub fn new(base_ms: u64, max_ms: u64, budget: Duration) -> Self {
+        Self {
+            base_ms,
+            max_ms,
+            budget,
+            attempt: 0,
+            slept: Duration::ZERO,
+        }
+    }
+
+    /// Upper bound of the delay before retry number `attempt`.
+    pub fn ceiling(&self, attempt: u32) -> Duration {
+        let exp = self.base_ms.saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX));
+        Duration::from_millis(exp.min(self.max_ms))
+    }
+
+    /// Next jittered delay, trimmed to what is left of the budget; `None`
+    /// once the budget is spent.
+    pub fn next_delay<R: Rng>(&mut self, rng: &mut R) -> Option<Duration> {
+        let remaining = self.budget.saturating_sub(self.slept);
+        if remaining.is_zero() {
+            return None;
+        }
+        let ceiling = self.ceiling(self.attempt).as_millis() as u64;
+        self.attempt = self.attempt.saturating_add(1);
+        let delay = Duration::from_millis(rng.gen_range(0..=ceiling)).min(remaining);
+        self.slept += delay;
+        Some(delay)
+    }
+
+    /// Total time slept so far.
+    pub fn slept(&self) -> Duration {
+        self.slept
+    }
+}
+
+/// Per-endpoint counters and the latest measured latency.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EndpointStats {
+    pub successes: u64,
+    pub failures: u64,
+    /// Round-trip time of the last successful health probe.
+    pub latency: Option<Duration>,
+}
 
 /// Lightweight RPC failover client that uses the blocking RpcClient under the hood.
-/// It recreates a blocking RpcClient for each endpoint attempt and rotates through
-/// the configured endpoints when RPC calls fail. The API surface implemented is
+/// It recreates a blocking RpcClient for each endpoint attempt and moves on to the
+/// next endpoint when RPC calls fail. The API surface implemented is
 /// intentionally small and matches the call sites used in main.rs (get_account,
 /// get_token_accounts_by_owner, get_latest_blockhash, send_and_confirm_transaction).
+///
+/// Endpoints in `local_region` are always preferred; endpoints in other regions
+/// are only used once every local endpoint has been marked unhealthy. Within
+/// each group, endpoints are ranked by the latency measured by
+/// [`RpcFailover::probe_all`], so a recovered, faster primary is picked again.
 #[derive(Clone)]
 pub struct RpcFailover {
-    endpoints: Arc<Vec<String>>,
-    cur_idx: Arc<Mutex<usize>>,
+    endpoints: Arc<Vec<RpcEndpoint>>,
+    local_region: Option<String>,
+    unhealthy: Arc<Mutex<HashSet<usize>>>,
+    stats: Arc<Mutex<Vec<EndpointStats>>>,
     retry_attempts: usize,
     timeout: Duration,
+    commitment: CommitmentConfig,
+    backoff_base_ms: u64,
+    backoff_max_ms: u64,
+    backoff_budget: Duration,
 }
 
 impl RpcFailover {
     /// Build from explicit list
     pub fn new(endpoints: Vec<String>, retry_attempts: usize, timeout: Duration) -> Result<Self, String> {
-        let endpoints_clean: Vec<String> = endpoints.into_iter().map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
-        if endpoints_clean.is_empty() {
+        let parsed = endpoints.iter().filter_map(|s| RpcEndpoint::parse(s)).collect();
+        Self::with_endpoints(parsed, None, retry_attempts, timeout)
+    }
+
+    /// Build from region-tagged endpoints, preferring `local_region`.
+    pub fn with_endpoints(
+        endpoints: Vec<RpcEndpoint>,
+        local_region: Option<String>,
+        retry_attempts: usize,
+        timeout: Duration,
+    ) -> Result<Self, String> {
+        if endpoints.is_empty() {
             return Err("no rpc endpoints provided".into());
         }
+        let stats = vec![EndpointStats::default(); endpoints.len()];
         Ok(Self {
-            endpoints: Arc::new(endpoints_clean),
-            cur_idx: Arc::new(Mutex::new(0)),
+            endpoints: Arc::new(endpoints),
+            stats: Arc::new(Mutex::new(stats)),
+            local_region: local_region.map(|r| r.trim().to_lowercase()).filter(|r| !r.is_empty()),
+            unhealthy: Arc::new(Mutex::new(HashSet::new())),
             retry_attempts: if retry_attempts == 0 { 2 } else { retry_attempts },
             timeout,
+            commitment: CommitmentConfig::confirmed(),
+            backoff_base_ms: 100,
+            backoff_max_ms: 5_000,
+            backoff_budget: Duration::from_secs(10),
         })
     }
 
+    /// Retry backoff: `base_ms * 2^n` capped at `max_ms`, fully jittered, with
+    /// at most `budget` of total sleep per call.
+    pub fn with_backoff(mut self, base_ms: u64, max_ms: u64, budget: Duration) -> Self {
+        self.backoff_base_ms = base_ms;
+        self.backoff_max_ms = max_ms.max(base_ms);
+        self.backoff_budget = budget;
+        self
+    }
+
+    pub fn rpc_backoff_base_ms(&self) -> u64 {
+        self.backoff_base_ms
+    }
+
+    pub fn rpc_backoff_max_ms(&self) -> u64 {
+        self.backoff_max_ms
+    }
+
+    /// Fresh backoff state for one call.
+    pub fn retry_backoff(&self) -> RetryBackoff {
+        RetryBackoff::new(self.backoff_base_ms, self.backoff_max_ms, self.backoff_budget)
+    }
+
+    /// Commitment used by every per-endpoint client (default `confirmed`).
+    pub fn with_commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    pub fn commitment(&self) -> CommitmentConfig {
+        self.commitment
+    }
+
+    /// Blocking client for `url` honoring the configured timeout and commitment.
+    fn client_for(&self, url: &str) -> RpcClient {
+        RpcClient::new_with_timeout_and_commitment(url.to_string(), self.timeout, self.commitment)
+    }
+
+    /// Nonblocking client for `url` honoring the configured timeout and commitment.
+    fn nonblocking_client_for(&self, url: &str) -> NonblockingRpcClient {
+        NonblockingRpcClient::new_with_timeout_and_commitment(
+            url.to_string(),
+            self.timeout,
+            self.commitment,
+        )
+    }
+
+    /// [`from_env`](Self::from_env), or `None` when neither RPC_ENDPOINTS nor
+    /// RPC_HTTP is set in the env or the bot config file.
+    pub fn from_config() -> Result<Option<Self>, String> {
+        if bot_config::var("RPC_ENDPOINTS").is_err() && bot_config::var("RPC_HTTP").is_err() {
+            return Ok(None);
+        }
+        Self::from_env().map(Some)
+    }
+
     /// Build from environment variables:
-    /// - RPC_ENDPOINTS (comma-separated URLs)
+    /// - RPC_ENDPOINTS (comma-separated URLs, each optionally `region|url`)
+    /// - RPC_LOCAL_REGION (optional, region to prefer)
     /// - RPC_RETRY_ATTEMPTS (optional)
     /// - RPC_TIMEOUT_SECONDS (optional)
+    /// - RPC_COMMITMENT (optional: processed | confirmed | finalized; defaults to
+    ///   COMMITMENT, then confirmed)
+    /// - RPC_BACKOFF_BASE_MS / RPC_BACKOFF_MAX_MS (optional, default 100 / 5000)
+    /// - RPC_BACKOFF_BUDGET_MS (optional, total retry sleep per call, default 10000)
     pub fn from_env() -> Result<Self, String> {
-        let endpoints = env::var("RPC_ENDPOINTS")
-            .or_else(|_| env::var("RPC_HTTP"))
+        let endpoints = bot_config::var("RPC_ENDPOINTS")
+            .or_else(|_| bot_config::var("RPC_HTTP"))
             .map_err(|_| "RPC_ENDPOINTS or RPC_HTTP must be set".to_string())?;
-        let list: Vec<String> = endpoints.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
-        let retry_attempts = env::var("RPC_RETRY_ATTEMPTS").ok().and_then(|v| v.parse::<usize>().ok()).unwrap_or(2);
-        let timeout_seconds = env::var("RPC_TIMEOUT_SECONDS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(10);
-        RpcFailover::new(list, retry_attempts, Duration::from_secs(timeout_seconds))
+        let list: Vec<RpcEndpoint> = endpoints.split(',').filter_map(RpcEndpoint::parse).collect();
+        let local_region = bot_config::var("RPC_LOCAL_REGION").ok();
+        let retry_attempts = bot_config::var("RPC_RETRY_ATTEMPTS").ok().and_then(|v| v.parse::<usize>().ok()).unwrap_or(2);
+        let timeout_seconds = bot_config::var("RPC_TIMEOUT_SECONDS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(10);
+        let commitment = bot_config::var("RPC_COMMITMENT")
+            .ok()
+            .and_then(|v| CommitmentConfig::from_str(v.trim()).ok())
+            .unwrap_or_else(crate::common::config::commitment_from_env);
+        let backoff_base_ms = bot_config::var("RPC_BACKOFF_BASE_MS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(100);
+        let backoff_max_ms = bot_config::var("RPC_BACKOFF_MAX_MS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(5_000);
+        let backoff_budget_ms = bot_config::var("RPC_BACKOFF_BUDGET_MS").ok().and_then(|v| v.parse::<u64>().ok()).unwrap_or(10_000);
+        RpcFailover::with_endpoints(list, local_region, retry_attempts, Duration::from_secs(timeout_seconds))
+            .map(|f| {
+                f.with_commitment(commitment).with_backoff(
+                    backoff_base_ms,
+                    backoff_max_ms,
+                    Duration::from_millis(backoff_budget_ms),
+                )
+            })
+    }
+
+    fn is_local(&self, ep: &RpcEndpoint) -> bool {
+        match (&self.local_region, &ep.region) {
+            (Some(local), Some(region)) => local == region,
+            // Without a configured region every endpoint counts as local.
+            (None, _) => true,
+            (Some(_), None) => false,
+        }
     }
 
-    fn current_endpoint(&self) -> String {
-        let idx = *self.cur_idx.lock().unwrap();
-        self.endpoints[idx].clone()
+    /// Endpoint indices in the order they should be tried:
+    /// healthy local, healthy remote, then unhealthy local and remote as a last resort.
+    /// Each group is ordered by measured latency; unmeasured endpoints keep
+    /// their configured order after the measured ones.
+    fn selection_order_indices(&self) -> Vec<usize> {
+        let unhealthy = self.unhealthy.lock().map(|g| g.clone()).unwrap_or_default();
+        let latencies: Vec<Option<Duration>> = self
+            .stats
+            .lock()
+            .map(|g| g.iter().map(|s| s.latency).collect())
+            .unwrap_or_default();
+        let mut order: Vec<usize> = (0..self.endpoints.len()).collect();
+        order.sort_by_key(|&i| {
+            let remote = !self.is_local(&self.endpoints[i]);
+            let latency = latencies.get(i).copied().flatten();
+            (unhealthy.contains(&i), remote, latency.is_none(), latency)
+        });
+        order
+    }
+
+    fn update_stats<F: FnOnce(&mut EndpointStats)>(&self, url: &str, f: F) {
+        if let Some(idx) = self.endpoints.iter().position(|e| e.url == url) {
+            if let Ok(mut guard) = self.stats.lock() {
+                if let Some(stats) = guard.get_mut(idx) {
+                    f(stats);
+                }
+            }
+        }
     }
 
-    fn advance_endpoint(&self) {
-        let mut idx = self.cur_idx.lock().unwrap();
-        *idx = (*idx + 1) % self.endpoints.len();
-        warn!("RPC failover: switching to endpoint index {}", *idx);
+    /// Success/failure counts and latency per endpoint URL, in configured order.
+    pub fn endpoint_stats(&self) -> Vec<(String, EndpointStats)> {
+        let stats = self.stats.lock().map(|g| g.clone()).unwrap_or_default();
+        self.endpoints
+            .iter()
+            .zip(stats)
+            .map(|(e, s)| (e.url.clone(), s))
+            .collect()
+    }
+
+    /// Apply the result of a health probe: a success records its latency and
+    /// marks the endpoint healthy, a failure marks it unhealthy.
+    pub fn record_probe(&self, url: &str, result: Result<Duration, String>) {
+        match result {
+            Ok(latency) => {
+                self.update_stats(url, |s| {
+                    s.successes += 1;
+                    s.latency = Some(latency);
+                });
+                self.mark_healthy(url);
+            }
+            Err(e) => {
+                warn!("RPC health probe failed for {}: {}", url, e);
+                self.update_stats(url, |s| s.failures += 1);
+                self.mark_unhealthy(url);
+            }
+        }
+    }
+
+    /// Probe every endpoint with `getSlot` (bounded by the configured timeout)
+    /// and update health and latency rankings. Blocking; see
+    /// [`spawn_health_probe`] for the periodic async variant.
+    pub fn probe_all(&self) {
+        for endpoint in self.endpoints.iter() {
+            let client = self.client_for(&endpoint.url);
+            let started = Instant::now();
+            let result = client.get_slot().map(|_| started.elapsed()).map_err(|e| e.to_string());
+            self.record_probe(&endpoint.url, result);
+        }
+        info!("RPC health probe: selection order {:?}", self.selection_order());
+    }
+
+    /// Endpoint URLs in the order they will be tried for the next call.
+    pub fn selection_order(&self) -> Vec<String> {
+        self.selection_order_indices()
+            .into_iter()
+            .map(|i| self.endpoints[i].url.clone())
+            .collect()
+    }
+
+    /// The endpoint the next call will start with.
+    pub fn current_endpoint(&self) -> String {
+        self.selection_order().into_iter().next().unwrap_or_default()
+    }
+
+    /// Mark an endpoint unhealthy so it is tried only after healthy ones.
+    pub fn mark_unhealthy(&self, url: &str) {
+        if let Some(idx) = self.endpoints.iter().position(|e| e.url == url) {
+            if let Ok(mut guard) = self.unhealthy.lock() {
+                if guard.insert(idx) {
+                    warn!("RPC failover: marking endpoint {} unhealthy", url);
+                }
+            }
+        }
+    }
+
+    /// Mark an endpoint healthy again.
+    pub fn mark_healthy(&self, url: &str) {
+        if let Some(idx) = self.endpoints.iter().position(|e| e.url == url) {
+            if let Ok(mut guard) = self.unhealthy.lock() {
+                guard.remove(&idx);
+            }
+        }
     }
 
     /// Attempt a closure against the available endpoints; the closure receives a fresh RpcClient
     /// and should perform the RPC call. If the closure returns Ok, the result is returned.
-    /// On error, the failover rotates endpoints and retries according to retry_attempts.
-    fn try_endpoints<F, T>(&self, mut f: F) -> Result<T, String>
+    /// On error, the endpoint is marked unhealthy and the next endpoint in
+    /// region-preference order is tried, according to retry_attempts.
+    /// Retries wait per [`RetryBackoff`]; the call fails once its budget is spent.
+    fn try_endpoints<F, T>(&self, op: &'static str, mut f: F) -> Result<T, RpcError>
     where
         F: FnMut(&RpcClient) -> Result<T, ClientError>,
     {
-        let endpoints_count = self.endpoints.len();
-        if endpoints_count == 0 {
-            return Err("no rpc endpoints configured".to_string());
+        if self.endpoints.is_empty() {
+            return Err(RpcError::NoEndpoints { op });
         }
 
+        let mut backoff = self.retry_backoff();
+        let mut first = true;
+        let mut last = None;
         // For each endpoint, try up to retry_attempts
-        for _ep_round in 0..endpoints_count {
-            let endpoint = self.current_endpoint();
-            // Build a fresh RpcClient for the endpoint (using provided timeout via CommitmentConfig is limited,
-            // RpcClient doesn't take a timeout param directly—users may configure HTTP client globally if needed).
-            let client = RpcClient::new(endpoint.clone());
+        for endpoint in self.selection_order() {
+            // Fresh client per endpoint, bounded by the configured timeout so a
+            // hung endpoint cannot stall the call beyond RPC_TIMEOUT_SECONDS.
+            let client = self.client_for(&endpoint);
 
             for _attempt in 0..self.retry_attempts {
+                if !first {
+                    match backoff.next_delay(&mut rand::thread_rng()) {
+                        Some(delay) => std::thread::sleep(delay),
+                        None => return Err(RpcError::Timeout { op, source: None }),
+                    }
+                }
+                first = false;
                 match f(&client) {
-                    Ok(res) => return Ok(res),
+                    Ok(res) => {
+                        self.update_stats(&endpoint, |s| s.successes += 1);
+                        self.mark_healthy(&endpoint);
+                        return Ok(res);
+                    }
                     Err(e) => {
+                        self.update_stats(&endpoint, |s| s.failures += 1);
                         warn!("RPC request failed for {}: {}", endpoint, e);
+                        last = Some(Box::new(RpcError::from_client_error(op, e)));
                     }
                 }
-                // simple sleep between attempts; convert seconds from timeout as backoff base
-                std::thread::sleep(std::time::Duration::from_millis(100));
             }
-            // rotate to next endpoint
-            self.advance_endpoint();
+            // move on to the next endpoint in preference order
+            self.mark_unhealthy(&endpoint);
+            COUNTERS.inc_rpc_failovers();
         }
 
-        Err("all rpc endpoints failed after retries".to_string())
+        Err(RpcError::AllEndpointsFailed { op, last })
     }
 
     pub fn get_token_accounts_by_owner(
         &self,
         owner: &Pubkey,
         filter: TokenAccountsFilter,
-    ) -> Result<Vec<rpc_response::RpcKeyedAccount>, String> {
-        self.try_endpoints(|client| client.get_token_accounts_by_owner(owner, filter.clone()))
-            .map_err(|e| format!("get_token_accounts_by_owner failed: {}", e))
+    ) -> Result<Vec<rpc_response::RpcKeyedAccount>, RpcError> {
+        self.try_endpoints("get_token_accounts_by_owner", |client| {
+            client.get_token_accounts_by_owner(owner, filter.clone())
+        })
+    }
+
+    pub fn get_account(&self, pubkey: &Pubkey) -> Result<Account, RpcError> {
+        self.try_endpoints("get_account", |client| client.get_account(pubkey))
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<solana_sdk::hash::Hash, RpcError> {
+        self.try_endpoints("get_latest_blockhash", |client| {
+            client.get_latest_blockhash()
+        })
+    }
+
+    pub fn send_and_confirm_transaction(&self, tx: &Transaction) -> Result<Signature, RpcError> {
+        self.try_endpoints("send_and_confirm_transaction", |client| {
+            client.send_and_confirm_transaction(tx)
+        })
     }
+}
+
+/// Probe all endpoints of `failover` every `interval` with async `getSlot`
+/// calls, updating health and latency rankings (see [`RpcFailover::record_probe`]).
+pub fn spawn_health_probe(failover: RpcFailover, interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            for endpoint in failover.endpoints.iter() {
+                let client = failover.nonblocking_client_for(&endpoint.url);
+                let started = Instant::now();
+                let result = client
+                    .get_slot()
+                    .await
+                    .map(|_| started.elapsed())
+                    .map_err(|e| e.to_string());
+                failover.record_probe(&endpoint.url, result);
+            }
+            for (url, stats) in failover.endpoint_stats() {
+                info!(
+                    "RPC {}: ok={} failed={} latency={:?}",
+                    url, stats.successes, stats.failures, stats.latency
+                );
+            }
+        }
+    })
+}
 
-    pub fn get_account(
+/// Async counterpart of [`RpcFailover`] built on the nonblocking RpcClient.
+///
+/// Endpoint rotation, region preference and retry/backoff semantics are
+/// identical, and health state is shared with the wrapped [`RpcFailover`], but
+/// backoff uses `tokio::time::sleep` so failover never blocks a runtime worker.
+#[derive(Clone)]
+pub struct AsyncRpcFailover {
+    failover: RpcFailover,
+}
+
+impl AsyncRpcFailover {
+    pub fn new(failover: RpcFailover) -> Self {
+        Self { failover }
+    }
+
+    /// Build from the same environment variables as [`RpcFailover::from_env`].
+    pub fn from_env() -> Result<Self, String> {
+        RpcFailover::from_env().map(Self::new)
+    }
+
+    /// The underlying failover state (selection order, health marks).
+    pub fn failover(&self) -> &RpcFailover {
+        &self.failover
+    }
+
+    /// Async version of `RpcFailover::try_endpoints`. The closure receives a
+    /// nonblocking client for the endpoint being tried.
+    async fn try_endpoints<F, Fut, T>(&self, op: &'static str, mut f: F) -> Result<T, RpcError>
+    where
+        F: FnMut(Arc<NonblockingRpcClient>) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let failover = &self.failover;
+        if failover.endpoints.is_empty() {
+            return Err(RpcError::NoEndpoints { op });
+        }
+
+        let mut backoff = failover.retry_backoff();
+        let mut first = true;
+        let mut last = None;
+        for endpoint in failover.selection_order() {
+            let client = Arc::new(failover.nonblocking_client_for(&endpoint));
+
+            for _attempt in 0..failover.retry_attempts {
+                if !first {
+                    let delay = backoff.next_delay(&mut rand::thread_rng());
+                    match delay {
+                        Some(delay) => tokio::time::sleep(delay).await,
+                        None => return Err(RpcError::Timeout { op, source: None }),
+                    }
+                }
+                first = false;
+                match f(client.clone()).await {
+                    Ok(res) => {
+                        failover.update_stats(&endpoint, |s| s.successes += 1);
+                        failover.mark_healthy(&endpoint);
+                        return Ok(res);
+                    }
+                    Err(e) => {
+                        failover.update_stats(&endpoint, |s| s.failures += 1);
+                        warn!("RPC request failed for {}: {}", endpoint, e);
+                        last = Some(Box::new(RpcError::from_client_error(op, e)));
+                    }
+                }
+            }
+            failover.mark_unhealthy(&endpoint);
+            COUNTERS.inc_rpc_failovers();
+        }
+
+        Err(RpcError::AllEndpointsFailed { op, last })
+    }
+
+    pub async fn get_token_accounts_by_owner(
         &self,
-        pubkey: &Pubkey,
-    ) -> Result<rpc_response::Response<rpc_response::RpcAccount>, String> {
-        // RpcClient::get_account returns Result<Account, ClientError>. But in some codebases
-        // they expect the full response. We will return the plain RpcAccount for simplicity.
-        self.try_endpoints(|client| client.get_account(pubkey))
-            .map_err(|e| format!("get_account failed: {}", e))
+        owner: &Pubkey,
+        filter: TokenAccountsFilter,
+    ) -> Result<Vec<rpc_response::RpcKeyedAccount>, RpcError> {
+        let owner = *owner;
+        self.try_endpoints("get_token_accounts_by_owner", |client| {
+            let filter = filter.clone();
+            async move { client.get_token_accounts_by_owner(&owner, filter).await }
+        })
+        .await
     }
 
-    pub fn get_latest_blockhash(&self) -> Result<solana_sdk::hash::Hash, String> {
-        self.try_endpoints(|client| client.get_latest_blockhash())
-            .map_err(|e| format!("get_latest_blockhash failed: {}", e))
+    pub async fn get_account(&self, pubkey: &Pubkey) -> Result<Account, RpcError> {
+        let pubkey = *pubkey;
+        self.try_endpoints("get_account", |client| async move {
+            client.get_account(&pubkey).await
+        })
+        .await
+    }
+
+    pub async fn get_latest_blockhash(&self) -> Result<solana_sdk::hash::Hash, RpcError> {
+        self.try_endpoints("get_latest_blockhash", |client| async move {
+            client.get_latest_blockhash().await
+        })
+        .await
     }
 
-    pub fn send_and_confirm_transaction(&self, tx: &Transaction) -> Result<Signature, String> {
-        self.try_endpoints(|client| client.send_and_confirm_transaction(tx))
-            .map_err(|e| format!("send_and_confirm_transaction failed: {}", e))
+    /// Latest blockhash with its expiry height and the current block height,
+    /// both from the same endpoint.
+    pub async fn get_latest_blockhash_info(&self) -> Result<BlockhashInfo, RpcError> {
+        self.try_endpoints("get_latest_blockhash", |client| async move {
+            let (hash, last_valid_block_height) = client
+                .get_latest_blockhash_with_commitment(client.commitment())
+                .await?;
+            let block_height = client.get_block_height().await?;
+            Ok(BlockhashInfo { hash, last_valid_block_height, block_height })
+        })
+        .await
+    }
+
+    pub async fn send_and_confirm_transaction(
+        &self,
+        tx: &Transaction,
+    ) -> Result<Signature, RpcError> {
+        self.try_endpoints("send_and_confirm_transaction", |client| {
+            let tx = tx.clone();
+            async move { client.send_and_confirm_transaction(&tx).await }
+        })
+        .await
+    }
+}
+
+/// Lets `BlockhashCache::get_or_fetch` fall back to the failover endpoints.
+impl BlockhashSource for AsyncRpcFailover {
+    fn fetch_blockhash(&self) -> BoxFuture<'_, Result<BlockhashInfo, String>> {
+        Box::pin(async move {
+            self.get_latest_blockhash_info()
+                .await
+                .map_err(|e| e.to_string())
+        })
     }
 }
@@ -38,6 +38,7 @@ use crate::{
         monitor::BondingCurveInfo,
         swap::{SwapDirection, SwapInType},
     },
+    universal::slippage::{max_in_with_slippage_bps, min_out_with_slippage_bps},
 };
 
 // Constants for cache
@@ -307,7 +308,7 @@ impl Pump {
             SwapDirection::Buy => {
                 let amount_specified =
                     ui_amount_to_amount(swap_config.amount_in, spl_token::native_mint::DECIMALS);
-                let max_sol_cost = max_amount_with_slippage(amount_specified, 20000);
+                let max_sol_cost = max_in_with_slippage_bps(amount_specified, _slippage_bps);
 
                 // Use virtual reserves from trade_info for accurate calculation
                 let tokens_out = Self::calculate_buy_token_amount(
@@ -376,11 +377,18 @@ impl Pump {
                         }
                     };
 
-                // Set minimum SOL output to ensure transaction always builds
-                let min_sol_output = MIN_SOL_OUTPUT_SELLING;
+                // Expected SOL out less the configured slippage, never below the
+                // floor that keeps the instruction valid
+                let expected_sol_output = Self::calculate_sell_sol_amount(
+                    actual_token_amount,
+                    trade_info.virtual_sol_reserves,
+                    trade_info.virtual_token_reserves,
+                );
+                let min_sol_output = min_out_with_slippage_bps(expected_sol_output, _slippage_bps)
+                    .max(MIN_SOL_OUTPUT_SELLING);
 
-                _logger.log(format!("Sell calculation - ACTUAL tokens in: {}, Min SOL out: {} (fixed), Virtual SOL: {}, Virtual Tokens: {}", 
-                    actual_token_amount, min_sol_output, trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves));
+                _logger.log(format!("Sell calculation - ACTUAL tokens in: {}, Min SOL out: {} ({} bps slippage), Virtual SOL: {}, Virtual Tokens: {}", 
+                    actual_token_amount, min_sol_output, _slippage_bps, trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves));
 
                 // Return accounts for sell
                 (
@@ -581,14 +589,6 @@ pub async fn get_bonding_curve_account(
     ))
 }
 
-fn max_amount_with_slippage(input_amount: u64, _slippage_bps: u64) -> u64 {
-    input_amount
-        .checked_mul(_slippage_bps.checked_add(TEN_THOUSAND).unwrap())
-        .unwrap()
-        .checked_div(TEN_THOUSAND)
-        .unwrap()
-}
-
 pub fn get_pda(mint: &Pubkey, program_id: &Pubkey) -> Result<Pubkey> {
     let seeds = [b"bonding-curve".as_ref(), mint.as_ref()];
     let (bonding_curve, _bump) = Pubkey::find_program_address(&seeds, program_id);
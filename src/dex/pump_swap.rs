@@ -36,6 +36,7 @@ use crate::{
     block_engine::token,
     common::{cache::WALLET_TOKEN_ACCOUNTS, config::SwapConfig, logger::Logger},
     processor::swap::{SwapDirection, SwapInType},
+    universal::slippage::{max_in_with_slippage_bps, min_out_with_slippage_bps},
 };
 
 // Import the volume accumulator structures from pump_fun
@@ -87,7 +88,6 @@ fn get_user_volume_accumulator_pda(user: &Pubkey) -> Result<Pubkey> {
 // Thread-safe cache with LRU eviction policy
 static TOKEN_ACCOUNT_CACHE: OnceCell<LruCache<Pubkey, bool>> = OnceCell::const_new();
 
-const TEN_THOUSAND: u64 = 10000;
 const CACHE_SIZE: usize = 1000;
 
 async fn init_caches() {
@@ -281,7 +281,7 @@ impl PumpSwap {
             trade_info.virtual_token_reserves,
         );
 
-        let max_quote_amount_in = max_amount_with_slippage(amount_specified, _slippage_bps);
+        let max_quote_amount_in = max_in_with_slippage_bps(amount_specified, _slippage_bps);
         let out_ata = get_associated_token_address(&owner, &mint);
 
         // Check token account existence and create if needed
@@ -398,7 +398,8 @@ impl PumpSwap {
             trade_info.virtual_token_reserves,
         );
 
-        let min_quote_amount_out = 0; // this ensures must sell
+        // Force sells widen the slippage to 10_000 bps, which accepts any output
+        let min_quote_amount_out = min_out_with_slippage_bps(quote_amount_out, _slippage_bps);
         println!("Sell calculation - Tokens in: {}, Expected SOL out: {}, Virtual SOL: {}, Virtual Tokens: {}", 
             amount, quote_amount_out, trade_info.virtual_sol_reserves, trade_info.virtual_token_reserves);
 
@@ -652,22 +653,6 @@ fn calculate_sell_quote_amount(base_amount_in: u64, base_reserve: u64, quote_res
     quote_reserve.saturating_sub(quote_reserve_after as u64)
 }
 
-#[inline]
-fn min_amount_with_slippage(input_amount: u64, _slippage_bps: u64) -> u64 {
-    input_amount
-        .saturating_mul(TEN_THOUSAND.saturating_sub(_slippage_bps))
-        .checked_div(TEN_THOUSAND)
-        .unwrap_or(0)
-}
-
-#[inline]
-fn max_amount_with_slippage(input_amount: u64, _slippage_bps: u64) -> u64 {
-    input_amount
-        .saturating_mul(TEN_THOUSAND.saturating_add(_slippage_bps))
-        .checked_div(TEN_THOUSAND)
-        .unwrap_or(input_amount)
-}
-
 // Optimized account creation with const pubkeys
 fn create_buy_accounts(
     pool_id: Pubkey,
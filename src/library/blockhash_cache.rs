@@ -0,0 +1,199 @@
+//! Recent blockhash cache with background refresh.
+//!
+//! Fetching a blockhash per send puts an RPC round-trip on the critical path
+//! of every snipe. [`spawn_blockhash_refresh`] keeps a [`BlockhashCache`]
+//! current in the background (every BLOCKHASH_REFRESH_MS, default 2000) and
+//! the send path reads it, fetching a fresh hash only when the cached one is
+//! stale.
+//!
+//! A cached hash is served only while it is younger than the cache's
+//! `max_age` and the estimated block height stays [`EXPIRY_MARGIN_BLOCKS`]
+//! below its `last_valid_block_height`, so nothing is signed with a blockhash
+//! that is about to expire.
+
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient as NonblockingRpcClient;
+use anchor_client::solana_client::rpc_client::RpcClient;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use solana_sdk::hash::Hash;
+
+/// Nominal slot time, used to estimate block height between refreshes.
+pub const SLOT_TIME: Duration = Duration::from_millis(400);
+/// Blocks of headroom kept before `last_valid_block_height`.
+pub const EXPIRY_MARGIN_BLOCKS: u64 = 20;
+/// Oldest cached hash served without a fresh fetch.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(10);
+
+/// A blockhash and the block heights needed to judge its expiry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockhashInfo {
+    pub hash: Hash,
+    /// Last block height at which a transaction using `hash` can land.
+    pub last_valid_block_height: u64,
+    /// Block height when `hash` was fetched.
+    pub block_height: u64,
+}
+
+/// Something that can fetch a [`BlockhashInfo`], e.g. an RPC client.
+pub trait BlockhashSource: Send + Sync {
+    fn fetch_blockhash(&self) -> BoxFuture<'_, Result<BlockhashInfo, String>>;
+}
+
+impl BlockhashSource for NonblockingRpcClient {
+    fn fetch_blockhash(&self) -> BoxFuture<'_, Result<BlockhashInfo, String>> {
+        Box::pin(async move {
+            let (hash, last_valid_block_height) = self
+                .get_latest_blockhash_with_commitment(self.commitment())
+                .await
+                .map_err(|e| format!("get_latest_blockhash failed: {}", e))?;
+            let block_height = self
+                .get_block_height()
+                .await
+                .map_err(|e| format!("get_block_height failed: {}", e))?;
+            Ok(BlockhashInfo {
+                hash,
+                last_valid_block_height,
+                block_height,
+            })
+        })
+    }
+}
+
+/// The blocking client runs on the blocking thread pool.
+impl BlockhashSource for Arc<RpcClient> {
+    fn fetch_blockhash(&self) -> BoxFuture<'_, Result<BlockhashInfo, String>> {
+        let client = self.clone();
+        Box::pin(async move {
+            tokio::task::spawn_blocking(move || {
+                let (hash, last_valid_block_height) = client
+                    .get_latest_blockhash_with_commitment(client.commitment())
+                    .map_err(|e| format!("get_latest_blockhash failed: {}", e))?;
+                let block_height = client
+                    .get_block_height()
+                    .map_err(|e| format!("get_block_height failed: {}", e))?;
+                Ok(BlockhashInfo {
+                    hash,
+                    last_valid_block_height,
+                    block_height,
+                })
+            })
+            .await
+            .map_err(|e| format!("blockhash fetch task failed: {}", e))?
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    info: BlockhashInfo,
+    fetched_at: Instant,
+}
+
+/// The most recently fetched blockhash.
+#[derive(Debug)]
+pub struct BlockhashCache {
+    entry: RwLock<Option<Entry>>,
+    max_age: Duration,
+}
+
+impl Default for BlockhashCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_AGE)
+    }
+}
+
+impl BlockhashCache {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            entry: RwLock::new(None),
+            max_age,
+        }
+    }
+
+    /// Store `info` as fetched now.
+    pub fn record(&self, info: BlockhashInfo) {
+        self.record_at(info, Instant::now());
+    }
+
+    pub fn record_at(&self, info: BlockhashInfo, fetched_at: Instant) {
+        let entry = Some(Entry { info, fetched_at });
+        match self.entry.write() {
+            Ok(mut guard) => *guard = entry,
+            Err(poisoned) => *poisoned.into_inner() = entry,
+        }
+    }
+
+    /// The cached hash if it is still safe to sign with.
+    pub fn cached(&self) -> Option<Hash> {
+        self.cached_at(Instant::now())
+    }
+
+    /// The cached hash if, at `now`, it is at most `max_age` old and the
+    /// estimated block height is more than [`EXPIRY_MARGIN_BLOCKS`] short of
+    /// its last valid block height.
+    pub fn cached_at(&self, now: Instant) -> Option<Hash> {
+        let entry = match self.entry.read() {
+            Ok(guard) => *guard,
+            Err(poisoned) => *poisoned.into_inner(),
+        }?;
+        let age = now.saturating_duration_since(entry.fetched_at);
+        if age > self.max_age {
+            return None;
+        }
+        let elapsed_blocks = (age.as_millis() / SLOT_TIME.as_millis()) as u64;
+        let est_height = entry.info.block_height + elapsed_blocks;
+        if est_height + EXPIRY_MARGIN_BLOCKS >= entry.info.last_valid_block_height {
+            return None;
+        }
+        Some(entry.info.hash)
+    }
+
+    /// The cached hash, or a fresh one from `source` (which is then cached)
+    /// when the cache is empty or stale.
+    pub async fn get_or_fetch(&self, source: &dyn BlockhashSource) -> Result<Hash, String> {
+        if let Some(hash) = self.cached() {
+            return Ok(hash);
+        }
+        let info = source.fetch_blockhash().await?;
+        self.record(info);
+        Ok(info.hash)
+    }
+}
+
+/// Process-wide cache fed by the blockhash processor and read by the send
+/// paths.
+pub static GLOBAL_BLOCKHASH_CACHE: Lazy<BlockhashCache> = Lazy::new(BlockhashCache::default);
+
+/// Background refresh interval from BLOCKHASH_REFRESH_MS (default 2000).
+pub fn refresh_interval_from_env() -> Duration {
+    if let Ok(v) = std::env::var("BLOCKHASH_REFRESH_MS") {
+        if let Ok(parsed) = v.parse::<u64>() {
+            if parsed > 0 {
+                return Duration::from_millis(parsed);
+            }
+        }
+    }
+    Duration::from_millis(2_000)
+}
+
+/// Refresh `cache` from `source` every `interval`. Failed fetches are logged
+/// and leave the previous hash to age out.
+pub fn spawn_blockhash_refresh(
+    cache: &'static BlockhashCache,
+    source: Arc<dyn BlockhashSource>,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match source.fetch_blockhash().await {
+                Ok(info) => cache.record(info),
+                Err(e) => eprintln!("[BLOCKHASH] refresh failed: {}", e),
+            }
+        }
+    })
+}
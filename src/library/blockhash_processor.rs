@@ -1,22 +1,15 @@
 use crate::common::logger::Logger;
+use crate::library::blockhash_cache::{
+    refresh_interval_from_env, spawn_blockhash_refresh, GLOBAL_BLOCKHASH_CACHE,
+};
 use anyhow::{anyhow, Result};
 use colored::Colorize;
-use lazy_static::lazy_static;
 use solana_client::rpc_client::RpcClient;
 use solana_sdk::hash::Hash;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
-
-// Global state for latest blockhash and timestamp
-lazy_static! {
-    static ref LATEST_BLOCKHASH: Arc<RwLock<Option<Hash>>> = Arc::new(RwLock::new(None));
-    static ref BLOCKHASH_LAST_UPDATED: Arc<RwLock<Option<Instant>>> = Arc::new(RwLock::new(None));
-}
-
-const BLOCKHASH_STALENESS_THRESHOLD: Duration = Duration::from_secs(10);
-const UPDATE_INTERVAL: Duration = Duration::from_millis(300);
 
+/// Keeps [`GLOBAL_BLOCKHASH_CACHE`] refreshed in the background so the send
+/// paths can sign without fetching a blockhash first.
 pub struct BlockhashProcessor {
     rpc_client: Arc<RpcClient>,
     logger: Logger,
@@ -33,67 +26,19 @@ impl BlockhashProcessor {
         self.logger
             .log("Starting blockhash processor...".green().to_string());
 
-        // Clone necessary components for the background task
-        let rpc_client = self.rpc_client.clone();
-        let logger = self.logger.clone();
-
-        tokio::spawn(async move {
-            loop {
-                match Self::update_blockhash_from_rpc(&rpc_client).await {
-                    Ok(blockhash) => {
-                        // Update global blockhash
-                        let mut latest = LATEST_BLOCKHASH.write().await;
-                        *latest = Some(blockhash);
-
-                        // Update timestamp
-                        let mut last_updated = BLOCKHASH_LAST_UPDATED.write().await;
-                        *last_updated = Some(Instant::now());
-
-                        // logger.log(format!("Updated latest blockhash: {}", blockhash));
-                    }
-                    Err(e) => {
-                        logger.log(
-                            format!("Error getting latest blockhash: {}", e)
-                                .red()
-                                .to_string(),
-                        );
-                    }
-                }
-
-                tokio::time::sleep(UPDATE_INTERVAL).await;
-            }
-        });
+        spawn_blockhash_refresh(
+            &GLOBAL_BLOCKHASH_CACHE,
+            Arc::new(self.rpc_client.clone()),
+            refresh_interval_from_env(),
+        );
 
         Ok(())
     }
 
-    async fn update_blockhash_from_rpc(rpc_client: &RpcClient) -> Result<Hash> {
-        rpc_client
-            .get_latest_blockhash()
-            .map_err(|e| anyhow!("Failed to get blockhash from RPC: {}", e))
-    }
-
-    /// Update the latest blockhash and its timestamp
-    async fn update_blockhash(hash: Hash) {
-        let mut latest = LATEST_BLOCKHASH.write().await;
-        *latest = Some(hash);
-
-        let mut last_updated = BLOCKHASH_LAST_UPDATED.write().await;
-        *last_updated = Some(Instant::now());
-    }
-
-    /// Get the latest cached blockhash with freshness check
+    /// Get the latest cached blockhash, or `None` if it is missing, stale or
+    /// close to expiry
     pub async fn get_latest_blockhash() -> Option<Hash> {
-        // Check if blockhash is stale
-        let last_updated = BLOCKHASH_LAST_UPDATED.read().await;
-        if let Some(instant) = *last_updated {
-            if instant.elapsed() > BLOCKHASH_STALENESS_THRESHOLD {
-                return None;
-            }
-        }
-
-        let latest = LATEST_BLOCKHASH.read().await;
-        *latest
+        GLOBAL_BLOCKHASH_CACHE.cached()
     }
 
     /// Get a fresh blockhash, falling back to RPC if necessary
@@ -108,12 +53,9 @@ impl BlockhashProcessor {
                 .yellow()
                 .to_string(),
         );
-        let new_hash = self
-            .rpc_client
-            .get_latest_blockhash()
-            .map_err(|e| anyhow!("Failed to get blockhash from RPC: {}", e))?;
-
-        Self::update_blockhash(new_hash).await;
-        Ok(new_hash)
+        GLOBAL_BLOCKHASH_CACHE
+            .get_or_fetch(&self.rpc_client)
+            .await
+            .map_err(|e| anyhow!("Failed to get blockhash from RPC: {}", e))
     }
 }
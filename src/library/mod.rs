@@ -1,6 +1,8 @@
+pub mod blockhash_cache;
 pub mod blockhash_processor;
 pub mod cache_maintenance;
 pub mod health_check;
 pub mod jupiter_api;
 pub mod rpc_client;
+pub mod rpc_failover;
 pub mod zeroslot;
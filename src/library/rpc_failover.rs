@@ -2,7 +2,7 @@
 // Simple RPC failover wrapper for the project's Anchor / solana_client RpcClient
 //
 // Usage:
-//   let rpc_set = RpcClientSet::new(primary_url, backup_url_option);
+//   let rpc_set = RpcClientSet::from_urls(&[primary_url, backup1, backup2]);
 //   let sig = rpc_set.send_and_confirm_transaction_with_failover(&tx)?;
 
 use std::sync::Arc;
@@ -12,75 +12,70 @@ use anchor_client::solana_client::rpc_client::RpcClient;
 use solana_sdk::transaction::Transaction;
 use solana_sdk::signature::Signature;
 
-/// Wrapper struct containing primary & optional backup RpcClient
+/// Wrapper struct containing a primary RpcClient and any number of backups
 #[derive(Clone)]
 pub struct RpcClientSet {
     pub primary: Arc<RpcClient>,
-    pub backup: Option<Arc<RpcClient>>,
+    pub backups: Vec<Arc<RpcClient>>,
 }
 
 impl RpcClientSet {
-    /// Construct from primary URL and optional backup URL.
-    pub fn new(primary_url: &str, backup_url: Option<&str>) -> Self {
+    fn client(url: &str) -> Arc<RpcClient> {
         // Using same commitment/timeout pattern as project
-        let timeout = Duration::from_secs(30);
-        let primary = Arc::new(
-            anchor_client::solana_client::rpc_client::RpcClient::new_with_timeout_and_commitment(
-                primary_url.to_string(),
-                timeout,
-                solana_sdk::commitment_config::CommitmentConfig::processed(),
-            ),
-        );
+        Arc::new(RpcClient::new_with_timeout_and_commitment(
+            url.to_string(),
+            Duration::from_secs(30),
+            solana_sdk::commitment_config::CommitmentConfig::processed(),
+        ))
+    }
 
-        let backup_client = backup_url.map(|u| {
-            Arc::new(
-                anchor_client::solana_client::rpc_client::RpcClient::new_with_timeout_and_commitment(
-                    u.to_string(),
-                    timeout,
-                    solana_sdk::commitment_config::CommitmentConfig::processed(),
-                ),
-            )
-        });
+    /// Construct from primary URL and optional backup URL.
+    pub fn new(primary_url: &str, backup_url: Option<&str>) -> Self {
+        match backup_url {
+            Some(backup) => Self::from_urls(&[primary_url, backup]),
+            None => Self::from_urls(&[primary_url]),
+        }
+    }
 
+    /// Construct from a list of URLs: the first is the primary, the rest are
+    /// backups tried in order.
+    ///
+    /// Panics if `urls` is empty.
+    pub fn from_urls(urls: &[&str]) -> Self {
+        let (primary, backups) = urls.split_first().expect("at least one RPC url");
         Self {
-            primary,
-            backup: backup_client,
+            primary: Self::client(primary),
+            backups: backups.iter().map(|u| Self::client(u)).collect(),
         }
     }
 
-    /// Try to send using primary; if that fails and a backup is configured, try the backup.
-    /// Returns the successful tx signature or the last error.
+    /// Try the primary, then each backup in order.
+    /// Returns the first successful tx signature, or an error listing every failure.
     pub fn send_and_confirm_transaction_with_failover(
         &self,
         tx: &Transaction,
     ) -> Result<Signature, Box<dyn std::error::Error>> {
-        // Try primary
-        match self.primary.send_and_confirm_transaction(tx) {
-            Ok(sig) => return Ok(sig),
-            Err(e_primary) => {
-                log::warn!("primary RPC send failed: {}", e_primary);
-                // If backup exists, try it
-                if let Some(backup) = &self.backup {
-                    // small blocking backoff
-                    std::thread::sleep(std::time::Duration::from_millis(150));
-                    match backup.send_and_confirm_transaction(tx) {
-                        Ok(sig2) => {
-                            log::warn!("backup RPC succeeded after primary failed");
-                            return Ok(sig2);
-                        }
-                        Err(e_backup) => {
-                            let err_msg = format!(
-                                "both RPCs failed: primary: {} ; backup: {}",
-                                e_primary, e_backup
-                            );
-                            return Err(Box::<dyn std::error::Error + Send + Sync>::from(err_msg));
-                        }
+        let mut errors = Vec::new();
+        for (i, client) in std::iter::once(&self.primary).chain(&self.backups).enumerate() {
+            if i > 0 {
+                // small blocking backoff
+                std::thread::sleep(Duration::from_millis(150));
+            }
+            match client.send_and_confirm_transaction(tx) {
+                Ok(sig) => {
+                    if i > 0 {
+                        log::warn!("backup RPC #{} ({}) succeeded after earlier failures", i, client.url());
                     }
-                } else {
-                    let err_msg = format!("primary RPC failed and no backup configured: {}", e_primary);
-                    return Err(Box::<dyn std::error::Error + Send + Sync>::from(err_msg));
+                    return Ok(sig);
+                }
+                Err(e) => {
+                    let label = if i == 0 { "primary".to_string() } else { format!("backup #{}", i) };
+                    log::warn!("{} RPC send failed: {}", label, e);
+                    errors.push(format!("{} ({}): {}", label, client.url(), e));
                 }
             }
         }
+        let err_msg = format!("all {} RPCs failed: {}", errors.len(), errors.join(" ; "));
+        Err(Box::<dyn std::error::Error + Send + Sync>::from(err_msg))
     }
 }
@@ -47,6 +47,10 @@ pub fn get_tip_account() -> Result<Pubkey> {
 }
 
 pub async fn get_tip_value() -> Result<f64> {
+    // Adaptive tip (seeded from ZERO_SLOT_TIP_VALUE) takes over when enabled
+    if let Some(adaptive) = crate::universal::mev::ADAPTIVE_TIP.as_ref() {
+        return Ok(adaptive.current_tip());
+    }
     // If ZERO_SLOT_TIP_VALUE is set, use it
     if let Ok(tip_value) = std::env::var("ZERO_SLOT_TIP_VALUE") {
         match f64::from_str(&tip_value) {
@@ -17,14 +17,38 @@ use colored::Colorize;
 use solana_program_pack::Pack;
 use solana_vntr_sniper::{
     block_engine::token,
-    common::{cache::WALLET_TOKEN_ACCOUNTS, config::Config, constants::RUN_MSG},
+    common::{
+        bot_config,
+        cache::{MINT_INFO_CACHE, WALLET_TOKEN_ACCOUNTS},
+        config::Config,
+        constants::RUN_MSG,
+        price_oracle,
+        rpc_failover::RpcFailover,
+    },
     library::{
         blockhash_processor::BlockhashProcessor, cache_maintenance, jupiter_api::JupiterClient,
     },
     processor::{
+        selling_strategy::TOKEN_METRICS,
         sniper_bot::{start_dex_monitoring, start_target_wallet_monitoring, SniperConfig},
         swap::SwapProtocol,
     },
+    universal::{
+        events, force_sell,
+        gates::stats::spawn_gate_stats_reporter,
+        metrics::{
+            birdeye_rest::install_rest_fallback_from_env,
+            birdeye_ws::spawn_birdeye_ws_client_from_env, GLOBAL_METRICS_STORE,
+        },
+        positions::{
+            reconcile_positions, restore_positions_from_env, spawn_position_reaper_from_env,
+            GLOBAL_POSITIONS_REGISTRY,
+        },
+        prometheus::spawn_metrics_server_from_env,
+        shutdown,
+        tp_sl::{spawn_tp_sl_monitor, tp_sl_monitor_enabled, TpSlConfig, TpSlMonitor},
+        wallet_config::WalletConfigMap,
+    },
 };
 use spl_associated_token_account::get_associated_token_address;
 use spl_token::instruction::sync_native;
@@ -306,24 +330,14 @@ async fn sell_all_tokens(config: &Config) -> Result<(), String> {
 
             total_token_count += 1;
 
-            // Get mint account to determine decimals
-            let mint_data = match config.app_state.rpc_client.get_account(&token_data.mint) {
-                Ok(data) => data,
-                Err(e) => {
-                    logger.log(
-                        format!("Failed to get mint data for {}: {}", token_data.mint, e)
-                            .yellow()
-                            .to_string(),
-                    );
-                    continue;
-                }
-            };
-
-            let mint_info = match spl_token::state::Mint::unpack(&mint_data.data) {
+            // Get mint decimals (cached; works for Token-2022 mints too)
+            let mint_info = match MINT_INFO_CACHE
+                .get_or_fetch(&config.app_state.rpc_client, &token_data.mint)
+            {
                 Ok(info) => info,
                 Err(e) => {
                     logger.log(
-                        format!("Failed to parse mint data for {}: {}", token_data.mint, e)
+                        format!("Failed to get mint info for {}: {}", token_data.mint, e)
                             .yellow()
                             .to_string(),
                     );
@@ -332,7 +346,7 @@ async fn sell_all_tokens(config: &Config) -> Result<(), String> {
             };
 
             let decimals = mint_info.decimals;
-            let token_amount = token_data.amount as f64 / 10f64.powi(decimals as i32);
+            let token_amount = mint_info.ui_amount(token_data.amount);
 
             logger.log(format!(
                 "📦 Found token: {} - Amount: {} (decimals: {})",
@@ -603,6 +617,32 @@ async fn main() {
     let config = Config::new().await;
     let config = config.lock().await;
 
+    // Settings file (BOT_CONFIG_PATH); env vars override it. Fail fast on a
+    // bad file rather than silently running on defaults.
+    if let Err(e) = bot_config::load() {
+        eprintln!("Failed to load bot config: {}", e);
+        return;
+    }
+    let wallets = match WalletConfigMap::from_config() {
+        Ok(wallets) => wallets,
+        Err(e) => {
+            eprintln!("Failed to load bot config: {}", e);
+            return;
+        }
+    };
+    let rpc = match RpcFailover::from_config() {
+        Ok(rpc) => rpc,
+        Err(e) => {
+            eprintln!("Failed to load bot config: {}", e);
+            return;
+        }
+    };
+    println!(
+        "Bot config loaded: {} wallet override(s), rpc failover {}",
+        wallets.len(),
+        if rpc.is_some() { "on" } else { "off" }
+    );
+
     /* Running Bot */
     let run_msg = RUN_MSG;
     println!("{}", run_msg);
@@ -622,6 +662,52 @@ async fn main() {
         }
     }
 
+    // Pick up positions saved by the last shutdown (POSITIONS_FILE)
+    let restored = restore_positions_from_env(&GLOBAL_POSITIONS_REGISTRY);
+    if restored > 0 {
+        println!("Restored {} open position(s) from POSITIONS_FILE", restored);
+    }
+
+    // Reconcile open positions against on-chain balances (e.g. after a crash)
+    let report = reconcile_positions(&GLOBAL_POSITIONS_REGISTRY, config.app_state.rpc_client.as_ref());
+    if report.checked > 0 {
+        println!(
+            "Position reconciliation: checked={} closed={} discrepancies={}",
+            report.checked,
+            report.closed.len(),
+            report.discrepancies.len()
+        );
+        for d in &report.discrepancies {
+            eprintln!("Position discrepancy wallet={} mint={}: {}", d.wallet, d.mint, d.detail);
+        }
+    }
+
+    // Auto-close positions stuck open past MAX_POSITION_AGE_SECS
+    spawn_position_reaper_from_env(&GLOBAL_POSITIONS_REGISTRY);
+
+    // Keep the SOL/USD oracle fresh for USD thresholds (SOL_USD_ORACLE)
+    price_oracle::spawn_sol_usd_refresher();
+
+    // Market metrics for the buy gates and dry run: Birdeye WS feed
+    // (BIRDEYE_WS_ENABLED) and on-miss REST back-fill (BIRDEYE_REST_FALLBACK)
+    if spawn_birdeye_ws_client_from_env(&GLOBAL_METRICS_STORE).is_some() {
+        println!("Streaming token metrics from Birdeye WS");
+    }
+    if install_rest_fallback_from_env(&GLOBAL_METRICS_STORE) {
+        println!("Back-filling missing token metrics from Birdeye REST");
+    }
+
+    // Periodic gate stats report (GATE_STATS_REPORT_PATH)
+    spawn_gate_stats_reporter();
+    // Prometheus counters on GET /metrics (METRICS_PORT)
+    spawn_metrics_server_from_env().await;
+    // Structured JSONL events for gate rejections / simulations (EVENTS_JSONL_PATH)
+    if events::install_from_env() {
+        println!("Writing structured events to EVENTS_JSONL_PATH");
+    }
+    // On SIGINT/SIGTERM: stop opens, drain sends, save state (SHUTDOWN_DRAIN_SECS)
+    shutdown::spawn_shutdown_handler();
+
     // Parse command line arguments
     let args: Vec<String> = std::env::args().collect();
     if args.len() > 1 {
@@ -705,6 +791,31 @@ async fn main() {
         selling_config,
     );
     selling_engine.log_selling_parameters();
+    // Panic exits from /exit, /exit_all and SHUTDOWN_FORCE_EXIT
+    force_sell::install_force_seller(Arc::new(selling_engine.clone()));
+
+    // Take-profit / stop-loss monitor over recorded positions (TP_SL_MONITOR)
+    if tp_sl_monitor_enabled() {
+        let prices = |mint: &str| {
+            TOKEN_METRICS
+                .get(mint)
+                .map(|m| m.current_price)
+                .filter(|p| *p > 0.0)
+        };
+        let tp_sl_cfg = TpSlConfig::from_env();
+        println!(
+            "TP/SL monitor: every {}s, tp x{:.2}, sl x{:.2}",
+            tp_sl_cfg.interval.as_secs(),
+            tp_sl_cfg.tp,
+            tp_sl_cfg.sl
+        );
+        spawn_tp_sl_monitor(Arc::new(TpSlMonitor::new(
+            &GLOBAL_POSITIONS_REGISTRY,
+            Arc::new(prices),
+            Arc::new(selling_engine.clone()),
+            tp_sl_cfg,
+        )));
+    }
 
     // Initialize copy selling for existing token balances
     match selling_engine
@@ -50,6 +50,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::time;
 
+use crate::common::cache::MINT_INFO_CACHE;
 use crate::common::config::{import_env_var, AppState, SwapConfig};
 use crate::common::logger::Logger;
 use crate::processor::sniper_bot::{BoughtTokenInfo, BOUGHT_TOKEN_LIST};
@@ -297,32 +298,23 @@ impl RiskManagementService {
                             .iter()
                             .any(|(token_mint, _)| token_mint == &mint_str)
                         {
-                            // Get mint info to calculate actual balance
-                            if let Ok(mint_data) = self
-                                .config
-                                .app_state
-                                .rpc_client
-                                .get_account(&parsed_account.mint)
+                            // Apply the mint's decimals so the balance compares
+                            // against the UI-denominated threshold
+                            if let Ok(mint_info) = MINT_INFO_CACHE
+                                .get_or_fetch(&self.config.app_state.rpc_client, &parsed_account.mint)
                             {
-                                if let Ok(mint_info) =
-                                    spl_token::state::Mint::unpack(&mint_data.data)
-                                {
-                                    let raw_balance = parsed_account.amount;
-                                    let decimals = mint_info.decimals;
-                                    let actual_balance =
-                                        raw_balance as f64 / 10_f64.powi(decimals as i32);
-
-                                    balances.insert(mint_str.clone(), actual_balance);
-
-                                    self.logger.log(
-                                        format!(
-                                            "Found target balance for {}: {:.2} tokens",
-                                            mint_str, actual_balance
-                                        )
-                                        .blue()
-                                        .to_string(),
-                                    );
-                                }
+                                let actual_balance = mint_info.ui_amount(parsed_account.amount);
+
+                                balances.insert(mint_str.clone(), actual_balance);
+
+                                self.logger.log(
+                                    format!(
+                                        "Found target balance for {}: {:.2} tokens",
+                                        mint_str, actual_balance
+                                    )
+                                    .blue()
+                                    .to_string(),
+                                );
                             }
                         }
                     }
@@ -10,6 +10,7 @@
     allow(unused_imports, dead_code)
 )]
 use crate::common::config::import_env_var;
+use crate::common::quote::QuoteMint;
 use crate::processor::monitor::PoolInfo;
 use anchor_client::solana_sdk::{
     hash::Hash,
@@ -32,7 +33,7 @@ use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use crate::common::timeseries as ts;
 use crate::common::{
-    cache::WALLET_TOKEN_ACCOUNTS,
+    cache::{MINT_INFO_CACHE, WALLET_TOKEN_ACCOUNTS},
     config::{AppState, SwapConfig},
     logger::Logger,
 };
@@ -401,6 +402,71 @@ impl TimeExitConfig {
     }
 }
 
+/// Fee-aware take profit: raises the TP so a take-profit exit still nets at
+/// least `min_net_profit_pct` after fees on small positions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeAwareTpConfig {
+    pub enabled: bool,
+    pub min_net_profit_pct: f64, // Minimum net-of-fees profit, e.g. 2.0 for 2%
+    pub fee_per_tx_sol: f64,     // Priority fee + tip per transaction (SOL)
+    pub swap_fee_bps: f64,       // DEX swap fee charged on each leg
+}
+
+impl Default for FeeAwareTpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_net_profit_pct: 0.0,
+            fee_per_tx_sol: 0.0005, // ~priority fee + zeroslot tip
+            swap_fee_bps: 100.0,    // 1% pump.fun style swap fee
+        }
+    }
+}
+
+impl FeeAwareTpConfig {
+    /// Enabled when MIN_NET_PROFIT_PCT is set; FEE_PER_TX_SOL and
+    /// SWAP_FEE_BPS tune the fee model.
+    pub fn set_from_env() -> Self {
+        let mut cfg = Self::default();
+        if let Ok(v) = std::env::var("MIN_NET_PROFIT_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.enabled = true;
+                cfg.min_net_profit_pct = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("FEE_PER_TX_SOL") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.fee_per_tx_sol = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("SWAP_FEE_BPS") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.swap_fee_bps = parsed;
+            }
+        }
+        cfg
+    }
+
+    /// TP percentage to use for a position that cost `position_sol`.
+    ///
+    /// Round trip costs are a fixed fee on the buy and the sell plus the swap
+    /// fee on each leg (the sell leg on the marked-up value). Solving
+    /// `C*tp - 2*fee - C*bps - C*(1+tp)*bps >= C*min` for `tp` gives the
+    /// smallest profitable TP; the configured TP is only ever raised.
+    pub fn effective_take_profit(&self, take_profit_pct: f64, position_sol: f64) -> f64 {
+        if !self.enabled || position_sol <= 0.0 {
+            return take_profit_pct;
+        }
+        let bps = self.swap_fee_bps / 10_000.0;
+        if bps >= 1.0 {
+            return take_profit_pct;
+        }
+        let fixed = 2.0 * self.fee_per_tx_sol / position_sol;
+        let required = (self.min_net_profit_pct / 100.0 + 2.0 * bps + fixed) / (1.0 - bps);
+        take_profit_pct.max(required * 100.0)
+    }
+}
+
 /// Configuration for dynamic whale selling based on PNL thresholds
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DynamicWhaleSelling {
@@ -492,6 +558,7 @@ pub struct SellingConfig {
     pub volume_analysis: VolumeAnalysisConfig,
     pub time_based: TimeExitConfig,
     pub dynamic_whale_selling: DynamicWhaleSelling,
+    pub fee_aware_tp: FeeAwareTpConfig,
 }
 
 impl Default for SellingConfig {
@@ -510,6 +577,7 @@ impl Default for SellingConfig {
             volume_analysis: VolumeAnalysisConfig::default(),
             time_based: TimeExitConfig::default(),
             dynamic_whale_selling: DynamicWhaleSelling::default(),
+            fee_aware_tp: FeeAwareTpConfig::default(),
         }
     }
 }
@@ -533,6 +601,7 @@ impl SellingConfig {
         let volume_analysis = VolumeAnalysisConfig::set_from_env();
         let time_based = TimeExitConfig::set_from_env();
         let dynamic_whale_selling = DynamicWhaleSelling::set_from_env();
+        let fee_aware_tp = FeeAwareTpConfig::set_from_env();
 
         Self {
             take_profit,
@@ -546,6 +615,7 @@ impl SellingConfig {
             volume_analysis,
             time_based,
             dynamic_whale_selling,
+            fee_aware_tp,
         }
     }
 }
@@ -1088,7 +1158,15 @@ impl SellingEngine {
             // Parse token account data
             if let Ok(parsed_account) = spl_token::state::Account::unpack(&account_data.data) {
                 let token_mint = parsed_account.mint.to_string();
-                let token_amount = parsed_account.amount as f64 / 10f64.powi(9); // Assume 9 decimals for simplicity
+                let token_amount = match MINT_INFO_CACHE
+                    .get_or_fetch(&self.app_state.rpc_client, &parsed_account.mint)
+                {
+                    Ok(mint_info) => mint_info.ui_amount(parsed_account.amount),
+                    Err(e) => {
+                        self.logger.log(format!("Skipping {}: {}", token_mint, e).yellow().to_string());
+                        continue;
+                    }
+                };
 
                 // Skip WSOL and very small amounts
                 if parsed_account.mint == spl_token::native_mint::id() || token_amount <= 0.000001 {
@@ -1313,6 +1391,22 @@ impl SellingEngine {
             _ => 0.0,
         };
 
+        // Rug detection: abrupt price collapse with vanishing liquidity
+        if crate::universal::rug::observe_and_blacklist(
+            &crate::universal::rug::GLOBAL_RUG_DETECTOR,
+            token_mint,
+            timestamp as i64,
+            price,
+            current_liquidity,
+        ) {
+            logger.log(
+                format!("☠️ RUG detected for {}: added to session blacklist", token_mint)
+                    .red()
+                    .bold()
+                    .to_string(),
+            );
+        }
+
         // Update token metrics using entry API
         let mut entry = TOKEN_METRICS
             .entry(token_mint.to_string())
@@ -1526,14 +1620,17 @@ impl SellingEngine {
     ) -> crate::universal::gates::TradeContext {
         use crate::universal::gates::TradeContext;
 
-        let price_usd = metrics.current_price;
+        // Prices, volume and market cap are tracked in SOL; the
+        // gates compare USD. Without an oracle price they stay in SOL.
+        let sol_usd = crate::common::price_oracle::sol_usd_price().unwrap_or(1.0);
+        let price_usd = metrics.current_price * sol_usd;
 
         // Approximate 5m and 15m volume windows from 24h volume
         // 24h = 288 x 5m, 24h = 96 x 15m
-        let window5m_usd = metrics.volume_24h / 288.0;
-        let window15m_usd = metrics.volume_24h / 96.0;
+        let window5m_usd = metrics.volume_24h / 288.0 * sol_usd;
+        let window15m_usd = metrics.volume_24h / 96.0 * sol_usd;
 
-        let notional = price_usd * metrics.amount_held.max(0.0);
+        let notional = metrics.current_price * metrics.amount_held.max(0.0);
         let depth_multiple = if notional > 0.0 {
             metrics.liquidity_at_current / notional
         } else {
@@ -1541,7 +1638,7 @@ impl SellingEngine {
         };
 
         let est_mcap_usd = if metrics.market_cap > 0.0 {
-            Some(metrics.market_cap)
+            Some(metrics.market_cap * sol_usd)
         } else {
             None
         };
@@ -1586,6 +1683,10 @@ impl SellingEngine {
             window_vol_pct,
             is_pumpfun,
             pumpfun_migrated: None,
+            amihud_5m: None,
+            range_eff_5m: None,
+            metrics_age_secs: Some(metrics.last_update.elapsed().as_secs() as i64),
+            ..TradeContext::default()
         }
     }
 
@@ -1690,12 +1791,17 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
             return Ok((true, false));
         }
 
-        // Standard take profit (fallback for lower PNL levels)
-        if pnl >= self.config.take_profit {
+        // Standard take profit (fallback for lower PNL levels), raised if
+        // needed so the exit clears fees on small positions
+        let take_profit = self
+            .config
+            .fee_aware_tp
+            .effective_take_profit(self.config.take_profit, metrics.cost_basis);
+        if pnl >= take_profit {
             self.logger.log(
                 format!(
                     "🎯 Selling due to take profit reached: {:.2}% >= {:.2}%",
-                    pnl, self.config.take_profit
+                    pnl, take_profit
                 )
                 .green()
                 .to_string(),
@@ -1988,8 +2094,11 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
         // Calculate token amount for selling
         let token_amount = metrics.amount_held;
 
-        // Convert to raw amount (assuming 9 decimals)
-        let _raw_token_amount = (token_amount * 1_000_000_000.0) as u64;
+        // Convert to raw amount using the mint's decimals
+        let _raw_token_amount = Pubkey::from_str(token_mint)
+            .ok()
+            .and_then(|mint| MINT_INFO_CACHE.get_or_fetch(&self.app_state.rpc_client, &mint).ok())
+            .map(|mint_info| mint_info.raw_amount(token_amount));
 
         // Create a DexType based on protocol
         let dex_type = match protocol_to_use {
@@ -2147,6 +2256,7 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
             liquidity: pool_quote_token_reserves.unwrap_or(0) as f64 / 1_000_000_000.0,
             virtual_sol_reserves: pool_quote_token_reserves.unwrap_or(0),
             virtual_token_reserves: pool_base_token_reserves.unwrap_or(0),
+            quote_mint: QuoteMint::Sol,
         })
     }
 
@@ -2324,6 +2434,7 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
                 is_whale_emergency,
                 parsed_data,
                 protocol,
+                false,
             ),
         )
         .await;
@@ -2349,6 +2460,57 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
         }
     }
 
+    /// Panic sell at market, ignoring sell gates.
+    ///
+    /// Only for `/exit_all` and kill-switch flows: sell-side gates are
+    /// bypassed (still evaluated and logged) and slippage is widened to
+    /// FORCE_SELL_SLIPPAGE_BPS. Getting out at any price beats being stuck.
+    pub async fn force_sell(
+        &self,
+        token_mint: &str,
+        parsed_data: Option<&TradeInfoFromToken>,
+        protocol: Option<SwapProtocol>,
+    ) -> Result<String> {
+        use tokio::time::{timeout, Duration};
+
+        self.logger.log(
+            format!(
+                "☠️ FORCE SELL requested for token: {} (gates bypassed, slippage {} bps)",
+                token_mint,
+                crate::universal::force_sell::force_sell_slippage_bps()
+            )
+            .red()
+            .bold()
+            .to_string(),
+        );
+
+        let timeout_duration = Duration::from_secs(30);
+        match timeout(
+            timeout_duration,
+            self.execute_emergency_sell_internal(token_mint, true, parsed_data, protocol, true),
+        )
+        .await
+        {
+            Ok(inner_result) => inner_result,
+            Err(_timeout_err) => {
+                self.logger.log(
+                    format!(
+                        "☠️ FORCE SELL timed out for token: {} ({}s timeout)",
+                        token_mint,
+                        timeout_duration.as_secs()
+                    )
+                    .red()
+                    .bold()
+                    .to_string(),
+                );
+                Err(anyhow!(
+                    "Force sell operation timed out after {}s",
+                    timeout_duration.as_secs()
+                ))
+            }
+        }
+    }
+
     /// Internal implementation of emergency sell without timeout wrapper
     async fn execute_emergency_sell_internal(
         &self,
@@ -2356,9 +2518,20 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
         is_whale_emergency: bool,
         parsed_data: Option<&TradeInfoFromToken>,
         protocol: Option<SwapProtocol>,
+        force: bool,
     ) -> Result<String> {
         // Log the type of emergency sell
-        if is_whale_emergency {
+        if force {
+            self.logger.log(
+                format!(
+                    "☠️ FORCE SELL executing for token: {} — sell gates and slippage limits ignored",
+                    token_mint
+                )
+                .red()
+                .bold()
+                .to_string(),
+            );
+        } else if is_whale_emergency {
             self.logger.log(
                 format!(
                     "🐋 WHALE EMERGENCY SELL triggered for token: {}",
@@ -2399,10 +2572,24 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
                     mcap_min: config.simulation.min_mcap,
                     vol_max_pct: config.simulation.max_vol_pct,
                     exclude_non_migrated: config.simulation.exclude_non_migrated,
+                    ..crate::universal::executor::SimConfig::default()
                 };
 
                 let backend = SimBackend::new(sim_cfg);
-                    let sim_result = backend.simulate(&trade_ctx, SimulationAction::Sell);
+                    let mut sim_result = backend.simulate(&trade_ctx, SimulationAction::Sell);
+                    if force && !sim_result.passed {
+                        self.logger.log(
+                            format!(
+                                "[DRY RUN][SIM] FORCE SELL bypassing gates for {}: {:?}",
+                                token_mint, sim_result.gate_reasons
+                            )
+                            .red()
+                            .bold()
+                            .to_string(),
+                        );
+                        sim_result.passed = true;
+                        sim_result.est_fill_pct = 1.0;
+                    }
 
                     self.logger.log(
                         format!(
@@ -2412,6 +2599,14 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
                         .cyan()
                         .to_string(),
                     );
+
+                    // Paper trading: close the simulated position the dry-run buy opened.
+                    #[cfg(feature = "position_tracking")]
+                    if sim_result.passed {
+                        if let Ok(wallet) = self.app_state.wallet.try_pubkey() {
+                            let _ = crate::universal::dry_run::dry_run_sell(&wallet.to_string(), token_mint);
+                        }
+                    }
                 } else {
                     self.logger.log(
                         format!(
@@ -2491,6 +2686,8 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
         emergency_config.amount_in = 1.0; // Sell 100% of tokens
                                           // Use higher slippage for whale emergency sells for faster execution
         emergency_config.slippage = if is_whale_emergency { 1500 } else { 1000 }; // 15% vs 10% slippage
+        emergency_config.slippage =
+            crate::universal::force_sell::sell_slippage_bps(emergency_config.slippage, force);
 
         // Create or use provided trade info
         let emergency_trade_info = if let Some(data) = parsed_data {
@@ -2525,6 +2722,7 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
                 liquidity: data.liquidity,
                 virtual_sol_reserves: data.virtual_sol_reserves,
                 virtual_token_reserves: data.virtual_token_reserves,
+                quote_mint: data.quote_mint,
             }
         } else {
             // Create trade info from metrics (for execute_emergency_sell_via_engine replacement)
@@ -2691,15 +2889,41 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
 
                                 #[cfg(feature = "position_tracking")]
                                 {
-                                    use crate::universal::positions::GLOBAL_POSITIONS_REGISTRY;
+                                    use crate::universal::positions::{PositionStatus, GLOBAL_POSITIONS_REGISTRY};
                                     if let Ok(wallet_pubkey) = self.app_state.wallet.try_pubkey() {
                                         let wallet_str = wallet_pubkey.to_string();
-                                        if let Err(e) = GLOBAL_POSITIONS_REGISTRY.record_close(&wallet_str, token_mint) {
-                                            self.logger.log(format!(
-                                                "⚠️ Failed to record close position for token {} (PumpSwap): {}",
-                                                token_mint,
-                                                e
-                                            ));
+                                        // Keep the slot until the sell confirms; a dropped sell
+                                        // restores the position so the exit is retried.
+                                        match GLOBAL_POSITIONS_REGISTRY.begin_close(&wallet_str, token_mint) {
+                                            Ok(()) => {
+                                                // Same unit as the recorded entry (SOL per token).
+                                                let exit_price = TOKEN_METRICS
+                                                    .get(token_mint)
+                                                    .map(|metrics| metrics.current_price)
+                                                    .filter(|price| *price > 0.0);
+                                                tokio::spawn(settle_sell_after_confirmation(
+                                                    self.app_state.rpc_nonblocking_client.clone(),
+                                                    wallet_str,
+                                                    token_mint.to_string(),
+                                                    signature.to_string(),
+                                                    exit_price,
+                                                    self.logger.clone(),
+                                                ));
+                                            }
+                                            Err(e) => {
+                                                // Already `Closing`: the exit that claimed it
+                                                // (e.g. the TP/SL monitor) settles it.
+                                                let claimed = GLOBAL_POSITIONS_REGISTRY
+                                                    .get_open_position(&wallet_str, token_mint)
+                                                    .map_or(false, |pos| pos.status == PositionStatus::Closing);
+                                                if !claimed {
+                                                    self.logger.log(format!(
+                                                        "⚠️ Failed to record close position for token {} (PumpSwap): {}",
+                                                        token_mint,
+                                                        e
+                                                    ));
+                                                }
+                                            }
                                         }
                                     }
                                 }
@@ -3235,15 +3459,7 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
                 let trade_ctx = TradeContext {
                     mint: "unknown".to_string(),
                     target_wallet: keypair.pubkey().to_string(),
-                    price_usd: 0.0,
-                    est_cost_bps: 0.0,
-                    window5m_usd: 0.0,
-                    window15m_usd: 0.0,
-                    depth_multiple: 0.0,
-                    est_mcap_usd: None,
-                    window_vol_pct: 0.0,
-                    is_pumpfun: false,
-                    pumpfun_migrated: None,
+                    ..TradeContext::default()
                 };
 
                 let sim_cfg = crate::universal::executor::SimConfig {
@@ -3253,6 +3469,7 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
                     mcap_min: config.simulation.min_mcap,
                     vol_max_pct: config.simulation.max_vol_pct,
                     exclude_non_migrated: config.simulation.exclude_non_migrated,
+                    ..crate::universal::executor::SimConfig::default()
                 };
 
                 let backend = SimBackend::new(sim_cfg);
@@ -3288,3 +3505,145 @@ pub async fn evaluate_sell_conditions(&self, token_mint: &str) -> Result<(bool,
         self.token_manager.get_active_tokens_count().await
     }
 }
+
+/// TP/SL monitor exits go through the regular emergency sell path. The
+/// monitor has already claimed the position, so this only sends the sell and
+/// reports how far it confirmed; the monitor settles the position.
+impl crate::universal::tp_sl::ExitExecutor for SellingEngine {
+    fn close<'a>(
+        &'a self,
+        signal: &'a crate::universal::tp_sl::ExitSignal,
+    ) -> futures::future::BoxFuture<
+        'a,
+        std::result::Result<Option<crate::universal::positions::SellCommitment>, String>,
+    > {
+        Box::pin(async move {
+            let signature = self
+                .unified_emergency_sell(&signal.mint, false, None, None)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(await_sell_commitment(&self.app_state.rpc_nonblocking_client, &signature).await)
+        })
+    }
+}
+
+/// Panic exits (`/exit`, `/exit_all`, SHUTDOWN_FORCE_EXIT) go through
+/// [`SellingEngine::force_sell`]. Positions the sell path did not already
+/// claim are closed here once the sell confirms.
+impl crate::universal::force_sell::ForceSeller for SellingEngine {
+    fn force_sell<'a>(
+        &'a self,
+        mint: &'a str,
+    ) -> futures::future::BoxFuture<'a, std::result::Result<String, String>> {
+        Box::pin(async move {
+            let signature = SellingEngine::force_sell(self, mint, None, None)
+                .await
+                .map_err(|e| e.to_string())?;
+
+            #[cfg(feature = "position_tracking")]
+            {
+                use crate::universal::positions::GLOBAL_POSITIONS_REGISTRY;
+                if let Ok(wallet_pubkey) = self.app_state.wallet.try_pubkey() {
+                    let wallet_str = wallet_pubkey.to_string();
+                    // Fails when the position is already `Closing`; whoever
+                    // claimed it settles it.
+                    if GLOBAL_POSITIONS_REGISTRY.begin_close(&wallet_str, mint).is_ok() {
+                        let exit_price = TOKEN_METRICS
+                            .get(mint)
+                            .map(|metrics| metrics.current_price)
+                            .filter(|price| *price > 0.0);
+                        tokio::spawn(settle_sell_after_confirmation(
+                            self.app_state.rpc_nonblocking_client.clone(),
+                            wallet_str,
+                            mint.to_string(),
+                            signature.clone(),
+                            exit_price,
+                            self.logger.clone(),
+                        ));
+                    }
+                }
+            }
+            Ok(signature)
+        })
+    }
+}
+
+/// Seconds to wait for a sell to reach SELL_MIN_COMMITMENT before treating it
+/// as dropped (SELL_CONFIRM_TIMEOUT_SECS, default 30).
+fn sell_confirm_timeout_secs() -> u64 {
+    std::env::var("SELL_CONFIRM_TIMEOUT_SECS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(30)
+}
+
+/// Poll a sell's signature status until it reaches SELL_MIN_COMMITMENT, fails
+/// or SELL_CONFIRM_TIMEOUT_SECS passes. Returns the commitment it reached
+/// (`None` if it never landed).
+async fn await_sell_commitment(
+    rpc: &anchor_client::solana_client::nonblocking::rpc_client::RpcClient,
+    signature: &str,
+) -> Option<crate::universal::positions::SellCommitment> {
+    use anchor_client::solana_sdk::signature::Signature;
+    use solana_transaction_status::TransactionConfirmationStatus;
+    use crate::universal::positions::SellCommitment;
+
+    let required = SellCommitment::from_env();
+    let deadline = Instant::now() + std::time::Duration::from_secs(sell_confirm_timeout_secs());
+    let mut reached = None;
+
+    if let Ok(sig) = Signature::from_str(signature) {
+        while Instant::now() < deadline {
+            if let Ok(resp) = rpc.get_signature_statuses(&[sig]).await {
+                if let Some(Some(status)) = resp.value.into_iter().next() {
+                    if status.err.is_some() {
+                        break;
+                    }
+                    reached = match status.confirmation_status {
+                        Some(TransactionConfirmationStatus::Processed) => Some(SellCommitment::Processed),
+                        Some(TransactionConfirmationStatus::Confirmed) => Some(SellCommitment::Confirmed),
+                        Some(TransactionConfirmationStatus::Finalized) => Some(SellCommitment::Finalized),
+                        None => reached,
+                    };
+                    if matches!(reached, Some(r) if r >= required) {
+                        break;
+                    }
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        }
+    }
+    reached
+}
+
+/// Wait for a sell to confirm and settle the `Closing` position: close it
+/// once SELL_MIN_COMMITMENT is reached, booking its PnL at `exit_price`, or
+/// restore it for retry if the sell fails or never lands within the timeout.
+#[cfg(feature = "position_tracking")]
+async fn settle_sell_after_confirmation(
+    rpc: Arc<anchor_client::solana_client::nonblocking::rpc_client::RpcClient>,
+    wallet: String,
+    mint: String,
+    signature: String,
+    exit_price: Option<f64>,
+    logger: Logger,
+) {
+    use crate::universal::positions::{CloseSettlement, SellCommitment, GLOBAL_POSITIONS_REGISTRY};
+
+    let required = SellCommitment::from_env();
+    let reached = await_sell_commitment(&rpc, &signature).await;
+    match GLOBAL_POSITIONS_REGISTRY.settle_close(&wallet, &mint, reached, required, exit_price) {
+        Ok(CloseSettlement::Closed) => {
+            logger.log(format!("✅ Sell for {} reached {:?}; position closed", mint, required));
+        }
+        Ok(CloseSettlement::Restored) => {
+            logger.log(format!(
+                "⚠️ Sell {} for {} did not reach {:?} (got {:?}); position restored for retry",
+                signature, mint, required, reached
+            ).yellow().to_string());
+        }
+        Err(e) => {
+            logger.log(format!("⚠️ Failed to settle close for {}: {}", mint, e));
+        }
+    }
+}
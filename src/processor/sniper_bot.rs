@@ -6,6 +6,7 @@ use std::time::Duration;
 use std::time::Instant;
 use std::sync::atomic::{AtomicBool, Ordering};
 use crate::common::config::import_env_var;
+use crate::common::quote::QuoteMint;
 use crate::processor::selling_strategy::{SellingEngine, SellingConfig};
 use anyhow::Result;
 
@@ -855,31 +856,180 @@ async fn verify_transaction(
 }
 
 
+/// Check a confirmed buy for a sandwich in the background, against the
+/// leader's fill (`quote_price`, SOL per token). Detections escalate later
+/// copies to zeroslot (see `landing_mode_for_wallet`).
+fn spawn_sandwich_check(
+    app_state: Arc<AppState>,
+    signature: String,
+    mint: String,
+    amount_in: f64,
+    quote_price: f64,
+) {
+    let detector = &crate::universal::mev::SANDWICH_DETECTOR;
+    if !detector.enabled() || !quote_price.is_finite() || quote_price <= 0.0 {
+        return;
+    }
+    tokio::spawn(async move {
+        let Ok(wallet) = app_state.wallet.try_pubkey() else {
+            return;
+        };
+        let verdict = crate::universal::mev::analyze_confirmed_buy(
+            &app_state.rpc_nonblocking_client,
+            &signature,
+            &wallet.to_string(),
+            &mint,
+            amount_in,
+            quote_price,
+        )
+        .await;
+        if let Some(crate::universal::mev::SandwichVerdict::Sandwiched { attacker, adverse_bps, .. }) = verdict {
+            let logger = Logger::new("[SANDWICH] => ".red().to_string());
+            logger.log(format!(
+                "🥪 Buy {} of {} sandwiched by {} ({:.0} bps adverse); private landing {}",
+                signature,
+                mint,
+                attacker,
+                adverse_bps,
+                if detector.prefer_private_landing() { "on" } else { "pending" }
+            ).red().to_string());
+        }
+    });
+}
+
+/// Gate context for copying `trade_info` from `leader_wallet` into
+/// `owner_wallet`: the mint's metrics from the global metrics store, any
+/// position we already hold, and the buy size in USD at the oracle SOL price.
+fn live_trade_context(
+    trade_info: &transaction_parser::TradeInfoFromToken,
+    leader_wallet: &str,
+    owner_wallet: &str,
+    swap_config: &SwapConfig,
+) -> Result<crate::universal::gates::TradeContext, String> {
+    let sol_usd = crate::common::quote::sol_usd_price()
+        .ok_or_else(|| "no SOL/USD price".to_string())?;
+    let mut ctx = crate::universal::context::build_trade_context(
+        &trade_info.mint,
+        leader_wallet,
+        owner_wallet,
+        &crate::universal::metrics::GLOBAL_METRICS_STORE,
+        &crate::universal::positions::GLOBAL_POSITIONS_REGISTRY,
+        swap_config.amount_in * sol_usd,
+    )
+    .map_err(|e| e.to_string())?;
+    ctx.leader_signature = Some(trade_info.signature.clone()).filter(|sig| !sig.is_empty());
+    Ok(ctx)
+}
+
+/// Run the buy gates, at `leader_wallet`'s thresholds, against the live
+/// context. Fails closed: a buy whose context cannot be built (e.g. no
+/// metrics for the mint yet) is rejected.
 #[cfg(feature = "universal_gates")]
 fn passes_universal_gates(
     trade_info: &transaction_parser::TradeInfoFromToken,
-) -> bool {
-    // TODO: implement real universal gates (liquidity, market cap, volatility, etc.).
-    // Stub returns true for now so behavior is unchanged while wiring is being built.
-    true
+    leader_wallet: &str,
+    owner_wallet: &str,
+    swap_config: &SwapConfig,
+) -> Result<(), String> {
+    use crate::universal::executor::{SimBackend, SimConfig, SimulationAction};
+
+    let ctx = live_trade_context(trade_info, leader_wallet, owner_wallet, swap_config)?;
+    let gates = SimBackend::new(SimConfig::for_wallet(leader_wallet)).gates_for(SimulationAction::Buy);
+    let (ok, reasons) = crate::universal::gates::run_gates(&ctx, &gates);
+    if ok {
+        return Ok(());
+    }
+    Err(reasons
+        .iter()
+        .map(|(gate, reason)| format!("{}: {}", gate, reason))
+        .collect::<Vec<_>>()
+        .join(", "))
 }
 
 
-// Maximum number of attempts for executing a buy transaction.
-// This is a simple global control; can be later made configurable per wallet.
-const EXECUTE_BUY_MAX_RETRIES: u32 = 3;
+/// Swap config for one buy attempt on `mint`: `swap_config` with its
+/// slippage raised to the learned per-mint floor in `book`, if any.
+pub fn attempt_swap_config(
+    book: &crate::universal::mint_slippage::MintSlippageBook,
+    mint: &str,
+    swap_config: &Arc<SwapConfig>,
+) -> Arc<SwapConfig> {
+    let slippage = book.effective_slippage_bps(mint, swap_config.slippage);
+    if slippage == swap_config.slippage {
+        return swap_config.clone();
+    }
+    let mut cfg = (**swap_config).clone();
+    cfg.slippage = slippage;
+    Arc::new(cfg)
+}
 
 /// Execute buy with simple retry logic.
-/// Retries the existing `execute_buy` up to EXECUTE_BUY_MAX_RETRIES times
-/// if it returns an error (e.g., exceeded slippage allowance or transient RPC issues).
+/// Retries the existing `execute_buy` up to the copied wallet's `retries`
+/// (DEFAULT_RETRIES unless overridden in the wallet config) times if it
+/// returns an error (e.g., exceeded slippage allowance or transient RPC issues).
+///
+/// Admission and, with the `universal_gates` feature, the buy gates run once
+/// up front. Without the feature live buys are not gated.
+///
+/// With `dry_run` set nothing is sent: the buy is paper-traded through
+/// [`crate::universal::dry_run::dry_run_buy`], which always runs the gates.
 pub async fn execute_buy_with_retry(
     trade_info: transaction_parser::TradeInfoFromToken,
     app_state: Arc<AppState>,
     swap_config: Arc<SwapConfig>,
     protocol: SwapProtocol,
+    leader_wallet: Option<String>,
 ) -> Result<(), String> {
     let logger = Logger::new("[EXECUTE-BUY-RETRY] => ".green().to_string());
     let mut attempt: u32 = 0;
+    let max_attempts = leader_wallet
+        .as_deref()
+        .and_then(|w| crate::universal::wallet_config::get_wallet_config_map().get(w).and_then(|c| c.retries))
+        .unwrap_or(crate::universal::wallet_config::types::DEFAULT_RETRIES);
+
+    // Admission and gates run once, not per attempt. The leader signature is
+    // claimed last, so a rejected buy does not consume it and a retry is not
+    // taken for a duplicate.
+    let owner_wallet = app_state.wallet.pubkey().to_string();
+    let leader = leader_wallet.as_deref().unwrap_or(&owner_wallet);
+    if let Err(reject) =
+        crate::universal::control::admission_check(leader, &owner_wallet, &trade_info.mint, None)
+    {
+        logger.log(format!("⏸️ Admission rejected buy for token {}: {}", trade_info.mint, reject).yellow().to_string());
+        return Err(format!("Admission rejected: {}", reject));
+    }
+
+    let dry_run = Config::get().await.dry_run;
+    if dry_run {
+        let ctx = match live_trade_context(&trade_info, leader, &owner_wallet, &swap_config) {
+            Ok(ctx) => ctx,
+            Err(e) => {
+                logger.log(format!("[DRY RUN] Paper buy of {} skipped: {}", trade_info.mint, e).yellow().to_string());
+                return Err(format!("Dry run: {}", e));
+            }
+        };
+        let result = crate::universal::dry_run::dry_run_buy(&owner_wallet, &ctx);
+        if !result.passed {
+            logger.log(format!("[DRY RUN] Gates blocked paper buy of {}: {:?}", trade_info.mint, result.gate_reasons).yellow().to_string());
+            return Err(format!("Dry run gates blocked buy: {:?}", result.gate_reasons));
+        }
+        logger.log(format!("[DRY RUN] Paper buy of {} recorded, nothing sent", trade_info.mint).green().to_string());
+        return Ok(());
+    }
+
+    #[cfg(feature = "universal_gates")]
+    {
+        if let Err(reason) = passes_universal_gates(&trade_info, leader, &owner_wallet, &swap_config) {
+            logger.log(format!("🚫 Gates blocked buy for token {}: {}", trade_info.mint, reason).yellow().to_string());
+            return Err(format!("Gates blocked buy: {}", reason));
+        }
+    }
+
+    let leader_sig = Some(trade_info.signature.as_str()).filter(|sig| !sig.is_empty());
+    if let Err(reject) = crate::universal::control::claim_leader_signature(leader_sig) {
+        logger.log(format!("⏸️ Skipping already copied trade for token {}: {}", trade_info.mint, reject).yellow().to_string());
+        return Err(format!("Admission rejected: {}", reject));
+    }
 
     loop {
         attempt += 1;
@@ -894,13 +1044,33 @@ pub async fn execute_buy_with_retry(
             .to_string(),
         );
 
+        // Use the learned per-mint slippage floor if this mint has been
+        // failing at the configured slippage.
+        let mint_slippage = &crate::universal::mint_slippage::GLOBAL_MINT_SLIPPAGE;
+        let attempt_config = attempt_swap_config(mint_slippage, &trade_info.mint, &swap_config);
+        let attempt_slippage = attempt_config.slippage;
+        if attempt_slippage != swap_config.slippage {
+            logger.log(
+                format!(
+                    "Using learned slippage {} bps (configured {}) for token {}",
+                    attempt_slippage,
+                    swap_config.slippage,
+                    trade_info.mint
+                )
+                .yellow()
+                .to_string(),
+            );
+        }
+
         match execute_buy(
             trade_info.clone(),
             app_state.clone(),
-            swap_config.clone(),
+            attempt_config,
             protocol.clone(),
+            leader_wallet.as_deref(),
         ).await {
             Ok(_) => {
+                mint_slippage.record_success(&trade_info.mint);
                 if attempt > 1 {
                     logger.log(
                         format!(
@@ -915,7 +1085,18 @@ pub async fn execute_buy_with_retry(
                 return Ok(());
             }
             Err(e) => {
-                if attempt >= EXECUTE_BUY_MAX_RETRIES {
+                if let Some(bumped) = mint_slippage.record_failure(&trade_info.mint, attempt_slippage) {
+                    logger.log(
+                        format!(
+                            "📈 Raised learned slippage for token {} to {} bps after repeated failures",
+                            trade_info.mint,
+                            bumped
+                        )
+                        .yellow()
+                        .to_string(),
+                    );
+                }
+                if attempt >= max_attempts {
                     logger.log(
                         format!(
                             "❌ Buy failed after {} attempts for token {}: {}",
@@ -949,31 +1130,34 @@ pub async fn execute_buy_with_retry(
     }
 }
 
-/// Execute buy operation based on detected transaction
+/// Execute buy operation based on detected transaction. Copies of
+/// `leader_wallet` land through its wallet-config `landing_mode`, zeroslot
+/// otherwise.
 pub async fn execute_buy(
     trade_info: transaction_parser::TradeInfoFromToken,
     app_state: Arc<AppState>,
     swap_config: Arc<SwapConfig>,
     protocol: SwapProtocol,
+    leader_wallet: Option<&str>,
 ) -> Result<(), String> {
     let logger = Logger::new("[EXECUTE-BUY] => ".green().to_string());
     let start_time = Instant::now();
-    
+    let landing_mode = crate::common::config::landing_mode_for_wallet(
+        leader_wallet,
+        crate::common::config::TransactionLandingMode::Zeroslot,
+    );
+
     // Check if this token is in the permanent blacklist (never rebuy)
     if BOUGHT_TOKENS_BLACKLIST.contains_key(&trade_info.mint) {
         logger.log(format!("🚫 Token {} is blacklisted (previously bought), skipping buy", trade_info.mint).yellow().to_string());
         return Err("Token is blacklisted - previously bought".to_string());
     }
-
-    #[cfg(feature = "universal_gates")]
-    {
-        if !passes_universal_gates(&trade_info) {
-            logger.log(format!("🚫 Universal gates blocked buy for token {} (stubbed)", trade_info.mint));
-            return Err("Universal gates blocked buy (stub)".to_string());
-        }
+    if crate::universal::blacklist::is_blacklisted(&trade_info.mint) {
+        logger.log(format!("🚫 Token {} is on the session blacklist, skipping buy", trade_info.mint).yellow().to_string());
+        return Err("Token is blacklisted - session blacklist".to_string());
     }
 
-    
+
     // Create a modified swap config based on the trade_info
     let mut buy_config = (*swap_config).clone();
     buy_config.swap_direction = SwapDirection::Buy;
@@ -1039,9 +1223,10 @@ pub async fn execute_buy(
                         }
                     };
                     println!("time taken for get_latest_blockhash: {:?}", start_time.elapsed());
-                    println!("using zeroslot for buy transaction >>>>>>>>");
-                    // Execute the transaction using zeroslot for buying
-                    match crate::block_engine::tx::new_signed_and_send_zeroslot(
+                    println!("using {:?} for buy transaction >>>>>>>>", landing_mode);
+                    // Execute the transaction through the wallet's landing mode
+                    match crate::block_engine::tx::new_signed_and_send_with_landing_mode(
+                        landing_mode.clone(),
                         &app_state,
                         recent_blockhash,
                         &keypair,
@@ -1062,6 +1247,7 @@ pub async fn execute_buy(
                                 Ok(verified) => {
                                     if verified {
                                         logger.log("Buy transaction verified successfully".to_string());
+                                        spawn_sandwich_check(app_state.clone(), signature.to_string(), trade_info.mint.clone(), amount_in, _amount_in / _token_amount);
                                         
                                         // Add token account to our global list and tracking
                                         if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
@@ -1153,9 +1339,10 @@ pub async fn execute_buy(
                         }
                     };
 
-                    println!("using zeroslot for buy transaction >>>>>>>>");
-                    // Execute the transaction using zeroslot for buying
-                    match crate::block_engine::tx::new_signed_and_send_zeroslot(
+                    println!("using {:?} for buy transaction >>>>>>>>", landing_mode);
+                    // Execute the transaction through the wallet's landing mode
+                    match crate::block_engine::tx::new_signed_and_send_with_landing_mode(
+                        landing_mode.clone(),
                         &app_state,
                         recent_blockhash,
                         &keypair,
@@ -1175,6 +1362,7 @@ pub async fn execute_buy(
                                 Ok(verified) => {
                                     if verified {
                                         logger.log("Buy transaction verified successfully".to_string());
+                                        spawn_sandwich_check(app_state.clone(), signature.to_string(), trade_info.mint.clone(), amount_in, _amount_in / _token_amount);
                                         
                                         // Add token account to our global list and tracking
                                         if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
@@ -1212,7 +1400,18 @@ pub async fn execute_buy(
                                                     .duration_since(std::time::UNIX_EPOCH)
                                                     .unwrap_or_default()
                                                     .as_secs() as i64;
-                                                if let Err(e) = GLOBAL_POSITIONS_REGISTRY.record_open(&wallet_str, &trade_info.mint, now_ts) {
+                                                // Entry in SOL per token (the leader's fill), for TP/SL and PnL.
+                                                let entry_price = if _token_amount > 0.0 {
+                                                    Some(_amount_in / _token_amount)
+                                                } else {
+                                                    None
+                                                };
+                                                let open_params = crate::universal::positions::OpenParams {
+                                                    size: entry_price.map(|p| amount_in / p),
+                                                    entry_price,
+                                                    slippage_bps: Some(buy_config.slippage as f64),
+                                                };
+                                                if let Err(e) = GLOBAL_POSITIONS_REGISTRY.record_open_with(&wallet_str, &trade_info.mint, now_ts, open_params) {
                                                     logger.log(format!(
                                                         "⚠️ Failed to record open position for token {} (PumpSwap): {}",
                                                         trade_info.mint,
@@ -1275,8 +1474,9 @@ pub async fn execute_buy(
                         }
                     };
                     
-                    // Execute the transaction using zeroslot for buying
-                    match crate::block_engine::tx::new_signed_and_send_zeroslot(
+                    // Execute the transaction through the wallet's landing mode
+                    match crate::block_engine::tx::new_signed_and_send_with_landing_mode(
+                        landing_mode.clone(),
                         &app_state,
                         recent_blockhash,
                         &keypair,
@@ -1296,6 +1496,7 @@ pub async fn execute_buy(
                                 Ok(verified) => {
                                     if verified {
                                         logger.log("Buy transaction verified successfully".to_string());
+                                        spawn_sandwich_check(app_state.clone(), signature.to_string(), trade_info.mint.clone(), amount_in, _amount_in / _token_amount);
                                         
                                         // Add token account to our global list and tracking
                                         if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
@@ -1381,9 +1582,10 @@ pub async fn execute_buy(
                         }
                     };
                     println!("time taken for get_latest_blockhash: {:?}", start_time.elapsed());
-                    println!("using zeroslot for buy transaction >>>>>>>>");
-                    // Execute the transaction using zeroslot for buying
-                    match crate::block_engine::tx::new_signed_and_send_zeroslot(
+                    println!("using {:?} for buy transaction >>>>>>>>", landing_mode);
+                    // Execute the transaction through the wallet's landing mode
+                    match crate::block_engine::tx::new_signed_and_send_with_landing_mode(
+                        landing_mode.clone(),
                         &app_state,
                         recent_blockhash,
                         &keypair,
@@ -1403,6 +1605,7 @@ pub async fn execute_buy(
                                 Ok(verified) => {
                                     if verified {
                                         logger.log("Buy transaction verified successfully".to_string());
+                                        spawn_sandwich_check(app_state.clone(), signature.to_string(), trade_info.mint.clone(), amount_in, _amount_in / _token_amount);
                                         
                                         // Add token account to our global list and tracking
                                         if let Ok(wallet_pubkey) = app_state.wallet.try_pubkey() {
@@ -1886,6 +2089,7 @@ async fn execute_pumpfun_emergency_sell_with_normal(
                 recent_blockhash,
                 &keypair,
                 instructions,
+                app_state.rpc_nonblocking_client.commitment(),
                 logger,
             ).await {
                 Ok(signatures) => {
@@ -1933,6 +2137,7 @@ async fn execute_pumpswap_emergency_sell_with_normal(
                 recent_blockhash,
                 &keypair,
                 instructions,
+                app_state.rpc_nonblocking_client.commitment(),
                 logger,
             ).await {
                 Ok(signatures) => {
@@ -1980,6 +2185,7 @@ async fn execute_raydium_emergency_sell_with_normal(
                 recent_blockhash,
                 &keypair,
                 instructions,
+                app_state.rpc_nonblocking_client.commitment(),
                 logger,
             ).await {
                 Ok(signatures) => {
@@ -2256,6 +2462,7 @@ fn create_sell_trade_info_from_original(
         liquidity: original_trade_info.liquidity,
         virtual_sol_reserves: original_trade_info.virtual_sol_reserves,
         virtual_token_reserves: original_trade_info.virtual_token_reserves,
+        quote_mint: original_trade_info.quote_mint,
     }
 }
 
@@ -2443,6 +2650,7 @@ async fn execute_pumpfun_sell_with_normal(
                 recent_blockhash,
                 &keypair,
                 instructions,
+                app_state.rpc_nonblocking_client.commitment(),
                 logger,
             ).await {
                 Ok(signatures) => {
@@ -2494,6 +2702,7 @@ async fn execute_pumpswap_sell_with_normal(
                 recent_blockhash,
                 &keypair,
                 instructions,
+                app_state.rpc_nonblocking_client.commitment(),
                 logger,
             ).await {
                 Ok(signatures) => {
@@ -2596,6 +2805,7 @@ async fn execute_raydium_sell_with_normal(
                 recent_blockhash,
                 &keypair,
                 instructions,
+                app_state.rpc_nonblocking_client.commitment(),
                 logger,
             ).await {
                 Ok(signatures) => {
@@ -2663,6 +2873,7 @@ pub async fn execute_sell(
         liquidity: trade_info.liquidity,
         virtual_sol_reserves: trade_info.virtual_sol_reserves,
         virtual_token_reserves: trade_info.virtual_token_reserves,
+        quote_mint: trade_info.quote_mint,
     };
 
     // Create a modified swap config for selling
@@ -2810,6 +3021,7 @@ pub async fn execute_sell(
                         liquidity: 0.0,
                         virtual_sol_reserves: 0,
                         virtual_token_reserves: 0,
+                        quote_mint: QuoteMint::Sol,
                     };
                     
                     // Build swap instructions for sell
@@ -2891,6 +3103,7 @@ pub async fn execute_sell(
                         liquidity: trade_info.liquidity,
                         virtual_sol_reserves: trade_info.virtual_sol_reserves,
                         virtual_token_reserves: trade_info.virtual_token_reserves,
+                        quote_mint: trade_info.quote_mint,
                     };
                     
                     // Build swap instructions for sell - use chunk_sell_config
@@ -2976,6 +3189,7 @@ pub async fn execute_sell(
                         liquidity: trade_info.liquidity,
                         virtual_sol_reserves: trade_info.virtual_sol_reserves,
                         virtual_token_reserves: trade_info.virtual_token_reserves,
+                        quote_mint: trade_info.quote_mint,
                     };
                     
                     match raydium.build_swap_from_parsed_data(&trade_info_clone, sell_config.clone()).await {
@@ -3056,6 +3270,7 @@ pub async fn execute_sell(
                         liquidity: 0.0,
                         virtual_sol_reserves: 0,
                         virtual_token_reserves: 0,
+                        quote_mint: QuoteMint::Sol,
                     };
                     
                     match pump.build_swap_from_parsed_data(&trade_info_clone, sell_config.clone()).await {
@@ -3192,6 +3407,7 @@ pub async fn execute_sell(
                     liquidity: 0.0,
                     virtual_sol_reserves: 0,
                     virtual_token_reserves: 0,
+                    quote_mint: QuoteMint::Sol,
                 };
                 
                 // Build swap instructions for sell
@@ -3253,6 +3469,7 @@ pub async fn execute_sell(
                 liquidity: 0.0,
                 virtual_sol_reserves: 0,
                 virtual_token_reserves: 0,
+                quote_mint: QuoteMint::Sol,
             };
                 
                 // Use the new retry mechanism with Jupiter fallback
@@ -3308,6 +3525,7 @@ pub async fn execute_sell(
                     liquidity: trade_info.liquidity,
                     virtual_sol_reserves: trade_info.virtual_sol_reserves,
                     virtual_token_reserves: trade_info.virtual_token_reserves,
+                    quote_mint: trade_info.quote_mint,
                 };
                 
                 // Use the new retry mechanism with Jupiter fallback
@@ -3366,6 +3584,7 @@ pub async fn execute_sell(
                     liquidity: 0.0,
                     virtual_sol_reserves: 0,
                     virtual_token_reserves: 0,
+                    quote_mint: QuoteMint::Sol,
                 };
                 
                 // Use the new retry mechanism with Jupiter fallback
@@ -3561,7 +3780,7 @@ async fn process_message_for_dex_monitoring(
 
 /// SNIPER BOT: Main logic for handling both target wallet and DEX monitoring transactions
 async fn handle_sniper_bot_logic(
-    parsed_data: transaction_parser::TradeInfoFromToken,
+    mut parsed_data: transaction_parser::TradeInfoFromToken,
     config: Arc<SniperConfig>,
     target_signature: Option<Signature>,
     txn: &SubscribeUpdateTransaction,
@@ -3571,6 +3790,15 @@ async fn handle_sniper_bot_logic(
     
     // Extract signer from transaction to identify target wallet
     if let Some(ref target_signature) = target_signature {
+        parsed_data.signature = target_signature.to_string();
+        // The same leader trade can arrive from more than one ingest source.
+        // Buys are deduplicated by the admission check before the copy.
+        if !parsed_data.is_buy
+            && !crate::universal::dedup::SEEN_LEADER_SIGNATURES.first_seen(&parsed_data.signature)
+        {
+            logger.log(format!("Skipping already handled leader trade {}", target_signature));
+            return Ok(());
+        }
         // Extract the actual signer from the transaction
         if let Some(signer) = extract_signer_from_transaction(txn) {
             // Check if this transaction is from one of our target wallets
@@ -3583,12 +3811,46 @@ async fn handle_sniper_bot_logic(
                     parsed_data.sol_change.abs()
                 ).purple().bold().to_string());
                 
-                // Handle buy transactions from target wallets
-                if parsed_data.is_buy {
-                    return handle_target_wallet_buy(parsed_data, config, logger, signer).await;
-                } else {
-                    // Handle sell transactions from target wallets
-                    return handle_target_wallet_sell(parsed_data, config, logger).await;
+                // The wallet's copy mode decides which side we mirror; buys
+                // from a disabled wallet are not copied
+                let side = if parsed_data.is_buy { "buy" } else { "sell" };
+                match crate::universal::executor::copy_action_for_wallet(&signer, side) {
+                    crate::universal::executor::CopyAction::Buy => {
+                        // Skip dust buys below the wallet's min_leader_trade_usd
+                        if let Some(sol_usd) = crate::common::quote::sol_usd_price() {
+                            let leader_trade = crate::universal::models::SimInput {
+                                ts: chrono::Utc::now().timestamp(),
+                                target_wallet: signer.clone(),
+                                mint: parsed_data.mint.clone(),
+                                side: side.to_string(),
+                                qty: parsed_data.sol_change.abs(),
+                                price_usd: sol_usd,
+                            };
+                            if let Some(reason) =
+                                crate::universal::executor::leader_trade_too_small_for_wallet(&leader_trade)
+                            {
+                                logger.log(format!("Skipping buy by {}: {}", signer, reason));
+                                return Ok(());
+                            }
+                        }
+                        return handle_target_wallet_buy(parsed_data, config, logger, signer).await;
+                    }
+                    crate::universal::executor::CopyAction::Sell
+                    | crate::universal::executor::CopyAction::Close => {
+                        return handle_target_wallet_sell(parsed_data, config, logger).await;
+                    }
+                    crate::universal::executor::CopyAction::Skip => {
+                        let reason = if crate::universal::wallet_config::is_wallet_enabled(&signer) {
+                            format!(
+                                "copy_mode={}",
+                                crate::universal::wallet_config::get_wallet_config_map().get_copy_mode(&signer)
+                            )
+                        } else {
+                            "wallet disabled".to_string()
+                        };
+                        logger.log(format!("Skipping {} by {}: {}", side, signer, reason));
+                        return Ok(());
+                    }
                 }
             }
         }
@@ -3748,8 +4010,7 @@ async fn handle_volume_based_buying(
 ) -> Result<(), String> {
     let mint = parsed_data.mint.clone();
     // Read sniper trigger config once (avoid awaiting while holding map guard)
-    let cfg_guard = crate::common::config::Config::get().await;
-    let trigger_size = cfg_guard.focus_trigger_sol.max(1.0);
+    let trigger_size = crate::common::config::Config::get().await.focus_trigger_sol.max(1.0);
     
     // Check if token is in focus list
     if let Some(mut focus_info) = FOCUS_TOKEN_LIST.get_mut(&mint) {
@@ -3888,6 +4149,24 @@ async fn start_price_monitoring(
     Ok(())
 }
 
+/// The followed wallet a focus token's buy copies: the most trusted of the
+/// whales that bought it (ties broken by pubkey).
+fn focus_token_leader(mint: &str) -> Option<String> {
+    let wallets = crate::universal::wallet_config::get_wallet_config_map();
+    FOCUS_TOKEN_LIST.get(mint).and_then(|focus_info| {
+        focus_info
+            .whale_wallets
+            .iter()
+            .max_by(|a, b| {
+                wallets
+                    .get_trust(a)
+                    .total_cmp(&wallets.get_trust(b))
+                    .then_with(|| b.cmp(a))
+            })
+            .cloned()
+    })
+}
+
 /// SNIPER BOT: Execute buy when sniper conditions are met
 async fn execute_sniper_buy(
     parsed_data: transaction_parser::TradeInfoFromToken,
@@ -3919,6 +4198,7 @@ async fn execute_sniper_buy(
         config.app_state.clone().into(),
         Arc::new(config.swap_config.clone()),
         protocol.clone(),
+        focus_token_leader(&mint),
     ).await {
         Ok(_) => {
             // Update focus token buy count
@@ -3970,6 +4250,14 @@ async fn handle_parsed_data_for_selling(
                     signer, parsed_data.mint, parsed_data.sol_change.abs()
                 ).purple().bold().to_string());
                 
+                // The source exited, so any entry we parked copying its buy is void
+                for cancelled in crate::universal::limit_entry::GLOBAL_LIMIT_ENTRIES.on_source_sell(&signer, &parsed_data.mint) {
+                    logger.log(format!(
+                        "🗑️ Cancelled pending limit entry #{} for token {}: {}",
+                        cancelled.entry.id, parsed_data.mint, cancelled.reason
+                    ).yellow().to_string());
+                }
+                
                 // Check if we own this token
                 if let Some(mut token_info) = BOUGHT_TOKEN_LIST.get_mut(&parsed_data.mint) {
                     logger.log(format!(
@@ -4251,6 +4539,13 @@ async fn setup_selling_strategy(
     protocol_preference: SwapProtocol,
 ) -> Result<(), String> {
     let logger = Logger::new("[SETUP-SELLING-STRATEGY] => ".green().to_string());
+
+    // Dry-run buys hold nothing on-chain; the TP/SL monitor exits the paper
+    // position.
+    if Config::get().await.dry_run {
+        logger.log(format!("[DRY RUN] No selling strategy for paper position in {}", token_mint));
+        return Ok(());
+    }
     
     // Initialize
     logger.log(format!("Setting up selling strategy for token: {}", token_mint));
@@ -4661,6 +4956,7 @@ async fn handle_parsed_data_for_buying(
             config.app_state.clone().into(),
             Arc::new(config.swap_config.clone()),
             protocol.clone(),
+            None,
         ).await {
             Err(e) => {
                 logger.log(format!("Error executing buy: {}", e).red().to_string());
@@ -10,6 +10,7 @@
     allow(unused_imports, dead_code)
 )]
 use crate::common::logger::Logger;
+use crate::common::quote::{QuoteConverter, QuoteMint};
 use bs58;
 use colored::Colorize;
 use lazy_static;
@@ -54,6 +55,9 @@ pub struct TradeInfoFromToken {
     pub liquidity: f64, // this is for filtering out small trades
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
+    /// Mint the pool is quoted in. `sol_change`, `liquidity` and `price`
+    /// are always converted to SOL regardless of the quote.
+    pub quote_mint: QuoteMint,
 }
 /// Helper function to check if transaction contains MintTo instruction
 /// NOTE: This function is no longer used - we now process all transactions regardless of MintTo
@@ -70,6 +74,25 @@ fn _has_mint_to_instruction(txn: &SubscribeUpdateTransaction) -> bool {
     false
 }
 
+/// Detect the pool's quote mint from the mints touched by the transaction
+fn detect_quote_mint(txn: &SubscribeUpdateTransaction) -> QuoteMint {
+    if let Some(tx_inner) = &txn.transaction {
+        if let Some(meta) = &tx_inner.meta {
+            return QuoteMint::detect(meta.post_token_balances.iter().map(|b| b.mint.as_str()));
+        }
+    }
+    QuoteMint::Sol
+}
+
+/// Multiplier converting quote amounts decoded as lamports into SOL.
+/// Returns None for non-SOL quotes while no SOL price is known yet.
+fn quote_rescale(quote: QuoteMint) -> Option<f64> {
+    match quote {
+        QuoteMint::Sol => Some(1.0),
+        _ => QuoteConverter::current().map(|c| c.sol_rescale(quote)),
+    }
+}
+
 /// Helper function to check if transaction contains Buy instruction
 fn has_buy_instruction(txn: &SubscribeUpdateTransaction) -> bool {
     if let Some(tx_inner) = &txn.transaction {
@@ -136,12 +159,12 @@ pub fn parse_transaction_data(
                 if !meta.post_token_balances.is_empty() {
                     mint = meta.post_token_balances[0].mint.clone();
 
-                    // Check if this is a reverse case (WSOL is the first mint)
-                    if mint == "So11111111111111111111111111111111111111112" {
+                    // Check if this is a reverse case (a quote mint is the first mint)
+                    if QuoteMint::is_quote_mint(&mint) {
                         // In reverse case, look for the second mint which should be the token
                         if meta.post_token_balances.len() > 1 {
                             mint = meta.post_token_balances[1].mint.clone();
-                            if mint == "So11111111111111111111111111111111111111112" {
+                            if QuoteMint::is_quote_mint(&mint) {
                                 // In reverse case, look for the second mint which should be the token
                                 if meta.post_token_balances.len() > 2 {
                                     mint = meta.post_token_balances[2].mint.clone();
@@ -264,6 +287,13 @@ pub fn parse_transaction_data(
                 pool_base_token_reserves as f64 / 1_000_000_000.0
             };
 
+            // Amounts above were decoded as lamports; convert USDC-quoted pools to SOL
+            let quote = detect_quote_mint(txn);
+            let rescale = quote_rescale(quote)?;
+            let sol_change = sol_change * rescale;
+            let liquidity = liquidity * rescale;
+            let price = (price as f64 * rescale) as u64;
+
             dex_log(
                 format!(
                     "PumpSwap {}: {} SOL (Price: {}) Reverse: {}",
@@ -293,6 +323,7 @@ pub fn parse_transaction_data(
                 // Map pool reserves to virtual reserves as requested
                 virtual_sol_reserves: pool_quote_token_reserves,
                 virtual_token_reserves: pool_base_token_reserves,
+                quote_mint: quote,
             })
         }
 
@@ -317,7 +348,7 @@ pub fn parse_transaction_data(
             let pool_id = parse_public_key(buffer, 128)?;
 
             // Determine if this is a reverse case by checking if the mint is WSOL
-            let _is_reverse_when_pump_swap = mint == "So11111111111111111111111111111111111111112";
+            let _is_reverse_when_pump_swap = QuoteMint::is_quote_mint(&mint);
 
             // Determine buy/sell based on reverse case and log messages
             let is_buy = if _is_reverse_when_pump_swap {
@@ -380,6 +411,13 @@ pub fn parse_transaction_data(
                 pool_base_token_reserves as f64 / 1_000_000_000.0
             };
 
+            // Amounts above were decoded as lamports; convert USDC-quoted pools to SOL
+            let quote = detect_quote_mint(txn);
+            let rescale = quote_rescale(quote)?;
+            let sol_change = sol_change * rescale;
+            let liquidity = liquidity * rescale;
+            let price = (price as f64 * rescale) as u64;
+
             dex_log(
                 format!(
                     "PumpSwap {}: {} SOL (Price: {}) Reverse: {}",
@@ -409,6 +447,7 @@ pub fn parse_transaction_data(
                 // Map pool reserves to virtual reserves as requested
                 virtual_sol_reserves: pool_quote_token_reserves,
                 virtual_token_reserves: pool_base_token_reserves,
+                quote_mint: quote,
             })
         }
 
@@ -472,6 +511,7 @@ pub fn parse_transaction_data(
                 liquidity,
                 virtual_sol_reserves,
                 virtual_token_reserves,
+                quote_mint: QuoteMint::Sol, // bonding curves are always SOL-quoted
             })
         }
 
@@ -536,6 +576,7 @@ pub fn parse_transaction_data(
                 liquidity,
                 virtual_sol_reserves,
                 virtual_token_reserves,
+                quote_mint: QuoteMint::Sol, // bonding curves are always SOL-quoted
             })
         }
 
@@ -600,6 +641,7 @@ pub fn parse_transaction_data(
                 liquidity,
                 virtual_sol_reserves,
                 virtual_token_reserves,
+                quote_mint: QuoteMint::Sol, // bonding curves are always SOL-quoted
             })
         }
 
@@ -651,6 +693,12 @@ pub fn parse_transaction_data(
                 );
                 0u64
             };
+            // Launchpad pools may be quoted in USDC; convert to SOL
+            let quote = detect_quote_mint(txn);
+            let rescale = quote_rescale(quote)?;
+            let price = (price as f64 * rescale) as u64;
+            let sol_change_amount = sol_change_amount * rescale;
+
             // For Raydium Launchpad:
             // - Buy: SOL decreases (negative), tokens increase (positive)
             // - Sell: SOL increases (positive), tokens decrease (negative)
@@ -675,9 +723,10 @@ pub fn parse_transaction_data(
                 coin_creator: None,               // no need for raydium launchpad
                 sol_change,
                 token_change,
-                liquidity: real_quote_after as f64 / 1_000_000_000.0,
+                liquidity: real_quote_after as f64 / 1_000_000_000.0 * rescale,
                 virtual_sol_reserves: virtual_quote_reserve,
                 virtual_token_reserves: virtual_base_reserve,
+                quote_mint: quote,
             })
         }
         _ => None,
@@ -0,0 +1,174 @@
+//! Offline backtest of the simulation gates over recorded trade contexts.
+//!
+//! Inputs are JSONL files with one serialized [`TradeContext`] per line, e.g.
+//! captured during a dry run or rebuilt from a replayed WS feed. Each row is
+//! run through an [`ExecutionSimulator`] and the results are folded into a
+//! [`BacktestSummary`], so threshold changes can be compared offline against
+//! the same market data.
+
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::universal::executor::{ExecutionSimulator, SimulationAction, SimulationResult};
+use crate::universal::gates::TradeContext;
+
+/// One simulated input and its outcome.
+#[derive(Clone, Debug, Serialize)]
+pub struct BacktestRow {
+    pub mint: String,
+    pub target_wallet: String,
+    pub passed: bool,
+    /// Gate that rejected the trade, if any.
+    pub gate: Option<String>,
+    pub reason: Option<String>,
+    pub est_fill_pct: f64,
+    pub est_pnl_usd: Option<f64>,
+}
+
+impl BacktestRow {
+    fn new(ctx: &TradeContext, result: &SimulationResult) -> Self {
+        let rejection = result.gate_reasons.first();
+        Self {
+            mint: ctx.mint.clone(),
+            target_wallet: ctx.target_wallet.clone(),
+            passed: result.passed,
+            gate: rejection.map(|(gate, _)| gate.clone()),
+            reason: rejection.map(|(_, reason)| reason.clone()),
+            est_fill_pct: result.est_fill_pct,
+            est_pnl_usd: result.est_pnl_usd,
+        }
+    }
+}
+
+/// Aggregate over a backtest run.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct BacktestSummary {
+    pub total: usize,
+    pub passed: usize,
+    pub rejected: usize,
+    /// Rejections keyed by the gate that rejected.
+    pub rejected_by_gate: BTreeMap<String, usize>,
+    /// Sum of `est_fill_pct`, i.e. how many orders' worth filled.
+    pub filled_orders: f64,
+    /// Filled notional for inputs with a `requested_size_usd`.
+    pub filled_usd: f64,
+    /// Sum of `est_pnl_usd` over rows that have one.
+    pub est_pnl_usd: f64,
+}
+
+impl BacktestSummary {
+    fn record(&mut self, ctx: &TradeContext, row: &BacktestRow) {
+        self.total += 1;
+        if row.passed {
+            self.passed += 1;
+        } else {
+            self.rejected += 1;
+            let gate = row.gate.clone().unwrap_or_else(|| "unknown".to_string());
+            *self.rejected_by_gate.entry(gate).or_insert(0) += 1;
+        }
+        self.filled_orders += row.est_fill_pct;
+        if let Some(size) = ctx.requested_size_usd {
+            self.filled_usd += size * row.est_fill_pct;
+        }
+        if let Some(pnl) = row.est_pnl_usd {
+            self.est_pnl_usd += pnl;
+        }
+    }
+}
+
+/// Per-row results plus their summary.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BacktestReport {
+    pub summary: BacktestSummary,
+    pub rows: Vec<BacktestRow>,
+}
+
+impl BacktestReport {
+    /// Write the rows as CSV with a header line.
+    pub fn write_csv<W: Write>(&self, mut out: W) -> io::Result<()> {
+        writeln!(
+            out,
+            "mint,target_wallet,passed,gate,reason,est_fill_pct,est_pnl_usd"
+        )?;
+        for row in &self.rows {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                csv_field(&row.mint),
+                csv_field(&row.target_wallet),
+                row.passed,
+                csv_field(row.gate.as_deref().unwrap_or("")),
+                csv_field(row.reason.as_deref().unwrap_or("")),
+                row.est_fill_pct,
+                row.est_pnl_usd.map(|p| p.to_string()).unwrap_or_default(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Write the report to `path`: CSV rows for a `.csv` extension,
+    /// otherwise pretty JSON with the summary and rows.
+    pub fn write_to<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let is_csv = path
+            .extension()
+            .map(|ext| ext.eq_ignore_ascii_case("csv"))
+            .unwrap_or(false);
+        if is_csv {
+            self.write_csv(File::create(path)?)
+        } else {
+            let json = serde_json::to_string_pretty(self)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            fs::write(path, json)
+        }
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Read [`TradeContext`] rows from a JSONL file. Blank lines are skipped;
+/// a malformed line fails the read with its line number.
+pub fn read_inputs<P: AsRef<Path>>(path: P) -> io::Result<Vec<TradeContext>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut inputs = Vec::new();
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let ctx = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("line {}: {}", idx + 1, e),
+            )
+        })?;
+        inputs.push(ctx);
+    }
+    Ok(inputs)
+}
+
+/// Run every input through `sim` as `action` and collect the results.
+pub fn backtest<S: ExecutionSimulator + ?Sized>(
+    sim: &S,
+    inputs: &[TradeContext],
+    action: SimulationAction,
+) -> BacktestReport {
+    let mut report = BacktestReport::default();
+    for ctx in inputs {
+        let result = sim.simulate(ctx, action);
+        let row = BacktestRow::new(ctx, &result);
+        report.summary.record(ctx, &row);
+        report.rows.push(row);
+    }
+    report
+}
@@ -1,14 +1,198 @@
-
 use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+
+use crate::universal::gates::{Gate, GateDecision, RejectReason, TradeContext};
+
+fn parse_csv(csv: &str) -> HashSet<String> {
+    csv.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect()
+}
 
+/// One entry per line (commas also accepted); `#` starts a comment line.
+fn parse_list(text: &str) -> HashSet<String> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .flat_map(|line| parse_csv(line).into_iter())
+        .collect()
+}
+
+/// Blacklisted mints and target wallets.
+///
+/// Interior mutability lets the live set be shared (`Arc<Blacklist>`, or a
+/// clone, which shares the same sets) between gates and the Telegram layer
+/// while entries are added or removed at runtime.
+/// Poisoned locks are treated as "listed" (conservative: skip the trade).
+#[derive(Clone, Debug, Default)]
 pub struct Blacklist {
-    set: HashSet<String>,
+    mints: Arc<RwLock<HashSet<String>>>,
+    wallets: Arc<RwLock<HashSet<String>>>,
 }
 
 impl Blacklist {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Build a mint blacklist from a comma-separated list.
     pub fn from_csv(csv: &str) -> Self {
-        let set = csv.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect();
-        Self{ set }
+        Self::with_mints(parse_csv(csv))
+    }
+    fn with_mints(mints: HashSet<String>) -> Self {
+        Self { mints: Arc::new(RwLock::new(mints)), wallets: Arc::default() }
+    }
+    /// Load a mint blacklist file: one mint per line, blank lines and `#`
+    /// comments ignored. Entries are trimmed and deduplicated.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::with_mints(parse_list(&fs::read_to_string(path)?)))
+    }
+    /// Write the mint blacklist to `path`, one mint per line in sorted order.
+    /// Goes through a temp file + rename so a crash never leaves it truncated.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut mints: Vec<String> = match self.mints.read() {
+            Ok(set) => set.iter().cloned().collect(),
+            Err(poisoned) => poisoned.into_inner().iter().cloned().collect(),
+        };
+        mints.sort();
+        let mut contents = mints.join("\n");
+        contents.push('\n');
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, contents)?;
+        fs::rename(&tmp, path)
+    }
+    /// Build from BLACKLIST_MINTS and BLACKLIST_WALLETS (comma-separated),
+    /// plus the mints in BLACKLIST_FILE if it exists.
+    pub fn from_env() -> Self {
+        let blacklist = Self::from_csv(&env::var("BLACKLIST_MINTS").unwrap_or_default());
+        if let Ok(v) = env::var("BLACKLIST_WALLETS") {
+            for wallet in parse_csv(&v) {
+                blacklist.insert_wallet(&wallet);
+            }
+        }
+        if let Some(path) = blacklist_file() {
+            if Path::new(&path).exists() {
+                match Self::from_file(&path) {
+                    Ok(file) => {
+                        for mint in file.mints() {
+                            blacklist.insert(&mint);
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to load BLACKLIST_FILE {}: {}", path, e),
+                }
+            }
+        }
+        blacklist
+    }
+    /// Snapshot of the blacklisted mints.
+    pub fn mints(&self) -> Vec<String> {
+        match self.mints.read() {
+            Ok(set) => set.iter().cloned().collect(),
+            Err(poisoned) => poisoned.into_inner().iter().cloned().collect(),
+        }
+    }
+    pub fn contains(&self, mint: &str) -> bool {
+        self.mints.read().map(|s| s.contains(mint)).unwrap_or(true)
+    }
+    pub fn contains_wallet(&self, wallet: &str) -> bool {
+        self.wallets.read().map(|s| s.contains(wallet)).unwrap_or(true)
+    }
+    /// Add a mint; returns true if it was not already listed.
+    pub fn insert(&self, mint: &str) -> bool {
+        match self.mints.write() {
+            Ok(mut set) => set.insert(mint.trim().to_string()),
+            Err(poisoned) => poisoned.into_inner().insert(mint.trim().to_string()),
+        }
+    }
+    /// Remove a mint; returns true if it was listed.
+    pub fn remove(&self, mint: &str) -> bool {
+        match self.mints.write() {
+            Ok(mut set) => set.remove(mint.trim()),
+            Err(poisoned) => poisoned.into_inner().remove(mint.trim()),
+        }
+    }
+    /// Add a target wallet; returns true if it was not already listed.
+    pub fn insert_wallet(&self, wallet: &str) -> bool {
+        match self.wallets.write() {
+            Ok(mut set) => set.insert(wallet.trim().to_string()),
+            Err(poisoned) => poisoned.into_inner().insert(wallet.trim().to_string()),
+        }
+    }
+    /// Remove a target wallet; returns true if it was listed.
+    pub fn remove_wallet(&self, wallet: &str) -> bool {
+        match self.wallets.write() {
+            Ok(mut set) => set.remove(wallet.trim()),
+            Err(poisoned) => poisoned.into_inner().remove(wallet.trim()),
+        }
+    }
+    /// Number of blacklisted mints.
+    pub fn len(&self) -> usize { self.mints.read().map(|s| s.len()).unwrap_or(0) }
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+/// Session blacklist: mints we must never buy again during this run
+/// (e.g. auto-detected rugs), seeded from BLACKLIST_MINTS / BLACKLIST_WALLETS.
+pub static SESSION_BLACKLIST: Lazy<Arc<Blacklist>> = Lazy::new(|| Arc::new(Blacklist::from_env()));
+
+/// Whether `mint` is on the session blacklist. A poisoned lock is treated
+/// as blacklisted (conservative: skip the buy).
+pub fn is_blacklisted(mint: &str) -> bool {
+    SESSION_BLACKLIST.contains(mint)
+}
+
+fn blacklist_file() -> Option<String> {
+    env::var("BLACKLIST_FILE").ok().map(|p| p.trim().to_string()).filter(|p| !p.is_empty())
+}
+
+/// Write the session mint blacklist to BLACKLIST_FILE, if set. Errors are
+/// logged; a failed save must not stop trading.
+pub fn persist_session_blacklist() {
+    if let Some(path) = blacklist_file() {
+        if let Err(e) = SESSION_BLACKLIST.save_to_file(&path) {
+            eprintln!("Failed to save BLACKLIST_FILE {}: {}", path, e);
+        }
+    }
+}
+
+/// Add `mint` to the session blacklist; returns true if it was newly added.
+/// New entries are persisted to BLACKLIST_FILE when configured.
+pub fn blacklist_mint(mint: &str) -> bool {
+    let added = SESSION_BLACKLIST.insert(mint);
+    if added {
+        persist_session_blacklist();
+    }
+    added
+}
+
+/// Rejects trades in blacklisted mints or copied from blacklisted wallets.
+pub struct BlacklistGate {
+    pub blacklist: Arc<Blacklist>,
+}
+
+impl BlacklistGate {
+    pub fn new(blacklist: Arc<Blacklist>) -> Self {
+        Self { blacklist }
+    }
+
+    /// Gate backed by the live session blacklist.
+    pub fn session() -> Self {
+        Self::new(Arc::clone(&SESSION_BLACKLIST))
+    }
+}
+
+impl Gate for BlacklistGate {
+    fn name(&self) -> &'static str { "BlacklistGate" }
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        if self.blacklist.contains(&ctx.mint) {
+            return GateDecision::Rejected{code: RejectReason::Blacklisted, reason: "blacklisted_mint".to_string()};
+        }
+        if self.blacklist.contains_wallet(&ctx.target_wallet) {
+            return GateDecision::Rejected{code: RejectReason::Blacklisted, reason: "blacklisted_wallet".to_string()};
+        }
+        GateDecision::Passed
     }
-    pub fn contains(&self, mint: &str) -> bool { self.set.contains(mint) }
 }
@@ -0,0 +1,127 @@
+//! Assemble a [`TradeContext`] from the live data sources.
+//!
+//! [`build_trade_context`] reads the mint's [`TokenMetrics`] from the
+//! [`MetricsStore`], any position already held in the mint from the
+//! [`PositionsRegistry`], and the copied wallet's slippage from the wallet
+//! config, so callers don't hand-fill a dozen fields.
+
+use std::fmt;
+
+use crate::universal::gates::curve_progress::bonding_curve_progress_pct;
+use crate::universal::gates::holders::top10_holder_pct;
+use crate::universal::gates::TradeContext;
+use crate::universal::impact::estimate_impact_bps;
+use crate::universal::metrics::{clock, MetricsStore};
+use crate::universal::positions::PositionsRegistry;
+use crate::universal::wallet_config::get_wallet_config_map;
+
+/// Slippage used when neither the wallet config nor SLIPPAGE sets one (bps).
+const DEFAULT_SLIPPAGE_BPS: f64 = 5_000.0;
+
+/// An input [`build_trade_context`] could not get.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ContextError {
+    /// The metrics store has nothing for this mint.
+    MissingMetrics(String),
+    /// The mint's metrics have no usable price.
+    MissingPrice(String),
+    /// `size_usd` was zero, negative or not a number.
+    InvalidSize(f64),
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextError::MissingMetrics(mint) => write!(f, "no metrics for mint={}", mint),
+            ContextError::MissingPrice(mint) => write!(f, "no price for mint={}", mint),
+            ContextError::InvalidSize(size) => write!(f, "invalid size_usd={}", size),
+        }
+    }
+}
+
+impl std::error::Error for ContextError {}
+
+/// Slippage tolerance for `wallet` in bps: the wallet-config override, else
+/// the global SLIPPAGE (bps), else 5000.
+fn slippage_bps_for_wallet(wallet: &str) -> f64 {
+    if let Some(fraction) = get_wallet_config_map().get_slippage(wallet) {
+        return fraction * 10_000.0;
+    }
+    if let Ok(v) = std::env::var("SLIPPAGE") {
+        if let Ok(parsed) = v.parse::<f64>() {
+            return parsed;
+        }
+    }
+    DEFAULT_SLIPPAGE_BPS
+}
+
+/// Build the context for `owner_wallet` (our signing wallet) buying
+/// `size_usd` of `mint` copied from `target_wallet`.
+///
+/// - Volume, market cap, liquidity, Amihud and range efficiency come from the
+///   mint's metrics; `metrics_age_secs` is measured against the metrics clock
+///   and `first_seen_ts` is the store's first observation of the mint.
+/// - `top10_holder_pct` is the last cached holder-concentration fetch and
+///   `bonding_curve_progress_pct` the last cached PumpFun curve fetch.
+/// - `depth_multiple` is liquidity over the combined exposure: `size_usd`
+///   plus whatever `owner_wallet` already holds in the mint, priced at the
+///   current price. Without liquidity it is 0, which the depth guard rejects.
+/// - `est_cost_bps` is the worst-case fill cost: the estimated price impact
+///   of `size_usd` against the pool plus the wallet's slippage tolerance.
+///
+/// Window volatility, PumpFun status and transfer hooks are not tracked by the
+/// metrics store and are left neutral for their own gates to fill in.
+pub fn build_trade_context(
+    mint: &str,
+    target_wallet: &str,
+    owner_wallet: &str,
+    metrics_store: &MetricsStore,
+    registry: &PositionsRegistry,
+    size_usd: f64,
+) -> Result<TradeContext, ContextError> {
+    if size_usd.is_nan() || size_usd <= 0.0 {
+        return Err(ContextError::InvalidSize(size_usd));
+    }
+    let metrics = metrics_store
+        .get(mint)
+        .ok_or_else(|| ContextError::MissingMetrics(mint.to_string()))?;
+    if !metrics.price_usd.is_finite() || metrics.price_usd <= 0.0 {
+        return Err(ContextError::MissingPrice(mint.to_string()));
+    }
+
+    let held_usd = registry
+        .get_open_position(owner_wallet, mint)
+        .and_then(|p| p.size)
+        .map(|tokens| tokens * metrics.price_usd)
+        .unwrap_or(0.0);
+    let depth_multiple = match metrics.liq_usd {
+        Some(liq) if liq > 0.0 => liq / (size_usd + held_usd),
+        _ => 0.0,
+    };
+    let impact_bps = estimate_impact_bps(size_usd, metrics.liq_usd.unwrap_or(0.0));
+
+    Ok(TradeContext {
+        mint: mint.to_string(),
+        target_wallet: target_wallet.to_string(),
+        price_usd: metrics.price_usd,
+        est_cost_bps: impact_bps + slippage_bps_for_wallet(target_wallet),
+        window5m_usd: metrics.vol_5m_usd,
+        window15m_usd: metrics.vol_15m_usd,
+        depth_multiple,
+        est_mcap_usd: metrics.mcap_usd,
+        window_vol_pct: 0.0,
+        is_pumpfun: false,
+        pumpfun_migrated: None,
+        amihud_5m: metrics.amihud_5m,
+        range_eff_5m: metrics.range_eff_5m,
+        metrics_age_secs: Some((clock::now_secs() - metrics.last_update).max(0)),
+        requested_size_usd: Some(size_usd),
+        liq_usd: metrics.liq_usd,
+        transfer_hook_program: None,
+        first_seen_ts: metrics_store.first_seen(mint),
+        top10_holder_pct: top10_holder_pct(mint),
+        bonding_curve_progress_pct: bonding_curve_progress_pct(mint),
+        leader_signature: None,
+        owner_wallet: Some(owner_wallet.to_string()),
+    })
+}
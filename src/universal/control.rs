@@ -0,0 +1,430 @@
+//! Global and per-wallet pause state (kill switch).
+//!
+//! Pausing only blocks *new* positions; closes and sells always proceed so
+//! a paused bot can still exit. Besides the manual pause, the send
+//! [`CircuitBreaker`] pauses trading on its own after a streak of failed
+//! sends. [`admission_check`] combines the pause state with the wallet's
+//! `enabled` flag, the position limits and leader-trade dedup into the one
+//! check run before an open.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
+
+use once_cell::sync::Lazy;
+
+use crate::common::bot_config;
+use crate::universal::dedup::{SeenSignatures, SEEN_LEADER_SIGNATURES};
+use crate::universal::events::{emit, Event};
+use crate::universal::gates::stats::{Clock, SystemClock};
+use crate::universal::positions::{PositionError, PositionsRegistry, GLOBAL_POSITIONS_REGISTRY};
+use crate::universal::wallet_config::get_wallet_config_map;
+
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+static PAUSED_WALLETS: Lazy<RwLock<HashSet<String>>> = Lazy::new(|| RwLock::new(HashSet::new()));
+
+/// Stop opening new positions for every wallet.
+pub fn pause_all() {
+    PAUSED.store(true, Ordering::SeqCst);
+}
+
+/// Lift the global pause and reset the send breaker. Per-wallet pauses stay
+/// in place.
+pub fn resume_all() {
+    PAUSED.store(false, Ordering::SeqCst);
+    SEND_BREAKER.reset();
+}
+
+/// Whether new positions are blocked for every wallet: manually paused,
+/// the send breaker is open, or a graceful shutdown has started.
+pub fn is_paused() -> bool {
+    PAUSED.load(Ordering::SeqCst)
+        || SEND_BREAKER.is_open()
+        || crate::universal::shutdown::is_shutting_down()
+}
+
+/// Stop opening new positions copied from `wallet`.
+pub fn pause_wallet(wallet: &str) {
+    match PAUSED_WALLETS.write() {
+        Ok(mut guard) => guard.insert(wallet.to_string()),
+        Err(poisoned) => poisoned.into_inner().insert(wallet.to_string()),
+    };
+}
+
+pub fn resume_wallet(wallet: &str) {
+    match PAUSED_WALLETS.write() {
+        Ok(mut guard) => guard.remove(wallet),
+        Err(poisoned) => poisoned.into_inner().remove(wallet),
+    };
+}
+
+/// Whether `wallet` is individually paused. Treated as paused if the lock
+/// is poisoned.
+pub fn is_wallet_paused(wallet: &str) -> bool {
+    match PAUSED_WALLETS.read() {
+        Ok(guard) => guard.contains(wallet),
+        Err(_) => true,
+    }
+}
+
+/// Check whether a new position may be opened for `wallet`.
+///
+/// Returns `Err("shutting_down")` once a graceful shutdown has started,
+/// `Err("paused")` under the global pause,
+/// `Err("circuit_breaker_open")` while the send breaker is open and
+/// `Err("wallet_paused")` when only this wallet is paused.
+pub fn check_can_open(wallet: &str) -> Result<(), String> {
+    check_pause_state(wallet).map_err(|reject| reject.as_str().to_string())
+}
+
+fn check_pause_state(wallet: &str) -> Result<(), AdmissionReject> {
+    if crate::universal::shutdown::is_shutting_down() {
+        return Err(AdmissionReject::ShuttingDown);
+    }
+    if PAUSED.load(Ordering::SeqCst) {
+        return Err(AdmissionReject::Paused);
+    }
+    if SEND_BREAKER.is_open() {
+        return Err(AdmissionReject::CircuitBreakerOpen);
+    }
+    if is_wallet_paused(wallet) {
+        return Err(AdmissionReject::WalletPaused);
+    }
+    Ok(())
+}
+
+/// Why [`admission_check`] refused a new position.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdmissionReject {
+    ShuttingDown,
+    Paused,
+    CircuitBreakerOpen,
+    WalletPaused,
+    /// Copying from the wallet is switched off (`enabled = false` in its
+    /// config). Unlike a pause, the flag is kept with the wallet config.
+    WalletDisabled,
+    /// A position in this (wallet, mint) is already open or closing.
+    AlreadyOpen,
+    /// The wallet is at its configured `max_positions`.
+    WalletMaxPositions {
+        open: usize,
+        max: u32,
+    },
+    /// MAX_OPEN_POSITIONS positions are open across all wallets.
+    MaxOpenPositions {
+        open: usize,
+        max: usize,
+    },
+    /// The leader trade was already handled.
+    DuplicateSignal,
+    /// The positions registry could not be read.
+    Registry(String),
+}
+
+impl AdmissionReject {
+    /// Stable snake-case code, as returned by [`check_can_open`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdmissionReject::ShuttingDown => "shutting_down",
+            AdmissionReject::Paused => "paused",
+            AdmissionReject::CircuitBreakerOpen => "circuit_breaker_open",
+            AdmissionReject::WalletPaused => "wallet_paused",
+            AdmissionReject::WalletDisabled => "wallet_disabled",
+            AdmissionReject::AlreadyOpen => "already_open",
+            AdmissionReject::WalletMaxPositions { .. } => "wallet_max_positions",
+            AdmissionReject::MaxOpenPositions { .. } => "max_open_positions",
+            AdmissionReject::DuplicateSignal => "duplicate_signal",
+            AdmissionReject::Registry(_) => "registry_error",
+        }
+    }
+}
+
+impl fmt::Display for AdmissionReject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdmissionReject::WalletMaxPositions { open, max } => {
+                write!(f, "{} {} >= {}", self.as_str(), open, max)
+            }
+            AdmissionReject::MaxOpenPositions { open, max } => {
+                write!(f, "{} {} >= {}", self.as_str(), open, max)
+            }
+            AdmissionReject::Registry(msg) => write!(f, "{} {}", self.as_str(), msg),
+            _ => f.write_str(self.as_str()),
+        }
+    }
+}
+
+impl std::error::Error for AdmissionReject {}
+
+/// MAX_OPEN_POSITIONS: open positions allowed across all wallets (default
+/// 0 = unlimited).
+pub fn max_open_positions() -> usize {
+    let mut max = 0;
+    if let Ok(v) = bot_config::var("MAX_OPEN_POSITIONS") {
+        if let Ok(parsed) = v.parse::<usize>() {
+            max = parsed;
+        }
+    }
+    max
+}
+
+/// Whether `owner_wallet` may open a position in `mint` copying
+/// `leader_wallet`, against the global positions registry and leader-trade
+/// dedup. See [`admission_check_in`].
+pub fn admission_check(
+    leader_wallet: &str,
+    owner_wallet: &str,
+    mint: &str,
+    leader_sig: Option<&str>,
+) -> Result<(), AdmissionReject> {
+    admission_check_in(
+        &GLOBAL_POSITIONS_REGISTRY,
+        &SEEN_LEADER_SIGNATURES,
+        leader_wallet,
+        owner_wallet,
+        mint,
+        leader_sig,
+    )
+}
+
+/// Every check that must pass before opening a position, first failure
+/// wins: shutdown / pause / breaker / wallet pause ([`check_can_open`]),
+/// a disabled wallet, an already open (wallet, mint) position, the wallet's `max_positions`,
+/// MAX_OPEN_POSITIONS, and finally `leader_sig` against `seen`. The dedup
+/// goes last because it records the signature, so only an admitted trade
+/// consumes it.
+///
+/// Pause state and wallet config are looked up for `leader_wallet`, the
+/// followed wallet; positions are counted for `owner_wallet`, the signing
+/// wallet they are recorded under.
+pub fn admission_check_in(
+    registry: &PositionsRegistry,
+    seen: &SeenSignatures,
+    leader_wallet: &str,
+    owner_wallet: &str,
+    mint: &str,
+    leader_sig: Option<&str>,
+) -> Result<(), AdmissionReject> {
+    check_pause_state(leader_wallet)?;
+    let configs = get_wallet_config_map();
+    if !configs.is_enabled(leader_wallet) {
+        return Err(AdmissionReject::WalletDisabled);
+    }
+
+    registry.can_open(owner_wallet, mint).map_err(|e| match e {
+        PositionError::AlreadyOpen(..) => AdmissionReject::AlreadyOpen,
+        other => AdmissionReject::Registry(other.to_string()),
+    })?;
+
+    if let Some(max) = configs.get_max_positions(leader_wallet) {
+        let open = registry.list_open_positions_for_wallet(owner_wallet).len();
+        // A zero cap admits nothing, as in `can_open_more_positions_for_wallet`.
+        if open >= max as usize {
+            return Err(AdmissionReject::WalletMaxPositions { open, max });
+        }
+    }
+
+    let max = max_open_positions();
+    if max > 0 {
+        let open = registry.open_count();
+        if open >= max {
+            return Err(AdmissionReject::MaxOpenPositions { open, max });
+        }
+    }
+
+    claim_leader_signature_in(seen, leader_sig)
+}
+
+/// Record `leader_sig` as handled in the global leader-trade dedup, or
+/// reject it as already handled. For callers that run further checks after
+/// [`admission_check`] and only claim the signature once those pass.
+pub fn claim_leader_signature(leader_sig: Option<&str>) -> Result<(), AdmissionReject> {
+    claim_leader_signature_in(&SEEN_LEADER_SIGNATURES, leader_sig)
+}
+
+fn claim_leader_signature_in(
+    seen: &SeenSignatures,
+    leader_sig: Option<&str>,
+) -> Result<(), AdmissionReject> {
+    if let Some(sig) = leader_sig {
+        if !seen.first_seen(sig) {
+            return Err(AdmissionReject::DuplicateSignal);
+        }
+    }
+    Ok(())
+}
+
+/// Breaker position. `HalfOpen` lets trades through again after the
+/// cooldown; the next send decides whether it closes or reopens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BreakerState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+impl BreakerState {
+    fn as_str(&self) -> &'static str {
+        match self {
+            BreakerState::Closed => "closed",
+            BreakerState::Open => "open",
+            BreakerState::HalfOpen => "half_open",
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: BreakerState,
+    consecutive_failures: u32,
+    opened_at: i64,
+}
+
+/// Pauses trading after `fail_threshold` consecutive failed sends, so a
+/// broken RPC or landing path does not keep burning fees.
+///
+/// After `cooldown_secs` the breaker goes half-open and lets trades through:
+/// a successful send closes it, a failure reopens it straight away. Any
+/// success resets the failure count. A threshold of 0 disables the breaker.
+/// Opening and closing are emitted as [`Event::CircuitBreaker`].
+#[derive(Debug)]
+pub struct CircuitBreaker<C: Clock = SystemClock> {
+    pub fail_threshold: u32,
+    pub cooldown_secs: i64,
+    inner: Mutex<BreakerInner>,
+    clock: C,
+}
+
+impl CircuitBreaker {
+    pub fn new(fail_threshold: u32, cooldown_secs: i64) -> Self {
+        Self::with_clock(fail_threshold, cooldown_secs, SystemClock)
+    }
+
+    /// Build from BREAKER_FAIL_THRESHOLD (default 5; 0 = disabled) and
+    /// BREAKER_COOLDOWN_SECS (default 60).
+    pub fn from_env() -> Self {
+        let mut fail_threshold = 5;
+        let mut cooldown_secs = 60;
+        if let Ok(v) = std::env::var("BREAKER_FAIL_THRESHOLD") {
+            if let Ok(parsed) = v.parse::<u32>() {
+                fail_threshold = parsed;
+            }
+        }
+        if let Ok(v) = std::env::var("BREAKER_COOLDOWN_SECS") {
+            if let Ok(parsed) = v.parse::<i64>() {
+                cooldown_secs = parsed;
+            }
+        }
+        Self::new(fail_threshold, cooldown_secs)
+    }
+}
+
+impl<C: Clock> CircuitBreaker<C> {
+    pub fn with_clock(fail_threshold: u32, cooldown_secs: i64, clock: C) -> Self {
+        Self {
+            fail_threshold,
+            cooldown_secs,
+            inner: Mutex::new(BreakerInner {
+                state: BreakerState::Closed,
+                consecutive_failures: 0,
+                opened_at: 0,
+            }),
+            clock,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerInner> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Current state; an open breaker whose cooldown has passed moves to
+    /// half-open.
+    pub fn state(&self) -> BreakerState {
+        let mut inner = self.lock();
+        if inner.state == BreakerState::Open
+            && self.clock.now_secs() - inner.opened_at >= self.cooldown_secs
+        {
+            inner.state = BreakerState::HalfOpen;
+        }
+        inner.state
+    }
+
+    /// Whether the breaker is currently blocking new positions.
+    pub fn is_open(&self) -> bool {
+        self.state() == BreakerState::Open
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.lock().consecutive_failures
+    }
+
+    /// Record a failed send; opens the breaker at the threshold, or at once
+    /// when half-open.
+    pub fn record_failure(&self) {
+        if self.fail_threshold == 0 {
+            return;
+        }
+        let state = self.state();
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        let trip = match state {
+            BreakerState::Closed => inner.consecutive_failures >= self.fail_threshold,
+            BreakerState::HalfOpen => true,
+            BreakerState::Open => false,
+        };
+        if trip {
+            inner.state = BreakerState::Open;
+            inner.opened_at = self.clock.now_secs();
+            let failures = inner.consecutive_failures;
+            drop(inner);
+            emit_breaker(BreakerState::Open, failures);
+        }
+    }
+
+    /// Record a successful send: resets the failure count and closes the
+    /// breaker.
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        inner.consecutive_failures = 0;
+        if inner.state != BreakerState::Closed {
+            inner.state = BreakerState::Closed;
+            drop(inner);
+            emit_breaker(BreakerState::Closed, 0);
+        }
+    }
+
+    /// Close the breaker without waiting for a send (manual resume).
+    pub fn reset(&self) {
+        self.record_success();
+    }
+}
+
+fn emit_breaker(state: BreakerState, consecutive_failures: u32) {
+    eprintln!(
+        "[BREAKER] {} after {} consecutive send failures",
+        state.as_str(),
+        consecutive_failures
+    );
+    emit(Event::CircuitBreaker {
+        ts: chrono::Utc::now().timestamp(),
+        state: state.as_str().to_string(),
+        consecutive_failures,
+    });
+}
+
+/// Process-wide breaker fed by every transaction send.
+pub static SEND_BREAKER: Lazy<CircuitBreaker> = Lazy::new(CircuitBreaker::from_env);
+
+/// Feed the outcome of a transaction send into [`SEND_BREAKER`].
+pub fn record_send_result(ok: bool) {
+    if ok {
+        SEND_BREAKER.record_success();
+    } else {
+        SEND_BREAKER.record_failure();
+    }
+}
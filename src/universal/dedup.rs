@@ -0,0 +1,140 @@
+//! Dedup of leader trades by transaction signature.
+//!
+//! With more than one ingest source (e.g. the gRPC stream plus an RPC poll)
+//! the same leader buy can arrive twice and would be copied twice.
+//! [`SeenSignatures`] remembers each signature for a short TTL, capped in
+//! size, and [`SeenSignatures::first_seen`] tells the ingest path whether a
+//! trade is new.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+/// Default time a signature is remembered.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(120);
+/// Default maximum number of remembered signatures.
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+#[derive(Debug, Default)]
+struct Inner {
+    seen: HashMap<String, Instant>,
+    /// Signatures in insertion order, for expiry and eviction.
+    order: VecDeque<(String, Instant)>,
+}
+
+/// Time-bounded, size-capped set of handled signatures.
+#[derive(Debug)]
+pub struct SeenSignatures {
+    ttl: Duration,
+    capacity: usize,
+    inner: Mutex<Inner>,
+}
+
+impl Default for SeenSignatures {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL, DEFAULT_CAPACITY)
+    }
+}
+
+impl SeenSignatures {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Build from LEADER_DEDUP_TTL_SECS (default 120) and
+    /// LEADER_DEDUP_CAPACITY (default 10000).
+    pub fn from_env() -> Self {
+        let mut ttl = DEFAULT_TTL;
+        if let Ok(v) = std::env::var("LEADER_DEDUP_TTL_SECS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                ttl = Duration::from_secs(parsed);
+            }
+        }
+        let mut capacity = DEFAULT_CAPACITY;
+        if let Ok(v) = std::env::var("LEADER_DEDUP_CAPACITY") {
+            if let Ok(parsed) = v.parse::<usize>() {
+                capacity = parsed;
+            }
+        }
+        Self::new(ttl, capacity)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Record `signature` and return whether it had not been seen within the
+    /// TTL, i.e. whether the trade should be processed.
+    pub fn first_seen(&self, signature: &str) -> bool {
+        self.first_seen_at(signature, Instant::now())
+    }
+
+    pub fn first_seen_at(&self, signature: &str, now: Instant) -> bool {
+        let mut inner = self.lock();
+        self.expire(&mut inner, now);
+        if inner.seen.contains_key(signature) {
+            return false;
+        }
+        while inner.order.len() >= self.capacity {
+            match inner.order.pop_front() {
+                Some((oldest, _)) => {
+                    inner.seen.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        inner.seen.insert(signature.to_string(), now);
+        inner.order.push_back((signature.to_string(), now));
+        true
+    }
+
+    /// Whether `signature` was seen within the TTL, without recording it.
+    pub fn contains(&self, signature: &str) -> bool {
+        self.contains_at(signature, Instant::now())
+    }
+
+    pub fn contains_at(&self, signature: &str, now: Instant) -> bool {
+        match self.lock().seen.get(signature) {
+            Some(seen_at) => now.saturating_duration_since(*seen_at) < self.ttl,
+            None => false,
+        }
+    }
+
+    /// Number of remembered signatures, including any not yet expired out.
+    pub fn len(&self) -> usize {
+        self.lock().seen.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut inner = self.lock();
+        inner.seen.clear();
+        inner.order.clear();
+    }
+
+    fn expire(&self, inner: &mut Inner, now: Instant) {
+        while let Some((_, seen_at)) = inner.order.front() {
+            if now.saturating_duration_since(*seen_at) < self.ttl {
+                break;
+            }
+            if let Some((signature, _)) = inner.order.pop_front() {
+                inner.seen.remove(&signature);
+            }
+        }
+    }
+}
+
+/// Signatures of leader trades already handled by the ingest path.
+pub static SEEN_LEADER_SIGNATURES: Lazy<SeenSignatures> = Lazy::new(SeenSignatures::from_env);
@@ -0,0 +1,68 @@
+use crate::universal::gates::{run_gates, Gate, TradeContext};
+use crate::universal::impact::{estimate_impact_bps, estimate_min_out};
+
+/// Result of the `/gate_test <mint> <size>` diagnostic.
+///
+/// Combines the gate verdict with the estimated price impact and min-out for
+/// the requested size, so operators can see how big a trade a token absorbs.
+#[derive(Clone, Debug)]
+pub struct GateTestReport {
+    pub mint: String,
+    pub size_usd: f64,
+    pub passed: bool,
+    pub reasons: Vec<(String, String)>,
+    /// None when pool liquidity is unknown.
+    pub est_impact_bps: Option<f64>,
+    /// Estimated minimum tokens out after impact and slippage tolerance.
+    pub est_min_out: Option<f64>,
+    pub slippage_bps: u64,
+}
+
+impl GateTestReport {
+    /// Evaluate `gates` for a trade of `size_usd` on `ctx`.
+    pub fn run(
+        ctx: &TradeContext,
+        gates: &[Box<dyn Gate>],
+        size_usd: f64,
+        slippage_bps: u64,
+    ) -> Self {
+        let mut ctx = ctx.clone();
+        ctx.requested_size_usd = Some(size_usd);
+
+        let (passed, reasons) = run_gates(&ctx, gates);
+        let est_impact_bps = ctx.liq_usd.map(|liq| estimate_impact_bps(size_usd, liq));
+        let est_min_out = est_impact_bps
+            .map(|impact| estimate_min_out(size_usd, ctx.price_usd, impact, slippage_bps));
+
+        Self {
+            mint: ctx.mint,
+            size_usd,
+            passed,
+            reasons,
+            est_impact_bps,
+            est_min_out,
+            slippage_bps,
+        }
+    }
+
+    /// Multi-line reply suitable for echoing back over Telegram.
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "gate_test {} size=${:.2}\nresult: {}",
+            self.mint,
+            self.size_usd,
+            if self.passed { "PASS" } else { "REJECT" }
+        );
+        for (gate, reason) in &self.reasons {
+            out.push_str(&format!("\n  {}: {}", gate, reason));
+        }
+        match (self.est_impact_bps, self.est_min_out) {
+            (Some(impact), Some(min_out)) => out.push_str(&format!(
+                "\nest_impact: {:.1} bps\nmin_out: {:.4} tokens @ {} bps slippage",
+                impact, min_out, self.slippage_bps
+            )),
+            _ => out.push_str("\nest_impact: unknown (no liquidity data)"),
+        }
+        out
+    }
+}
@@ -1,19 +1,143 @@
+use solana_sdk::hash::hashv;
 use solana_sdk::signature::Signature;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::universal::executor::{
+    ExecutionSimulator, SimBackend, SimConfig, SimulationAction, SimulationResult,
+};
+use crate::universal::gates::TradeContext;
+use crate::universal::positions::{
+    OpenParams, PositionError, PositionsRegistry, GLOBAL_POSITIONS_REGISTRY,
+};
+
+/// How side effects are executed: logged only (`DryRun`), simulated against
+/// the chain without sending (`Simulate`), or sent for real (`Live`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionMode {
+    DryRun,
+    Simulate,
+    Live,
+}
+
+impl ExecutionMode {
+    /// Read EXECUTION_MODE (`LIVE`, `SIMULATE`, anything else = `DRY_RUN`).
+    pub fn from_env() -> Self {
+        match std::env::var("EXECUTION_MODE")
+            .unwrap_or_else(|_| "DRY_RUN".into())
+            .to_uppercase()
+            .as_str()
+        {
+            "LIVE" => ExecutionMode::Live,
+            "SIMULATE" => ExecutionMode::Simulate,
+            _ => ExecutionMode::DryRun,
+        }
+    }
+
+    pub fn is_live(&self) -> bool {
+        matches!(self, ExecutionMode::Live)
+    }
+}
 
 /// Result returned when in dry-run mode instead of a real blockchain signature.
 #[derive(Debug, Clone)]
 pub struct DryRunSignature;
 
 impl DryRunSignature {
+    /// Fixed fake signature, for tests that don't care which send it was.
     pub fn mock() -> Signature {
         // A fake but valid-looking signature format.
         Signature::from_str("DryRun111111111111111111111111111111111111111").unwrap()
     }
+
+    /// Pseudo-signature of a dry-run send of `label` at `ts_nanos`, `seq`
+    /// disambiguating sends within the same nanosecond. The first 32 bytes
+    /// are the SHA-256 of the inputs, the last 32 the hash of those; the
+    /// same inputs always give the same signature.
+    pub fn derive(label: &str, ts_nanos: i64, seq: u64) -> Signature {
+        let head = hashv(&[
+            label.as_bytes(),
+            &ts_nanos.to_le_bytes(),
+            &seq.to_le_bytes(),
+        ]);
+        let tail = hashv(&[head.as_ref()]);
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(head.as_ref());
+        bytes[32..].copy_from_slice(tail.as_ref());
+        Signature::from(bytes)
+    }
 }
 
-/// Utility function that logs and returns a mock signature.
+static DRY_RUN_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Log a bypassed send and return a pseudo-signature unique to it (see
+/// [`DryRunSignature::derive`]), so dry-run logs can be traced per trade.
 pub fn dry_run_send(label: &str) -> Signature {
-    println!("[DRY RUN] Execution bypassed for: {}", label);
-    DryRunSignature::mock()
+    let ts_nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let seq = DRY_RUN_SEQ.fetch_add(1, Ordering::Relaxed);
+    let signature = DryRunSignature::derive(label, ts_nanos, seq);
+    println!(
+        "[DRY RUN] Execution bypassed for: {} ({})",
+        label, signature
+    );
+    signature
+}
+
+/// Paper-trade a copy buy: run the full gate set for `ctx` and, when it
+/// passes, record a simulated open position for `wallet` (our wallet, as in
+/// live mode) so `/positions` shows what the bot would hold. The entry is
+/// `ctx.price_usd`, so the TP/SL monitor can exit it. A position that is
+/// already open is left alone.
+pub fn paper_buy<S: ExecutionSimulator + ?Sized>(
+    registry: &PositionsRegistry,
+    sim: &S,
+    wallet: &str,
+    ctx: &TradeContext,
+    now_ts: i64,
+) -> SimulationResult {
+    let ctx = &TradeContext {
+        owner_wallet: Some(wallet.to_string()),
+        ..ctx.clone()
+    };
+    let result = sim.simulate(ctx, SimulationAction::Buy);
+    if result.passed {
+        let priced = ctx.price_usd > 0.0;
+        let params = OpenParams {
+            size: ctx
+                .requested_size_usd
+                .filter(|_| priced)
+                .map(|usd| usd * result.est_fill_pct / ctx.price_usd),
+            entry_price: Some(ctx.price_usd).filter(|_| priced),
+            slippage_bps: None,
+        };
+        if let Err(e) = registry.record_open_simulated(wallet, &ctx.mint, now_ts, params) {
+            println!("[DRY RUN] Paper buy of {} not recorded: {}", ctx.mint, e);
+        }
+    }
+    result
+}
+
+/// Paper-trade a sell: close the simulated position for (wallet, mint).
+/// Real positions are never touched by dry-run.
+pub fn paper_sell(
+    registry: &PositionsRegistry,
+    wallet: &str,
+    mint: &str,
+) -> Result<(), PositionError> {
+    match registry.get_open_position(wallet, mint) {
+        Some(pos) if pos.simulated => registry.record_close(wallet, mint),
+        _ => Err(PositionError::NotOpen(wallet.into(), mint.into())),
+    }
+}
+
+/// [`paper_buy`] against the global registry, with thresholds from
+/// [`SimConfig::for_wallet`] for the copied wallet.
+pub fn dry_run_buy(wallet: &str, ctx: &TradeContext) -> SimulationResult {
+    let backend = SimBackend::new(SimConfig::for_wallet(&ctx.target_wallet));
+    paper_buy(&GLOBAL_POSITIONS_REGISTRY, &backend, wallet, ctx, chrono::Utc::now().timestamp())
+}
+
+/// [`paper_sell`] against the global registry.
+pub fn dry_run_sell(wallet: &str, mint: &str) -> Result<(), PositionError> {
+    paper_sell(&GLOBAL_POSITIONS_REGISTRY, wallet, mint)
 }
@@ -0,0 +1,171 @@
+//! Structured JSONL event output for gate rejections, simulation results,
+//! TP/SL exit triggers, circuit-breaker changes, expired positions and
+//! shutdowns.
+//!
+//! Nothing is written until an emitter is installed, either explicitly via
+//! [`install_emitter`] or from EVENTS_JSONL_PATH via [`install_from_env`].
+//! Each event is one JSON object per line so the file can be tailed and fed
+//! into dashboards.
+
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Mutex, RwLock};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::universal::executor::SimulationResult;
+use crate::universal::gates::{RejectReason, TradeContext};
+
+/// One structured log line.
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+    GateRejection {
+        ts: i64,
+        mint: String,
+        target_wallet: String,
+        gate: String,
+        code: RejectReason,
+        reason: String,
+    },
+    Simulation {
+        ts: i64,
+        mint: String,
+        target_wallet: String,
+        result: SimulationResult,
+    },
+    ExitTrigger {
+        ts: i64,
+        wallet: String,
+        mint: String,
+        /// `take_profit` or `stop_loss`.
+        trigger: String,
+        entry_price: f64,
+        price: f64,
+        threshold: f64,
+    },
+    CircuitBreaker {
+        ts: i64,
+        /// `open` or `closed`.
+        state: String,
+        consecutive_failures: u32,
+    },
+    PositionExpired {
+        ts: i64,
+        wallet: String,
+        mint: String,
+        opened_ts: i64,
+        age_secs: i64,
+    },
+    Shutdown {
+        ts: i64,
+        /// Signal that triggered it, e.g. `SIGINT`.
+        signal: String,
+        open_positions: usize,
+        /// Sends still running when the drain gave up (0 if drained).
+        in_flight_sends: usize,
+        drained: bool,
+    },
+}
+
+/// Appends [`Event`]s to a JSONL file.
+#[derive(Debug)]
+pub struct EventEmitter {
+    file: Mutex<File>,
+}
+
+impl EventEmitter {
+    /// Open (or create) `path` for appending.
+    pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Write one event. Failures are logged, never propagated: structured
+    /// logging must not interfere with trading.
+    pub fn emit(&self, event: &Event) {
+        let line = match serde_json::to_string(event) {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Failed to serialize event: {}", e);
+                return;
+            }
+        };
+        let mut file = match self.file.lock() {
+            Ok(file) => file,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Err(e) = writeln!(file, "{}", line) {
+            eprintln!("Failed to write event: {}", e);
+        }
+    }
+}
+
+static EMITTER: Lazy<RwLock<Option<EventEmitter>>> = Lazy::new(|| RwLock::new(None));
+
+/// Install `emitter` as the process-wide event sink, replacing any previous one.
+pub fn install_emitter(emitter: EventEmitter) {
+    match EMITTER.write() {
+        Ok(mut guard) => *guard = Some(emitter),
+        Err(poisoned) => *poisoned.into_inner() = Some(emitter),
+    }
+}
+
+/// Install an emitter writing to EVENTS_JSONL_PATH, if set.
+/// Returns true if an emitter was installed.
+pub fn install_from_env() -> bool {
+    let path = match env::var("EVENTS_JSONL_PATH") {
+        Ok(path) if !path.trim().is_empty() => path,
+        _ => return false,
+    };
+    match EventEmitter::open(path.trim()) {
+        Ok(emitter) => {
+            install_emitter(emitter);
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to open EVENTS_JSONL_PATH {}: {}", path, e);
+            false
+        }
+    }
+}
+
+/// Emit `event` if an emitter is installed.
+pub fn emit(event: Event) {
+    if let Ok(guard) = EMITTER.read() {
+        if let Some(emitter) = guard.as_ref() {
+            emitter.emit(&event);
+        }
+    }
+}
+
+fn now_ts() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// Emit a gate rejection for `ctx`.
+pub fn emit_gate_rejection(ctx: &TradeContext, gate: &str, code: RejectReason, reason: &str) {
+    emit(Event::GateRejection {
+        ts: now_ts(),
+        mint: ctx.mint.clone(),
+        target_wallet: ctx.target_wallet.clone(),
+        gate: gate.to_string(),
+        code,
+        reason: reason.to_string(),
+    });
+}
+
+/// Emit a simulation result for `ctx`.
+pub fn emit_simulation(ctx: &TradeContext, result: &SimulationResult) {
+    emit(Event::Simulation {
+        ts: now_ts(),
+        mint: ctx.mint.clone(),
+        target_wallet: ctx.target_wallet.clone(),
+        result: result.clone(),
+    });
+}
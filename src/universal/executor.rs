@@ -1,12 +1,39 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
 use crate::universal::gates::{
+    Gate,
     TradeContext,
+    enabled_gates,
+    gate_enabled,
     run_gates,
+    run_scored_gates,
+    ScoredGate,
     LiquidityGate,
     McapGate,
     VolatilityGate,
     PumpFunMigrationGate,
+    BondingCurveProgressGate,
+    TransferHookGate,
+    CooldownGate,
+    MintConcurrencyGate,
+    DuplicatePositionGate,
+    MinAgeGate,
+    HolderConcentrationGate,
+    MintAuthorityGate,
+    DailyLossGate,
+    BuyRateGate,
 };
+use crate::common::bot_config;
 use crate::universal::gates::liquidity::LiquidityGateConfig;
+use crate::universal::events::emit_simulation;
+use crate::universal::blacklist::BlacklistGate;
+use crate::universal::models::SimInput;
+use crate::universal::wallet_config::{
+    get_wallet_config_map, is_wallet_enabled, resolve_effective_params_for_wallet, CopyMode,
+    EffectiveWalletParams,
+};
 
 /// Configuration for the simulation backend.
 /// These thresholds mirror the intent of your gates:
@@ -15,6 +42,13 @@ use crate::universal::gates::liquidity::LiquidityGateConfig;
 /// - mcap_min: minimum market cap (USD)
 /// - vol_max_pct: maximum allowed volatility in the window (percent)
 /// - exclude_non_migrated: filter out non-migrated PumpFun tokens
+///
+/// The remaining fields drive the fill / P&L model (see [`estimate_fill`]
+/// and [`estimate_pnl_usd`]):
+/// - max_slippage_bps: total slippage tolerance of the order
+/// - tp_mult / sl_mult: default exit multiples when the wallet has no override
+/// - tp_probability: assumed chance the position exits at TP rather than SL
+/// - fee_usd_per_tx: network + priority fees paid on entry and on exit
 #[derive(Debug, Clone)]
 pub struct SimConfig {
     pub liq5m: f64,
@@ -23,12 +57,16 @@ pub struct SimConfig {
     pub mcap_min: f64,
     pub vol_max_pct: f64,
     pub exclude_non_migrated: bool,
+    pub max_slippage_bps: f64,
+    pub tp_mult: f64,
+    pub sl_mult: f64,
+    pub tp_probability: f64,
+    pub fee_usd_per_tx: f64,
 }
 
 impl Default for SimConfig {
     fn default() -> Self {
-        // Reasonable placeholder defaults; you can later wire these
-        // from env or Config.
+        // Fallbacks when nothing is configured; see `from_env`.
         Self {
             liq5m: 1_000.0,
             liq15m: 3_000.0,
@@ -36,10 +74,150 @@ impl Default for SimConfig {
             mcap_min: 50_000.0,
             vol_max_pct: 50.0,
             exclude_non_migrated: true,
+            max_slippage_bps: 500.0,
+            tp_mult: 1.25,
+            sl_mult: 0.8,
+            tp_probability: 0.5,
+            fee_usd_per_tx: 0.1,
+        }
+    }
+}
+
+impl SimConfig {
+    /// Build from env, using the same variable names as the live
+    /// `LiquidityGate::from_env` path so simulated and live thresholds agree:
+    ///
+    /// - LIQ_5M_MIN_USD
+    /// - LIQ_15M_MIN_USD
+    /// - DEPTH_MULT
+    /// - MCAP_MIN_USD (falls back to MIN_MCAP_USD)
+    /// - VOL_MAX_PCT
+    /// - EXCLUDE_NON_MIGRATED
+    ///
+    /// Unset or unparsable values keep the defaults.
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = bot_config::var("LIQ_5M_MIN_USD") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.liq5m = parsed;
+            }
+        }
+        if let Ok(v) = bot_config::var("LIQ_15M_MIN_USD") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.liq15m = parsed;
+            }
+        }
+        if let Ok(v) = bot_config::var("DEPTH_MULT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.depth_mult = parsed;
+            }
+        }
+        for key in ["MIN_MCAP_USD", "MCAP_MIN_USD"] {
+            if let Ok(v) = bot_config::var(key) {
+                if let Ok(parsed) = v.parse::<f64>() {
+                    cfg.mcap_min = parsed;
+                }
+            }
+        }
+        if let Ok(v) = bot_config::var("VOL_MAX_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.vol_max_pct = parsed;
+            }
+        }
+        if let Ok(v) = bot_config::var("EXCLUDE_NON_MIGRATED") {
+            if let Ok(parsed) = v.parse::<bool>() {
+                cfg.exclude_non_migrated = parsed;
+            }
         }
+
+        cfg
+    }
+
+    /// `from_env` with `wallet`'s slippage and TP/SL overrides layered on top.
+    pub fn for_wallet(wallet: &str) -> Self {
+        let cfg = Self::from_env();
+        let params = resolve_effective_params_for_wallet(
+            wallet,
+            cfg.max_slippage_bps / 10_000.0,
+            cfg.tp_mult,
+            cfg.sl_mult,
+            None,
+        );
+        cfg.with_wallet_params(&params)
+    }
+
+    /// Apply resolved wallet parameters: the slippage fraction becomes the
+    /// fill model's tolerance, TP/SL the assumed exit multiples.
+    pub fn with_wallet_params(mut self, params: &EffectiveWalletParams) -> Self {
+        self.max_slippage_bps = params.slippage * 10_000.0;
+        self.tp_mult = params.tp;
+        self.sl_mult = params.sl;
+        self
+    }
+}
+
+/// Estimated execution of an order against a pool.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillEstimate {
+    /// Fraction of the requested size that fills, in [0.0, 1.0].
+    pub fill_pct: f64,
+    /// Effective slippage of the filled part (fees/spread + price impact), in bps.
+    pub slippage_bps: f64,
+}
+
+/// Fill model for an order against a constant-product pool.
+///
+/// With the order notional normalised to 1 and the pool depth to
+/// `D = depth_multiple`, buying `q` moves the price by `q / (D + q)`. The
+/// order only fills up to the size whose impact stays within what is left of
+/// the slippage budget after fixed costs, `s = (max_slippage_bps -
+/// est_cost_bps) / 10_000`:
+///
+/// ```text
+/// fill_pct     = min(1, D * s / (1 - s))
+/// slippage_bps = est_cost_bps + 10_000 * fill_pct / (D + fill_pct)
+/// ```
+///
+/// No depth or no remaining budget means no fill.
+pub fn estimate_fill(est_cost_bps: f64, depth_multiple: f64, max_slippage_bps: f64) -> FillEstimate {
+    let cost_bps = est_cost_bps.max(0.0);
+    let budget = ((max_slippage_bps - cost_bps) / 10_000.0).min(0.99);
+    if depth_multiple.is_nan() || depth_multiple <= 0.0 || budget.is_nan() || budget <= 0.0 {
+        return FillEstimate { fill_pct: 0.0, slippage_bps: cost_bps };
+    }
+    let fill_pct = (depth_multiple * budget / (1.0 - budget)).min(1.0);
+    let impact = fill_pct / (depth_multiple + fill_pct);
+    FillEstimate {
+        fill_pct,
+        slippage_bps: cost_bps + impact * 10_000.0,
     }
 }
 
+/// Expected P&L (USD) of buying `filled_usd` and later exiting at `tp_mult`
+/// with probability `tp_probability`, else at `sl_mult`.
+///
+/// Entry and exit each pay `slippage_bps` (the exit is assumed to see the
+/// same pool conditions) plus `fee_usd_per_tx`:
+///
+/// ```text
+/// pnl(m) = filled_usd * (m * (1 - c) / (1 + c) - 1) - 2 * fee_usd_per_tx
+/// est    = p * pnl(tp_mult) + (1 - p) * pnl(sl_mult)
+/// ```
+pub fn estimate_pnl_usd(
+    filled_usd: f64,
+    slippage_bps: f64,
+    tp_mult: f64,
+    sl_mult: f64,
+    tp_probability: f64,
+    fee_usd_per_tx: f64,
+) -> f64 {
+    let c = slippage_bps.max(0.0) / 10_000.0;
+    let p = tp_probability.clamp(0.0, 1.0);
+    let pnl = |mult: f64| filled_usd * (mult * (1.0 - c) / (1.0 + c) - 1.0) - 2.0 * fee_usd_per_tx;
+    p * pnl(tp_mult) + (1.0 - p) * pnl(sl_mult)
+}
+
 /// What kind of trade is being simulated.
 #[derive(Debug, Clone, Copy, serde::Serialize)]
 pub enum SimulationAction {
@@ -47,6 +225,78 @@ pub enum SimulationAction {
     Sell,
 }
 
+/// What to do about one leader trade, given the wallet's [`CopyMode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum CopyAction {
+    /// Mirror the leader's buy.
+    Buy,
+    /// Mirror the leader's sell.
+    Sell,
+    /// Close our whole position in the mint (`InverseSells`).
+    Close,
+    /// Ignore the trade.
+    Skip,
+}
+
+impl CopyAction {
+    /// The simulated trade this action amounts to; `None` for [`CopyAction::Skip`].
+    pub fn simulation_action(&self) -> Option<SimulationAction> {
+        match self {
+            CopyAction::Buy => Some(SimulationAction::Buy),
+            CopyAction::Sell | CopyAction::Close => Some(SimulationAction::Sell),
+            CopyAction::Skip => None,
+        }
+    }
+}
+
+/// Decide how to react to a leader trade on `side` ("buy" / "sell", any
+/// case) under `mode`. Unknown sides are skipped.
+pub fn copy_action(mode: CopyMode, side: &str) -> CopyAction {
+    let side = side.trim().to_ascii_lowercase();
+    match (mode, side.as_str()) {
+        (CopyMode::All | CopyMode::BuysOnly, "buy") => CopyAction::Buy,
+        (CopyMode::All | CopyMode::SellsOnly, "sell") => CopyAction::Sell,
+        (CopyMode::InverseSells, "sell") => CopyAction::Close,
+        _ => CopyAction::Skip,
+    }
+}
+
+/// [`copy_action`] for a trade by `wallet`, using its configured copy mode.
+/// Buys from a disabled wallet are skipped; its sells are still mirrored so
+/// positions copied earlier can exit.
+pub fn copy_action_for_wallet(wallet: &str, side: &str) -> CopyAction {
+    let action = copy_action(get_wallet_config_map().get_copy_mode(wallet), side);
+    if action == CopyAction::Buy && !is_wallet_enabled(wallet) {
+        return CopyAction::Skip;
+    }
+    action
+}
+
+/// [`copy_action_for_wallet`] for a leader event's `target_wallet`.
+pub fn copy_action_for(input: &SimInput) -> CopyAction {
+    copy_action_for_wallet(&input.target_wallet, &input.side)
+}
+
+/// Pre-gate check: `Some(reason)` when the leader's trade notional
+/// (`qty * price_usd`) is below `min_leader_trade_usd`, i.e. a dust trade
+/// whose fees would exceed any edge. A minimum of 0 copies everything.
+pub fn leader_trade_too_small(input: &SimInput, min_leader_trade_usd: f64) -> Option<String> {
+    let notional = input.qty.abs() * input.price_usd;
+    if min_leader_trade_usd > 0.0 && notional < min_leader_trade_usd {
+        return Some(format!("leader_trade_too_small {} < {}", notional, min_leader_trade_usd));
+    }
+    None
+}
+
+/// [`leader_trade_too_small`] with the `min_leader_trade_usd` configured for
+/// the event's `target_wallet`.
+pub fn leader_trade_too_small_for_wallet(input: &SimInput) -> Option<String> {
+    leader_trade_too_small(
+        input,
+        get_wallet_config_map().get_min_leader_trade_usd(&input.target_wallet),
+    )
+}
+
 /// Structured result of a simulation run.
 /// This is meant to be easy to log / serialize and later
 /// extended with more P&L / slippage info.
@@ -58,11 +308,11 @@ pub struct SimulationResult {
     pub passed: bool,
     /// If any gate rejected, (gate_name, reason) entries.
     pub gate_reasons: Vec<(String, String)>,
-    /// Estimated fill fraction [0.0, 1.0].
-    /// For now this is a simple placeholder based on gate pass/fail.
+    /// Estimated fill fraction [0.0, 1.0] from [`estimate_fill`]; 0 when a
+    /// gate rejected the trade.
     pub est_fill_pct: f64,
-    /// Optional estimated P&L in USD.
-    /// You can enrich this later once you have full trade stats.
+    /// Estimated P&L in USD from [`estimate_pnl_usd`]. Only set for buys
+    /// that passed and carry a `requested_size_usd`.
     pub est_pnl_usd: Option<f64>,
 }
 
@@ -89,52 +339,264 @@ impl SimBackend {
     pub fn default() -> Self {
         Self { cfg: SimConfig::default() }
     }
-}
 
-impl ExecutionSimulator for SimBackend {
-    fn simulate(&self, ctx: &TradeContext, action: SimulationAction) -> SimulationResult {
+    /// The gates [`simulate`](ExecutionSimulator::simulate) runs for `action`,
+    /// in evaluation order, minus any switched off via their `*_GATE_ENABLED`
+    /// var. Callers can reorder or extend the list and pass it to `run_gates`
+    /// or `run_gates_collect` themselves.
+    pub fn gates_for(&self, action: SimulationAction) -> Vec<Box<dyn crate::universal::gates::Gate>> {
         // Build the same set of gates you intend to use in live trading.
         // NOTE: This relies on the gate structs having public fields:
         //   LiquidityGate { min5m, min15m, depth_mult_min }
         //   McapGate { min_mcap }
         //   VolatilityGate { max_pct }
         //   PumpFunMigrationGate { exclude_non_migrated }
-        let gates: Vec<Box<dyn crate::universal::gates::Gate>> = vec![
-    Box::new(LiquidityGate::new(LiquidityGateConfig {
-        liq_5m_min_usd: self.cfg.liq5m,
-        liq_15m_min_usd: self.cfg.liq15m,
-        min_mcap_usd: self.cfg.mcap_min,
-        depth_mult_min: self.cfg.depth_mult,
-    })),
-    Box::new(McapGate {
-        min_mcap: self.cfg.mcap_min,
-    }),
-    Box::new(VolatilityGate {
-        max_pct: self.cfg.vol_max_pct,
-    }),
-    Box::new(PumpFunMigrationGate {
-        exclude_non_migrated: self.cfg.exclude_non_migrated,
-    }),
-];
-
-        let (ok, reasons) = run_gates(ctx, &gates);
-
-        // For now, we approximate:
-        // - if gates passed: 100% fill, unknown P&L.
-        // - if gates failed: 0% fill, no P&L.
-        //
-        // Later, you can:
-        // - Model slippage based on est_cost_bps + depth_multiple.
-        // - Estimate P&L given entry price / target exit / fees.
-        let est_fill_pct = if ok { 1.0 } else { 0.0 };
-
-        SimulationResult {
+        let mut gates: Vec<Box<dyn crate::universal::gates::Gate>> = vec![
+            Box::new(LiquidityGate::new(LiquidityGateConfig {
+                liq_5m_min_usd: self.cfg.liq5m,
+                liq_15m_min_usd: self.cfg.liq15m,
+                min_mcap_usd: self.cfg.mcap_min,
+                depth_mult_min: self.cfg.depth_mult,
+                ..LiquidityGateConfig::default()
+            })),
+            Box::new(McapGate {
+                min_mcap: self.cfg.mcap_min,
+            }),
+            Box::new(VolatilityGate {
+                max_pct: self.cfg.vol_max_pct,
+            }),
+            Box::new(PumpFunMigrationGate {
+                exclude_non_migrated: self.cfg.exclude_non_migrated,
+            }),
+            Box::new(TransferHookGate::from_env()),
+        ];
+        // Blacklist, re-buy cooldown, per-mint caps, duplicate, age, curve
+        // progress, holder, authority, daily-loss and rate-limit checks only
+        // apply to opening positions; exits from a blacklisted mint must
+        // still go through.
+        // The rate limit goes last since a passing check spends a buy.
+        if let SimulationAction::Buy = action {
+            gates.insert(0, Box::new(BlacklistGate::session()));
+            gates.push(Box::new(CooldownGate::from_env()));
+            gates.push(Box::new(MintConcurrencyGate::from_env()));
+            gates.push(Box::new(DuplicatePositionGate::from_env()));
+            gates.push(Box::new(MinAgeGate::from_env()));
+            gates.push(Box::new(BondingCurveProgressGate::from_env()));
+            gates.push(Box::new(HolderConcentrationGate::from_env()));
+            gates.push(Box::new(MintAuthorityGate::from_env()));
+            gates.push(Box::new(DailyLossGate::from_env()));
+            gates.push(Box::new(BuyRateGate::from_env()));
+        }
+        enabled_gates(gates)
+    }
+
+    /// Soft versions of the liquidity and volatility gates (unless disabled),
+    /// for sizing trades by how comfortably they clear the thresholds.
+    pub fn scored_gates(&self) -> Vec<Box<dyn ScoredGate>> {
+        let mut gates: Vec<Box<dyn ScoredGate>> = vec![
+            Box::new(LiquidityGate::new(LiquidityGateConfig {
+                liq_5m_min_usd: self.cfg.liq5m,
+                liq_15m_min_usd: self.cfg.liq15m,
+                min_mcap_usd: self.cfg.mcap_min,
+                depth_mult_min: self.cfg.depth_mult,
+                ..LiquidityGateConfig::default()
+            })),
+            Box::new(VolatilityGate {
+                max_pct: self.cfg.vol_max_pct,
+            }),
+        ];
+        gates.retain(|g| gate_enabled(g.name()));
+        gates
+    }
+
+    /// `ctx.requested_size_usd` scaled by the aggregate score of
+    /// [`scored_gates`](Self::scored_gates); `None` without a requested size.
+    pub fn scored_size_usd(&self, ctx: &TradeContext) -> Option<f64> {
+        let score = run_scored_gates(ctx, &self.scored_gates());
+        ctx.requested_size_usd.map(|size| size * score)
+    }
+}
+
+impl ExecutionSimulator for SimBackend {
+    fn simulate(&self, ctx: &TradeContext, action: SimulationAction) -> SimulationResult {
+        // Pausing and position limits block new positions only; sells are
+        // always simulated. The leader signature is claimed only once the
+        // gates pass too, so a rejected buy can be evaluated again.
+        if let SimulationAction::Buy = action {
+            let owner_wallet = ctx.owner_wallet.as_deref().unwrap_or(&ctx.target_wallet);
+            if let Err(reject) = crate::universal::control::admission_check(
+                &ctx.target_wallet,
+                owner_wallet,
+                &ctx.mint,
+                None,
+            ) {
+                let result = SimulationResult {
+                    mint: ctx.mint.clone(),
+                    action,
+                    passed: false,
+                    gate_reasons: vec![("Control".to_string(), reject.to_string())],
+                    est_fill_pct: 0.0,
+                    est_pnl_usd: None,
+                };
+                emit_simulation(ctx, &result);
+                return result;
+            }
+        }
+
+        let gates = self.gates_for(action);
+        let (mut ok, mut reasons) = run_gates(ctx, &gates);
+        if ok && matches!(action, SimulationAction::Buy) {
+            if let Err(reject) =
+                crate::universal::control::claim_leader_signature(ctx.leader_signature.as_deref())
+            {
+                ok = false;
+                reasons.push(("Control".to_string(), reject.to_string()));
+            }
+        }
+
+        // Rejected trades don't fill. Otherwise size the fill against pool
+        // depth, and for buys price the round trip at the wallet's TP/SL.
+        let (est_fill_pct, est_pnl_usd) = if ok {
+            let fill = estimate_fill(ctx.est_cost_bps, ctx.depth_multiple, self.cfg.max_slippage_bps);
+            let pnl = match (action, ctx.requested_size_usd) {
+                (SimulationAction::Buy, Some(size_usd)) => {
+                    let wallets = get_wallet_config_map();
+                    Some(estimate_pnl_usd(
+                        size_usd * fill.fill_pct,
+                        fill.slippage_bps,
+                        wallets.get_tp(&ctx.target_wallet).unwrap_or(self.cfg.tp_mult),
+                        wallets.get_sl(&ctx.target_wallet).unwrap_or(self.cfg.sl_mult),
+                        self.cfg.tp_probability,
+                        self.cfg.fee_usd_per_tx,
+                    ))
+                }
+                _ => None,
+            };
+            (fill.fill_pct, pnl)
+        } else {
+            (0.0, None)
+        };
+
+        let result = SimulationResult {
             mint: ctx.mint.clone(),
             action,
             passed: ok,
             gate_reasons: reasons,
             est_fill_pct,
-            est_pnl_usd: None,
+            est_pnl_usd,
+        };
+        emit_simulation(ctx, &result);
+        result
+    }
+}
+
+/// Deadline for the whole buy decision (metrics fetch, gates and send) from
+/// DECISION_DEADLINE_MS; `None` when unset or 0.
+pub fn decision_deadline_from_env() -> Option<Duration> {
+    if let Ok(v) = bot_config::var("DECISION_DEADLINE_MS") {
+        if let Ok(parsed) = v.parse::<u64>() {
+            if parsed > 0 {
+                return Some(Duration::from_millis(parsed));
+            }
         }
     }
+    None
+}
+
+/// Records how long each phase of a decision took. Cloned into the decision
+/// so the phases finished before a timeout are still reported.
+#[derive(Clone, Debug, Default)]
+pub struct PhaseTimer {
+    phases: Arc<Mutex<Vec<(&'static str, Duration)>>>,
+}
+
+impl PhaseTimer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Await `fut` and record its wall time under `phase`.
+    pub async fn time<F: Future>(&self, phase: &'static str, fut: F) -> F::Output {
+        let started = Instant::now();
+        let output = fut.await;
+        self.record(phase, started.elapsed());
+        output
+    }
+
+    pub fn record(&self, phase: &'static str, elapsed: Duration) {
+        match self.phases.lock() {
+            Ok(mut guard) => guard.push((phase, elapsed)),
+            Err(poisoned) => poisoned.into_inner().push((phase, elapsed)),
+        }
+    }
+
+    /// Completed phases in the order they finished.
+    pub fn phases(&self) -> Vec<(&'static str, Duration)> {
+        match self.phases.lock() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+/// Outcome of a decision raced against its deadline.
+#[derive(Debug)]
+pub enum DeadlineResult<T> {
+    Completed {
+        value: T,
+        phases: Vec<(&'static str, Duration)>,
+    },
+    /// The deadline passed first and the trade was abandoned. `phases` holds
+    /// the phases that had finished by then.
+    TimedOut {
+        deadline: Duration,
+        phases: Vec<(&'static str, Duration)>,
+    },
+}
+
+impl<T> DeadlineResult<T> {
+    pub fn is_timed_out(&self) -> bool {
+        matches!(self, DeadlineResult::TimedOut { .. })
+    }
+}
+
+/// Race `decision` against `deadline`. The decision gets a [`PhaseTimer`]
+/// to time its phases; if the deadline fires first the decision future is
+/// dropped, abandoning the trade, and `TimedOut` is returned.
+///
+/// Synchronous work only yields to the timer between awaits, so run slow
+/// blocking steps such as gates through [`run_gates_blocking`].
+pub async fn with_deadline<F, Fut, T>(deadline: Duration, decision: F) -> DeadlineResult<T>
+where
+    F: FnOnce(PhaseTimer) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let timer = PhaseTimer::new();
+    match tokio::time::timeout(deadline, decision(timer.clone())).await {
+        Ok(value) => DeadlineResult::Completed {
+            value,
+            phases: timer.phases(),
+        },
+        Err(_) => {
+            let phases = timer.phases();
+            eprintln!(
+                "[EXECUTOR] decision abandoned after {:?} deadline; finished phases: {:?}",
+                deadline, phases
+            );
+            DeadlineResult::TimedOut { deadline, phases }
+        }
+    }
+}
+
+/// [`run_gates`] on the blocking thread pool, so a deadline can fire while a
+/// slow gate is still running. The gates' result is discarded if the caller
+/// has given up by the time they finish.
+pub async fn run_gates_blocking(
+    ctx: TradeContext,
+    gates: Vec<Box<dyn Gate>>,
+) -> (bool, Vec<(String, String)>) {
+    match tokio::task::spawn_blocking(move || run_gates(&ctx, &gates)).await {
+        Ok(result) => result,
+        Err(e) => (false, vec![("executor".to_string(), format!("gate task failed: {}", e))]),
+    }
 }
@@ -0,0 +1,138 @@
+use std::sync::{Arc, RwLock};
+
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+
+use crate::common::bot_config;
+use crate::universal::gates::{run_gates, Gate, GateDecision, TradeContext};
+use crate::universal::positions::PositionsRegistry;
+
+/// Upper bound on any slippage setting (100%).
+const MAX_SLIPPAGE_BPS: u64 = 10_000;
+
+/// Slippage used by forced exits when FORCE_SELL_SLIPPAGE_BPS is unset:
+/// the maximum, i.e. accept any price.
+pub const DEFAULT_FORCE_SELL_SLIPPAGE_BPS: u64 = MAX_SLIPPAGE_BPS;
+
+/// Slippage (bps) a forced exit widens to.
+///
+/// Reads FORCE_SELL_SLIPPAGE_BPS (default 10000), capped at 10000.
+pub fn force_sell_slippage_bps() -> u64 {
+    let mut bps = DEFAULT_FORCE_SELL_SLIPPAGE_BPS;
+    if let Ok(v) = bot_config::var("FORCE_SELL_SLIPPAGE_BPS") {
+        if let Ok(parsed) = v.parse::<u64>() {
+            bps = parsed;
+        }
+    }
+    bps.min(MAX_SLIPPAGE_BPS)
+}
+
+/// Slippage to use for a sell. Forced exits never tighten the configured
+/// slippage, they only widen it up to [`force_sell_slippage_bps`].
+pub fn sell_slippage_bps(configured_bps: u64, force: bool) -> u64 {
+    if force {
+        configured_bps.max(force_sell_slippage_bps())
+    } else {
+        configured_bps
+    }
+}
+
+/// Run sell-side gates.
+///
+/// With `force` set (panic / kill-switch exits only) every gate is still
+/// evaluated so the log shows what *would* have blocked, but the sell always
+/// proceeds. Returns `(proceed, reasons)` like [`run_gates`].
+pub fn run_sell_gates(
+    ctx: &TradeContext,
+    gates: &[Box<dyn Gate>],
+    force: bool,
+) -> (bool, Vec<(String, String)>) {
+    if !force {
+        return run_gates(ctx, gates);
+    }
+
+    let mut bypassed = Vec::new();
+    for g in gates {
+        if let GateDecision::Rejected { reason, .. } = g.check(ctx) {
+            eprintln!(
+                "[FORCE SELL] {} bypassing gate {} for {}: {}",
+                ctx.target_wallet,
+                g.name(),
+                ctx.mint,
+                reason
+            );
+            bypassed.push((g.name().to_string(), reason));
+        }
+    }
+    (true, bypassed)
+}
+
+/// Sells a mint at market for the panic / kill-switch flows (`/exit`,
+/// `/exit_all`, SHUTDOWN_FORCE_EXIT). The live implementation is
+/// `SellingEngine`, which also closes the positions once the sell confirms.
+/// Resolves with the sell signature.
+pub trait ForceSeller: Send + Sync {
+    fn force_sell<'a>(&'a self, mint: &'a str) -> BoxFuture<'a, Result<String, String>>;
+}
+
+static FORCE_SELLER: Lazy<RwLock<Option<Arc<dyn ForceSeller>>>> = Lazy::new(|| RwLock::new(None));
+
+/// Install `seller` as the process-wide force seller, replacing any previous one.
+pub fn install_force_seller(seller: Arc<dyn ForceSeller>) {
+    match FORCE_SELLER.write() {
+        Ok(mut guard) => *guard = Some(seller),
+        Err(poisoned) => *poisoned.into_inner() = Some(seller),
+    }
+}
+
+/// The installed force seller, if any.
+pub fn force_seller() -> Option<Arc<dyn ForceSeller>> {
+    match FORCE_SELLER.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Mints with a real (not simulated) position still open in `registry`,
+/// sorted and deduplicated. Paper positions have nothing to sell.
+pub fn live_open_mints(registry: &PositionsRegistry) -> Vec<String> {
+    let mut mints: Vec<String> = registry
+        .all_open_positions()
+        .into_iter()
+        .filter(|p| !p.simulated)
+        .map(|p| p.mint)
+        .collect();
+    mints.sort();
+    mints.dedup();
+    mints
+}
+
+/// Force-sell each of `mints` in turn through `seller`, logging and
+/// returning every outcome. One failed sell does not stop the rest.
+pub async fn force_exit(
+    seller: &dyn ForceSeller,
+    mints: &[String],
+) -> Vec<(String, Result<String, String>)> {
+    let mut results = Vec::with_capacity(mints.len());
+    for mint in mints {
+        let result = seller.force_sell(mint).await;
+        match &result {
+            Ok(signature) => println!("[FORCE SELL] {} sold: {}", mint, signature),
+            Err(e) => eprintln!("[FORCE SELL] {} failed: {}", mint, e),
+        }
+        results.push((mint.clone(), result));
+    }
+    results
+}
+
+/// Start [`force_exit`] of `mints` in the background through the installed
+/// seller. Errs when no seller is installed or there is no Tokio runtime to
+/// run it on.
+pub fn spawn_force_exit(mints: Vec<String>) -> Result<(), String> {
+    let seller = force_seller().ok_or_else(|| "no force seller installed".to_string())?;
+    let runtime = tokio::runtime::Handle::try_current().map_err(|e| e.to_string())?;
+    runtime.spawn(async move {
+        force_exit(seller.as_ref(), &mints).await;
+    });
+    Ok(())
+}
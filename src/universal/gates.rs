@@ -1,11 +1,33 @@
 
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
+use crate::common::bot_config;
+use crate::universal::positions::{
+    DailyLossTracker, PositionsRegistry, RecentCloses, GLOBAL_POSITIONS_REGISTRY,
+};
+use crate::universal::wallet_config::get_wallet_config_map;
+
+pub mod authority;
+pub mod curve_progress;
+pub mod holders;
 pub mod liquidity;
+pub mod rate_limit;
+pub mod stats;
+pub mod transfer_hook;
+pub use authority::MintAuthorityGate;
+pub use curve_progress::BondingCurveProgressGate;
+pub use holders::HolderConcentrationGate;
 pub use liquidity::LiquidityGate;
+pub use rate_limit::BuyRateGate;
+pub use transfer_hook::TransferHookGate;
+pub use stats::GLOBAL_GATE_STATS;
+use stats::{Clock, SystemClock};
+use crate::universal::metrics::clock::MetricsClock;
 
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct TradeContext {
     pub mint: String,
     pub target_wallet: String,
@@ -18,12 +40,134 @@ pub struct TradeContext {
     pub window_vol_pct: f64,
     pub is_pumpfun: bool,
     pub pumpfun_migrated: Option<bool>,
+    /// Amihud illiquidity over the last 5 minutes (|return| / volume_usd).
+    pub amihud_5m: Option<f64>,
+    /// Range efficiency over the last 5 minutes (net move / sum of moves).
+    pub range_eff_5m: Option<f64>,
+    /// Age of the underlying metrics snapshot in seconds.
+    pub metrics_age_secs: Option<i64>,
+    /// Size of the order we intend to place, in USD.
+    pub requested_size_usd: Option<f64>,
+    /// Pool liquidity (TVL) in USD.
+    pub liq_usd: Option<f64>,
+    /// Token-2022 transfer-hook program on the mint, if it has one.
+    pub transfer_hook_program: Option<String>,
+    /// Unix time (seconds) the metrics pipeline first observed the mint.
+    #[serde(default)]
+    pub first_seen_ts: Option<i64>,
+    /// Percentage of supply held by the ten largest token accounts.
+    #[serde(default)]
+    pub top10_holder_pct: Option<f64>,
+    /// PumpFun bonding-curve progress in percent (100 once complete).
+    #[serde(default)]
+    pub bonding_curve_progress_pct: Option<f64>,
+    /// Signature of the leader trade being copied, for dedup on admission.
+    #[serde(default)]
+    pub leader_signature: Option<String>,
+    /// Our signing wallet, which positions are recorded under. Falls back to
+    /// `target_wallet` when unset.
+    #[serde(default)]
+    pub owner_wallet: Option<String>,
+}
+
+/// Stable, machine-readable cause of a gate rejection, carried next to the
+/// human-readable reason so metrics, retries and UIs need not parse text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectReason {
+    LowLiq5m,
+    LowLiq15m,
+    LowLiqScore,
+    LowMcap,
+    NoMcap,
+    Amihud,
+    NoAmihud,
+    RangeEff,
+    NoRangeEff,
+    Stale,
+    NoMetricsAge,
+    DepthMultiple,
+    SizeOverLiq,
+    PriceImpact,
+    DeadLiquidity,
+    HighVolatility,
+    NotMigrated,
+    MigrationUnknown,
+    CurveProgress,
+    NoCurveProgress,
+    Blacklisted,
+    Cooldown,
+    MintConcurrency,
+    DailyLossLimit,
+    NoFirstSeen,
+    TooNew,
+    DuplicatePosition,
+    TransferHook,
+    NoMintData,
+    MintAuthorityActive,
+    FreezeAuthorityActive,
+    HolderConcentration,
+    NoHolderData,
+    RateLimited,
+    /// Anything not covered above, e.g. a gate defined outside this crate.
+    Other,
+}
+
+impl RejectReason {
+    /// Snake-case code, as serialized; used as the metrics label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RejectReason::LowLiq5m => "low_liq5m",
+            RejectReason::LowLiq15m => "low_liq15m",
+            RejectReason::LowLiqScore => "low_liq_score",
+            RejectReason::LowMcap => "low_mcap",
+            RejectReason::NoMcap => "no_mcap",
+            RejectReason::Amihud => "amihud",
+            RejectReason::NoAmihud => "no_amihud",
+            RejectReason::RangeEff => "range_eff",
+            RejectReason::NoRangeEff => "no_range_eff",
+            RejectReason::Stale => "stale",
+            RejectReason::NoMetricsAge => "no_metrics_age",
+            RejectReason::DepthMultiple => "depth_multiple",
+            RejectReason::SizeOverLiq => "size_over_liq",
+            RejectReason::PriceImpact => "price_impact",
+            RejectReason::DeadLiquidity => "dead_liquidity",
+            RejectReason::HighVolatility => "high_volatility",
+            RejectReason::NotMigrated => "not_migrated",
+            RejectReason::MigrationUnknown => "migration_unknown",
+            RejectReason::CurveProgress => "curve_progress",
+            RejectReason::NoCurveProgress => "no_curve_progress",
+            RejectReason::Blacklisted => "blacklisted",
+            RejectReason::Cooldown => "cooldown",
+            RejectReason::MintConcurrency => "mint_concurrency",
+            RejectReason::DailyLossLimit => "daily_loss_limit",
+            RejectReason::NoFirstSeen => "no_first_seen",
+            RejectReason::TooNew => "too_new",
+            RejectReason::DuplicatePosition => "duplicate_position",
+            RejectReason::TransferHook => "transfer_hook",
+            RejectReason::NoMintData => "no_mint_data",
+            RejectReason::MintAuthorityActive => "mint_authority_active",
+            RejectReason::FreezeAuthorityActive => "freeze_authority_active",
+            RejectReason::HolderConcentration => "holder_concentration",
+            RejectReason::NoHolderData => "no_holder_data",
+            RejectReason::RateLimited => "rate_limited",
+            RejectReason::Other => "other",
+        }
+    }
 }
 
+impl std::fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Outcome of one gate. A rejection carries both a stable [`RejectReason`]
+/// and a human-readable `reason` for logs.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum GateDecision {
     Passed,
-    Rejected { reason: String },
+    Rejected { code: RejectReason, reason: String },
 }
 
 pub trait Gate: Send + Sync {
@@ -31,6 +175,46 @@ pub trait Gate: Send + Sync {
     fn check(&self, ctx: &TradeContext) -> GateDecision;
 }
 
+/// Soft counterpart of [`Gate`]: how comfortably a trade clears the gate's
+/// thresholds, in `[0, 1]`. Exactly at a threshold scores 0.5, twice as far
+/// on the good side scores 1.0 and the far bad side falls to 0.0, so a narrow
+/// miss still scores just under 0.5.
+pub trait ScoredGate: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn score(&self, ctx: &TradeContext) -> f64;
+}
+
+/// Score `value` against a floor: 0.5 at `min`, 1.0 at `2 * min` and above,
+/// 0.0 at zero. A non-positive `min` is not enforced and scores 1.0.
+pub fn score_at_least(value: f64, min: f64) -> f64 {
+    if min <= 0.0 {
+        return 1.0;
+    }
+    if value.is_nan() {
+        return 0.0;
+    }
+    (value / (2.0 * min)).clamp(0.0, 1.0)
+}
+
+/// Score `value` against a ceiling: 1.0 at zero, 0.5 at `max`, 0.0 at
+/// `2 * max` and above. A non-positive `max` scores 1.0 only at zero.
+pub fn score_at_most(value: f64, max: f64) -> f64 {
+    if value.is_nan() {
+        return 0.0;
+    }
+    if max <= 0.0 {
+        return if value <= 0.0 { 1.0 } else { 0.0 };
+    }
+    (1.0 - value / (2.0 * max)).clamp(0.0, 1.0)
+}
+
+/// Aggregate score of `gates`: the weakest gate's score, so one narrow miss
+/// caps the trade no matter how strongly the rest pass. No gates scores 1.0.
+/// Callers scale the position size by the result.
+pub fn run_scored_gates(ctx: &TradeContext, gates: &[Box<dyn ScoredGate>]) -> f64 {
+    gates.iter().map(|g| g.score(ctx)).fold(1.0, f64::min)
+}
+
 
 pub struct McapGate { pub min_mcap: f64 }
 pub struct VolatilityGate { pub max_pct: f64 }
@@ -41,8 +225,8 @@ impl Gate for McapGate {
     fn check(&self, ctx: &TradeContext) -> GateDecision {
         match ctx.est_mcap_usd {
             Some(v) if v >= self.min_mcap => GateDecision::Passed,
-            Some(v) => GateDecision::Rejected{reason: format!("mcap_usd {} < {}", v, self.min_mcap)},
-            None => GateDecision::Rejected{reason: "mcap_usd missing".to_string()},
+            Some(v) => GateDecision::Rejected{code: RejectReason::LowMcap, reason: format!("mcap_usd {} < {}", v, self.min_mcap)},
+            None => GateDecision::Rejected{code: RejectReason::NoMcap, reason: "mcap_usd missing".to_string()},
         }
     }
 }
@@ -51,36 +235,369 @@ impl Gate for VolatilityGate {
     fn name(&self) -> &'static str { "VolatilityGate" }
     fn check(&self, ctx: &TradeContext) -> GateDecision {
         if ctx.window_vol_pct > self.max_pct {
-            return GateDecision::Rejected{reason: format!("vol_pct {} > {}", ctx.window_vol_pct, self.max_pct)};
+            return GateDecision::Rejected{code: RejectReason::HighVolatility, reason: format!("vol_pct {} > {}", ctx.window_vol_pct, self.max_pct)};
         }
         GateDecision::Passed
     }
 }
 
+impl ScoredGate for VolatilityGate {
+    fn name(&self) -> &'static str { "VolatilityGate" }
+    fn score(&self, ctx: &TradeContext) -> f64 {
+        score_at_most(ctx.window_vol_pct, self.max_pct)
+    }
+}
+
 impl Gate for PumpFunMigrationGate {
     fn name(&self) -> &'static str { "PumpFunMigrationGate" }
     fn check(&self, ctx: &TradeContext) -> GateDecision {
         if self.exclude_non_migrated && ctx.is_pumpfun {
             if let Some(m) = ctx.pumpfun_migrated {
-                if !m { return GateDecision::Rejected{reason: "pumpfun_non_migrated".into()}; }
+                if !m { return GateDecision::Rejected{code: RejectReason::NotMigrated, reason: "pumpfun_non_migrated".into()}; }
             } else {
-                return GateDecision::Rejected{reason: "pumpfun_migration_unknown".into()};
+                return GateDecision::Rejected{code: RejectReason::MigrationUnknown, reason: "pumpfun_migration_unknown".into()};
             }
         }
         GateDecision::Passed
     }
 }
 
+/// Rejects buys of a mint within `cooldown_secs` of the last close in that
+/// mint, so the bot does not thrash in and out as the leader flip-flops.
+///
+/// Close timestamps come from the positions registry's close log.
+pub struct CooldownGate<C: Clock = SystemClock> {
+    pub cooldown_secs: i64,
+    closes: Arc<RecentCloses>,
+    clock: C,
+}
+
+impl CooldownGate {
+    pub fn new(cooldown_secs: i64, closes: Arc<RecentCloses>) -> Self {
+        Self::with_clock(cooldown_secs, closes, SystemClock)
+    }
+
+    /// Build from COOLDOWN_SECS (default 0 = disabled), reading closes
+    /// recorded by the global positions registry.
+    pub fn from_env() -> Self {
+        let mut cooldown_secs = 0;
+        if let Ok(v) = bot_config::var("COOLDOWN_SECS") {
+            if let Ok(parsed) = v.parse::<i64>() {
+                cooldown_secs = parsed;
+            }
+        }
+        Self::new(cooldown_secs, GLOBAL_POSITIONS_REGISTRY.close_log())
+    }
+}
+
+impl<C: Clock> CooldownGate<C> {
+    pub fn with_clock(cooldown_secs: i64, closes: Arc<RecentCloses>, clock: C) -> Self {
+        Self { cooldown_secs, closes, clock }
+    }
+}
+
+impl<C: Clock + Send + Sync> Gate for CooldownGate<C> {
+    fn name(&self) -> &'static str { "CooldownGate" }
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        if let Some(closed_ts) = self.closes.last_close(&ctx.mint) {
+            let remaining = closed_ts + self.cooldown_secs - self.clock.now_secs();
+            if remaining > 0 {
+                return GateDecision::Rejected{code: RejectReason::Cooldown, reason: format!("cooldown {}s", remaining)};
+            }
+        }
+        GateDecision::Passed
+    }
+}
+
+/// Caps how many wallets may hold the same mint at once, so several leaders
+/// aping the same token do not stack correlated risk. A cap of 0 disables it.
+pub struct MintConcurrencyGate {
+    pub max_positions_per_mint: usize,
+    registry: &'static PositionsRegistry,
+}
+
+impl MintConcurrencyGate {
+    pub fn new(max_positions_per_mint: usize, registry: &'static PositionsRegistry) -> Self {
+        Self { max_positions_per_mint, registry }
+    }
+
+    /// Build from MAX_POSITIONS_PER_MINT (default 0 = unlimited) against the
+    /// global positions registry.
+    pub fn from_env() -> Self {
+        let mut max_positions_per_mint = 0;
+        if let Ok(v) = bot_config::var("MAX_POSITIONS_PER_MINT") {
+            if let Ok(parsed) = v.parse::<usize>() {
+                max_positions_per_mint = parsed;
+            }
+        }
+        Self::new(max_positions_per_mint, &GLOBAL_POSITIONS_REGISTRY)
+    }
+}
+
+impl Gate for MintConcurrencyGate {
+    fn name(&self) -> &'static str { "MintConcurrencyGate" }
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        if self.max_positions_per_mint == 0 {
+            return GateDecision::Passed;
+        }
+        let n = self.registry.count_open_for_mint(&ctx.mint);
+        if n >= self.max_positions_per_mint {
+            return GateDecision::Rejected{code: RejectReason::MintConcurrency, reason: format!("mint_concurrency {} >= {}", n, self.max_positions_per_mint)};
+        }
+        GateDecision::Passed
+    }
+}
+
+/// Halts new buys once the day's realized PnL (UTC) falls below
+/// `-max_daily_loss_usd` across all wallets, or below the copied wallet's own
+/// `max_daily_loss_usd` from the wallet config. Only applied to buys, so
+/// closes still go through. A global limit of 0 disables the global check.
+///
+/// The per-wallet limit is configured on the copied (leader) wallet but
+/// measured against the PnL booked for our own wallet, which positions are
+/// recorded under (`ctx.owner_wallet`, falling back to `ctx.target_wallet`).
+pub struct DailyLossGate<C: Clock = SystemClock> {
+    pub max_daily_loss_usd: f64,
+    tracker: Arc<DailyLossTracker>,
+    clock: C,
+}
+
+impl DailyLossGate {
+    pub fn new(max_daily_loss_usd: f64, tracker: Arc<DailyLossTracker>) -> Self {
+        Self::with_clock(max_daily_loss_usd, tracker, SystemClock)
+    }
+
+    /// Build from MAX_DAILY_LOSS_USD (default 0 = no global limit), reading
+    /// PnL realized through the global positions registry.
+    pub fn from_env() -> Self {
+        let mut max_daily_loss_usd = 0.0;
+        if let Ok(v) = bot_config::var("MAX_DAILY_LOSS_USD") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                max_daily_loss_usd = parsed;
+            }
+        }
+        Self::new(max_daily_loss_usd, GLOBAL_POSITIONS_REGISTRY.daily_pnl())
+    }
+}
+
+impl<C: Clock> DailyLossGate<C> {
+    pub fn with_clock(max_daily_loss_usd: f64, tracker: Arc<DailyLossTracker>, clock: C) -> Self {
+        Self { max_daily_loss_usd, tracker, clock }
+    }
+}
+
+impl<C: Clock + Send + Sync> Gate for DailyLossGate<C> {
+    fn name(&self) -> &'static str { "DailyLossGate" }
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        let now = self.clock.now_secs();
+        if self.max_daily_loss_usd > 0.0 && self.tracker.today_pnl_usd(now) < -self.max_daily_loss_usd {
+            return GateDecision::Rejected{code: RejectReason::DailyLossLimit, reason: "daily_loss_limit_hit".into()};
+        }
+        if let Some(limit) = get_wallet_config_map().get_max_daily_loss_usd(&ctx.target_wallet) {
+            let owner_wallet = ctx.owner_wallet.as_deref().unwrap_or(&ctx.target_wallet);
+            if self.tracker.wallet_pnl_usd(owner_wallet, now) < -limit {
+                return GateDecision::Rejected{code: RejectReason::DailyLossLimit, reason: "daily_loss_limit_hit".into()};
+            }
+        }
+        GateDecision::Passed
+    }
+}
+
+/// Rejects tokens younger than `min_age_secs`, measured from the first time
+/// the metrics pipeline saw the mint, to skip the first seconds after
+/// creation where rug risk is highest. Age runs on the metrics clock so it
+/// follows event time during a replay. A threshold of 0 disables the gate.
+pub struct MinAgeGate<C: Clock = MetricsClock> {
+    pub min_age_secs: i64,
+    clock: C,
+}
+
+impl MinAgeGate {
+    pub fn new(min_age_secs: i64) -> Self {
+        Self::with_clock(min_age_secs, MetricsClock)
+    }
+
+    /// Build from MIN_TOKEN_AGE_SECS (default 0 = disabled).
+    pub fn from_env() -> Self {
+        let mut min_age_secs = 0;
+        if let Ok(v) = bot_config::var("MIN_TOKEN_AGE_SECS") {
+            if let Ok(parsed) = v.parse::<i64>() {
+                min_age_secs = parsed;
+            }
+        }
+        Self::new(min_age_secs)
+    }
+}
+
+impl<C: Clock> MinAgeGate<C> {
+    pub fn with_clock(min_age_secs: i64, clock: C) -> Self {
+        Self { min_age_secs, clock }
+    }
+}
+
+impl<C: Clock + Send + Sync> Gate for MinAgeGate<C> {
+    fn name(&self) -> &'static str { "MinAgeGate" }
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        if self.min_age_secs <= 0 {
+            return GateDecision::Passed;
+        }
+        let Some(first_seen) = ctx.first_seen_ts else {
+            return GateDecision::Rejected{code: RejectReason::NoFirstSeen, reason: "no_first_seen".into()};
+        };
+        let age = (self.clock.now_secs() - first_seen).max(0);
+        if age < self.min_age_secs {
+            return GateDecision::Rejected{code: RejectReason::TooNew, reason: format!("too_new {}s < {}", age, self.min_age_secs)};
+        }
+        GateDecision::Passed
+    }
+}
+
+/// Rejects a buy when we already hold an active position in the mint, so a
+/// leader adding to a position does not make us buy twice.
+///
+/// Backed by the shared positions registry rather than a separate
+/// gate-local one, so gate decisions, TP/SL and `/positions` all agree on
+/// what is open. Positions are looked up under our wallet,
+/// `ctx.owner_wallet` (falling back to `ctx.target_wallet`); with
+/// `dup_position_block` off the gate always passes.
+pub struct DuplicatePositionGate {
+    pub dup_position_block: bool,
+    registry: &'static PositionsRegistry,
+}
+
+impl DuplicatePositionGate {
+    pub fn new(dup_position_block: bool, registry: &'static PositionsRegistry) -> Self {
+        Self { dup_position_block, registry }
+    }
+
+    /// Build from DUP_POSITION_BLOCK (default true) against the global
+    /// positions registry.
+    pub fn from_env() -> Self {
+        let mut dup_position_block = true;
+        if let Ok(v) = bot_config::var("DUP_POSITION_BLOCK") {
+            if let Ok(parsed) = v.parse::<bool>() {
+                dup_position_block = parsed;
+            }
+        }
+        Self::new(dup_position_block, &GLOBAL_POSITIONS_REGISTRY)
+    }
+}
+
+impl Gate for DuplicatePositionGate {
+    fn name(&self) -> &'static str { "DuplicatePositionGate" }
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        let owner_wallet = ctx.owner_wallet.as_deref().unwrap_or(&ctx.target_wallet);
+        if self.dup_position_block && self.registry.has_open_position(owner_wallet, &ctx.mint) {
+            return GateDecision::Rejected{code: RejectReason::DuplicatePosition, reason: "duplicate_position".into()};
+        }
+        GateDecision::Passed
+    }
+}
+
+/// Env var that switches each gate off, keyed by [`Gate::name`]. Gates are
+/// on unless their var is `0`, `false` or `off`.
+pub const GATE_ENABLED_VARS: [(&str, &str); 15] = [
+    ("liquidity", "LIQUIDITY_GATE_ENABLED"),
+    ("McapGate", "MCAP_GATE_ENABLED"),
+    ("VolatilityGate", "VOLATILITY_GATE_ENABLED"),
+    ("PumpFunMigrationGate", "PUMPFUN_MIGRATION_GATE_ENABLED"),
+    ("BondingCurveProgressGate", "CURVE_PROGRESS_GATE_ENABLED"),
+    ("TransferHookGate", "TRANSFER_HOOK_GATE_ENABLED"),
+    ("BlacklistGate", "BLACKLIST_GATE_ENABLED"),
+    ("CooldownGate", "COOLDOWN_GATE_ENABLED"),
+    ("MintConcurrencyGate", "MINT_CONCURRENCY_GATE_ENABLED"),
+    ("DuplicatePositionGate", "DUPLICATE_POSITION_GATE_ENABLED"),
+    ("MinAgeGate", "MIN_AGE_GATE_ENABLED"),
+    ("HolderConcentrationGate", "HOLDER_CONCENTRATION_GATE_ENABLED"),
+    ("MintAuthorityGate", "MINT_AUTHORITY_GATE_ENABLED"),
+    ("DailyLossGate", "DAILY_LOSS_GATE_ENABLED"),
+    ("BuyRateGate", "BUY_RATE_GATE_ENABLED"),
+];
+
+/// Whether the gate named `name` is enabled by its entry in
+/// [`GATE_ENABLED_VARS`]. Gates without an entry are always enabled.
+pub fn gate_enabled(name: &str) -> bool {
+    let Some((_, var)) = GATE_ENABLED_VARS.iter().find(|(gate, _)| *gate == name) else {
+        return true;
+    };
+    match bot_config::var(var) {
+        Ok(v) => !matches!(v.trim().to_ascii_lowercase().as_str(), "0" | "false" | "off"),
+        Err(_) => true,
+    }
+}
+
+/// `gates` without the ones switched off by [`gate_enabled`], so a disabled
+/// gate never contributes a rejection.
+pub fn enabled_gates(mut gates: Vec<Box<dyn Gate>>) -> Vec<Box<dyn Gate>> {
+    gates.retain(|g| gate_enabled(g.name()));
+    gates
+}
+
+/// Run `gates` in the order given and stop at the first rejection. This is
+/// the hot path; use [`run_gates_collect`] when every failing reason matters.
 pub fn run_gates(ctx: &TradeContext, gates: &[Box<dyn Gate>]) -> (bool, Vec<(String, String)>) {
     let mut reasons = Vec::new();
     for g in gates {
         match g.check(ctx) {
             GateDecision::Passed => {}
-            GateDecision::Rejected{reason} => {
+            GateDecision::Rejected{code, reason} => {
+                GLOBAL_GATE_STATS.record(Some(g.name()));
+                crate::universal::prometheus::COUNTERS.inc_gate_rejection(g.name());
+                crate::universal::prometheus::COUNTERS.inc_reject_reason(code);
+                crate::universal::events::emit_gate_rejection(ctx, g.name(), code, &reason);
                 reasons.push((g.name().into(), reason));
                 return (false, reasons);
             }
         }
     }
+    GLOBAL_GATE_STATS.record(None);
     (true, reasons)
 }
+
+/// Run every gate in `gates`, in the order given, and return all rejection
+/// reasons instead of stopping at the first. Each rejection is emitted as an
+/// event; the run counts once in the gate stats, attributed to the first
+/// rejecting gate, so stats agree with [`run_gates`].
+pub fn run_gates_collect(ctx: &TradeContext, gates: &[Box<dyn Gate>]) -> (bool, Vec<(String, String)>) {
+    let mut reasons: Vec<(String, String)> = Vec::new();
+    for g in gates {
+        if let GateDecision::Rejected{code, reason} = g.check(ctx) {
+            crate::universal::prometheus::COUNTERS.inc_gate_rejection(g.name());
+            crate::universal::prometheus::COUNTERS.inc_reject_reason(code);
+            crate::universal::events::emit_gate_rejection(ctx, g.name(), code, &reason);
+            reasons.push((g.name().into(), reason));
+        }
+    }
+    GLOBAL_GATE_STATS.record(reasons.first().map(|(name, _)| name.as_str()));
+    (reasons.is_empty(), reasons)
+}
+
+/// Batches at least this large are split across threads by
+/// [`run_gates_batch`]; smaller ones are not worth the spawn.
+pub const BATCH_PARALLEL_MIN: usize = 64;
+
+/// Run [`run_gates`] over every context in `contexts`, e.g. several buys by
+/// one leader in the same block. Results are in the order of `contexts`.
+///
+/// The gates are shared by the whole batch, so anything they hold (the
+/// registry, holder cache, wallet config) is not re-resolved per context.
+/// Batches of [`BATCH_PARALLEL_MIN`] or more are split into one chunk per
+/// available core and evaluated on scoped threads; stateful gates such as
+/// [`BuyRateGate`] then see the contexts in no particular order.
+pub fn run_gates_batch(contexts: &[TradeContext], gates: &[Box<dyn Gate>]) -> Vec<(bool, Vec<(String, String)>)> {
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    if contexts.len() < BATCH_PARALLEL_MIN || threads < 2 {
+        return contexts.iter().map(|ctx| run_gates(ctx, gates)).collect();
+    }
+    let chunk_len = contexts.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = contexts
+            .chunks(chunk_len)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(|ctx| run_gates(ctx, gates)).collect::<Vec<_>>()))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+            .collect()
+    })
+}
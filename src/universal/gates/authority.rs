@@ -0,0 +1,142 @@
+//! Mint / freeze-authority safety gate.
+//!
+//! A mint whose mint authority is still set can be inflated at will, and an
+//! active freeze authority can freeze holders' token accounts so they cannot
+//! sell. [`MintAuthorityCache::fetch`] reads the mint account over RPC once
+//! per mint (authorities rarely change) and [`MintAuthorityGate`] checks the
+//! cached result.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+use spl_token_2022::extension::StateWithExtensions;
+use spl_token_2022::state::Mint;
+
+use super::{Gate, GateDecision, RejectReason, TradeContext};
+use crate::common::bot_config;
+
+/// Authorities set on a mint; `None` means renounced.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MintAuthorities {
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+}
+
+/// Mint and freeze authorities of a raw SPL Token or Token-2022 mint
+/// account. `None` if `data` does not unpack as a mint.
+pub fn mint_authorities(data: &[u8]) -> Option<MintAuthorities> {
+    let state = StateWithExtensions::<Mint>::unpack(data).ok()?;
+    let mint_authority: Option<_> = state.base.mint_authority.into();
+    let freeze_authority: Option<_> = state.base.freeze_authority.into();
+    Some(MintAuthorities {
+        mint_authority: mint_authority.map(|p| p.to_string()),
+        freeze_authority: freeze_authority.map(|p| p.to_string()),
+    })
+}
+
+/// Per-mint cache of [`MintAuthorities`].
+#[derive(Debug, Default)]
+pub struct MintAuthorityCache {
+    inner: RwLock<HashMap<String, MintAuthorities>>,
+}
+
+impl MintAuthorityCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, mint: &str) -> Option<MintAuthorities> {
+        match self.inner.read() {
+            Ok(guard) => guard.get(mint).cloned(),
+            Err(poisoned) => poisoned.into_inner().get(mint).cloned(),
+        }
+    }
+
+    pub fn insert(&self, mint: &str, authorities: MintAuthorities) {
+        match self.inner.write() {
+            Ok(mut guard) => guard.insert(mint.to_string(), authorities),
+            Err(poisoned) => poisoned.into_inner().insert(mint.to_string(), authorities),
+        };
+    }
+
+    /// Cached authorities for `mint`, fetching and caching the mint account
+    /// from `rpc` on a miss.
+    pub async fn fetch(&self, rpc: &RpcClient, mint: &str) -> Result<MintAuthorities> {
+        if let Some(cached) = self.get(mint) {
+            return Ok(cached);
+        }
+        let pubkey = Pubkey::from_str(mint)
+            .map_err(|e| anyhow!("authority: invalid mint {}: {}", mint, e))?;
+        let account = rpc
+            .get_account(&pubkey)
+            .await
+            .map_err(|e| anyhow!("authority: get_account for {} failed: {}", mint, e))?;
+        let authorities = mint_authorities(&account.data)
+            .ok_or_else(|| anyhow!("authority: {} is not a mint account", mint))?;
+        self.insert(mint, authorities.clone());
+        Ok(authorities)
+    }
+}
+
+/// Process-wide mint authority cache read by [`MintAuthorityGate::from_env`].
+pub static GLOBAL_MINT_AUTHORITIES: Lazy<MintAuthorityCache> = Lazy::new(MintAuthorityCache::new);
+
+/// With `require_renounced` set, rejects mints whose mint or freeze
+/// authority is still active. Authorities must already be in the cache (see
+/// [`MintAuthorityCache::fetch`]); an uncached mint is rejected as
+/// `no_mint_data`.
+pub struct MintAuthorityGate {
+    pub require_renounced: bool,
+    cache: &'static MintAuthorityCache,
+}
+
+impl MintAuthorityGate {
+    pub fn new(require_renounced: bool, cache: &'static MintAuthorityCache) -> Self {
+        Self {
+            require_renounced,
+            cache,
+        }
+    }
+
+    /// Build from REQUIRE_RENOUNCED (`1` or `true`; default off) against the
+    /// global authority cache.
+    pub fn from_env() -> Self {
+        let require_renounced = match bot_config::var("REQUIRE_RENOUNCED") {
+            Ok(v) => matches!(v.trim(), "1" | "true"),
+            Err(_) => false,
+        };
+        Self::new(require_renounced, &GLOBAL_MINT_AUTHORITIES)
+    }
+}
+
+impl Gate for MintAuthorityGate {
+    fn name(&self) -> &'static str {
+        "MintAuthorityGate"
+    }
+
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        if !self.require_renounced {
+            return GateDecision::Passed;
+        }
+        match self.cache.get(&ctx.mint) {
+            None => GateDecision::Rejected {
+                code: RejectReason::NoMintData,
+                reason: "no_mint_data".to_string(),
+            },
+            Some(a) if a.mint_authority.is_some() => GateDecision::Rejected {
+                code: RejectReason::MintAuthorityActive,
+                reason: "mint_authority_active".to_string(),
+            },
+            Some(a) if a.freeze_authority.is_some() => GateDecision::Rejected {
+                code: RejectReason::FreezeAuthorityActive,
+                reason: "freeze_authority_active".to_string(),
+            },
+            Some(_) => GateDecision::Passed,
+        }
+    }
+}
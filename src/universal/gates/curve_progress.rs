@@ -0,0 +1,159 @@
+//! PumpFun bonding-curve progress gate.
+//!
+//! Progress is how much of the curve's sellable supply has been bought, read
+//! from the mint's PumpFun bonding-curve account: 0% at launch, 100% once the
+//! curve completes and the token migrates. [`refresh_curve_progress_pct`]
+//! fetches it and caches it per mint; [`bonding_curve_progress_pct`] reads
+//! the cache when a trade context is built.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anyhow::{anyhow, Result};
+use borsh::BorshDeserialize;
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+
+use super::{Gate, GateDecision, RejectReason, TradeContext};
+use crate::common::bot_config;
+use crate::dex::pump_fun::{get_pda, BondingCurveAccount, PUMP_FUN_PROGRAM};
+
+/// Real token reserves a fresh PumpFun curve starts with (raw units, 6
+/// decimals): the part of the supply sold along the curve.
+pub const INITIAL_REAL_TOKEN_RESERVES: u64 = 793_100_000_000_000;
+
+/// Curve progress in percent for a curve holding `real_token_reserves`,
+/// clamped to `[0, 100]`. A `complete` curve is 100%.
+pub fn curve_progress_pct(real_token_reserves: u64, complete: bool) -> f64 {
+    if complete {
+        return 100.0;
+    }
+    let left = real_token_reserves as f64 / INITIAL_REAL_TOKEN_RESERVES as f64;
+    ((1.0 - left) * 100.0).clamp(0.0, 100.0)
+}
+
+/// Fetch the bonding-curve progress of `mint` from its PumpFun curve account.
+pub async fn fetch_curve_progress_pct(rpc: &RpcClient, mint: &str) -> Result<f64> {
+    let mint_key =
+        Pubkey::from_str(mint).map_err(|e| anyhow!("curve: invalid mint {}: {}", mint, e))?;
+    let program = Pubkey::from_str(PUMP_FUN_PROGRAM)?;
+    let curve = get_pda(&mint_key, &program)?;
+    let data = rpc
+        .get_account_data(&curve)
+        .await
+        .map_err(|e| anyhow!("curve: account for {} failed: {}", mint, e))?;
+    // Newer curve accounts carry trailing fields, so don't require an exact fit.
+    let account = BondingCurveAccount::deserialize(&mut data.as_slice())
+        .map_err(|e| anyhow!("curve: invalid account for {}: {}", mint, e))?;
+    Ok(curve_progress_pct(
+        account.real_token_reserves,
+        account.complete,
+    ))
+}
+
+static CURVE_PROGRESS_PCT: Lazy<RwLock<HashMap<String, f64>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Cache `pct` as the bonding-curve progress of `mint`.
+pub fn record_curve_progress_pct(mint: &str, pct: f64) {
+    match CURVE_PROGRESS_PCT.write() {
+        Ok(mut guard) => guard.insert(mint.to_string(), pct),
+        Err(poisoned) => poisoned.into_inner().insert(mint.to_string(), pct),
+    };
+}
+
+/// Last cached bonding-curve progress of `mint`, if any.
+pub fn bonding_curve_progress_pct(mint: &str) -> Option<f64> {
+    match CURVE_PROGRESS_PCT.read() {
+        Ok(guard) => guard.get(mint).copied(),
+        Err(poisoned) => poisoned.into_inner().get(mint).copied(),
+    }
+}
+
+/// Fetch the bonding-curve progress of `mint` and cache it.
+pub async fn refresh_curve_progress_pct(rpc: &RpcClient, mint: &str) -> Result<f64> {
+    let pct = fetch_curve_progress_pct(rpc, mint).await?;
+    record_curve_progress_pct(mint, pct);
+    Ok(pct)
+}
+
+/// Accepts PumpFun tokens whose bonding-curve progress lies within
+/// `[min_progress, max_progress]` percent, e.g. to buy before migration.
+/// Non-PumpFun tokens pass; a migrated token counts as 100%. Missing
+/// progress is rejected as `no_curve_progress`. The full band `[0, 100]`
+/// (the default) disables the gate.
+///
+/// Independent of [`PumpFunMigrationGate`](super::PumpFunMigrationGate),
+/// which stays the way to only buy migrated tokens.
+#[derive(Clone, Debug)]
+pub struct BondingCurveProgressGate {
+    pub min_progress: f64,
+    pub max_progress: f64,
+}
+
+impl Default for BondingCurveProgressGate {
+    fn default() -> Self {
+        Self::new(0.0, 100.0)
+    }
+}
+
+impl BondingCurveProgressGate {
+    pub fn new(min_progress: f64, max_progress: f64) -> Self {
+        Self {
+            min_progress,
+            max_progress,
+        }
+    }
+
+    /// Build from CURVE_PROGRESS_MIN_PCT (default 0) and
+    /// CURVE_PROGRESS_MAX_PCT (default 100).
+    pub fn from_env() -> Self {
+        let mut gate = Self::default();
+        if let Ok(v) = bot_config::var("CURVE_PROGRESS_MIN_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                gate.min_progress = parsed;
+            }
+        }
+        if let Ok(v) = bot_config::var("CURVE_PROGRESS_MAX_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                gate.max_progress = parsed;
+            }
+        }
+        gate
+    }
+
+    fn is_disabled(&self) -> bool {
+        self.min_progress <= 0.0 && self.max_progress >= 100.0
+    }
+}
+
+impl Gate for BondingCurveProgressGate {
+    fn name(&self) -> &'static str {
+        "BondingCurveProgressGate"
+    }
+
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        if !ctx.is_pumpfun || self.is_disabled() {
+            return GateDecision::Passed;
+        }
+        let progress = match (ctx.bonding_curve_progress_pct, ctx.pumpfun_migrated) {
+            (_, Some(true)) => 100.0,
+            (Some(pct), _) => pct,
+            (None, _) => {
+                return GateDecision::Rejected {
+                    code: RejectReason::NoCurveProgress,
+                    reason: "no_curve_progress".to_string(),
+                }
+            }
+        };
+        if progress < self.min_progress || progress > self.max_progress {
+            return GateDecision::Rejected {
+                code: RejectReason::CurveProgress,
+                reason: format!("curve_progress {}", progress),
+            };
+        }
+        GateDecision::Passed
+    }
+}
@@ -0,0 +1,132 @@
+//! Holder-concentration gate.
+//!
+//! The share of supply held by the ten largest token accounts comes from the
+//! RPC's `getTokenLargestAccounts` / `getTokenSupply` (any provider, e.g. a
+//! Helius endpoint). [`refresh_top10_holder_pct`] fetches it and caches it
+//! per mint; [`top10_holder_pct`] reads the cache when a trade context is
+//! built.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use solana_sdk::pubkey::Pubkey;
+
+use super::{Gate, GateDecision, RejectReason, TradeContext};
+use crate::common::bot_config;
+
+/// Percentage of `supply` held by the ten largest of `balances` (raw token
+/// units). `None` when the supply is zero.
+///
+/// Balances are per token account, so a pool vault or a holder split across
+/// several accounts is counted as the account sees it.
+pub fn top10_pct(balances: &[u64], supply: u64) -> Option<f64> {
+    if supply == 0 {
+        return None;
+    }
+    let mut sorted = balances.to_vec();
+    sorted.sort_unstable();
+    let top: u128 = sorted.iter().rev().take(10).map(|b| *b as u128).sum();
+    Some(top as f64 / supply as f64 * 100.0)
+}
+
+/// Fetch the top-10 holder percentage for `mint` from `rpc`.
+pub async fn fetch_top10_holder_pct(rpc: &RpcClient, mint: &str) -> Result<f64> {
+    let pubkey =
+        Pubkey::from_str(mint).map_err(|e| anyhow!("holders: invalid mint {}: {}", mint, e))?;
+    let largest = rpc
+        .get_token_largest_accounts(&pubkey)
+        .await
+        .map_err(|e| anyhow!("holders: largest accounts for {} failed: {}", mint, e))?;
+    let supply = rpc
+        .get_token_supply(&pubkey)
+        .await
+        .map_err(|e| anyhow!("holders: supply for {} failed: {}", mint, e))?;
+    let balances: Vec<u64> = largest
+        .iter()
+        .filter_map(|account| account.amount.amount.parse::<u64>().ok())
+        .collect();
+    let supply = supply
+        .amount
+        .parse::<u64>()
+        .map_err(|e| anyhow!("holders: invalid supply for {}: {}", mint, e))?;
+    top10_pct(&balances, supply).ok_or_else(|| anyhow!("holders: {} has zero supply", mint))
+}
+
+static TOP10_HOLDER_PCT: Lazy<RwLock<HashMap<String, f64>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Cache `pct` as the top-10 holder percentage for `mint`.
+pub fn record_top10_holder_pct(mint: &str, pct: f64) {
+    match TOP10_HOLDER_PCT.write() {
+        Ok(mut guard) => guard.insert(mint.to_string(), pct),
+        Err(poisoned) => poisoned.into_inner().insert(mint.to_string(), pct),
+    };
+}
+
+/// Last cached top-10 holder percentage for `mint`, if any.
+pub fn top10_holder_pct(mint: &str) -> Option<f64> {
+    match TOP10_HOLDER_PCT.read() {
+        Ok(guard) => guard.get(mint).copied(),
+        Err(poisoned) => poisoned.into_inner().get(mint).copied(),
+    }
+}
+
+/// Fetch the top-10 holder percentage for `mint` and cache it.
+pub async fn refresh_top10_holder_pct(rpc: &RpcClient, mint: &str) -> Result<f64> {
+    let pct = fetch_top10_holder_pct(rpc, mint).await?;
+    record_top10_holder_pct(mint, pct);
+    Ok(pct)
+}
+
+/// Rejects tokens whose ten largest holders control more than
+/// `max_top10_pct` percent of supply. Missing holder data is rejected as
+/// `no_holder_data`, like `no_mcap` in the liquidity guard. A threshold of 0
+/// disables the gate.
+#[derive(Clone, Debug, Default)]
+pub struct HolderConcentrationGate {
+    pub max_top10_pct: f64,
+}
+
+impl HolderConcentrationGate {
+    pub fn new(max_top10_pct: f64) -> Self {
+        Self { max_top10_pct }
+    }
+
+    /// Build from MAX_TOP10_HOLDER_PCT (default 0 = disabled).
+    pub fn from_env() -> Self {
+        let mut max_top10_pct = 0.0;
+        if let Ok(v) = bot_config::var("MAX_TOP10_HOLDER_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                max_top10_pct = parsed;
+            }
+        }
+        Self { max_top10_pct }
+    }
+}
+
+impl Gate for HolderConcentrationGate {
+    fn name(&self) -> &'static str {
+        "HolderConcentrationGate"
+    }
+
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        if self.max_top10_pct <= 0.0 {
+            return GateDecision::Passed;
+        }
+        match ctx.top10_holder_pct {
+            Some(pct) if pct > self.max_top10_pct => GateDecision::Rejected {
+                code: RejectReason::HolderConcentration,
+                reason: format!("top10 {}% > {}%", pct, self.max_top10_pct),
+            },
+            Some(_) => GateDecision::Passed,
+            None => GateDecision::Rejected {
+                code: RejectReason::NoHolderData,
+                reason: "no_holder_data".to_string(),
+            },
+        }
+    }
+}
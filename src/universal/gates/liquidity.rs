@@ -1,19 +1,59 @@
-use super::{TradeContext, Gate, GateDecision};
+use super::{score_at_least, Gate, GateDecision, RejectReason, ScoredGate, TradeContext};
+use crate::common::bot_config;
+use crate::universal::impact::estimate_price_impact_bps;
+use crate::universal::metrics::TokenMetrics;
 
-/// Configuration for the liquidity / mcap gate.
+/// How the 5m / 15m volume windows are compared against thresholds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolumeMode {
+    /// Each window must clear its own floor (`liq_5m_min_usd`, `liq_15m_min_usd`).
+    Independent,
+    /// `w5m * vol5m + w15m * vol15m` must clear a single `min_score`.
+    Weighted { w5m: f64, w15m: f64, min_score: f64 },
+}
+
+/// Weighted liquidity score combining both volume windows.
+pub fn weighted_liquidity_score(vol_5m_usd: f64, vol_15m_usd: f64, w5m: f64, w15m: f64) -> f64 {
+    w5m * vol_5m_usd + w15m * vol_15m_usd
+}
+
+/// Thresholds for the liquidity / mcap guard.
 ///
-/// This mirrors the BirdEye liquidity guard thresholds and also keeps
-/// the original depth_multiple guard used in the older inline
-/// LiquidityGate implementation.
+/// This is the single source of truth for the BirdEye liquidity guard
+/// thresholds and also keeps the original depth_multiple guard used in the
+/// older inline LiquidityGate implementation. Both the gate (`TradeContext`)
+/// and the metrics-side guard (`TokenMetrics`) evaluate through [`evaluate`].
 #[derive(Debug, Clone)]
-pub struct LiquidityGateConfig {
+pub struct GuardThresholds {
     pub liq_5m_min_usd: f64,
     pub liq_15m_min_usd: f64,
     pub min_mcap_usd: f64,
     pub depth_mult_min: f64,
+    /// Maximum Amihud illiquidity (None = not enforced).
+    pub amihud_max: Option<f64>,
+    /// Minimum 5m range efficiency (None = not enforced).
+    pub range_eff_min: Option<f64>,
+    /// Maximum age of the metrics snapshot in seconds (None = not enforced).
+    pub max_age_secs: Option<i64>,
+    /// Maximum order size as a fraction of pool liquidity.
+    pub max_size_over_liq: f64,
+    /// Volume-window comparison mode (independent thresholds by default).
+    pub volume_mode: VolumeMode,
+    /// Minimum 5m volume / pool liquidity ratio (None = not enforced).
+    /// Catches fake or wash pools showing high TVL but no real trading.
+    pub min_vol_liq_ratio: Option<f64>,
+    /// Maximum estimated price impact of the order in bps (None = not
+    /// enforced). See [`estimate_price_impact_bps`].
+    pub max_impact_bps: Option<f64>,
 }
 
-impl Default for LiquidityGateConfig {
+/// Configuration for the liquidity / mcap gate.
+///
+/// Kept as an alias of [`GuardThresholds`] so existing callers keep
+/// compiling; `LiquidityGateConfig::from(thresholds)` is the identity.
+pub type LiquidityGateConfig = GuardThresholds;
+
+impl Default for GuardThresholds {
     fn default() -> Self {
         Self {
             liq_5m_min_usd: 15_000.0,
@@ -22,46 +62,402 @@ impl Default for LiquidityGateConfig {
             // For runtime-from-env usage we default depth_mult_min to 0.0 so it
             // never blocks trades unless explicitly configured (e.g. in sims).
             depth_mult_min: 0.0,
+            amihud_max: None,
+            range_eff_min: None,
+            max_age_secs: None,
+            // Generous default; only bites on orders >10% of pool liquidity.
+            max_size_over_liq: 0.1,
+            volume_mode: VolumeMode::Independent,
+            min_vol_liq_ratio: None,
+            max_impact_bps: None,
         }
     }
 }
 
-impl LiquidityGateConfig {
+impl GuardThresholds {
     /// Build config from env vars, matching the BirdEye patch:
     ///
     /// - LIQ_5M_MIN_USD  (default 15000)
     /// - LIQ_15M_MIN_USD (default 45000)
     /// - MIN_MCAP_USD    (default 5000000)
+    /// - AMIHUD_MAX            (default unset)
+    /// - RANGE_EFF_MIN         (default unset)
+    /// - METRICS_MAX_AGE_SECS  (default unset)
+    /// - MAX_SIZE_OVER_LIQ     (default 0.1)
+    /// - LIQ_SCORE_MIN_USD     (default unset; enables weighted volume mode)
+    /// - LIQ_SCORE_W5M         (default 0.7)
+    /// - LIQ_SCORE_W15M        (default 0.3)
+    /// - MIN_VOL_LIQ_RATIO     (default unset)
+    /// - MAX_IMPACT_BPS        (default unset)
     ///
     /// depth_mult_min is intentionally *not* read from env here; it is
     /// expected to be set by SimulationConfig when used in the simulator.
     pub fn from_env() -> Self {
-        use std::env;
-
         let mut cfg = Self::default();
 
-        if let Ok(v) = env::var("LIQ_5M_MIN_USD") {
+        if let Ok(v) = bot_config::var("LIQ_5M_MIN_USD") {
             if let Ok(parsed) = v.parse::<f64>() {
                 cfg.liq_5m_min_usd = parsed;
             }
         }
 
-        if let Ok(v) = env::var("LIQ_15M_MIN_USD") {
+        if let Ok(v) = bot_config::var("LIQ_15M_MIN_USD") {
             if let Ok(parsed) = v.parse::<f64>() {
                 cfg.liq_15m_min_usd = parsed;
             }
         }
 
-        if let Ok(v) = env::var("MIN_MCAP_USD") {
+        if let Ok(v) = bot_config::var("MIN_MCAP_USD") {
             if let Ok(parsed) = v.parse::<f64>() {
                 cfg.min_mcap_usd = parsed;
             }
         }
 
+        if let Ok(v) = bot_config::var("AMIHUD_MAX") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.amihud_max = Some(parsed);
+            }
+        }
+
+        if let Ok(v) = bot_config::var("RANGE_EFF_MIN") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.range_eff_min = Some(parsed);
+            }
+        }
+
+        if let Ok(v) = bot_config::var("METRICS_MAX_AGE_SECS") {
+            if let Ok(parsed) = v.parse::<i64>() {
+                cfg.max_age_secs = Some(parsed);
+            }
+        }
+
+        if let Ok(v) = bot_config::var("MAX_SIZE_OVER_LIQ") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.max_size_over_liq = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("MIN_VOL_LIQ_RATIO") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.min_vol_liq_ratio = Some(parsed);
+            }
+        }
+
+        if let Ok(v) = bot_config::var("MAX_IMPACT_BPS") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.max_impact_bps = Some(parsed);
+            }
+        }
+
+        if let Ok(v) = bot_config::var("LIQ_SCORE_MIN_USD") {
+            if let Ok(min_score) = v.parse::<f64>() {
+                let weight = |key: &str, default: f64| {
+                    bot_config::var(key)
+                        .ok()
+                        .and_then(|v| v.parse::<f64>().ok())
+                        .unwrap_or(default)
+                };
+                cfg.volume_mode = VolumeMode::Weighted {
+                    w5m: weight("LIQ_SCORE_W5M", 0.7),
+                    w15m: weight("LIQ_SCORE_W15M", 0.3),
+                    min_score,
+                };
+            }
+        }
+
         cfg
     }
 }
 
+/// The subset of market data the guard needs, independent of where it came from.
+#[derive(Debug, Clone)]
+pub struct GuardInput {
+    pub vol_5m_usd: f64,
+    pub vol_15m_usd: f64,
+    pub mcap_usd: Option<f64>,
+    pub depth_multiple: f64,
+    pub amihud_5m: Option<f64>,
+    pub range_eff_5m: Option<f64>,
+    pub age_secs: Option<i64>,
+    pub requested_size_usd: Option<f64>,
+    pub liq_usd: Option<f64>,
+}
+
+impl From<&TradeContext> for GuardInput {
+    fn from(ctx: &TradeContext) -> Self {
+        Self {
+            vol_5m_usd: ctx.window5m_usd,
+            vol_15m_usd: ctx.window15m_usd,
+            mcap_usd: ctx.est_mcap_usd,
+            depth_multiple: ctx.depth_multiple,
+            amihud_5m: ctx.amihud_5m,
+            range_eff_5m: ctx.range_eff_5m,
+            age_secs: ctx.metrics_age_secs,
+            requested_size_usd: ctx.requested_size_usd,
+            liq_usd: ctx.liq_usd,
+        }
+    }
+}
+
+impl GuardInput {
+    /// Build guard input from a metrics snapshot as of `now` (unix seconds).
+    ///
+    /// Metrics alone carry no order size, so depth and size-over-liquidity
+    /// are left unconstrained; callers that know the size should overwrite
+    /// `depth_multiple` and `requested_size_usd`.
+    pub fn from_metrics(metrics: &TokenMetrics, now: i64) -> Self {
+        Self {
+            vol_5m_usd: metrics.vol_5m_usd,
+            vol_15m_usd: metrics.vol_15m_usd,
+            mcap_usd: metrics.mcap_usd,
+            depth_multiple: f64::INFINITY,
+            amihud_5m: metrics.amihud_5m,
+            range_eff_5m: metrics.range_eff_5m,
+            age_secs: Some((now - metrics.last_update).max(0)),
+            requested_size_usd: None,
+            liq_usd: metrics.liq_usd,
+        }
+    }
+
+    /// [`GuardInput::from_metrics`] for an order of `size_usd`, with the
+    /// depth multiple taken as liquidity over the order size.
+    pub fn from_metrics_for_size(metrics: &TokenMetrics, size_usd: f64, now: i64) -> Self {
+        let mut input = Self::from_metrics(metrics, now);
+        input.requested_size_usd = Some(size_usd);
+        input.depth_multiple = match metrics.liq_usd {
+            Some(liq) if liq > 0.0 && size_usd > 0.0 => liq / size_usd,
+            _ => 0.0,
+        };
+        input
+    }
+
+    /// Estimated price impact of the order in bps, when both the order size
+    /// and the pool liquidity are known.
+    pub fn est_impact_bps(&self) -> Option<f64> {
+        match (self.requested_size_usd, self.liq_usd) {
+            (Some(size), Some(liq)) => {
+                Some(estimate_price_impact_bps(size, liq, self.depth_multiple))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Guard verdict together with the estimated price impact it was judged on.
+#[derive(Debug, Clone)]
+pub struct GuardDecision {
+    pub decision: GateDecision,
+    /// None when the order size or pool liquidity is unknown.
+    pub est_impact_bps: Option<f64>,
+}
+
+impl GuardDecision {
+    pub fn passed(&self) -> bool {
+        matches!(self.decision, GateDecision::Passed)
+    }
+}
+
+/// Evaluate guard input against thresholds. This is the only place the
+/// rejection branches live; reason strings are stable for log comparison.
+pub fn evaluate(th: &GuardThresholds, input: &GuardInput) -> GateDecision {
+    evaluate_with_impact(th, input).decision
+}
+
+/// [`evaluate`], also reporting the order's estimated price impact.
+pub fn evaluate_with_impact(th: &GuardThresholds, input: &GuardInput) -> GuardDecision {
+    let est_impact_bps = input.est_impact_bps();
+    GuardDecision {
+        decision: check_thresholds(th, input, est_impact_bps),
+        est_impact_bps,
+    }
+}
+
+fn check_thresholds(
+    th: &GuardThresholds,
+    input: &GuardInput,
+    est_impact_bps: Option<f64>,
+) -> GateDecision {
+    // 0) Staleness guard: everything below is meaningless on old metrics
+    if let Some(max_age) = th.max_age_secs {
+        match input.age_secs {
+            Some(age) if age > max_age => {
+                return GateDecision::Rejected {
+                    code: RejectReason::Stale,
+                    reason: format!("stale_metrics {}s > {}s", age, max_age),
+                };
+            }
+            Some(_) => {}
+            None => {
+                return GateDecision::Rejected {
+                    code: RejectReason::NoMetricsAge,
+                    reason: "no_metrics_age".to_string(),
+                };
+            }
+        }
+    }
+
+    match th.volume_mode {
+        VolumeMode::Independent => {
+            // 1) 5-minute rolling volume guard
+            if input.vol_5m_usd < th.liq_5m_min_usd {
+                return GateDecision::Rejected {
+                    code: RejectReason::LowLiq5m,
+                    reason: format!(
+                        "low_liq_5m {} < {}",
+                        input.vol_5m_usd, th.liq_5m_min_usd
+                    ),
+                };
+            }
+
+            // 2) 15-minute rolling volume guard
+            if input.vol_15m_usd < th.liq_15m_min_usd {
+                return GateDecision::Rejected {
+                    code: RejectReason::LowLiq15m,
+                    reason: format!(
+                        "low_liq_15m {} < {}",
+                        input.vol_15m_usd, th.liq_15m_min_usd
+                    ),
+                };
+            }
+        }
+        VolumeMode::Weighted { w5m, w15m, min_score } => {
+            // 1+2) Single weighted score across both windows
+            let score = weighted_liquidity_score(input.vol_5m_usd, input.vol_15m_usd, w5m, w15m);
+            if score < min_score {
+                return GateDecision::Rejected {
+                    code: RejectReason::LowLiqScore,
+                    reason: format!("low_liq_score {} < {}", score, min_score),
+                };
+            }
+        }
+    }
+
+    // 3) Market cap floor (or no_mcap)
+    match input.mcap_usd {
+        Some(mcap) => {
+            if mcap < th.min_mcap_usd {
+                return GateDecision::Rejected {
+                    code: RejectReason::LowMcap,
+                    reason: format!(
+                        "low_mcap {} < {}",
+                        mcap, th.min_mcap_usd
+                    ),
+                };
+            }
+        }
+        None => {
+            return GateDecision::Rejected {
+                code: RejectReason::NoMcap,
+                reason: "no_mcap".to_string(),
+            };
+        }
+    }
+
+    // 4) Depth multiple guard (preserved from old LiquidityGate)
+    if input.depth_multiple < th.depth_mult_min {
+        return GateDecision::Rejected {
+            code: RejectReason::DepthMultiple,
+            reason: format!(
+                "depth_multiple {} < {}",
+                input.depth_multiple, th.depth_mult_min
+            ),
+        };
+    }
+
+    // 5) Order size relative to pool liquidity. Only enforced when both
+    //    the size and the liquidity are known.
+    if let (Some(size), Some(liq)) = (input.requested_size_usd, input.liq_usd) {
+        let ratio = if liq > 0.0 { size / liq } else { f64::INFINITY };
+        if ratio > th.max_size_over_liq {
+            return GateDecision::Rejected {
+                code: RejectReason::SizeOverLiq,
+                reason: format!("size_over_liq {} > {}", ratio, th.max_size_over_liq),
+            };
+        }
+    }
+
+    // 6) Simulated price impact of the order. Only enforced when it can be
+    //    estimated (size and liquidity known).
+    if let (Some(max), Some(impact)) = (th.max_impact_bps, est_impact_bps) {
+        if impact > max {
+            return GateDecision::Rejected {
+                code: RejectReason::PriceImpact,
+                reason: format!("price_impact {:.1}bps > {}bps", impact, max),
+            };
+        }
+    }
+
+    // 7) Dead liquidity: realized volume implausibly low for the claimed
+    //    pool liquidity. Only enforced when liquidity is known.
+    if let (Some(min), Some(liq)) = (th.min_vol_liq_ratio, input.liq_usd) {
+        if liq > 0.0 {
+            let ratio = input.vol_5m_usd / liq;
+            if ratio < min {
+                return GateDecision::Rejected {
+                    code: RejectReason::DeadLiquidity,
+                    reason: format!("dead_liquidity ratio={} < {}", ratio, min),
+                };
+            }
+        }
+    }
+
+    // 8) Amihud illiquidity guard (price impact per USD traded)
+    if let Some(max) = th.amihud_max {
+        match input.amihud_5m {
+            Some(amihud) if amihud > max => {
+                return GateDecision::Rejected {
+                    code: RejectReason::Amihud,
+                    reason: format!("amihud {} > {}", amihud, max),
+                };
+            }
+            Some(_) => {}
+            None => {
+                return GateDecision::Rejected {
+                    code: RejectReason::NoAmihud,
+                    reason: "no_amihud".to_string(),
+                };
+            }
+        }
+    }
+
+    // 9) Range efficiency guard (choppy vs directional price action)
+    if let Some(min) = th.range_eff_min {
+        match input.range_eff_5m {
+            Some(eff) if eff < min => {
+                return GateDecision::Rejected {
+                    code: RejectReason::RangeEff,
+                    reason: format!("range_eff {} < {}", eff, min),
+                };
+            }
+            Some(_) => {}
+            None => {
+                return GateDecision::Rejected {
+                    code: RejectReason::NoRangeEff,
+                    reason: "no_range_eff".to_string(),
+                };
+            }
+        }
+    }
+
+    GateDecision::Passed
+}
+
+/// Metrics-side entry point: evaluate a `TokenMetrics` snapshot as of `now`.
+pub fn evaluate_guard(metrics: &TokenMetrics, th: &GuardThresholds, now: i64) -> GateDecision {
+    evaluate(th, &GuardInput::from_metrics(metrics, now))
+}
+
+/// [`evaluate_guard`] for an order of `size_usd`, so the size-over-liquidity
+/// and price-impact checks apply and the estimated impact is reported.
+pub fn evaluate_guard_for_size(
+    metrics: &TokenMetrics,
+    th: &GuardThresholds,
+    size_usd: f64,
+    now: i64,
+) -> GuardDecision {
+    evaluate_with_impact(th, &GuardInput::from_metrics_for_size(metrics, size_usd, now))
+}
+
 /// Liquidity gate that evaluates a TradeContext against BirdEye-style thresholds.
 ///
 /// NOTE:
@@ -90,59 +486,33 @@ impl Gate for LiquidityGate {
     }
 
     fn check(&self, ctx: &TradeContext) -> GateDecision {
-        // 1) 5-minute rolling volume guard
-        if ctx.window5m_usd < self.cfg.liq_5m_min_usd {
-            return GateDecision::Rejected {
-                reason: format!(
-                    "low_liq_5m {} < {}",
-                    ctx.window5m_usd, self.cfg.liq_5m_min_usd
-                ),
-            };
-        }
-
-        // 2) 15-minute rolling volume guard
-        if ctx.window15m_usd < self.cfg.liq_15m_min_usd {
-            return GateDecision::Rejected {
-                reason: format!(
-                    "low_liq_15m {} < {}",
-                    ctx.window15m_usd, self.cfg.liq_15m_min_usd
-                ),
-            };
-        }
-
-        // 3) Market cap floor (or no_mcap)
-        match ctx.est_mcap_usd {
-            Some(mcap) => {
-                if mcap < self.cfg.min_mcap_usd {
-                    return GateDecision::Rejected {
-                        reason: format!(
-                            "low_mcap {} < {}",
-                            mcap, self.cfg.min_mcap_usd
-                        ),
-                    };
-                }
-            }
-            None => {
-                return GateDecision::Rejected {
-                    reason: "no_mcap".to_string(),
-                };
-            }
-        }
+        evaluate(&self.cfg, &GuardInput::from(ctx))
+    }
+}
 
-        // 4) Depth multiple guard (preserved from old LiquidityGate)
-        if ctx.depth_multiple < self.cfg.depth_mult_min {
-            return GateDecision::Rejected {
-                reason: format!(
-                    "depth_multiple {} < {}",
-                    ctx.depth_multiple, self.cfg.depth_mult_min
-                ),
-            };
-        }
+impl ScoredGate for LiquidityGate {
+    fn name(&self) -> &'static str {
+        "liquidity"
+    }
 
-        // Amihud / range efficiency / staleness checks are intentionally omitted
-        // for now because TradeContext does not yet carry those fields. Once the
-        // Birdeye metrics are threaded through, we can extend this gate without
-        // changing its public interface.
-        GateDecision::Passed
+    /// Weakest of the volume, market-cap and depth scores. The staleness,
+    /// microstructure, size-over-liquidity and price-impact checks stay
+    /// strict and are left to [`Gate::check`].
+    fn score(&self, ctx: &TradeContext) -> f64 {
+        let th = &self.cfg;
+        let volume = match th.volume_mode {
+            VolumeMode::Independent => score_at_least(ctx.window5m_usd, th.liq_5m_min_usd)
+                .min(score_at_least(ctx.window15m_usd, th.liq_15m_min_usd)),
+            VolumeMode::Weighted { w5m, w15m, min_score } => score_at_least(
+                weighted_liquidity_score(ctx.window5m_usd, ctx.window15m_usd, w5m, w15m),
+                min_score,
+            ),
+        };
+        let mcap = match ctx.est_mcap_usd {
+            Some(mcap) => score_at_least(mcap, th.min_mcap_usd),
+            None => 0.0,
+        };
+        let depth = score_at_least(ctx.depth_multiple, th.depth_mult_min);
+        volume.min(mcap).min(depth)
     }
 }
@@ -0,0 +1,160 @@
+//! Per-wallet buy rate limiter.
+//!
+//! A leader wallet on a buying spree would otherwise have the bot fire a buy
+//! for every one of its trades. [`BuyRateLimiter`] keeps a token bucket per
+//! `target_wallet` holding up to N buys and refilling N per 60s window, and
+//! [`BuyRateGate`] takes a token for every buy it lets through.
+//!
+//! Buckets run on a monotonic clock so wall-clock adjustments cannot refill
+//! or drain them.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use super::{Gate, GateDecision, RejectReason, TradeContext};
+use crate::common::bot_config;
+use crate::universal::wallet_config::get_wallet_config_map;
+
+/// Window over which a bucket refills completely.
+pub const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Source of monotonic time for the limiter.
+pub trait MonotonicClock {
+    fn now(&self) -> Instant;
+}
+
+/// [`Instant::now`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InstantClock;
+
+impl MonotonicClock for InstantClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Token buckets keyed by wallet.
+#[derive(Debug)]
+pub struct BuyRateLimiter<C: MonotonicClock = InstantClock> {
+    window: Duration,
+    /// Per wallet, the instant the bucket would be full again; each buy
+    /// pushes it one refill interval (window / capacity) further out.
+    buckets: Mutex<HashMap<String, Instant>>,
+    clock: C,
+}
+
+impl BuyRateLimiter {
+    pub fn new(window: Duration) -> Self {
+        Self::with_clock(window, InstantClock)
+    }
+}
+
+impl Default for BuyRateLimiter {
+    fn default() -> Self {
+        Self::new(RATE_WINDOW)
+    }
+}
+
+impl<C: MonotonicClock> BuyRateLimiter<C> {
+    pub fn with_clock(window: Duration, clock: C) -> Self {
+        Self {
+            window,
+            buckets: Mutex::new(HashMap::new()),
+            clock,
+        }
+    }
+
+    /// Take one buy from `wallet`'s bucket of `capacity` buys per window.
+    ///
+    /// Returns `Err(retry_in)` with the time until a buy is available again
+    /// when the bucket is empty. A `capacity` of 0 means unlimited.
+    pub fn try_acquire(&self, wallet: &str, capacity: u32) -> Result<(), Duration> {
+        if capacity == 0 {
+            return Ok(());
+        }
+        let now = self.clock.now();
+        let interval = self.window / capacity;
+        // Once the bucket is more than this far from full it has no buy left.
+        let allowance = self.window - interval;
+        let mut guard = match self.buckets.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let full_at = guard.get(wallet).copied().unwrap_or(now).max(now);
+        let until_full = full_at - now;
+        if until_full > allowance {
+            return Err(until_full - allowance);
+        }
+        guard.insert(wallet.to_string(), full_at + interval);
+        Ok(())
+    }
+
+    /// Drop every bucket.
+    pub fn reset(&self) {
+        match self.buckets.lock() {
+            Ok(mut guard) => guard.clear(),
+            Err(poisoned) => poisoned.into_inner().clear(),
+        }
+    }
+}
+
+/// Process-wide limiter used by [`BuyRateGate::from_env`].
+pub static BUY_RATE_LIMITER: Lazy<Arc<BuyRateLimiter>> =
+    Lazy::new(|| Arc::new(BuyRateLimiter::default()));
+
+/// Rejects a buy with `rate_limited {retry_in}s` once the copied wallet has
+/// used up its buys for the window: the wallet config's `max_buys_per_min`,
+/// else `max_buys_per_min` here (0 = unlimited).
+///
+/// A passing check takes a token, so the gate belongs after every other buy
+/// gate: `run_gates` stops at the first rejection and a buy rejected earlier
+/// then costs nothing.
+pub struct BuyRateGate<C: MonotonicClock = InstantClock> {
+    pub max_buys_per_min: u32,
+    limiter: Arc<BuyRateLimiter<C>>,
+}
+
+impl BuyRateGate {
+    /// Build from MAX_BUYS_PER_MIN (default 0 = unlimited) against the
+    /// global limiter.
+    pub fn from_env() -> Self {
+        let mut max_buys_per_min = 0;
+        if let Ok(v) = bot_config::var("MAX_BUYS_PER_MIN") {
+            if let Ok(parsed) = v.parse::<u32>() {
+                max_buys_per_min = parsed;
+            }
+        }
+        Self::new(max_buys_per_min, BUY_RATE_LIMITER.clone())
+    }
+}
+
+impl<C: MonotonicClock> BuyRateGate<C> {
+    pub fn new(max_buys_per_min: u32, limiter: Arc<BuyRateLimiter<C>>) -> Self {
+        Self {
+            max_buys_per_min,
+            limiter,
+        }
+    }
+}
+
+impl<C: MonotonicClock + Send + Sync> Gate for BuyRateGate<C> {
+    fn name(&self) -> &'static str {
+        "BuyRateGate"
+    }
+
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        let capacity = get_wallet_config_map()
+            .get_max_buys_per_min(&ctx.target_wallet)
+            .unwrap_or(self.max_buys_per_min);
+        match self.limiter.try_acquire(&ctx.target_wallet, capacity) {
+            Ok(()) => GateDecision::Passed,
+            Err(retry_in) => GateDecision::Rejected {
+                code: RejectReason::RateLimited,
+                reason: format!("rate_limited {}s", retry_in.as_secs_f64().ceil() as u64),
+            },
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Gate outcome counters and a periodic JSONL report of them.
+//!
+//! Every call to [`run_gates`](super::run_gates) is tallied in
+//! [`GLOBAL_GATE_STATS`]. A [`GateStatsReporter`] appends one JSON line per
+//! interval with the running totals and the delta since the previous line, so
+//! rejection reasons can be charted over a session without scraping logs.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// Default reporting interval when GATE_STATS_REPORT_MINS is unset.
+const DEFAULT_REPORT_MINS: u64 = 5;
+
+/// Point-in-time copy of the gate counters.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GateStatsSnapshot {
+    /// Number of gate runs.
+    pub evaluated: u64,
+    /// Runs where every gate passed.
+    pub passed: u64,
+    /// Rejections keyed by the name of the gate that rejected.
+    pub rejected: BTreeMap<String, u64>,
+}
+
+impl GateStatsSnapshot {
+    /// Counter increase from `prev` to `self`.
+    pub fn delta_since(&self, prev: &GateStatsSnapshot) -> GateStatsSnapshot {
+        let rejected = self
+            .rejected
+            .iter()
+            .map(|(gate, n)| {
+                let before = prev.rejected.get(gate).copied().unwrap_or(0);
+                (gate.clone(), n.saturating_sub(before))
+            })
+            .filter(|(_, n)| *n > 0)
+            .collect();
+        GateStatsSnapshot {
+            evaluated: self.evaluated.saturating_sub(prev.evaluated),
+            passed: self.passed.saturating_sub(prev.passed),
+            rejected,
+        }
+    }
+}
+
+/// Thread-safe gate outcome counters.
+#[derive(Debug, Default)]
+pub struct GateStats {
+    inner: Mutex<GateStatsSnapshot>,
+}
+
+impl GateStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one gate run. `rejected_by` is the rejecting gate's name, or
+    /// `None` if the run passed.
+    pub fn record(&self, rejected_by: Option<&str>) {
+        let mut guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        guard.evaluated += 1;
+        match rejected_by {
+            Some(gate) => *guard.rejected.entry(gate.to_string()).or_insert(0) += 1,
+            None => guard.passed += 1,
+        }
+    }
+
+    pub fn snapshot(&self) -> GateStatsSnapshot {
+        match self.inner.lock() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+}
+
+/// Process-wide gate counters fed by `run_gates`.
+pub static GLOBAL_GATE_STATS: Lazy<GateStats> = Lazy::new(GateStats::new);
+
+/// Source of the current unix time, abstracted so reports can be tested.
+pub trait Clock {
+    fn now_secs(&self) -> i64;
+}
+
+/// Wall-clock time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now_secs(&self) -> i64 {
+        chrono::Utc::now().timestamp()
+    }
+}
+
+/// One line of the gate stats report.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GateStatsReport {
+    /// Unix timestamp (seconds) the report was taken.
+    pub ts: i64,
+    /// Seconds covered by `delta`.
+    pub interval_secs: i64,
+    /// Totals since startup.
+    pub totals: GateStatsSnapshot,
+    /// Change since the previous report.
+    pub delta: GateStatsSnapshot,
+}
+
+/// Where and how often to write gate stats reports.
+#[derive(Clone, Debug)]
+pub struct GateStatsReportConfig {
+    pub path: PathBuf,
+    pub interval_secs: i64,
+}
+
+impl GateStatsReportConfig {
+    /// Build from GATE_STATS_REPORT_PATH and GATE_STATS_REPORT_MINS (default 5).
+    ///
+    /// Returns `None` (reporting disabled) when no path is configured.
+    pub fn from_env() -> Option<Self> {
+        let path = env::var("GATE_STATS_REPORT_PATH")
+            .ok()
+            .filter(|p| !p.trim().is_empty())?;
+        let mut mins = DEFAULT_REPORT_MINS;
+        if let Ok(v) = env::var("GATE_STATS_REPORT_MINS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                if parsed > 0 {
+                    mins = parsed;
+                }
+            }
+        }
+        Some(Self {
+            path: PathBuf::from(path.trim()),
+            interval_secs: (mins * 60) as i64,
+        })
+    }
+}
+
+/// Appends a [`GateStatsReport`] to a JSONL file once per interval.
+pub struct GateStatsReporter<C: Clock> {
+    config: GateStatsReportConfig,
+    clock: C,
+    last_ts: i64,
+    last: GateStatsSnapshot,
+}
+
+impl<C: Clock> GateStatsReporter<C> {
+    /// Start a reporter; the first report is due one interval from now.
+    pub fn new(config: GateStatsReportConfig, clock: C) -> Self {
+        let last_ts = clock.now_secs();
+        Self {
+            config,
+            clock,
+            last_ts,
+            last: GateStatsSnapshot::default(),
+        }
+    }
+
+    /// Write a report if an interval has elapsed since the last one.
+    ///
+    /// Returns the report written, or `None` if none was due. Write failures
+    /// are logged and do not stop the next interval from being reported.
+    pub fn tick(&mut self, stats: &GateStats) -> Option<GateStatsReport> {
+        let now = self.clock.now_secs();
+        if now - self.last_ts < self.config.interval_secs {
+            return None;
+        }
+
+        let totals = stats.snapshot();
+        let report = GateStatsReport {
+            ts: now,
+            interval_secs: now - self.last_ts,
+            delta: totals.delta_since(&self.last),
+            totals: totals.clone(),
+        };
+        self.last_ts = now;
+        self.last = totals;
+
+        if let Err(e) = self.append(&report) {
+            eprintln!(
+                "Failed to write gate stats report to {}: {}",
+                self.config.path.display(),
+                e
+            );
+        }
+        Some(report)
+    }
+
+    fn append(&self, report: &GateStatsReport) -> Result<(), String> {
+        let line = serde_json::to_string(report).map_err(|e| e.to_string())?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.config.path)
+            .map_err(|e| e.to_string())?;
+        writeln!(file, "{}", line).map_err(|e| e.to_string())
+    }
+}
+
+/// Spawn a background task reporting [`GLOBAL_GATE_STATS`] if
+/// GATE_STATS_REPORT_PATH is set.
+pub fn spawn_gate_stats_reporter() {
+    let config = match GateStatsReportConfig::from_env() {
+        Some(config) => config,
+        None => return,
+    };
+    println!(
+        "Gate stats report: {} every {}s",
+        config.path.display(),
+        config.interval_secs
+    );
+    let period = std::time::Duration::from_secs(config.interval_secs as u64);
+    let mut reporter = GateStatsReporter::new(config, SystemClock);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(period);
+        // The first tick fires immediately; nothing is due yet.
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            reporter.tick(&GLOBAL_GATE_STATS);
+        }
+    });
+}
@@ -0,0 +1,60 @@
+use std::collections::HashSet;
+
+use spl_token_2022::extension::{transfer_hook, StateWithExtensions};
+use spl_token_2022::state::Mint;
+
+use super::{Gate, GateDecision, RejectReason, TradeContext};
+use crate::common::bot_config;
+
+/// Transfer-hook program configured on a Token-2022 mint, if any.
+///
+/// `data` is the raw mint account. Returns `None` for legacy SPL mints,
+/// Token-2022 mints without the extension, and data that fails to unpack.
+pub fn transfer_hook_program(data: &[u8]) -> Option<String> {
+    let state = StateWithExtensions::<Mint>::unpack(data).ok()?;
+    transfer_hook::get_program_id(&state).map(|p| p.to_string())
+}
+
+/// Rejects mints whose Token-2022 transfer hook runs a program that is not
+/// on the allowlist. A malicious hook can block or tax sells and trap funds,
+/// so with an empty allowlist every hooked mint is rejected.
+#[derive(Clone, Debug, Default)]
+pub struct TransferHookGate {
+    pub allowed_programs: HashSet<String>,
+}
+
+impl TransferHookGate {
+    pub fn new(allowed_programs: HashSet<String>) -> Self {
+        Self { allowed_programs }
+    }
+
+    /// Build from TRANSFER_HOOK_ALLOWLIST (comma-separated program ids).
+    pub fn from_env() -> Self {
+        let mut allowed_programs = HashSet::new();
+        if let Ok(v) = bot_config::var("TRANSFER_HOOK_ALLOWLIST") {
+            allowed_programs = v
+                .split(',')
+                .map(|s| s.trim())
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        Self { allowed_programs }
+    }
+}
+
+impl Gate for TransferHookGate {
+    fn name(&self) -> &'static str {
+        "TransferHookGate"
+    }
+
+    fn check(&self, ctx: &TradeContext) -> GateDecision {
+        match &ctx.transfer_hook_program {
+            Some(program) if !self.allowed_programs.contains(program) => GateDecision::Rejected {
+                code: RejectReason::TransferHook,
+                reason: format!("transfer_hook {} not allowlisted", program),
+            },
+            _ => GateDecision::Passed,
+        }
+    }
+}
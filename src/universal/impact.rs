@@ -0,0 +1,39 @@
+//! Price-impact helpers for sizing against pool liquidity.
+//!
+//! Pools are approximated as constant-product (x*y=k) with liquidity split
+//! evenly between the two sides, so half of `liq_usd` is the quote reserve.
+
+/// Estimated price impact (bps) of buying `size_usd` into a pool holding
+/// `liq_usd` total liquidity. Returns 10000 (100%) for an empty pool.
+pub fn estimate_impact_bps(size_usd: f64, liq_usd: f64) -> f64 {
+    estimate_price_impact_bps(size_usd, liq_usd, f64::INFINITY)
+}
+
+/// [`estimate_impact_bps`] with the pool depth also capped by
+/// `depth_multiple` (liquidity over our total exposure in the mint, see
+/// `TradeContext`): the order sees at most `depth_multiple * size_usd` of
+/// liquidity, so exposure we already hold in the mint raises the estimate.
+/// A NaN `depth_multiple` (or `liq_usd`) is ignored in favor of the other;
+/// a non-positive one means no depth and returns 10000.
+pub fn estimate_price_impact_bps(size_usd: f64, liq_usd: f64, depth_multiple: f64) -> f64 {
+    if size_usd <= 0.0 {
+        return 0.0;
+    }
+    let quote_reserve = liq_usd.min(depth_multiple * size_usd) / 2.0;
+    if quote_reserve.is_nan() || quote_reserve <= 0.0 {
+        return 10_000.0;
+    }
+    size_usd / (quote_reserve + size_usd) * 10_000.0
+}
+
+/// Expected tokens out for `size_usd` at `price_usd`, after `impact_bps`
+/// and then the `slippage_bps` tolerance. This is the min-out a swap would
+/// be submitted with.
+pub fn estimate_min_out(size_usd: f64, price_usd: f64, impact_bps: f64, slippage_bps: u64) -> f64 {
+    if size_usd <= 0.0 || price_usd <= 0.0 {
+        return 0.0;
+    }
+    let spot_out = size_usd / price_usd;
+    let after_impact = spot_out * (1.0 - (impact_bps / 10_000.0).clamp(0.0, 1.0));
+    after_impact * (1.0 - (slippage_bps.min(10_000) as f64 / 10_000.0))
+}
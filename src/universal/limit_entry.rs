@@ -0,0 +1,145 @@
+//! Parked limit entries: copy buys waiting for the price to come down to a
+//! target before executing.
+//!
+//! An entry is tied to the source wallet whose buy it copies. If that wallet
+//! then sells the mint the thesis is gone, so (unless disabled with
+//! CANCEL_LIMIT_ON_SOURCE_SELL=false) matching pending entries are cancelled
+//! with reason [`THESIS_INVALIDATED`].
+
+use std::env;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+/// Cancellation reason for entries whose source wallet sold the mint.
+pub const THESIS_INVALIDATED: &str = "thesis_invalidated";
+
+/// A pending buy that fills once the price is at or below `target_price`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LimitEntry {
+    pub id: u64,
+    /// Wallet whose buy this entry copies.
+    pub source_wallet: String,
+    pub mint: String,
+    pub target_price: f64,
+    pub size_usd: f64,
+    pub created_ts: i64,
+}
+
+/// A pending entry removed from the book without filling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CancelledEntry {
+    pub entry: LimitEntry,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+struct BookState {
+    next_id: u64,
+    pending: Vec<LimitEntry>,
+}
+
+/// In-memory book of pending limit entries.
+#[derive(Debug)]
+pub struct LimitEntryBook {
+    state: Mutex<BookState>,
+    /// Cancel pending entries when their source wallet sells the mint.
+    pub cancel_on_source_sell: bool,
+}
+
+impl Default for LimitEntryBook {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl LimitEntryBook {
+    pub fn new(cancel_on_source_sell: bool) -> Self {
+        Self {
+            state: Mutex::new(BookState::default()),
+            cancel_on_source_sell,
+        }
+    }
+
+    /// Build from CANCEL_LIMIT_ON_SOURCE_SELL (default true).
+    pub fn from_env() -> Self {
+        let mut cancel_on_source_sell = true;
+        if let Ok(v) = env::var("CANCEL_LIMIT_ON_SOURCE_SELL") {
+            if let Ok(parsed) = v.parse::<bool>() {
+                cancel_on_source_sell = parsed;
+            }
+        }
+        Self::new(cancel_on_source_sell)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BookState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Park a new entry and return its id.
+    pub fn park(
+        &self,
+        source_wallet: &str,
+        mint: &str,
+        target_price: f64,
+        size_usd: f64,
+        created_ts: i64,
+    ) -> u64 {
+        let mut state = self.lock();
+        state.next_id += 1;
+        let id = state.next_id;
+        state.pending.push(LimitEntry {
+            id,
+            source_wallet: source_wallet.to_string(),
+            mint: mint.to_string(),
+            target_price,
+            size_usd,
+            created_ts,
+        });
+        id
+    }
+
+    /// Snapshot of all pending entries, oldest first.
+    pub fn pending(&self) -> Vec<LimitEntry> {
+        self.lock().pending.clone()
+    }
+
+    /// Feed a price update. Entries in `mint` whose target is reached are
+    /// removed from the book and returned for execution.
+    pub fn on_price(&self, mint: &str, price: f64) -> Vec<LimitEntry> {
+        let mut state = self.lock();
+        let (triggered, rest) = state
+            .pending
+            .drain(..)
+            .partition(|e| e.mint == mint && price <= e.target_price);
+        state.pending = rest;
+        triggered
+    }
+
+    /// React to `source_wallet` selling `mint`: cancel its pending entries
+    /// in that mint. Returns nothing when cancellation is disabled.
+    pub fn on_source_sell(&self, source_wallet: &str, mint: &str) -> Vec<CancelledEntry> {
+        if !self.cancel_on_source_sell {
+            return Vec::new();
+        }
+        let mut state = self.lock();
+        let (cancelled, rest): (Vec<LimitEntry>, Vec<LimitEntry>) = state
+            .pending
+            .drain(..)
+            .partition(|e| e.source_wallet == source_wallet && e.mint == mint);
+        state.pending = rest;
+        cancelled
+            .into_iter()
+            .map(|entry| CancelledEntry {
+                entry,
+                reason: THESIS_INVALIDATED.to_string(),
+            })
+            .collect()
+    }
+}
+
+/// Process-wide limit entry book.
+pub static GLOBAL_LIMIT_ENTRIES: Lazy<LimitEntryBook> = Lazy::new(LimitEntryBook::from_env);
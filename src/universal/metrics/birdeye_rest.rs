@@ -0,0 +1,215 @@
+//! Birdeye REST fallback for mints the live feed has not seen yet.
+//!
+//! A freshly launched mint bought by a leader usually has no ticks in the
+//! [`MetricsStore`], so the freshness gates would block it. When
+//! BIRDEYE_REST_FALLBACK=1, [`install_rest_fallback`] hooks the store's
+//! `on_missing` callback and fills the gap from the Birdeye token-overview
+//! endpoint (API key from BIRDEYE_API_KEY).
+
+use std::collections::HashSet;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+
+use super::{MetricsStore, TokenMetrics};
+use crate::common::rpc_failover::RetryBackoff;
+
+const TOKEN_OVERVIEW_URL: &str = "https://public-api.birdeye.so/defi/token_overview";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Rate-limit backoff: 250ms base, 4s cap, give up after 10s of waiting.
+const BACKOFF_BASE_MS: u64 = 250;
+const BACKOFF_MAX_MS: u64 = 4_000;
+const BACKOFF_BUDGET: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Deserialize)]
+struct OverviewResponse {
+    #[serde(default)]
+    success: bool,
+    data: Option<OverviewData>,
+    message: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OverviewData {
+    price: Option<f64>,
+    liquidity: Option<f64>,
+    #[serde(alias = "marketCap", alias = "mc")]
+    market_cap: Option<f64>,
+    #[serde(rename = "v30mUSD")]
+    v30m_usd: Option<f64>,
+    #[serde(rename = "v1hUSD")]
+    v1h_usd: Option<f64>,
+}
+
+/// Map a token-overview response body into [`TokenMetrics`] stamped `now`.
+///
+/// Birdeye has no 5m/15m volume buckets, so `vol_5m_usd` / `vol_15m_usd` are
+/// prorated from the 30m volume (or the 1h volume if 30m is absent). That
+/// assumes uniform flow, which is good enough to unblock a first entry until
+/// live ticks take over. Amihud and range efficiency are left unset.
+pub fn parse_token_overview(mint: &str, body: &str, now: i64) -> Result<TokenMetrics> {
+    let resp: OverviewResponse = serde_json::from_str(body)
+        .map_err(|e| anyhow!("birdeye: invalid token_overview body for {}: {}", mint, e))?;
+    if !resp.success {
+        return Err(anyhow!(
+            "birdeye: token_overview failed for {}: {}",
+            mint,
+            resp.message.unwrap_or_else(|| "unknown error".to_string())
+        ));
+    }
+    let data = resp
+        .data
+        .ok_or_else(|| anyhow!("birdeye: token_overview for {} has no data", mint))?;
+    let price_usd = match data.price {
+        Some(price) if price.is_finite() && price > 0.0 => price,
+        _ => return Err(anyhow!("birdeye: no price for {}", mint)),
+    };
+    let per_minute = match (data.v30m_usd, data.v1h_usd) {
+        (Some(v30m), _) => v30m / 30.0,
+        (None, Some(v1h)) => v1h / 60.0,
+        (None, None) => 0.0,
+    };
+    Ok(TokenMetrics {
+        mint: mint.to_string(),
+        price_usd,
+        liq_usd: data.liquidity,
+        mcap_usd: data.market_cap,
+        vol_5m_usd: per_minute * 5.0,
+        vol_15m_usd: per_minute * 15.0,
+        amihud_5m: None,
+        range_eff_5m: None,
+        last_update: now,
+    })
+}
+
+pub(super) fn api_key() -> Result<String> {
+    match env::var("BIRDEYE_API_KEY") {
+        Ok(key) if !key.trim().is_empty() => Ok(key.trim().to_string()),
+        _ => Err(anyhow!("birdeye: BIRDEYE_API_KEY is not set")),
+    }
+}
+
+static HTTP: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .unwrap_or_else(|_| Client::new())
+});
+
+/// Fetch current metrics for `mint` from the Birdeye token-overview endpoint.
+///
+/// 429 responses are retried with jittered exponential backoff (honouring
+/// `Retry-After` when it is longer) until the backoff budget runs out; any
+/// other non-success status is returned as an error.
+pub async fn fetch_token_metrics(mint: &str) -> Result<TokenMetrics> {
+    let key = api_key()?;
+    let mut backoff = RetryBackoff::new(BACKOFF_BASE_MS, BACKOFF_MAX_MS, BACKOFF_BUDGET);
+    loop {
+        let resp = HTTP
+            .get(TOKEN_OVERVIEW_URL)
+            .query(&[("address", mint)])
+            .header("X-API-KEY", &key)
+            .header("x-chain", "solana")
+            .header("accept", "application/json")
+            .send()
+            .await
+            .map_err(|e| anyhow!("birdeye: request for {} failed: {}", mint, e))?;
+
+        let status = resp.status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = resp
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let delay = match backoff.next_delay(&mut rand::thread_rng()) {
+                Some(delay) => delay.max(retry_after.unwrap_or_default()),
+                None => {
+                    return Err(anyhow!(
+                        "birdeye: rate limited for {} after {:?} of backoff",
+                        mint,
+                        backoff.slept()
+                    ))
+                }
+            };
+            tokio::time::sleep(delay).await;
+            continue;
+        }
+
+        let body = resp
+            .text()
+            .await
+            .map_err(|e| anyhow!("birdeye: reading response for {} failed: {}", mint, e))?;
+        if !status.is_success() {
+            return Err(anyhow!("birdeye: HTTP {} for {}: {}", status, mint, body));
+        }
+        return parse_token_overview(mint, &body, super::clock::now_secs());
+    }
+}
+
+/// Whether BIRDEYE_REST_FALLBACK is enabled (`1` or `true`; default off).
+pub fn rest_fallback_enabled() -> bool {
+    match env::var("BIRDEYE_REST_FALLBACK") {
+        Ok(v) => matches!(v.trim(), "1" | "true"),
+        Err(_) => false,
+    }
+}
+
+/// Mints with a REST fetch in flight, so repeated misses don't stack requests.
+static IN_FLIGHT: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Register a miss hook on `store` that fetches the mint from Birdeye and
+/// upserts the result. Fetches run on the current tokio runtime; misses
+/// outside a runtime, or for a mint already being fetched, are ignored.
+pub fn install_rest_fallback(store: &Arc<MetricsStore>) {
+    let weak = Arc::downgrade(store);
+    store.on_missing(move |mint| {
+        let handle = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle,
+            Err(_) => return,
+        };
+        let started = match IN_FLIGHT.lock() {
+            Ok(mut set) => set.insert(mint.to_string()),
+            Err(poisoned) => poisoned.into_inner().insert(mint.to_string()),
+        };
+        if !started {
+            return;
+        }
+        let mint = mint.to_string();
+        let weak = weak.clone();
+        handle.spawn(async move {
+            match fetch_token_metrics(&mint).await {
+                Ok(metrics) => {
+                    if let Some(store) = weak.upgrade() {
+                        store.upsert(metrics);
+                    }
+                }
+                Err(e) => eprintln!("[METRICS] REST fallback failed: {}", e),
+            }
+            match IN_FLIGHT.lock() {
+                Ok(mut set) => set.remove(&mint),
+                Err(poisoned) => poisoned.into_inner().remove(&mint),
+            };
+        });
+    });
+}
+
+/// Install the REST fallback on `store` if BIRDEYE_REST_FALLBACK is enabled.
+/// Returns true if it was installed.
+pub fn install_rest_fallback_from_env(store: &Arc<MetricsStore>) -> bool {
+    if !rest_fallback_enabled() {
+        return false;
+    }
+    if api_key().is_err() {
+        eprintln!("BIRDEYE_REST_FALLBACK is set but BIRDEYE_API_KEY is missing; fallback disabled");
+        return false;
+    }
+    install_rest_fallback(store);
+    true
+}
@@ -0,0 +1,462 @@
+//! Birdeye WebSocket client feeding the [`MetricsStore`].
+//!
+//! [`BirdeyeWsClient`] keeps one connection to Birdeye's public socket and
+//! subscribes trades (`SUBSCRIBE_TXS`) and 1m price candles
+//! (`SUBSCRIBE_PRICE`) for every tracked mint. Trades go through
+//! [`MetricsStore::upsert_tick`], so rolling volume, Amihud and range
+//! efficiency stay live; candles only move the price. The tracked mints come
+//! from the [`SubscriptionSink`] impl, so attaching the client to
+//! [`GLOBAL_METRICS_SUBSCRIPTIONS`] follows open positions and watchers.
+//!
+//! A dropped or silent connection is re-established with exponential
+//! backoff and the current mints are subscribed again. Every raw text frame
+//! is handed to the line tap first (e.g. `ws_tap::record_line`, so the
+//! stream can be replayed); welcome, heartbeat and unknown frames are
+//! skipped.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use futures::{Sink, SinkExt, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::Notify;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+
+use super::birdeye_rest::api_key;
+use super::{MetricsStore, SubscriptionSink, GLOBAL_METRICS_SUBSCRIPTIONS};
+use crate::common::rpc_failover::RetryBackoff;
+
+const BIRDEYE_WS_URL: &str = "wss://public-api.birdeye.so/socket/solana";
+const BIRDEYE_WS_ORIGIN: &str = "ws://public-api.birdeye.so";
+/// Birdeye caps a complex subscription at 100 addresses.
+pub const MAX_SUBSCRIBED_MINTS: usize = 100;
+/// Reconnect backoff: 500ms base, 30s cap, reset once a session stayed up
+/// for a minute.
+const BACKOFF_BASE_MS: u64 = 500;
+const BACKOFF_MAX_MS: u64 = 30_000;
+const STABLE_SESSION: Duration = Duration::from_secs(60);
+/// Ping this often; reconnect when nothing arrives for `IDLE_TIMEOUT`.
+const PING_INTERVAL: Duration = Duration::from_secs(20);
+const IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A store update parsed from one WS frame.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WsUpdate {
+    /// A swap of `mint`: its USD price and the trade's USD volume.
+    Trade {
+        mint: String,
+        ts: i64,
+        price_usd: f64,
+        volume_usd: f64,
+    },
+    /// A price candle of `mint` (close price).
+    Price {
+        mint: String,
+        ts: i64,
+        price_usd: f64,
+    },
+}
+
+impl WsUpdate {
+    pub fn mint(&self) -> &str {
+        match self {
+            WsUpdate::Trade { mint, .. } | WsUpdate::Price { mint, .. } => mint,
+        }
+    }
+
+    /// Write the update into `store`.
+    pub fn apply(&self, store: &MetricsStore) {
+        match self {
+            WsUpdate::Trade {
+                mint,
+                ts,
+                price_usd,
+                volume_usd,
+            } => store.upsert_tick(mint, *ts, *price_usd, *volume_usd),
+            WsUpdate::Price {
+                mint,
+                ts,
+                price_usd,
+            } => store.record_price(mint, *ts, *price_usd),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Frame {
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    data: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceData {
+    address: Option<String>,
+    c: Option<f64>,
+    unix_time: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TxsData {
+    token_address: Option<String>,
+    address: Option<String>,
+    token_price: Option<f64>,
+    #[serde(rename = "volumeUSD", alias = "volumeUsd")]
+    volume_usd: Option<f64>,
+    block_unix_time: Option<i64>,
+    from: Option<TxsLeg>,
+    to: Option<TxsLeg>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TxsLeg {
+    address: Option<String>,
+    price: Option<f64>,
+    nearest_price: Option<f64>,
+}
+
+fn positive(value: Option<f64>) -> Option<f64> {
+    value.filter(|v| v.is_finite() && *v > 0.0)
+}
+
+/// Parse one text frame into a store update; frames stamped without a time
+/// use `now`.
+///
+/// `Ok(None)` for frames carrying no update: welcome, heartbeat,
+/// (un)subscribe acks and unknown types. Malformed or truncated JSON, data
+/// frames missing the mint or price, and server `ERROR` frames are errors.
+pub fn parse_frame(text: &str, now: i64) -> Result<Option<WsUpdate>> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() || matches!(trimmed, "ping" | "pong") {
+        return Ok(None);
+    }
+    let frame: Frame =
+        serde_json::from_str(trimmed).map_err(|e| anyhow!("birdeye ws: invalid frame: {}", e))?;
+    match frame.kind.as_str() {
+        "PRICE_DATA" => {
+            let data: PriceData = serde_json::from_value(frame.data)
+                .map_err(|e| anyhow!("birdeye ws: invalid PRICE_DATA: {}", e))?;
+            let mint = data
+                .address
+                .ok_or_else(|| anyhow!("birdeye ws: PRICE_DATA without address"))?;
+            let price_usd = positive(data.c)
+                .ok_or_else(|| anyhow!("birdeye ws: PRICE_DATA without price for {}", mint))?;
+            Ok(Some(WsUpdate::Price {
+                mint,
+                ts: data.unix_time.unwrap_or(now),
+                price_usd,
+            }))
+        }
+        "TXS_DATA" => {
+            let data: TxsData = serde_json::from_value(frame.data)
+                .map_err(|e| anyhow!("birdeye ws: invalid TXS_DATA: {}", e))?;
+            let mint = data
+                .token_address
+                .or(data.address)
+                .ok_or_else(|| anyhow!("birdeye ws: TXS_DATA without token address"))?;
+            // The traded token's leg carries its price when the frame has no
+            // top-level tokenPrice.
+            let leg_price = [data.from, data.to]
+                .into_iter()
+                .flatten()
+                .filter(|leg| leg.address.as_deref() == Some(mint.as_str()))
+                .find_map(|leg| positive(leg.price).or(positive(leg.nearest_price)));
+            let price_usd = positive(data.token_price)
+                .or(leg_price)
+                .ok_or_else(|| anyhow!("birdeye ws: TXS_DATA without price for {}", mint))?;
+            Ok(Some(WsUpdate::Trade {
+                mint,
+                ts: data.block_unix_time.unwrap_or(now),
+                price_usd,
+                volume_usd: data
+                    .volume_usd
+                    .filter(|v| v.is_finite())
+                    .unwrap_or(0.0)
+                    .max(0.0),
+            }))
+        }
+        "ERROR" => Err(anyhow!("birdeye ws: server error: {}", frame.data)),
+        _ => Ok(None),
+    }
+}
+
+/// Frames subscribing exactly `mints` (at most [`MAX_SUBSCRIBED_MINTS`]).
+/// A new subscription replaces the previous one of the same type, so the
+/// full set is sent on every change; an empty set unsubscribes both feeds.
+pub fn subscription_messages(mints: &[String]) -> Vec<String> {
+    if mints.is_empty() {
+        return vec![
+            json!({ "type": "UNSUBSCRIBE_PRICE" }).to_string(),
+            json!({ "type": "UNSUBSCRIBE_TXS" }).to_string(),
+        ];
+    }
+    let mints = &mints[..mints.len().min(MAX_SUBSCRIBED_MINTS)];
+    let price_query = mints
+        .iter()
+        .map(|mint| format!("(address = {} AND chartType = 1m AND currency = usd)", mint))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    let txs_query = mints
+        .iter()
+        .map(|mint| format!("address = {}", mint))
+        .collect::<Vec<_>>()
+        .join(" OR ");
+    vec![
+        json!({
+            "type": "SUBSCRIBE_PRICE",
+            "data": { "queryType": "complex", "query": price_query },
+        })
+        .to_string(),
+        json!({
+            "type": "SUBSCRIBE_TXS",
+            "data": { "queryType": "complex", "query": txs_query },
+        })
+        .to_string(),
+    ]
+}
+
+/// Callback receiving every raw text frame before it is parsed.
+pub type LineTap = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Reconnecting Birdeye WS client writing into a shared [`MetricsStore`].
+pub struct BirdeyeWsClient {
+    url: String,
+    store: Arc<MetricsStore>,
+    mints: Mutex<BTreeSet<String>>,
+    changed: Notify,
+    tap: RwLock<Option<LineTap>>,
+}
+
+impl BirdeyeWsClient {
+    /// A client for the socket at `url` (API key included) feeding `store`.
+    pub fn new(url: String, store: Arc<MetricsStore>) -> Self {
+        Self {
+            url,
+            store,
+            mints: Mutex::new(BTreeSet::new()),
+            changed: Notify::new(),
+            tap: RwLock::new(None),
+        }
+    }
+
+    /// A client for Birdeye's public socket with `api_key`.
+    pub fn with_api_key(api_key: &str, store: Arc<MetricsStore>) -> Self {
+        Self::new(format!("{}?x-api-key={}", BIRDEYE_WS_URL, api_key), store)
+    }
+
+    /// Pass every raw text frame to `tap` before parsing, e.g.
+    /// `ws_tap::record_line` to record the stream. Replaces any previous tap.
+    pub fn set_line_tap<F>(&self, tap: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        let tap: LineTap = Arc::new(tap);
+        match self.tap.write() {
+            Ok(mut guard) => *guard = Some(tap),
+            Err(poisoned) => *poisoned.into_inner() = Some(tap),
+        }
+    }
+
+    /// Mints currently subscribed, sorted.
+    pub fn tracked_mints(&self) -> Vec<String> {
+        match self.mints.lock() {
+            Ok(guard) => guard.iter().cloned().collect(),
+            Err(poisoned) => poisoned.into_inner().iter().cloned().collect(),
+        }
+    }
+
+    fn update_mints(&self, update: impl FnOnce(&mut BTreeSet<String>) -> bool) {
+        let changed = match self.mints.lock() {
+            Ok(mut guard) => update(&mut guard),
+            Err(poisoned) => update(&mut poisoned.into_inner()),
+        };
+        if changed {
+            self.changed.notify_one();
+        }
+    }
+
+    /// Tap, parse and apply one raw frame. Returns the update it carried.
+    pub fn handle_line(&self, line: &str) -> Option<WsUpdate> {
+        let tap = match self.tap.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        if let Some(tap) = tap {
+            tap(line);
+        }
+        match parse_frame(line, super::clock::now_secs()) {
+            Ok(Some(update)) => {
+                update.apply(&self.store);
+                Some(update)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                eprintln!("[BIRDEYE WS] {}", e);
+                None
+            }
+        }
+    }
+
+    /// Send the subscription frames if the tracked mints differ from `sent`.
+    async fn sync_subscriptions<S>(&self, write: &mut S, sent: &mut Vec<String>) -> Result<()>
+    where
+        S: Sink<Message, Error = WsError> + Unpin,
+    {
+        let mints = self.tracked_mints();
+        if mints == *sent {
+            return Ok(());
+        }
+        if mints.len() > MAX_SUBSCRIBED_MINTS {
+            eprintln!(
+                "[BIRDEYE WS] {} mints tracked, only the first {} are subscribed",
+                mints.len(),
+                MAX_SUBSCRIBED_MINTS
+            );
+        }
+        for msg in subscription_messages(&mints) {
+            write
+                .send(Message::Text(msg))
+                .await
+                .map_err(|e| anyhow!("birdeye ws: subscribe failed: {}", e))?;
+        }
+        *sent = mints;
+        Ok(())
+    }
+
+    /// One connection: subscribe, then read until the socket drops, closes
+    /// or goes silent for `IDLE_TIMEOUT`.
+    async fn session(&self) -> Result<()> {
+        let mut request = self
+            .url
+            .as_str()
+            .into_client_request()
+            .map_err(|e| anyhow!("birdeye ws: invalid url: {}", e))?;
+        let headers = request.headers_mut();
+        headers.insert("Origin", HeaderValue::from_static(BIRDEYE_WS_ORIGIN));
+        headers.insert(
+            "Sec-WebSocket-Protocol",
+            HeaderValue::from_static("echo-protocol"),
+        );
+        let (ws, _) = connect_async(request)
+            .await
+            .map_err(|e| anyhow!("birdeye ws: connect failed: {}", e))?;
+        let (mut write, mut read) = ws.split();
+
+        let mut sent = Vec::new();
+        self.sync_subscriptions(&mut write, &mut sent).await?;
+        let mut ping = tokio::time::interval(PING_INTERVAL);
+        let mut last_frame = Instant::now();
+        loop {
+            let idle = tokio::time::sleep_until((last_frame + IDLE_TIMEOUT).into());
+            tokio::select! {
+                frame = read.next() => {
+                    last_frame = Instant::now();
+                    match frame {
+                        Some(Ok(Message::Text(text))) => {
+                            self.handle_line(&text);
+                        }
+                        Some(Ok(Message::Binary(bytes))) => {
+                            if let Ok(text) = std::str::from_utf8(&bytes) {
+                                self.handle_line(text);
+                            }
+                        }
+                        // Pings are answered by tungstenite; pongs only count as liveness.
+                        Some(Ok(Message::Ping(_) | Message::Pong(_) | Message::Frame(_))) => {}
+                        Some(Ok(Message::Close(frame))) => {
+                            return Err(anyhow!("birdeye ws: closed by server: {:?}", frame));
+                        }
+                        Some(Err(e)) => return Err(anyhow!("birdeye ws: read failed: {}", e)),
+                        None => return Err(anyhow!("birdeye ws: stream ended")),
+                    }
+                }
+                _ = self.changed.notified() => {
+                    self.sync_subscriptions(&mut write, &mut sent).await?;
+                }
+                _ = ping.tick() => {
+                    write
+                        .send(Message::Ping(Vec::new()))
+                        .await
+                        .map_err(|e| anyhow!("birdeye ws: ping failed: {}", e))?;
+                }
+                _ = idle => {
+                    return Err(anyhow!("birdeye ws: no frames for {:?}", IDLE_TIMEOUT));
+                }
+            }
+        }
+    }
+
+    /// Run forever, reconnecting with jittered exponential backoff whenever
+    /// the session ends.
+    pub async fn run(self: Arc<Self>) {
+        let mut backoff = reconnect_backoff();
+        loop {
+            let started = Instant::now();
+            if let Err(e) = self.session().await {
+                eprintln!("[BIRDEYE WS] {}", e);
+            }
+            if started.elapsed() >= STABLE_SESSION {
+                backoff = reconnect_backoff();
+            }
+            let delay = backoff
+                .next_delay(&mut rand::thread_rng())
+                .unwrap_or(Duration::from_millis(BACKOFF_MAX_MS));
+            eprintln!("[BIRDEYE WS] reconnecting in {:?}", delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+fn reconnect_backoff() -> RetryBackoff {
+    RetryBackoff::new(BACKOFF_BASE_MS, BACKOFF_MAX_MS, Duration::MAX)
+}
+
+impl SubscriptionSink for BirdeyeWsClient {
+    fn subscribe(&self, mint: &str) {
+        self.update_mints(|mints| mints.insert(mint.to_string()));
+    }
+
+    fn unsubscribe(&self, mint: &str) {
+        self.update_mints(|mints| mints.remove(mint));
+    }
+}
+
+/// Whether BIRDEYE_WS_ENABLED is set (`1` or `true`; default off).
+pub fn birdeye_ws_enabled() -> bool {
+    match env::var("BIRDEYE_WS_ENABLED") {
+        Ok(v) => matches!(v.trim(), "1" | "true"),
+        Err(_) => false,
+    }
+}
+
+/// Start the client for `store` if BIRDEYE_WS_ENABLED is set: it is attached
+/// to [`GLOBAL_METRICS_SUBSCRIPTIONS`] and runs on the current tokio runtime.
+/// Returns the client so callers can install a line tap.
+pub fn spawn_birdeye_ws_client_from_env(store: &Arc<MetricsStore>) -> Option<Arc<BirdeyeWsClient>> {
+    if !birdeye_ws_enabled() {
+        return None;
+    }
+    let key = match api_key() {
+        Ok(key) => key,
+        Err(_) => {
+            eprintln!(
+                "BIRDEYE_WS_ENABLED is set but BIRDEYE_API_KEY is missing; WS client disabled"
+            );
+            return None;
+        }
+    };
+    let client = Arc::new(BirdeyeWsClient::with_api_key(&key, Arc::clone(store)));
+    GLOBAL_METRICS_SUBSCRIPTIONS.set_sink(client.clone());
+    tokio::spawn(Arc::clone(&client).run());
+    Some(client)
+}
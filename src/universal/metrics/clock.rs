@@ -0,0 +1,122 @@
+//! Time source for the metrics layer.
+//!
+//! Live, "now" is the wall clock. During a replay (REPLAY_USE_EVENT_TIME=1)
+//! an [`EventClock`] is installed and advanced to each recorded frame's
+//! timestamp, so rolling 5m/15m windows follow the recorded timeline instead
+//! of however fast the file is read back.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use once_cell::sync::Lazy;
+use serde_json::Value;
+
+use crate::universal::gates::stats::Clock;
+
+/// Manually driven clock, advanced from event timestamps. Never goes back.
+#[derive(Debug, Default)]
+pub struct EventClock {
+    now: AtomicI64,
+}
+
+impl EventClock {
+    pub fn new(start: i64) -> Self {
+        Self {
+            now: AtomicI64::new(start),
+        }
+    }
+
+    /// Move the clock forward to `ts`; earlier timestamps are ignored so
+    /// out-of-order frames can't rewind the windows. Returns the current time.
+    pub fn observe(&self, ts: i64) -> i64 {
+        self.now.fetch_max(ts, Ordering::SeqCst).max(ts)
+    }
+
+    /// Move the clock forward by `secs`, for frames without a timestamp.
+    pub fn advance(&self, secs: i64) -> i64 {
+        self.now.fetch_add(secs, Ordering::SeqCst) + secs
+    }
+}
+
+impl Clock for EventClock {
+    fn now_secs(&self) -> i64 {
+        self.now.load(Ordering::SeqCst)
+    }
+}
+
+static EVENT_CLOCK: Lazy<RwLock<Option<Arc<EventClock>>>> = Lazy::new(|| RwLock::new(None));
+
+/// Make `clock` the metrics layer's time source (replay mode).
+pub fn install_event_clock(clock: Arc<EventClock>) {
+    match EVENT_CLOCK.write() {
+        Ok(mut guard) => *guard = Some(clock),
+        Err(poisoned) => *poisoned.into_inner() = Some(clock),
+    }
+}
+
+/// Go back to wall-clock time.
+pub fn clear_event_clock() {
+    match EVENT_CLOCK.write() {
+        Ok(mut guard) => *guard = None,
+        Err(poisoned) => *poisoned.into_inner() = None,
+    }
+}
+
+/// Current unix time (seconds) as seen by the metrics layer: the installed
+/// event clock if any, otherwise the wall clock.
+pub fn now_secs() -> i64 {
+    let clock = match EVENT_CLOCK.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    match clock {
+        Some(clock) => clock.now_secs(),
+        None => chrono::Utc::now().timestamp(),
+    }
+}
+
+/// [`Clock`] backed by [`now_secs`], for components taking an injected clock.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricsClock;
+
+impl Clock for MetricsClock {
+    fn now_secs(&self) -> i64 {
+        now_secs()
+    }
+}
+
+/// Whether REPLAY_USE_EVENT_TIME is enabled (`1` or `true`; default off).
+pub fn replay_use_event_time() -> bool {
+    match std::env::var("REPLAY_USE_EVENT_TIME") {
+        Ok(v) => matches!(v.trim(), "1" | "true"),
+        Err(_) => false,
+    }
+}
+
+const TS_KEYS: [&str; 5] = [
+    "ts",
+    "unixTime",
+    "blockUnixTime",
+    "timestamp",
+    "recorded_at",
+];
+
+/// Event time (unix seconds) carried by a recorded frame, if any.
+///
+/// Looks for a numeric `ts` / `unixTime` / `blockUnixTime` / `timestamp` /
+/// `recorded_at` field at the top level or under `data`. Millisecond values
+/// are converted to seconds.
+pub fn event_time(line: &str) -> Option<i64> {
+    let value: Value = serde_json::from_str(line).ok()?;
+    let scopes = [Some(&value), value.get("data")];
+    let raw = scopes
+        .iter()
+        .flatten()
+        .flat_map(|scope| TS_KEYS.iter().filter_map(move |key| scope.get(*key)))
+        .find_map(|v| v.as_i64().or_else(|| v.as_f64().map(|f| f as i64)))?;
+    if raw > 1_000_000_000_000 {
+        Some(raw / 1_000)
+    } else {
+        Some(raw)
+    }
+}
@@ -0,0 +1,115 @@
+//! Rolling price-impact metrics derived from the trade tick stream.
+//!
+//! [`MetricsCalc`] keeps the `(ts, price, volume_usd)` ticks of a trailing
+//! window and computes, over consecutive tick pairs inside it:
+//!
+//! - Amihud illiquidity: `mean(|r_i| / volume_usd_i)` with the simple return
+//!   `r_i = p_i / p_{i-1} - 1`. Ticks without volume or with a non-positive
+//!   previous price are skipped. Higher means each dollar moves price more.
+//! - Range efficiency: `|p_last - p_first| / sum(|p_i - p_{i-1}|)`, in
+//!   `[0, 1]`. 1 is a straight-line move, values near 0 are chop.
+//!
+//! Both are `None` until the window holds at least two usable ticks; range
+//! efficiency is also `None` for a completely flat window.
+
+use std::collections::VecDeque;
+
+/// A single trade observation with its price.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PriceTick {
+    pub ts: i64,
+    pub price: f64,
+    pub volume_usd: f64,
+}
+
+/// Rolling accumulator over the trailing `window_secs`.
+#[derive(Clone, Debug)]
+pub struct MetricsCalc {
+    window_secs: i64,
+    ticks: VecDeque<PriceTick>,
+}
+
+impl Default for MetricsCalc {
+    /// Five minute window, matching `TokenMetrics::amihud_5m`.
+    fn default() -> Self {
+        Self::new(300)
+    }
+}
+
+impl MetricsCalc {
+    pub fn new(window_secs: i64) -> Self {
+        Self {
+            window_secs,
+            ticks: VecDeque::new(),
+        }
+    }
+
+    pub fn window_secs(&self) -> i64 {
+        self.window_secs
+    }
+
+    /// Change the window; ticks outside it are dropped on the next push.
+    pub fn set_window_secs(&mut self, window_secs: i64) {
+        self.window_secs = window_secs;
+    }
+
+    /// Ingest a tick and evict everything older than the window ending at `ts`.
+    /// Non-finite or non-positive prices are ignored.
+    pub fn push(&mut self, ts: i64, price: f64, volume_usd: f64) {
+        if price.is_finite() && price > 0.0 {
+            self.ticks.push_back(PriceTick {
+                ts,
+                price,
+                volume_usd,
+            });
+        }
+        while let Some(front) = self.ticks.front() {
+            if front.ts <= ts - self.window_secs {
+                self.ticks.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Number of ticks currently in the window.
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    fn pairs(&self) -> impl Iterator<Item = (&PriceTick, &PriceTick)> {
+        self.ticks.iter().zip(self.ticks.iter().skip(1))
+    }
+
+    /// Mean absolute return per USD of volume over the window.
+    pub fn amihud(&self) -> Option<f64> {
+        let (sum, n) = self
+            .pairs()
+            .filter(|(_, cur)| cur.volume_usd > 0.0 && cur.volume_usd.is_finite())
+            .map(|(prev, cur)| (cur.price / prev.price - 1.0).abs() / cur.volume_usd)
+            .fold((0.0, 0usize), |(sum, n), x| (sum + x, n + 1));
+        if n == 0 {
+            None
+        } else {
+            Some(sum / n as f64)
+        }
+    }
+
+    /// Net price move divided by the total path length over the window.
+    pub fn range_efficiency(&self) -> Option<f64> {
+        let first = self.ticks.front()?;
+        let last = self.ticks.back()?;
+        let path: f64 = self
+            .pairs()
+            .map(|(prev, cur)| (cur.price - prev.price).abs())
+            .sum();
+        if path <= 0.0 {
+            return None;
+        }
+        Some((last.price - first.price).abs() / path)
+    }
+}
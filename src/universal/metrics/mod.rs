@@ -0,0 +1,23 @@
+//! Per-mint market metrics (price, liquidity, rolling volume) used by the gates.
+//!
+//! The store is fed either with raw trade ticks (`upsert_tick`) or with
+//! pre-aggregated snapshots (`upsert`), and keeps rolling volume over the
+//! configured primary/secondary windows. Tick-fed mints also get Amihud and
+//! range efficiency over the primary window (see [`metrics_calc`]). Mints the
+//! live feed has not seen can be back-filled from Birdeye REST (see
+//! [`birdeye_rest`]). The live feed itself is the Birdeye WS client in
+//! [`birdeye_ws`]. Replays read time from [`clock`] so windows follow the
+//! recorded timeline.
+
+pub mod birdeye_rest;
+pub mod birdeye_ws;
+pub mod clock;
+pub mod metrics_calc;
+pub mod store;
+pub mod subscriptions;
+pub mod windows;
+
+pub use metrics_calc::*;
+pub use store::*;
+pub use subscriptions::*;
+pub use windows::*;
@@ -0,0 +1,234 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use super::{MetricsCalc, VolumeWindows};
+
+/// Latest known metrics for a single mint.
+///
+/// `vol_5m_usd` / `vol_15m_usd` hold the volume over the configured
+/// primary / secondary windows; they are named after the default 5m/15m
+/// durations so logs and gate reasons stay comparable.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TokenMetrics {
+    pub mint: String,
+    pub price_usd: f64,
+    pub liq_usd: Option<f64>,
+    pub mcap_usd: Option<f64>,
+    pub vol_5m_usd: f64,
+    pub vol_15m_usd: f64,
+    pub amihud_5m: Option<f64>,
+    pub range_eff_5m: Option<f64>,
+    /// Unix timestamp (seconds) of the last update.
+    pub last_update: i64,
+}
+
+/// A single trade observation.
+#[derive(Clone, Copy, Debug)]
+struct Tick {
+    ts: i64,
+    volume_usd: f64,
+}
+
+#[derive(Default)]
+struct MintEntry {
+    metrics: TokenMetrics,
+    ticks: VecDeque<Tick>,
+    calc: MetricsCalc,
+    /// Earliest observation of the mint (unix seconds).
+    first_seen: Option<i64>,
+}
+
+/// Callback invoked with a mint that has no (fresh) metrics.
+pub type MissingHook = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// In-memory metrics store keyed by mint.
+///
+/// Rolling volumes are recomputed on each tick for whichever windows are
+/// configured for that mint, so the gates always read the configured
+/// primary/secondary durations.
+pub struct MetricsStore {
+    windows: VolumeWindows,
+    inner: Mutex<HashMap<String, MintEntry>>,
+    on_missing: RwLock<Option<MissingHook>>,
+}
+
+impl MetricsStore {
+    pub fn new(windows: VolumeWindows) -> Self {
+        Self {
+            windows,
+            inner: Mutex::new(HashMap::new()),
+            on_missing: RwLock::new(None),
+        }
+    }
+
+    /// Register `hook` to be called (outside the store lock) whenever a
+    /// lookup finds no metrics, or only stale ones, for a mint, e.g. to kick
+    /// off a REST refresh. Replaces any previous hook.
+    pub fn on_missing<F>(&self, hook: F)
+    where
+        F: Fn(&str) + Send + Sync + 'static,
+    {
+        let hook: MissingHook = Arc::new(hook);
+        match self.on_missing.write() {
+            Ok(mut guard) => *guard = Some(hook),
+            Err(poisoned) => *poisoned.into_inner() = Some(hook),
+        }
+    }
+
+    fn notify_missing(&self, mint: &str) {
+        let hook = match self.on_missing.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        if let Some(hook) = hook {
+            hook(mint);
+        }
+    }
+
+    pub fn windows(&self) -> &VolumeWindows {
+        &self.windows
+    }
+
+    /// Record a trade tick and refresh the rolling volumes, Amihud and range
+    /// efficiency (over the primary window) for this mint.
+    pub fn upsert_tick(&self, mint: &str, ts: i64, price: f64, volume_usd: f64) {
+        let pair = self.windows.for_mint(mint);
+        let retention = pair.primary_secs.max(pair.secondary_secs);
+
+        let mut guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(_) => return,
+        };
+        let entry = guard.entry(mint.to_string()).or_default();
+
+        entry.ticks.push_back(Tick { ts, volume_usd });
+        while let Some(front) = entry.ticks.front() {
+            if front.ts < ts - retention {
+                entry.ticks.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        entry.metrics.mint = mint.to_string();
+        entry.metrics.price_usd = price;
+        entry.metrics.vol_5m_usd = volume_since(&entry.ticks, ts - pair.primary_secs);
+        entry.metrics.vol_15m_usd = volume_since(&entry.ticks, ts - pair.secondary_secs);
+        entry.calc.set_window_secs(pair.primary_secs);
+        entry.calc.push(ts, price, volume_usd);
+        entry.metrics.amihud_5m = entry.calc.amihud();
+        entry.metrics.range_eff_5m = entry.calc.range_efficiency();
+        entry.metrics.last_update = ts;
+        entry.first_seen = Some(entry.first_seen.map_or(ts, |seen| seen.min(ts)));
+    }
+
+    /// Move the price of `mint` to `price` at `ts` without recording volume
+    /// (e.g. a price candle from the WS feed). Ignored if the mint already
+    /// has a newer update.
+    pub fn record_price(&self, mint: &str, ts: i64, price: f64) {
+        if let Ok(mut guard) = self.inner.lock() {
+            let entry = guard.entry(mint.to_string()).or_default();
+            if entry.first_seen.is_some() && ts < entry.metrics.last_update {
+                return;
+            }
+            entry.metrics.mint = mint.to_string();
+            entry.metrics.price_usd = price;
+            entry.metrics.last_update = ts;
+            entry.first_seen = Some(entry.first_seen.map_or(ts, |seen| seen.min(ts)));
+        }
+    }
+
+    /// Insert or replace a pre-aggregated snapshot (e.g. from a WS or REST feed).
+    pub fn upsert(&self, metrics: TokenMetrics) {
+        if let Ok(mut guard) = self.inner.lock() {
+            let entry = guard.entry(metrics.mint.clone()).or_default();
+            entry.first_seen.get_or_insert(metrics.last_update);
+            entry.metrics = metrics;
+        }
+    }
+
+    /// Latest metrics for a mint, if any. A miss fires the `on_missing` hook.
+    pub fn get(&self, mint: &str) -> Option<TokenMetrics> {
+        let found = {
+            let guard = self.inner.lock().ok()?;
+            guard.get(mint).map(|e| e.metrics.clone())
+        };
+        if found.is_none() {
+            self.notify_missing(mint);
+        }
+        found
+    }
+
+    /// When the store first observed `mint` (unix seconds): its earliest
+    /// tick, or the first snapshot's `last_update`. Forgotten once the mint
+    /// is pruned as stale. Unlike [`get`](Self::get), a miss fires no hook.
+    pub fn first_seen(&self, mint: &str) -> Option<i64> {
+        let guard = self.inner.lock().ok()?;
+        guard.get(mint).and_then(|e| e.first_seen)
+    }
+
+    /// Metrics for a mint updated within `max_age_secs` of `now`; `None` if
+    /// missing or stale (either fires the `on_missing` hook).
+    pub fn get_fresh(&self, mint: &str, max_age_secs: i64, now: i64) -> Option<TokenMetrics> {
+        let fresh = {
+            let guard = self.inner.lock().ok()?;
+            guard
+                .get(mint)
+                .filter(|e| now - e.metrics.last_update <= max_age_secs)
+                .map(|e| e.metrics.clone())
+        };
+        if fresh.is_none() {
+            self.notify_missing(mint);
+        }
+        fresh
+    }
+
+    /// Drop mints not updated within `max_age_secs` of `now`. Returns how
+    /// many were removed.
+    pub fn prune_stale(&self, max_age_secs: i64, now: i64) -> usize {
+        let mut guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(_) => return 0,
+        };
+        let before = guard.len();
+        guard.retain(|_, e| now - e.metrics.last_update <= max_age_secs);
+        before - guard.len()
+    }
+
+    /// Volume (USD) traded for `mint` in the `window_secs` before `now`.
+    pub fn volume_over(&self, mint: &str, window_secs: i64, now: i64) -> f64 {
+        let guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(_) => return 0.0,
+        };
+        guard
+            .get(mint)
+            .map(|e| volume_since(&e.ticks, now - window_secs))
+            .unwrap_or(0.0)
+    }
+
+    /// Number of mints currently tracked.
+    pub fn len(&self) -> usize {
+        self.inner.lock().map(|g| g.len()).unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Process-wide store the live buy path reads; main attaches the Birdeye
+/// WS client and REST fallback to it.
+pub static GLOBAL_METRICS_STORE: Lazy<Arc<MetricsStore>> =
+    Lazy::new(|| Arc::new(MetricsStore::new(VolumeWindows::from_env())));
+
+fn volume_since(ticks: &VecDeque<Tick>, since: i64) -> f64 {
+    ticks
+        .iter()
+        .filter(|t| t.ts > since)
+        .map(|t| t.volume_usd)
+        .sum()
+}
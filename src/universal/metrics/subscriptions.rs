@@ -0,0 +1,130 @@
+//! Refcounted per-mint market-data subscriptions.
+//!
+//! Several consumers (open positions, watchers) may need live metrics for the
+//! same mint. Each takes a reference with [`MetricsSubscriptions::acquire`]
+//! and drops it with [`MetricsSubscriptions::release`]; the attached
+//! [`SubscriptionSink`] (the WS client) is only told to subscribe on the first
+//! reference and to unsubscribe when the last one goes away.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex, RwLock};
+
+use once_cell::sync::Lazy;
+
+/// Market-data feed that can (un)subscribe a mint, e.g. the WS client.
+pub trait SubscriptionSink: Send + Sync {
+    fn subscribe(&self, mint: &str);
+    fn unsubscribe(&self, mint: &str);
+}
+
+/// Per-mint subscription refcounts in front of a [`SubscriptionSink`].
+#[derive(Default)]
+pub struct MetricsSubscriptions {
+    refs: Mutex<HashMap<String, usize>>,
+    sink: RwLock<Option<Arc<dyn SubscriptionSink>>>,
+}
+
+impl MetricsSubscriptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn refs(&self) -> std::sync::MutexGuard<'_, HashMap<String, usize>> {
+        match self.refs.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    fn sink(&self) -> Option<Arc<dyn SubscriptionSink>> {
+        match self.sink.read() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Attach the feed. Mints that already have references are subscribed
+    /// immediately so nothing acquired before startup is missed.
+    pub fn set_sink(&self, sink: Arc<dyn SubscriptionSink>) {
+        let mints = self.subscribed_mints();
+        match self.sink.write() {
+            Ok(mut guard) => *guard = Some(Arc::clone(&sink)),
+            Err(poisoned) => *poisoned.into_inner() = Some(Arc::clone(&sink)),
+        }
+        for mint in mints {
+            sink.subscribe(&mint);
+        }
+    }
+
+    /// Take a reference on `mint`; subscribes it if this is the first one.
+    /// Returns the new refcount.
+    pub fn acquire(&self, mint: &str) -> usize {
+        let count = {
+            let mut refs = self.refs();
+            let count = refs.entry(mint.to_string()).or_insert(0);
+            *count += 1;
+            *count
+        };
+        if count == 1 {
+            if let Some(sink) = self.sink() {
+                sink.subscribe(mint);
+            }
+        }
+        count
+    }
+
+    /// Drop a reference on `mint`; unsubscribes it when the last one goes.
+    /// Returns the remaining refcount (0 also for unknown mints).
+    pub fn release(&self, mint: &str) -> usize {
+        let remaining = {
+            let mut refs = self.refs();
+            match refs.get_mut(mint) {
+                Some(count) if *count > 1 => {
+                    *count -= 1;
+                    *count
+                }
+                Some(_) => {
+                    refs.remove(mint);
+                    0
+                }
+                None => return 0,
+            }
+        };
+        if remaining == 0 {
+            if let Some(sink) = self.sink() {
+                sink.unsubscribe(mint);
+            }
+        }
+        remaining
+    }
+
+    /// Current number of references on `mint`.
+    pub fn refcount(&self, mint: &str) -> usize {
+        self.refs().get(mint).copied().unwrap_or(0)
+    }
+
+    /// Mints with at least one reference, sorted.
+    pub fn subscribed_mints(&self) -> Vec<String> {
+        let mut mints: Vec<String> = self.refs().keys().cloned().collect();
+        mints.sort();
+        mints
+    }
+}
+
+/// Whether opening a position subscribes its mint (AUTO_SUBSCRIBE_POSITIONS,
+/// default true).
+pub fn auto_subscribe_positions() -> bool {
+    let mut enabled = true;
+    if let Ok(v) = env::var("AUTO_SUBSCRIBE_POSITIONS") {
+        if let Ok(parsed) = v.parse::<bool>() {
+            enabled = parsed;
+        }
+    }
+    enabled
+}
+
+/// Process-wide subscriptions; the WS client attaches itself with
+/// [`MetricsSubscriptions::set_sink`].
+pub static GLOBAL_METRICS_SUBSCRIPTIONS: Lazy<Arc<MetricsSubscriptions>> =
+    Lazy::new(|| Arc::new(MetricsSubscriptions::new()));
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::env;
+
+/// Durations (seconds) of the two rolling volume windows the gates read.
+///
+/// The defaults match the classic 5m/15m guard. Very fast tokens may want a
+/// 1m primary window, slower ones a 30m secondary; both can be overridden
+/// globally or per mint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowPair {
+    pub primary_secs: i64,
+    pub secondary_secs: i64,
+}
+
+impl Default for WindowPair {
+    fn default() -> Self {
+        Self {
+            primary_secs: 300,
+            secondary_secs: 900,
+        }
+    }
+}
+
+/// Rolling-volume window configuration with optional per-mint overrides.
+#[derive(Debug, Clone, Default)]
+pub struct VolumeWindows {
+    pub default: WindowPair,
+    per_mint: HashMap<String, WindowPair>,
+}
+
+impl VolumeWindows {
+    pub fn new(default: WindowPair) -> Self {
+        Self {
+            default,
+            per_mint: HashMap::new(),
+        }
+    }
+
+    /// Build from env vars:
+    ///
+    /// - VOL_WINDOW_PRIMARY_SECS   (default 300)
+    /// - VOL_WINDOW_SECONDARY_SECS (default 900)
+    /// - VOL_WINDOW_OVERRIDES      (optional, `mint:primary:secondary,...`)
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = env::var("VOL_WINDOW_PRIMARY_SECS") {
+            if let Ok(parsed) = v.parse::<i64>() {
+                cfg.default.primary_secs = parsed.max(1);
+            }
+        }
+
+        if let Ok(v) = env::var("VOL_WINDOW_SECONDARY_SECS") {
+            if let Ok(parsed) = v.parse::<i64>() {
+                cfg.default.secondary_secs = parsed.max(1);
+            }
+        }
+
+        if let Ok(v) = env::var("VOL_WINDOW_OVERRIDES") {
+            for entry in v.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+                let parts: Vec<&str> = entry.split(':').collect();
+                if parts.len() != 3 {
+                    eprintln!("Ignoring malformed VOL_WINDOW_OVERRIDES entry: {entry}");
+                    continue;
+                }
+                match (parts[1].parse::<i64>(), parts[2].parse::<i64>()) {
+                    (Ok(p), Ok(s)) if p > 0 && s > 0 => {
+                        cfg.set_for_mint(
+                            parts[0],
+                            WindowPair {
+                                primary_secs: p,
+                                secondary_secs: s,
+                            },
+                        );
+                    }
+                    _ => eprintln!("Ignoring malformed VOL_WINDOW_OVERRIDES entry: {entry}"),
+                }
+            }
+        }
+
+        cfg
+    }
+
+    /// Override the windows for a single mint.
+    pub fn set_for_mint(&mut self, mint: &str, pair: WindowPair) {
+        self.per_mint.insert(mint.to_string(), pair);
+    }
+
+    /// Windows to use for this mint (override or global default).
+    pub fn for_mint(&self, mint: &str) -> WindowPair {
+        self.per_mint.get(mint).copied().unwrap_or(self.default)
+    }
+
+    /// Longest window configured anywhere; ticks older than this can be dropped.
+    pub fn max_retention_secs(&self) -> i64 {
+        self.per_mint
+            .values()
+            .chain(std::iter::once(&self.default))
+            .map(|p| p.primary_secs.max(p.secondary_secs))
+            .max()
+            .unwrap_or(900)
+    }
+}
@@ -1,11 +1,572 @@
 
+use std::collections::VecDeque;
+use std::env;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_client::rpc_config::{RpcBlockConfig, RpcTransactionConfig};
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::signature::Signature;
+use once_cell::sync::Lazy;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{
+    TransactionDetails, UiTransactionEncoding, UiTransactionStatusMeta, UiTransactionTokenBalance,
+};
+
+use crate::common::bot_config;
+
 /// MEV protection placeholder. In production, integrate zeroslot & priority fee tuning.
+///
+/// This bounds tips expressed in bps of the trade; the absolute lamport caps on
+/// the compute-unit price and landing tip live in
+/// [`MevConfig`](crate::universal::mev_submit::MevConfig) and are applied in the
+/// send path on top of this bound.
 pub struct MevProtection {
     pub tip_bps_cap: u64,
+    /// Absolute tip cap in lamports, whatever the trade size.
+    pub tip_lamports_cap: u64,
 }
 
 impl MevProtection {
     pub fn bound_tip_bps(&self, proposed_bps: u64) -> u64 {
         proposed_bps.min(self.tip_bps_cap)
     }
+
+    /// Cap a tip at `tip_bps_cap` of the trade notional or `tip_lamports_cap`,
+    /// whichever is smaller.
+    pub fn bound_tip_lamports(&self, proposed_lamports: u64, trade_notional_lamports: u64) -> u64 {
+        let notional_cap =
+            (trade_notional_lamports as u128 * self.tip_bps_cap as u128 / 10_000) as u64;
+        proposed_lamports
+            .min(notional_cap)
+            .min(self.tip_lamports_cap)
+    }
+}
+
+/// Which side of the pool a transaction traded on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Our own confirmed fill, as observed after the buy landed.
+#[derive(Debug, Clone)]
+pub struct FillObservation {
+    pub mint: String,
+    pub side: TradeSide,
+    /// Effective price we paid (quote per token).
+    pub price: f64,
+    /// Position of our transaction inside the block.
+    pub tx_index: usize,
+}
+
+/// The quote we expected before sending.
+#[derive(Debug, Clone)]
+pub struct QuoteSnapshot {
+    /// Quoted price (quote per token) at decision time.
+    pub price: f64,
+}
+
+/// A transaction in the same block touching the same pool.
+#[derive(Debug, Clone)]
+pub struct BlockTx {
+    pub signer: String,
+    pub mint: String,
+    pub side: TradeSide,
+    pub tx_index: usize,
+}
+
+/// Outcome of post-trade sandwich analysis.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SandwichVerdict {
+    /// Fill was within tolerance of the quote.
+    Clean,
+    /// Fill was materially worse than quoted but no bracketing pair was found.
+    AdverseFill { adverse_bps: f64 },
+    /// One signer traded in front of us and back out right after.
+    Sandwiched {
+        attacker: String,
+        front_index: usize,
+        back_index: usize,
+        adverse_bps: f64,
+    },
+}
+
+impl SandwichVerdict {
+    pub fn is_sandwiched(&self) -> bool {
+        matches!(self, SandwichVerdict::Sandwiched { .. })
+    }
+}
+
+/// Configuration for post-trade sandwich detection.
+#[derive(Debug, Clone)]
+pub struct SandwichConfig {
+    pub enabled: bool,
+    /// Fill worse than the quote by at least this many bps is considered adverse.
+    pub min_adverse_bps: f64,
+    /// Number of sandwiches before recommending private/jito landing.
+    pub escalate_after: u32,
+}
+
+impl Default for SandwichConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_adverse_bps: 150.0,
+            escalate_after: 1,
+        }
+    }
+}
+
+impl SandwichConfig {
+    /// Build config from env vars:
+    ///
+    /// - SANDWICH_DETECT          (default 1)
+    /// - SANDWICH_MIN_ADVERSE_BPS (default 150)
+    /// - SANDWICH_ESCALATE_AFTER  (default 1)
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = bot_config::var("SANDWICH_DETECT") {
+            cfg.enabled = v != "0" && !v.eq_ignore_ascii_case("false");
+        }
+
+        if let Ok(v) = bot_config::var("SANDWICH_MIN_ADVERSE_BPS") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.min_adverse_bps = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("SANDWICH_ESCALATE_AFTER") {
+            if let Ok(parsed) = v.parse::<u32>() {
+                cfg.escalate_after = parsed.max(1);
+            }
+        }
+
+        cfg
+    }
+}
+
+/// Post-trade sandwich detector.
+///
+/// Compares our fill against the pre-trade quote and looks for a single
+/// signer that traded the same mint in our direction right before us and
+/// in the opposite direction right after us. Verdicts are counted so the
+/// landing decision can escalate to a private/jito route next time.
+pub struct SandwichDetector {
+    cfg: SandwichConfig,
+    sandwiches_seen: AtomicU32,
+}
+
+impl SandwichDetector {
+    pub fn new(cfg: SandwichConfig) -> Self {
+        Self {
+            cfg,
+            sandwiches_seen: AtomicU32::new(0),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        Self::new(SandwichConfig::from_env())
+    }
+
+    /// How much worse than the quote we filled, in bps (positive = worse).
+    pub fn adverse_bps(fill: &FillObservation, quote: &QuoteSnapshot) -> f64 {
+        if quote.price <= 0.0 {
+            return 0.0;
+        }
+        let diff = match fill.side {
+            TradeSide::Buy => fill.price - quote.price,
+            TradeSide::Sell => quote.price - fill.price,
+        };
+        diff / quote.price * 10_000.0
+    }
+
+    pub fn analyze(
+        &self,
+        fill: &FillObservation,
+        quote: &QuoteSnapshot,
+        block_txs: &[BlockTx],
+    ) -> SandwichVerdict {
+        if !self.cfg.enabled {
+            return SandwichVerdict::Clean;
+        }
+
+        let adverse_bps = Self::adverse_bps(fill, quote);
+        if adverse_bps < self.cfg.min_adverse_bps {
+            return SandwichVerdict::Clean;
+        }
+
+        let opposite = match fill.side {
+            TradeSide::Buy => TradeSide::Sell,
+            TradeSide::Sell => TradeSide::Buy,
+        };
+
+        // Closest front-runner first: the tightest bracket is the most telling.
+        let mut fronts: Vec<&BlockTx> = block_txs
+            .iter()
+            .filter(|t| t.mint == fill.mint && t.side == fill.side && t.tx_index < fill.tx_index)
+            .collect();
+        fronts.sort_by(|a, b| b.tx_index.cmp(&a.tx_index));
+
+        for front in fronts {
+            let back = block_txs
+                .iter()
+                .filter(|t| {
+                    t.mint == fill.mint
+                        && t.side == opposite
+                        && t.signer == front.signer
+                        && t.tx_index > fill.tx_index
+                })
+                .min_by_key(|t| t.tx_index);
+
+            if let Some(back) = back {
+                let verdict = SandwichVerdict::Sandwiched {
+                    attacker: front.signer.clone(),
+                    front_index: front.tx_index,
+                    back_index: back.tx_index,
+                    adverse_bps,
+                };
+                self.sandwiches_seen.fetch_add(1, Ordering::Relaxed);
+                return verdict;
+            }
+        }
+
+        SandwichVerdict::AdverseFill { adverse_bps }
+    }
+
+    /// Number of sandwiches detected so far.
+    pub fn sandwiches_seen(&self) -> u32 {
+        self.sandwiches_seen.load(Ordering::Relaxed)
+    }
+
+    /// Whether the landing decision should escalate to a private/jito route.
+    pub fn prefer_private_landing(&self) -> bool {
+        self.cfg.enabled && self.sandwiches_seen() >= self.cfg.escalate_after
+    }
+
+    /// Clear the sandwich counter (e.g. after switching landing mode).
+    pub fn reset(&self) {
+        self.sandwiches_seen.store(0, Ordering::Relaxed);
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.cfg.enabled
+    }
+}
+
+/// Process-wide detector fed with our confirmed buys. Once it prefers private
+/// landing, [`landing_mode_for_wallet`](crate::common::config::landing_mode_for_wallet)
+/// escalates copies to zeroslot.
+pub static SANDWICH_DETECTOR: Lazy<SandwichDetector> = Lazy::new(SandwichDetector::from_env);
+
+/// Net change of `owner`'s `mint` balance in a transaction, in UI units.
+fn token_delta(meta: &UiTransactionStatusMeta, owner: &str, mint: &str) -> f64 {
+    let total = |balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>| -> f64 {
+        match balances {
+            OptionSerializer::Some(balances) => balances
+                .iter()
+                .filter(|b| b.mint == mint)
+                .filter(|b| matches!(&b.owner, OptionSerializer::Some(o) if o == owner))
+                .filter_map(|b| b.ui_token_amount.ui_amount)
+                .sum(),
+            _ => 0.0,
+        }
+    };
+    total(&meta.post_token_balances) - total(&meta.pre_token_balances)
+}
+
+/// Run [`SANDWICH_DETECTOR`] on our confirmed buy of `mint`.
+///
+/// The fill price is the `amount_in` SOL we committed over the tokens
+/// `wallet` actually received, compared with `quote_price` (SOL per token).
+/// Only an adverse fill pulls the block to look for a bracketing pair.
+/// `None` when the transaction or block can't be fetched.
+pub async fn analyze_confirmed_buy(
+    rpc: &RpcClient,
+    signature: &str,
+    wallet: &str,
+    mint: &str,
+    amount_in: f64,
+    quote_price: f64,
+) -> Option<SandwichVerdict> {
+    let sig = Signature::from_str(signature).ok()?;
+    let ours = rpc
+        .get_transaction_with_config(
+            &sig,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await
+        .ok()?;
+    let received = token_delta(ours.transaction.meta.as_ref()?, wallet, mint);
+    if received <= 0.0 {
+        return None;
+    }
+    let mut fill = FillObservation {
+        mint: mint.to_string(),
+        side: TradeSide::Buy,
+        price: amount_in / received,
+        tx_index: 0,
+    };
+    let quote = QuoteSnapshot { price: quote_price };
+    let verdict = SANDWICH_DETECTOR.analyze(&fill, &quote, &[]);
+    if verdict == SandwichVerdict::Clean {
+        return Some(verdict);
+    }
+
+    let block = rpc
+        .get_block_with_config(
+            ours.slot,
+            RpcBlockConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                transaction_details: Some(TransactionDetails::Full),
+                rewards: Some(false),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await
+        .ok()?;
+    let mut our_index = None;
+    let mut block_txs = Vec::new();
+    for (tx_index, tx) in block
+        .transactions
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+    {
+        let (Some(decoded), Some(meta)) = (tx.transaction.decode(), tx.meta) else {
+            continue;
+        };
+        if decoded.signatures.first() == Some(&sig) {
+            our_index = Some(tx_index);
+            continue;
+        }
+        let Some(signer) = decoded.message.static_account_keys().first() else {
+            continue;
+        };
+        let signer = signer.to_string();
+        let delta = token_delta(&meta, &signer, mint);
+        if delta != 0.0 {
+            block_txs.push(BlockTx {
+                signer,
+                mint: mint.to_string(),
+                side: if delta > 0.0 {
+                    TradeSide::Buy
+                } else {
+                    TradeSide::Sell
+                },
+                tx_index,
+            });
+        }
+    }
+    fill.tx_index = our_index?;
+    Some(SANDWICH_DETECTOR.analyze(&fill, &quote, &block_txs))
+}
+
+/// Configuration for the adaptive landing tip.
+#[derive(Debug, Clone)]
+pub struct AdaptiveTipConfig {
+    pub enabled: bool,
+    /// Number of most recent landing outcomes considered.
+    pub window: usize,
+    /// Outcomes required before the tip is adjusted.
+    pub min_samples: usize,
+    /// Raise the tip when the land rate falls below this (0..1).
+    pub target_land_rate: f64,
+    /// Lower the tip when the land rate is at or above this (0..1).
+    pub relax_land_rate: f64,
+    /// Relative step applied per adjustment, in percent.
+    pub step_pct: f64,
+    /// Floor for the tip (SOL).
+    pub min_tip: f64,
+    /// MEV cap: the tip is never raised above this (SOL).
+    pub max_tip: f64,
+}
+
+impl Default for AdaptiveTipConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: 20,
+            min_samples: 5,
+            target_land_rate: 0.8,
+            relax_land_rate: 0.95,
+            step_pct: 25.0,
+            min_tip: 0.0001,
+            max_tip: 0.01,
+        }
+    }
+}
+
+impl AdaptiveTipConfig {
+    /// Build config from env vars:
+    ///
+    /// - ADAPTIVE_TIP              (default 0)
+    /// - ADAPTIVE_TIP_WINDOW       (default 20)
+    /// - ADAPTIVE_TIP_MIN_SAMPLES  (default 5)
+    /// - ADAPTIVE_TIP_TARGET_RATE  (default 0.8)
+    /// - ADAPTIVE_TIP_RELAX_RATE   (default 0.95)
+    /// - ADAPTIVE_TIP_STEP_PCT     (default 25)
+    /// - ADAPTIVE_TIP_MIN          (default 0.0001 SOL)
+    /// - MEV_TIP_CAP               (default 0.01 SOL)
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = bot_config::var("ADAPTIVE_TIP") {
+            cfg.enabled = v == "1" || v.eq_ignore_ascii_case("true");
+        }
+
+        if let Ok(v) = bot_config::var("ADAPTIVE_TIP_WINDOW") {
+            if let Ok(parsed) = v.parse::<usize>() {
+                cfg.window = parsed.max(1);
+            }
+        }
+
+        if let Ok(v) = bot_config::var("ADAPTIVE_TIP_MIN_SAMPLES") {
+            if let Ok(parsed) = v.parse::<usize>() {
+                cfg.min_samples = parsed.max(1);
+            }
+        }
+
+        if let Ok(v) = bot_config::var("ADAPTIVE_TIP_TARGET_RATE") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.target_land_rate = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("ADAPTIVE_TIP_RELAX_RATE") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.relax_land_rate = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("ADAPTIVE_TIP_STEP_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.step_pct = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("ADAPTIVE_TIP_MIN") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.min_tip = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("MEV_TIP_CAP") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.max_tip = parsed;
+            }
+        }
+
+        cfg
+    }
+}
+
+#[derive(Debug)]
+struct TipState {
+    tip: f64,
+    outcomes: VecDeque<bool>,
+}
+
+/// Landing tip that follows the recent land success rate.
+///
+/// Each landing outcome is recorded into a rolling window. Once the window
+/// holds `min_samples` outcomes, a land rate below `target_land_rate` raises
+/// the tip by `step_pct` and a rate at or above `relax_land_rate` lowers it,
+/// always within `[min_tip, max_tip]`. The window is cleared after each
+/// adjustment so the next one is based on outcomes at the new tip.
+#[derive(Debug)]
+pub struct AdaptiveTip {
+    cfg: AdaptiveTipConfig,
+    state: Mutex<TipState>,
+}
+
+impl AdaptiveTip {
+    pub fn new(cfg: AdaptiveTipConfig, initial_tip: f64) -> Self {
+        let tip = initial_tip.max(cfg.min_tip).min(cfg.max_tip);
+        Self {
+            cfg,
+            state: Mutex::new(TipState {
+                tip,
+                outcomes: VecDeque::new(),
+            }),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TipState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Current tip (SOL).
+    pub fn current_tip(&self) -> f64 {
+        self.lock().tip
+    }
+
+    /// Land rate over the current window, if anything has been recorded.
+    pub fn land_rate(&self) -> Option<f64> {
+        let state = self.lock();
+        if state.outcomes.is_empty() {
+            return None;
+        }
+        let landed = state.outcomes.iter().filter(|&&l| l).count();
+        Some(landed as f64 / state.outcomes.len() as f64)
+    }
+
+    /// Record whether a tipped transaction landed; returns the tip to use next.
+    pub fn record(&self, landed: bool) -> f64 {
+        let mut state = self.lock();
+        state.outcomes.push_back(landed);
+        while state.outcomes.len() > self.cfg.window {
+            state.outcomes.pop_front();
+        }
+        if state.outcomes.len() < self.cfg.min_samples {
+            return state.tip;
+        }
+
+        let landed = state.outcomes.iter().filter(|&&l| l).count();
+        let rate = landed as f64 / state.outcomes.len() as f64;
+        let step = self.cfg.step_pct / 100.0;
+        let next = if rate < self.cfg.target_land_rate {
+            state.tip * (1.0 + step)
+        } else if rate >= self.cfg.relax_land_rate {
+            state.tip * (1.0 - step)
+        } else {
+            return state.tip;
+        };
+        state.tip = next.max(self.cfg.min_tip).min(self.cfg.max_tip);
+        state.outcomes.clear();
+        state.tip
+    }
+}
+
+/// Process-wide adaptive tip, seeded from ZERO_SLOT_TIP_VALUE; `None` when
+/// ADAPTIVE_TIP is off.
+pub static ADAPTIVE_TIP: Lazy<Option<AdaptiveTip>> = Lazy::new(|| {
+    let cfg = AdaptiveTipConfig::from_env();
+    if !cfg.enabled {
+        return None;
+    }
+    let initial = env::var("ZERO_SLOT_TIP_VALUE")
+        .ok()
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(cfg.min_tip);
+    Some(AdaptiveTip::new(cfg, initial))
+});
+
+/// Record a tipped landing outcome with the global adaptive tip, if enabled.
+pub fn record_land_outcome(landed: bool) {
+    if let Some(tip) = ADAPTIVE_TIP.as_ref() {
+        tip.record(landed);
+    }
 }
@@ -0,0 +1,354 @@
+//! MEV-protected transaction submission.
+//!
+//! [`submit_with_protection`] checks a signed transaction against the
+//! [`MevConfig`] caps (compute-unit price and landing tip) and then routes it
+//! through a [`RawSubmit`] implementor: the public RPC, a private RPC
+//! (PRIVATE_RPC_URL) or a Jito bundle. [`SolanaSubmitter`] is the concrete
+//! implementor used in production.
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use solana_sdk::compute_budget;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+
+use crate::common::bot_config;
+
+const DEFAULT_JITO_BLOCK_ENGINE_URL: &str = "https://mainnet.block-engine.jito.wtf";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Jito's mainnet tip accounts.
+pub const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+
+/// Where a protected transaction is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LandingRoute {
+    /// The regular RPC node (public mempool-equivalent path).
+    Rpc,
+    /// A private RPC that forwards straight to the leader.
+    Private,
+    /// A single-transaction Jito bundle.
+    JitoBundle,
+}
+
+impl FromStr for LandingRoute {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "rpc" => Ok(LandingRoute::Rpc),
+            "private" => Ok(LandingRoute::Private),
+            "jito" | "jito_bundle" => Ok(LandingRoute::JitoBundle),
+            other => Err(format!("unknown MEV route '{}'", other)),
+        }
+    }
+}
+
+/// Caps and routing for protected submission.
+#[derive(Debug, Clone)]
+pub struct MevConfig {
+    pub route: LandingRoute,
+    /// Highest compute-unit price allowed, in micro-lamports per CU (the unit
+    /// of `SetComputeUnitPrice`). 0 = no cap.
+    pub cu_price_max_lamports: u64,
+    /// Highest total tip to `tip_accounts` allowed, in lamports. 0 = no cap.
+    pub tip_cap_lamports: u64,
+    /// Accounts whose incoming transfers count as a landing tip.
+    pub tip_accounts: Vec<Pubkey>,
+    pub private_rpc_url: Option<String>,
+    pub jito_block_engine_url: String,
+}
+
+impl Default for MevConfig {
+    fn default() -> Self {
+        Self {
+            route: LandingRoute::Rpc,
+            cu_price_max_lamports: 0,
+            tip_cap_lamports: 0,
+            tip_accounts: JITO_TIP_ACCOUNTS
+                .iter()
+                .filter_map(|a| Pubkey::from_str(a).ok())
+                .collect(),
+            private_rpc_url: None,
+            jito_block_engine_url: DEFAULT_JITO_BLOCK_ENGINE_URL.to_string(),
+        }
+    }
+}
+
+impl MevConfig {
+    /// Build config from env vars:
+    ///
+    /// - MEV_ROUTE                  (rpc | private | jito, default rpc)
+    /// - MEV_CU_PRICE_MAX_LAMPORTS  (default 0 = no cap)
+    /// - MEV_TIP_CAP_LAMPORTS       (default 0 = no cap)
+    /// - PRIVATE_RPC_URL            (required for the private route)
+    /// - JITO_BLOCK_ENGINE_URL      (default mainnet block engine)
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = bot_config::var("MEV_ROUTE") {
+            match v.parse::<LandingRoute>() {
+                Ok(route) => cfg.route = route,
+                Err(e) => eprintln!("MEV_ROUTE: {}; using rpc", e),
+            }
+        }
+
+        if let Ok(v) = bot_config::var("MEV_CU_PRICE_MAX_LAMPORTS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                cfg.cu_price_max_lamports = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("MEV_TIP_CAP_LAMPORTS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                cfg.tip_cap_lamports = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("PRIVATE_RPC_URL") {
+            if !v.trim().is_empty() {
+                cfg.private_rpc_url = Some(v.trim().to_string());
+            }
+        }
+
+        if let Ok(v) = bot_config::var("JITO_BLOCK_ENGINE_URL") {
+            if !v.trim().is_empty() {
+                cfg.jito_block_engine_url = v.trim().trim_end_matches('/').to_string();
+            }
+        }
+
+        cfg
+    }
+
+    /// Compute-unit price to actually set, given the one we'd like.
+    pub fn clamp_cu_price(&self, price: u64) -> u64 {
+        clamp_to_cap(price, self.cu_price_max_lamports)
+    }
+
+    /// Tip to actually pay (lamports), given the one we'd like.
+    pub fn clamp_tip_lamports(&self, lamports: u64) -> u64 {
+        clamp_to_cap(lamports, self.tip_cap_lamports)
+    }
+}
+
+/// `value` limited to `cap`; a cap of 0 means no cap.
+pub fn clamp_to_cap(value: u64, cap: u64) -> u64 {
+    if cap == 0 {
+        value
+    } else {
+        value.min(cap)
+    }
+}
+
+/// Process-wide MEV config, read once from the environment.
+pub static MEV_CONFIG: Lazy<MevConfig> = Lazy::new(MevConfig::from_env);
+
+/// Compute-unit price (micro-lamports per CU) set by the transaction's
+/// `SetComputeUnitPrice` instruction, if it has one.
+pub fn compute_unit_price(tx: &Transaction) -> Option<u64> {
+    let keys = &tx.message.account_keys;
+    tx.message.instructions.iter().find_map(|ix| {
+        let program = keys.get(ix.program_id_index as usize)?;
+        // Borsh enum tag 3 = SetComputeUnitPrice(u64).
+        if *program != compute_budget::id() || ix.data.len() < 9 || ix.data[0] != 3 {
+            return None;
+        }
+        let mut raw = [0u8; 8];
+        raw.copy_from_slice(&ix.data[1..9]);
+        Some(u64::from_le_bytes(raw))
+    })
+}
+
+/// Total lamports the transaction transfers to any of `tip_accounts`.
+pub fn tip_lamports(tx: &Transaction, tip_accounts: &[Pubkey]) -> u64 {
+    let keys = &tx.message.account_keys;
+    tx.message
+        .instructions
+        .iter()
+        .filter_map(|ix| {
+            let program = keys.get(ix.program_id_index as usize)?;
+            // Bincode enum tag 2 = SystemInstruction::Transfer { lamports }.
+            if *program != system_program::id() || ix.data.len() < 12 || ix.data[..4] != [2, 0, 0, 0] {
+                return None;
+            }
+            let to = keys.get(*ix.accounts.get(1)? as usize)?;
+            if !tip_accounts.contains(to) {
+                return None;
+            }
+            let mut raw = [0u8; 8];
+            raw.copy_from_slice(&ix.data[4..12]);
+            Some(u64::from_le_bytes(raw))
+        })
+        .sum()
+}
+
+/// Reject `tx` if its compute-unit price or tip exceeds the configured caps.
+pub fn check_mev_limits(cfg: &MevConfig, tx: &Transaction) -> Result<()> {
+    if cfg.cu_price_max_lamports > 0 {
+        if let Some(price) = compute_unit_price(tx) {
+            if price > cfg.cu_price_max_lamports {
+                return Err(anyhow!(
+                    "mev: compute-unit price {} exceeds cap {}",
+                    price,
+                    cfg.cu_price_max_lamports
+                ));
+            }
+        }
+    }
+    if cfg.tip_cap_lamports > 0 {
+        let tip = tip_lamports(tx, &cfg.tip_accounts);
+        if tip > cfg.tip_cap_lamports {
+            return Err(anyhow!(
+                "mev: tip {} lamports exceeds cap {}",
+                tip,
+                cfg.tip_cap_lamports
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Raw send paths behind [`submit_with_protection`].
+pub trait RawSubmit: Send + Sync {
+    type Sig: Send;
+
+    fn submit_via_rpc<'a>(&'a self, tx: &'a Transaction) -> BoxFuture<'a, Result<Self::Sig>>;
+
+    fn submit_via_private<'a>(&'a self, tx: &'a Transaction) -> BoxFuture<'a, Result<Self::Sig>>;
+
+    /// Send `txs` as one bundle; resolves to the bundle id.
+    fn submit_via_jito_bundle<'a>(&'a self, txs: &'a [Transaction]) -> BoxFuture<'a, Result<String>>;
+}
+
+/// What a protected submission returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submitted<S> {
+    Signature(S),
+    Bundle { bundle_id: String },
+}
+
+/// Enforce the [`MevConfig`] caps on `tx`, then send it on the configured
+/// route. Nothing is sent if a cap is exceeded.
+pub async fn submit_with_protection<S: RawSubmit + ?Sized>(
+    submitter: &S,
+    cfg: &MevConfig,
+    tx: &Transaction,
+) -> Result<Submitted<S::Sig>> {
+    check_mev_limits(cfg, tx)?;
+    match cfg.route {
+        LandingRoute::Rpc => submitter.submit_via_rpc(tx).await.map(Submitted::Signature),
+        LandingRoute::Private => submitter.submit_via_private(tx).await.map(Submitted::Signature),
+        LandingRoute::JitoBundle => {
+            let bundle_id = submitter.submit_via_jito_bundle(std::slice::from_ref(tx)).await?;
+            Ok(Submitted::Bundle { bundle_id })
+        }
+    }
+}
+
+/// [`RawSubmit`] over the nonblocking RPC client, a private RPC endpoint and
+/// the Jito block engine.
+pub struct SolanaSubmitter {
+    rpc: Arc<RpcClient>,
+    http: reqwest::Client,
+    private_rpc_url: Option<String>,
+    jito_block_engine_url: String,
+}
+
+impl SolanaSubmitter {
+    pub fn new(rpc: Arc<RpcClient>, cfg: &MevConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            rpc,
+            http,
+            private_rpc_url: cfg.private_rpc_url.clone(),
+            jito_block_engine_url: cfg.jito_block_engine_url.clone(),
+        }
+    }
+
+    /// POST a JSON-RPC call and return its string `result`.
+    async fn json_rpc(&self, url: &str, method: &str, params: Value) -> Result<String> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let resp: Value = self
+            .http
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| anyhow!("mev: {} to {} failed: {}", method, url, e))?
+            .json()
+            .await
+            .map_err(|e| anyhow!("mev: invalid {} response from {}: {}", method, url, e))?;
+        if let Some(err) = resp.get("error") {
+            return Err(anyhow!("mev: {} rejected by {}: {}", method, url, err));
+        }
+        resp.get("result")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("mev: {} response from {} has no result", method, url))
+    }
+}
+
+fn encode_tx(tx: &Transaction) -> Result<String> {
+    let wire = bincode::serialize(tx).map_err(|e| anyhow!("mev: transaction serialization failed: {}", e))?;
+    Ok(bs64::encode(&wire))
+}
+
+impl RawSubmit for SolanaSubmitter {
+    type Sig = Signature;
+
+    fn submit_via_rpc<'a>(&'a self, tx: &'a Transaction) -> BoxFuture<'a, Result<Signature>> {
+        Box::pin(async move {
+            self.rpc
+                .send_transaction(tx)
+                .await
+                .map_err(|e| anyhow!("mev: rpc send failed: {}", e))
+        })
+    }
+
+    fn submit_via_private<'a>(&'a self, tx: &'a Transaction) -> BoxFuture<'a, Result<Signature>> {
+        Box::pin(async move {
+            let url = self
+                .private_rpc_url
+                .as_deref()
+                .ok_or_else(|| anyhow!("mev: private route selected but PRIVATE_RPC_URL is not set"))?;
+            let params = json!([encode_tx(tx)?, { "encoding": "base64", "skipPreflight": true }]);
+            let sig = self.json_rpc(url, "sendTransaction", params).await?;
+            Signature::from_str(&sig).map_err(|e| anyhow!("mev: invalid signature '{}': {}", sig, e))
+        })
+    }
+
+    fn submit_via_jito_bundle<'a>(&'a self, txs: &'a [Transaction]) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let encoded = txs.iter().map(encode_tx).collect::<Result<Vec<_>>>()?;
+            let url = format!("{}/api/v1/bundles", self.jito_block_engine_url);
+            self.json_rpc(&url, "sendBundle", json!([encoded, { "encoding": "base64" }]))
+                .await
+        })
+    }
+}
@@ -0,0 +1,235 @@
+//! Per-mint slippage learned from send failures.
+//!
+//! Some mints simply need more room than the configured slippage (thin
+//! pools, heavy tax, fast movers). Instead of burning fees on every retry,
+//! we remember mints that keep failing and bump their slippage in bounded
+//! steps. Learned overrides are persisted to a small JSON file so they
+//! survive restarts.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for learned per-mint slippage.
+#[derive(Debug, Clone)]
+pub struct MintSlippageConfig {
+    /// Master switch; when false the book never bumps and callers use the
+    /// configured slippage unchanged.
+    pub enabled: bool,
+    /// Consecutive failures on a mint before its slippage is bumped.
+    pub failure_threshold: u32,
+    /// How much to add (in bps) each time the threshold is hit.
+    pub step_bps: u64,
+    /// Hard upper bound (in bps) for any learned slippage.
+    pub max_bps: u64,
+    /// Where learned overrides are persisted. `None` disables persistence.
+    pub path: Option<PathBuf>,
+}
+
+impl Default for MintSlippageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            failure_threshold: 2,
+            step_bps: 200,
+            max_bps: 3_000,
+            path: Some(PathBuf::from("./mint_slippage.json")),
+        }
+    }
+}
+
+impl MintSlippageConfig {
+    /// Build config from env vars:
+    ///
+    /// - MINT_SLIPPAGE_LEARNING          (default 0, set to 1 to enable)
+    /// - MINT_SLIPPAGE_FAILURE_THRESHOLD (default 2)
+    /// - MINT_SLIPPAGE_STEP_BPS          (default 200)
+    /// - MINT_SLIPPAGE_MAX_BPS           (default 3000)
+    /// - MINT_SLIPPAGE_PATH              (default ./mint_slippage.json, empty disables)
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = env::var("MINT_SLIPPAGE_LEARNING") {
+            cfg.enabled = v == "1" || v.eq_ignore_ascii_case("true");
+        }
+
+        if let Ok(v) = env::var("MINT_SLIPPAGE_FAILURE_THRESHOLD") {
+            if let Ok(parsed) = v.parse::<u32>() {
+                cfg.failure_threshold = parsed.max(1);
+            }
+        }
+
+        if let Ok(v) = env::var("MINT_SLIPPAGE_STEP_BPS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                cfg.step_bps = parsed;
+            }
+        }
+
+        if let Ok(v) = env::var("MINT_SLIPPAGE_MAX_BPS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                cfg.max_bps = parsed.min(10_000);
+            }
+        }
+
+        if let Ok(v) = env::var("MINT_SLIPPAGE_PATH") {
+            cfg.path = if v.trim().is_empty() {
+                None
+            } else {
+                Some(PathBuf::from(v.trim()))
+            };
+        }
+
+        cfg
+    }
+}
+
+/// Learned state for a single mint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MintSlippageEntry {
+    /// Learned slippage floor in bps for this mint.
+    pub slippage_bps: u64,
+    /// Failures seen since the last bump or success.
+    #[serde(default)]
+    pub consecutive_failures: u32,
+}
+
+/// Book of learned per-mint slippage overrides.
+///
+/// All methods are synchronous and cheap; persistence happens only when a
+/// mint's slippage is actually bumped.
+pub struct MintSlippageBook {
+    cfg: MintSlippageConfig,
+    inner: Mutex<HashMap<String, MintSlippageEntry>>,
+}
+
+impl MintSlippageBook {
+    /// Create a book and load any previously persisted overrides.
+    pub fn new(cfg: MintSlippageConfig) -> Self {
+        let entries = cfg.path.as_deref().map(load_entries).unwrap_or_default();
+
+        Self {
+            cfg,
+            inner: Mutex::new(entries),
+        }
+    }
+
+    pub fn config(&self) -> &MintSlippageConfig {
+        &self.cfg
+    }
+
+    /// Slippage (bps) to use for this mint: the larger of the configured
+    /// value and the learned floor, capped at `max_bps`.
+    ///
+    /// If learning is disabled, `configured_bps` is returned unchanged.
+    pub fn effective_slippage_bps(&self, mint: &str, configured_bps: u64) -> u64 {
+        if !self.cfg.enabled {
+            return configured_bps;
+        }
+
+        let learned = self.learned_slippage_bps(mint).unwrap_or(0);
+        if learned > configured_bps {
+            learned.min(self.cfg.max_bps.max(configured_bps))
+        } else {
+            configured_bps
+        }
+    }
+
+    /// Learned slippage floor for a mint, if any.
+    pub fn learned_slippage_bps(&self, mint: &str) -> Option<u64> {
+        let guard = self.inner.lock().ok()?;
+        guard.get(mint).map(|e| e.slippage_bps)
+    }
+
+    /// Record a failed send for `mint` at `used_bps`.
+    ///
+    /// Once `failure_threshold` consecutive failures accumulate, the learned
+    /// slippage becomes `used_bps + step_bps` (bounded by `max_bps`) and the
+    /// book is persisted. Returns the new learned value when a bump happened.
+    pub fn record_failure(&self, mint: &str, used_bps: u64) -> Option<u64> {
+        if !self.cfg.enabled {
+            return None;
+        }
+
+        let bumped = {
+            let mut guard = self.inner.lock().ok()?;
+            let entry = guard.entry(mint.to_string()).or_default();
+            entry.consecutive_failures += 1;
+
+            if entry.consecutive_failures < self.cfg.failure_threshold {
+                return None;
+            }
+
+            entry.consecutive_failures = 0;
+            let base = entry.slippage_bps.max(used_bps);
+            let next = base.saturating_add(self.cfg.step_bps).min(self.cfg.max_bps);
+            if next <= entry.slippage_bps {
+                return None;
+            }
+            entry.slippage_bps = next;
+            next
+        };
+
+        self.persist();
+        Some(bumped)
+    }
+
+    /// Record a successful send; clears the failure streak but keeps the
+    /// learned floor so the mint continues to get the extra room.
+    pub fn record_success(&self, mint: &str) {
+        if let Ok(mut guard) = self.inner.lock() {
+            if let Some(entry) = guard.get_mut(mint) {
+                entry.consecutive_failures = 0;
+            }
+        }
+    }
+
+    /// Write learned overrides to the configured path, if any.
+    pub fn persist(&self) {
+        let Some(path) = self.cfg.path.as_deref() else {
+            return;
+        };
+
+        let snapshot = match self.inner.lock() {
+            Ok(guard) => guard.clone(),
+            Err(_) => return,
+        };
+
+        if let Err(err) = save_entries(path, &snapshot) {
+            eprintln!(
+                "Failed to persist mint slippage to {}: {err}",
+                path.display()
+            );
+        }
+    }
+}
+
+fn load_entries(path: &Path) -> HashMap<String, MintSlippageEntry> {
+    match fs::read_to_string(path) {
+        Ok(raw) => match serde_json::from_str(&raw) {
+            Ok(entries) => entries,
+            Err(err) => {
+                // Avoid panicking on a corrupt file; just log and start fresh.
+                eprintln!(
+                    "Failed to parse mint slippage file {}: {err}",
+                    path.display()
+                );
+                HashMap::new()
+            }
+        },
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn save_entries(path: &Path, entries: &HashMap<String, MintSlippageEntry>) -> std::io::Result<()> {
+    let raw = serde_json::to_string_pretty(entries)?;
+    fs::write(path, raw)
+}
+
+/// Global learned-slippage book, configured from env on first use.
+pub static GLOBAL_MINT_SLIPPAGE: Lazy<MintSlippageBook> =
+    Lazy::new(|| MintSlippageBook::new(MintSlippageConfig::from_env()));
@@ -1,6 +1,29 @@
+pub mod backtest;
+pub mod blacklist;
+pub mod context;
+pub mod control;
+pub mod dedup;
 pub mod executor;
+pub mod force_sell;
 pub mod models;
 pub mod dry_run;
+pub mod diagnostics;
+pub mod events;
 pub mod gates;
+pub mod impact;
+pub mod limit_entry;
+pub mod metrics;
+pub mod mev;
+pub mod mev_submit;
+pub mod mint_slippage;
+pub mod positions;
+pub mod prometheus;
+pub mod rug;
+pub mod shutdown;
+pub mod slippage;
+pub mod telegram;
+pub mod tiebreak;
+pub mod tp_sl;
 
 pub mod wallet_config;
+pub mod withdraw;
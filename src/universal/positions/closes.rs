@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Most recent close timestamp per mint, across all wallets.
+///
+/// Fed by [`PositionsRegistry`](super::PositionsRegistry) whenever a position
+/// closes, and read by the cooldown gate to avoid re-buying a mint right
+/// after exiting it.
+#[derive(Debug, Default)]
+pub struct RecentCloses {
+    inner: RwLock<HashMap<String, i64>>,
+}
+
+impl RecentCloses {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a position in `mint` closed at `ts` (unix seconds).
+    /// Older timestamps never overwrite newer ones.
+    pub fn record(&self, mint: &str, ts: i64) {
+        let mut guard = match self.inner.write() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let last = guard.entry(mint.to_string()).or_insert(ts);
+        if ts > *last {
+            *last = ts;
+        }
+    }
+
+    /// Timestamp of the most recent close in `mint`, if any.
+    pub fn last_close(&self, mint: &str) -> Option<i64> {
+        match self.inner.read() {
+            Ok(guard) => guard.get(mint).copied(),
+            Err(poisoned) => poisoned.into_inner().get(mint).copied(),
+        }
+    }
+}
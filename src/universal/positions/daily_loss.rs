@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+const SECS_PER_DAY: i64 = 86_400;
+
+#[derive(Debug, Default)]
+struct DayPnl {
+    /// UTC day number (days since the unix epoch) the totals belong to.
+    day: i64,
+    total_usd: f64,
+    per_wallet_usd: HashMap<String, f64>,
+}
+
+/// Realized PnL for the current UTC day, in total and per wallet.
+///
+/// Fed by [`PositionsRegistry::record_close_with_price`](super::PositionsRegistry::record_close_with_price)
+/// and [`PositionsRegistry::settle_close`](super::PositionsRegistry::settle_close),
+/// in USD, and read by the daily loss gate. Totals reset the first time they are
+/// touched on a new UTC day.
+#[derive(Debug, Default)]
+pub struct DailyLossTracker {
+    inner: Mutex<DayPnl>,
+}
+
+impl DailyLossTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn today(&self, now: i64) -> std::sync::MutexGuard<'_, DayPnl> {
+        let mut guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let day = now.div_euclid(SECS_PER_DAY);
+        if guard.day != day {
+            *guard = DayPnl {
+                day,
+                ..DayPnl::default()
+            };
+        }
+        guard
+    }
+
+    /// Add `pnl_usd` realized by `wallet` at `ts` (unix seconds).
+    pub fn record(&self, wallet: &str, pnl_usd: f64, ts: i64) {
+        if !pnl_usd.is_finite() {
+            return;
+        }
+        let mut day = self.today(ts);
+        day.total_usd += pnl_usd;
+        *day.per_wallet_usd.entry(wallet.to_string()).or_insert(0.0) += pnl_usd;
+    }
+
+    /// Realized PnL across all wallets for the UTC day containing `now`.
+    pub fn today_pnl_usd(&self, now: i64) -> f64 {
+        self.today(now).total_usd
+    }
+
+    /// Realized PnL of `wallet` for the UTC day containing `now`.
+    pub fn wallet_pnl_usd(&self, wallet: &str, now: i64) -> f64 {
+        self.today(now)
+            .per_wallet_usd
+            .get(wallet)
+            .copied()
+            .unwrap_or(0.0)
+    }
+}
@@ -0,0 +1,101 @@
+//! Auto-close of stale open positions.
+//!
+//! A missed close event (e.g. a dropped websocket) leaves a position open
+//! forever, blocking re-entry into the mint and inflating open counts. With
+//! MAX_POSITION_AGE_SECS set, [`spawn_position_reaper_from_env`] periodically
+//! closes every active position opened longer ago than that and emits a
+//! `position_expired` event for each. It is off by default so long-term
+//! holds are never closed unexpectedly.
+
+use std::env;
+use std::time::Duration;
+
+use crate::universal::events::{emit, Event};
+
+use super::PositionsRegistry;
+
+/// How often the reaper scans when MAX_POSITION_AGE_SECS is set.
+pub const REAPER_INTERVAL: Duration = Duration::from_secs(30);
+
+/// MAX_POSITION_AGE_SECS, or `None` when unset or 0 (reaper disabled).
+pub fn max_position_age_from_env() -> Option<u64> {
+    if let Ok(v) = env::var("MAX_POSITION_AGE_SECS") {
+        if let Ok(parsed) = v.parse::<u64>() {
+            if parsed > 0 {
+                return Some(parsed);
+            }
+        }
+    }
+    None
+}
+
+/// Close every active position in `registry` whose `opened_ts` is at least
+/// `max_age_secs` before `now` (unix seconds), emitting a `position_expired`
+/// event for each. Returns the (wallet, mint) pairs closed.
+pub fn reap_expired_positions(
+    registry: &PositionsRegistry,
+    max_age_secs: u64,
+    now: i64,
+) -> Vec<(String, String)> {
+    let max_age = i64::try_from(max_age_secs).unwrap_or(i64::MAX);
+    let mut expired = Vec::new();
+    for pos in registry.all_open_positions() {
+        let age = now.saturating_sub(pos.opened_ts);
+        if age < max_age {
+            continue;
+        }
+        if registry
+            .record_close_at(&pos.wallet, &pos.mint, now)
+            .is_err()
+        {
+            continue;
+        }
+        emit(Event::PositionExpired {
+            ts: now,
+            wallet: pos.wallet.clone(),
+            mint: pos.mint.clone(),
+            opened_ts: pos.opened_ts,
+            age_secs: age,
+        });
+        expired.push((pos.wallet, pos.mint));
+    }
+    expired
+}
+
+/// Run [`reap_expired_positions`] on `registry` every `interval`.
+pub fn spawn_position_reaper(
+    registry: &'static PositionsRegistry,
+    max_age_secs: u64,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let now = chrono::Utc::now().timestamp();
+            for (wallet, mint) in reap_expired_positions(registry, max_age_secs, now) {
+                println!(
+                    "Position expired after {}s: wallet={} mint={}",
+                    max_age_secs, wallet, mint
+                );
+            }
+        }
+    })
+}
+
+/// Spawn the reaper on `registry` if MAX_POSITION_AGE_SECS is set. Returns
+/// the task, or `None` when disabled.
+pub fn spawn_position_reaper_from_env(
+    registry: &'static PositionsRegistry,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let max_age_secs = max_position_age_from_env()?;
+    println!(
+        "Position reaper: closing positions older than {}s",
+        max_age_secs
+    );
+    Some(spawn_position_reaper(
+        registry,
+        max_age_secs,
+        REAPER_INTERVAL,
+    ))
+}
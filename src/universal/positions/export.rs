@@ -0,0 +1,121 @@
+//! Export of closed positions for PnL accounting.
+//!
+//! [`PositionsRegistry::export_closed`](super::PositionsRegistry::export_closed)
+//! writes one [`ClosedPositionRecord`] per closed position, either as CSV
+//! with a header line or as JSON lines, giving a trade history for taxes and
+//! analysis.
+
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use serde::Serialize;
+
+use super::{PositionState, PositionStatus};
+
+/// Output format of a closed-position export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// CSV with a header line; unknown values are left empty.
+    Csv,
+    /// One JSON object per line; unknown values are `null`.
+    JsonLines,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" | "jsonl" => Ok(ExportFormat::JsonLines),
+            other => Err(format!("unknown export format '{}'", other)),
+        }
+    }
+}
+
+/// One exported row.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ClosedPositionRecord {
+    pub wallet: String,
+    pub mint: String,
+    pub opened_ts: i64,
+    pub closed_ts: Option<i64>,
+    pub size: Option<f64>,
+    pub entry_price: Option<f64>,
+    pub exit_price: Option<f64>,
+    /// `(exit_price - entry_price) * size`, when all three are known.
+    pub pnl: Option<f64>,
+}
+
+impl ClosedPositionRecord {
+    /// The record for `pos`, or `None` if it is not closed.
+    pub fn from_position(pos: &PositionState) -> Option<Self> {
+        if pos.status != PositionStatus::Closed {
+            return None;
+        }
+        let pnl = match (pos.entry_price, pos.exit_price, pos.size) {
+            (Some(entry), Some(exit), Some(size)) => Some((exit - entry) * size),
+            _ => None,
+        };
+        Some(Self {
+            wallet: pos.wallet.clone(),
+            mint: pos.mint.clone(),
+            opened_ts: pos.opened_ts,
+            closed_ts: pos.closed_ts,
+            size: pos.size,
+            entry_price: pos.entry_price,
+            exit_price: pos.exit_price,
+            pnl,
+        })
+    }
+}
+
+/// Write `records` to `out` in `format`.
+pub fn write_closed_positions<W: Write>(
+    mut out: W,
+    format: ExportFormat,
+    records: &[ClosedPositionRecord],
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Csv => {
+            writeln!(
+                out,
+                "wallet,mint,opened_ts,closed_ts,size,entry_price,exit_price,pnl"
+            )?;
+            for r in records {
+                writeln!(
+                    out,
+                    "{},{},{},{},{},{},{},{}",
+                    csv_field(&r.wallet),
+                    csv_field(&r.mint),
+                    r.opened_ts,
+                    show(r.closed_ts),
+                    show(r.size),
+                    show(r.entry_price),
+                    show(r.exit_price),
+                    show(r.pnl),
+                )?;
+            }
+        }
+        ExportFormat::JsonLines => {
+            for r in records {
+                let line = serde_json::to_string(r)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                writeln!(out, "{}", line)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn show<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
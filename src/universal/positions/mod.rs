@@ -1,17 +1,43 @@
+use std::sync::Arc;
+
 use once_cell::sync::Lazy;
 
+use crate::universal::metrics::{auto_subscribe_positions, GLOBAL_METRICS_SUBSCRIPTIONS};
+
 pub mod types;
 pub mod registry;
 pub mod error;
+pub mod reconcile;
+pub mod closes;
+pub mod daily_loss;
+pub mod expiry;
+pub mod export;
+pub mod persist;
 
 pub use types::*;
 pub use registry::*;
 pub use error::*;
+pub use reconcile::*;
+pub use closes::*;
+pub use daily_loss::*;
+pub use expiry::*;
+pub use export::*;
+pub use persist::*;
 
 /// Global in-memory positions registry.
 ///
 /// This is intentionally simple and synchronous for Phase 1. If we ever
 /// need higher read concurrency, we can swap the internal lock in
 /// `PositionsRegistry` from `Mutex` to `RwLock` without changing this API.
-pub static GLOBAL_POSITIONS_REGISTRY: Lazy<PositionsRegistry> =
-    Lazy::new(PositionsRegistry::new);
+///
+/// Unless AUTO_SUBSCRIBE_POSITIONS=false, open positions hold a reference on
+/// their mint in the global metrics subscriptions so exits always see live data.
+pub static GLOBAL_POSITIONS_REGISTRY: Lazy<PositionsRegistry> = Lazy::new(|| {
+    let registry =
+        PositionsRegistry::new().with_quote_usd(crate::common::price_oracle::sol_usd_price);
+    if auto_subscribe_positions() {
+        registry.with_subscriptions(Arc::clone(&GLOBAL_METRICS_SUBSCRIPTIONS))
+    } else {
+        registry
+    }
+});
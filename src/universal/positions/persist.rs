@@ -0,0 +1,74 @@
+//! Active positions on disk across restarts (POSITIONS_FILE).
+//!
+//! The registry only lives in memory, so a restart or deploy would forget
+//! every open position. [`save_positions`] writes the open and closing ones
+//! as a JSON array, replacing the file through a temp file so a crash mid
+//! write leaves the previous snapshot intact. [`load_positions`] reads them
+//! back into a registry on startup, before reconciliation.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use super::{PositionState, PositionsRegistry};
+
+/// POSITIONS_FILE, if set.
+pub fn positions_file() -> Option<String> {
+    env::var("POSITIONS_FILE")
+        .ok()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+}
+
+/// Write the active positions of `registry` to `path`. Returns how many
+/// were written.
+pub fn save_positions<P: AsRef<Path>>(registry: &PositionsRegistry, path: P) -> io::Result<usize> {
+    let path = path.as_ref();
+    let mut positions = registry.all_open_positions();
+    positions.sort_by(|a, b| (&a.wallet, &a.mint).cmp(&(&b.wallet, &b.mint)));
+    let json = serde_json::to_string_pretty(&positions)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, json)?;
+    fs::rename(&tmp, path)?;
+    Ok(positions.len())
+}
+
+/// Restore the positions saved at `path` into `registry`. A missing file
+/// restores nothing. Returns how many positions were restored.
+pub fn load_positions<P: AsRef<Path>>(registry: &PositionsRegistry, path: P) -> io::Result<usize> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let positions: Vec<PositionState> =
+        serde_json::from_str(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(registry.restore_positions(positions))
+}
+
+/// Save `registry` to POSITIONS_FILE, if set. Errors are logged.
+pub fn persist_positions(registry: &PositionsRegistry) {
+    if let Some(path) = positions_file() {
+        if let Err(e) = save_positions(registry, &path) {
+            eprintln!("Failed to save POSITIONS_FILE {}: {}", path, e);
+        }
+    }
+}
+
+/// Load POSITIONS_FILE into `registry`, if set. Errors are logged. Returns
+/// how many positions were restored.
+pub fn restore_positions_from_env(registry: &PositionsRegistry) -> usize {
+    let path = match positions_file() {
+        Some(path) => path,
+        None => return 0,
+    };
+    match load_positions(registry, &path) {
+        Ok(restored) => restored,
+        Err(e) => {
+            eprintln!("Failed to load POSITIONS_FILE {}: {}", path, e);
+            0
+        }
+    }
+}
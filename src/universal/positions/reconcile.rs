@@ -0,0 +1,115 @@
+use std::str::FromStr;
+
+use anchor_client::solana_client::rpc_client::RpcClient;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use super::PositionsRegistry;
+
+/// Relative difference between recorded size and on-chain balance above
+/// which a position is flagged as a discrepancy.
+const SIZE_MISMATCH_TOLERANCE: f64 = 0.01;
+
+/// Source of on-chain token balances used for reconciliation.
+pub trait BalanceSource {
+    /// UI token balance (decimals applied) `wallet` holds of `mint`.
+    /// A missing token account counts as a zero balance.
+    fn token_balance(&self, wallet: &str, mint: &str) -> Result<f64, String>;
+}
+
+impl BalanceSource for RpcClient {
+    fn token_balance(&self, wallet: &str, mint: &str) -> Result<f64, String> {
+        let owner =
+            Pubkey::from_str(wallet).map_err(|e| format!("invalid wallet {}: {}", wallet, e))?;
+        let mint_pk =
+            Pubkey::from_str(mint).map_err(|e| format!("invalid mint {}: {}", mint, e))?;
+
+        // The mint may live under either token program; sum both ATAs.
+        let mut total = 0.0;
+        for program in [spl_token::id(), spl_token_2022::id()] {
+            let ata = get_associated_token_address_with_program_id(&owner, &mint_pk, &program);
+            let exists = self
+                .get_account_with_commitment(&ata, self.commitment())
+                .map_err(|e| format!("get_account {}: {}", ata, e))?
+                .value
+                .is_some();
+            if !exists {
+                continue;
+            }
+            let balance = self
+                .get_token_account_balance(&ata)
+                .map_err(|e| format!("get_token_account_balance {}: {}", ata, e))?;
+            total += balance.ui_amount.unwrap_or(0.0);
+        }
+        Ok(total)
+    }
+}
+
+/// A persisted position whose state could not be reconciled cleanly.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Discrepancy {
+    pub wallet: String,
+    pub mint: String,
+    pub detail: String,
+}
+
+/// Outcome of reconciling open positions against on-chain balances.
+#[derive(Clone, Debug, Default)]
+pub struct ReconcileReport {
+    /// Number of open positions examined.
+    pub checked: usize,
+    /// (wallet, mint) positions closed because the wallet holds none of the token.
+    pub closed: Vec<(String, String)>,
+    /// Positions left open but flagged for operator attention.
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+/// Reconcile open positions in `registry` against on-chain balances.
+///
+/// Intended for startup after a crash, when the registry may disagree with
+/// reality:
+/// - zero on-chain balance → the position is closed (sold externally or the
+///   buy never confirmed);
+/// - balance lookup failure → left open and flagged;
+/// - balance differing from the recorded size → left open and flagged.
+pub fn reconcile_positions<B: BalanceSource + ?Sized>(
+    registry: &PositionsRegistry,
+    rpc: &B,
+) -> ReconcileReport {
+    let mut report = ReconcileReport::default();
+
+    for pos in registry.all_open_positions() {
+        report.checked += 1;
+
+        let balance = match rpc.token_balance(&pos.wallet, &pos.mint) {
+            Ok(balance) => balance,
+            Err(e) => {
+                report.discrepancies.push(Discrepancy {
+                    wallet: pos.wallet,
+                    mint: pos.mint,
+                    detail: format!("balance_unavailable: {}", e),
+                });
+                continue;
+            }
+        };
+
+        if balance <= 0.0 {
+            if registry.record_close(&pos.wallet, &pos.mint).is_ok() {
+                report.closed.push((pos.wallet, pos.mint));
+            }
+            continue;
+        }
+
+        if let Some(size) = pos.size.filter(|s| *s > 0.0) {
+            if ((balance - size) / size).abs() > SIZE_MISMATCH_TOLERANCE {
+                report.discrepancies.push(Discrepancy {
+                    wallet: pos.wallet,
+                    mint: pos.mint,
+                    detail: format!("size_mismatch recorded {} onchain {}", size, balance),
+                });
+            }
+        }
+    }
+
+    report
+}
@@ -1,7 +1,14 @@
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
 
-use super::{PositionError, PositionState, PositionStatus};
+use crate::universal::metrics::MetricsSubscriptions;
+use crate::universal::prometheus::COUNTERS;
+
+use super::{
+    write_closed_positions, CloseSettlement, ClosedPositionRecord, DailyLossTracker, ExportFormat,
+    OpenParams, PositionError, PositionState, PositionStatus, RecentCloses, SellCommitment,
+};
 
 /// In-memory registry for tracking open/closed positions keyed by (wallet, mint).
 ///
@@ -10,15 +17,78 @@ use super::{PositionError, PositionState, PositionStatus};
 /// the public API.
 pub struct PositionsRegistry {
     inner: Mutex<HashMap<(String, String), PositionState>>,
+    closes: Arc<RecentCloses>,
+    daily_pnl: Arc<DailyLossTracker>,
+    subscriptions: Option<Arc<MetricsSubscriptions>>,
+    quote_usd: Option<fn() -> Option<f64>>,
 }
 
 impl PositionsRegistry {
     pub fn new() -> Self {
+        Self::with_close_log(Arc::new(RecentCloses::new()))
+    }
+
+    /// Create a registry that records every close into `closes`.
+    pub fn with_close_log(closes: Arc<RecentCloses>) -> Self {
         Self {
             inner: Mutex::new(HashMap::new()),
+            closes,
+            daily_pnl: Arc::new(DailyLossTracker::new()),
+            subscriptions: None,
+            quote_usd: None,
+        }
+    }
+
+    /// Keep a metrics subscription on each open position's mint: acquired
+    /// when the position opens and released once it is closed.
+    pub fn with_subscriptions(mut self, subscriptions: Arc<MetricsSubscriptions>) -> Self {
+        self.subscriptions = Some(subscriptions);
+        self
+    }
+
+    /// Convert realized PnL from quote units to USD at `quote_usd()`. Live
+    /// entries are SOL per token; without a converter prices are taken to
+    /// be USD already.
+    pub fn with_quote_usd(mut self, quote_usd: fn() -> Option<f64>) -> Self {
+        self.quote_usd = Some(quote_usd);
+        self
+    }
+
+    /// Realized PnL of `pos` exiting at `exit_price`, `(exit - entry) * size`,
+    /// in USD. Dry-run positions enter at a USD price and are not converted.
+    /// `None` without an entry price, a size or a quote price.
+    fn realized_pnl_usd(&self, pos: &PositionState, exit_price: f64) -> Option<f64> {
+        let pnl = (exit_price - pos.entry_price?) * pos.size?;
+        match self.quote_usd {
+            Some(quote_usd) if !pos.simulated => quote_usd().map(|usd| pnl * usd),
+            _ => Some(pnl),
+        }
+    }
+
+    fn subscribe(&self, mint: &str) {
+        if let Some(subs) = &self.subscriptions {
+            subs.acquire(mint);
+        }
+    }
+
+    fn unsubscribe(&self, mint: &str) {
+        if let Some(subs) = &self.subscriptions {
+            subs.release(mint);
         }
     }
 
+    /// Shared per-mint log of close timestamps fed by this registry.
+    pub fn close_log(&self) -> Arc<RecentCloses> {
+        Arc::clone(&self.closes)
+    }
+
+    /// Realized PnL for the current UTC day, fed by closes that carry an exit
+    /// price: [`record_close_with_price`](Self::record_close_with_price) and
+    /// [`settle_close`](Self::settle_close).
+    pub fn daily_pnl(&self) -> Arc<DailyLossTracker> {
+        Arc::clone(&self.daily_pnl)
+    }
+
     /// Returns `Ok(())` if a new position can be opened for the given (wallet, mint),
     /// or `Err(PositionError::AlreadyOpen)` if there is already an open position.
     pub fn can_open(&self, wallet: &str, mint: &str) -> Result<(), PositionError> {
@@ -27,7 +97,7 @@ impl PositionsRegistry {
         })?;
 
         if let Some(pos) = guard.get(&(wallet.to_string(), mint.to_string())) {
-            if pos.status == PositionStatus::Open {
+            if pos.status.is_active() {
                 return Err(PositionError::AlreadyOpen(wallet.into(), mint.into()));
             }
         }
@@ -35,7 +105,8 @@ impl PositionsRegistry {
         Ok(())
     }
 
-    /// Records a newly opened position for the given (wallet, mint).
+    /// Records a newly opened position for the given (wallet, mint), with no
+    /// fill details. See [`record_open_with`](Self::record_open_with).
     ///
     /// If there is already an open position, this returns `Err(PositionError::AlreadyOpen)`.
     pub fn record_open(
@@ -43,13 +114,50 @@ impl PositionsRegistry {
         wallet: &str,
         mint: &str,
         opened_ts: i64,
+    ) -> Result<(), PositionError> {
+        self.record_open_with(wallet, mint, opened_ts, OpenParams::default())
+    }
+
+    /// Records a newly opened position along with its size, entry price and
+    /// slippage.
+    ///
+    /// If there is already an open position, this returns `Err(PositionError::AlreadyOpen)`.
+    pub fn record_open_with(
+        &self,
+        wallet: &str,
+        mint: &str,
+        opened_ts: i64,
+        params: OpenParams,
+    ) -> Result<(), PositionError> {
+        self.open_position(wallet, mint, opened_ts, params, false)
+    }
+
+    /// Like [`record_open_with`](Self::record_open_with), for a dry-run paper
+    /// position. It occupies a slot and shows in `/positions` like a real one.
+    pub fn record_open_simulated(
+        &self,
+        wallet: &str,
+        mint: &str,
+        opened_ts: i64,
+        params: OpenParams,
+    ) -> Result<(), PositionError> {
+        self.open_position(wallet, mint, opened_ts, params, true)
+    }
+
+    fn open_position(
+        &self,
+        wallet: &str,
+        mint: &str,
+        opened_ts: i64,
+        params: OpenParams,
+        simulated: bool,
     ) -> Result<(), PositionError> {
         let mut guard = self.inner.lock().map_err(|e| {
             PositionError::Internal(format!("Mutex poisoned in record_open: {}", e))
         })?;
 
         if let Some(pos) = guard.get(&(wallet.to_string(), mint.to_string())) {
-            if pos.status == PositionStatus::Open {
+            if pos.status.is_active() {
                 return Err(PositionError::AlreadyOpen(wallet.into(), mint.into()));
             }
         }
@@ -58,28 +166,126 @@ impl PositionsRegistry {
             wallet: wallet.into(),
             mint: mint.into(),
             opened_ts,
-            size: None,
-            entry_price: None,
-            slippage_bps: None,
+            size: params.size,
+            entry_price: params.entry_price,
+            slippage_bps: params.slippage_bps,
+            high_water_price: params.entry_price,
             status: PositionStatus::Open,
+            closed_ts: None,
+            exit_price: None,
+            simulated,
         };
 
         guard.insert((wallet.into(), mint.into()), state);
+        drop(guard);
+        COUNTERS.inc_trades_opened();
+        self.subscribe(mint);
         Ok(())
     }
 
+    /// Records the entry price (and size, if known) of an active position,
+    /// e.g. once the buy has filled. Prices must be in the same units the
+    /// TP/SL monitor's price source quotes.
+    pub fn record_entry(
+        &self,
+        wallet: &str,
+        mint: &str,
+        entry_price: f64,
+        size: Option<f64>,
+    ) -> Result<(), PositionError> {
+        let mut guard = self.inner.lock().map_err(|e| {
+            PositionError::Internal(format!("Mutex poisoned in record_entry: {}", e))
+        })?;
+
+        match guard.get_mut(&(wallet.to_string(), mint.to_string())) {
+            Some(pos) if pos.status.is_active() => {
+                pos.entry_price = Some(entry_price);
+                pos.high_water_price = Some(pos.high_water_price.unwrap_or(entry_price).max(entry_price));
+                if size.is_some() {
+                    pos.size = size;
+                }
+                Ok(())
+            }
+            _ => Err(PositionError::NotOpen(wallet.into(), mint.into())),
+        }
+    }
+
+    /// Raise the high-water price of an active position to `price` if it is
+    /// a new peak. Returns the (possibly unchanged) high-water price, or
+    /// `None` if the position is not active.
+    pub fn update_high_water(&self, wallet: &str, mint: &str, price: f64) -> Option<f64> {
+        let mut guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let pos = guard
+            .get_mut(&(wallet.to_string(), mint.to_string()))
+            .filter(|p| p.status.is_active())?;
+        let high = match pos.high_water_price {
+            Some(prev) if prev >= price || price.is_nan() => prev,
+            _ => price,
+        };
+        pos.high_water_price = Some(high);
+        Some(high)
+    }
+
     /// Marks an existing position as closed for the given (wallet, mint).
     ///
     /// If there is no open position, this returns `Err(PositionError::NotOpen)`.
     pub fn record_close(&self, wallet: &str, mint: &str) -> Result<(), PositionError> {
+        self.record_close_at(wallet, mint, now_ts())
+    }
+
+    /// Close a position that exited at `exit_price` and add its realized PnL
+    /// in USD, `(exit_price - entry_price) * size` at the quote price (see
+    /// [`with_quote_usd`](Self::with_quote_usd)), to the day's total. Returns
+    /// the PnL, or `None` when the position has no entry price or size, or
+    /// no quote price is available.
+    pub fn record_close_with_price(
+        &self,
+        wallet: &str,
+        mint: &str,
+        exit_price: f64,
+    ) -> Result<Option<f64>, PositionError> {
+        let pnl = self
+            .get_open_position(wallet, mint)
+            .and_then(|pos| self.realized_pnl_usd(&pos, exit_price));
+        let closed_ts = now_ts();
+        self.close_position(wallet, mint, closed_ts, Some(exit_price))?;
+        if let Some(pnl) = pnl {
+            self.daily_pnl.record(wallet, pnl, closed_ts);
+        }
+        Ok(pnl)
+    }
+
+    /// Like [`record_close`](Self::record_close), with an explicit close
+    /// timestamp (unix seconds) for the close log.
+    pub fn record_close_at(
+        &self,
+        wallet: &str,
+        mint: &str,
+        closed_ts: i64,
+    ) -> Result<(), PositionError> {
+        self.close_position(wallet, mint, closed_ts, None)
+    }
+
+    fn close_position(
+        &self,
+        wallet: &str,
+        mint: &str,
+        closed_ts: i64,
+        exit_price: Option<f64>,
+    ) -> Result<(), PositionError> {
         let mut guard = self.inner.lock().map_err(|e| {
             PositionError::Internal(format!("Mutex poisoned in record_close: {}", e))
         })?;
 
         match guard.get_mut(&(wallet.to_string(), mint.to_string())) {
             Some(pos) => {
-                if pos.status == PositionStatus::Open {
-                    pos.status = PositionStatus::Closed;
+                if pos.status.is_active() {
+                    self.mark_closed(pos, closed_ts, exit_price);
+                    drop(guard);
+                    self.unsubscribe(mint);
                     Ok(())
                 } else {
                     Err(PositionError::NotOpen(wallet.into(), mint.into()))
@@ -87,9 +293,81 @@ impl PositionsRegistry {
             }
             None => Err(PositionError::NotOpen(wallet.into(), mint.into())),
         }
-    /// Returns true if there is currently an open position for the given
-    /// (wallet, mint) pair. If the internal mutex is poisoned, this will
-    /// return `false` as a conservative default.
+    }
+
+    /// Moves `pos` to `Closed`. Every close goes through here so the close
+    /// log and `trades_closed_total` count each position exactly once.
+    fn mark_closed(&self, pos: &mut PositionState, closed_ts: i64, exit_price: Option<f64>) {
+        pos.status = PositionStatus::Closed;
+        pos.closed_ts = Some(closed_ts);
+        pos.exit_price = exit_price;
+        self.closes.record(&pos.mint, closed_ts);
+        COUNTERS.inc_trades_closed();
+    }
+
+    /// Marks an open position as `Closing` once its sell has been sent.
+    ///
+    /// The position keeps its slot until [`settle_close`](Self::settle_close)
+    /// sees the sell confirmed. Returns `Err(PositionError::NotOpen)` if the
+    /// position is not `Open` (including when a sell is already in flight).
+    pub fn begin_close(&self, wallet: &str, mint: &str) -> Result<(), PositionError> {
+        let mut guard = self.inner.lock().map_err(|e| {
+            PositionError::Internal(format!("Mutex poisoned in begin_close: {}", e))
+        })?;
+
+        match guard.get_mut(&(wallet.to_string(), mint.to_string())) {
+            Some(pos) if pos.status == PositionStatus::Open => {
+                pos.status = PositionStatus::Closing;
+                Ok(())
+            }
+            _ => Err(PositionError::NotOpen(wallet.into(), mint.into())),
+        }
+    }
+
+    /// Settles a `Closing` position given the commitment its sell reached
+    /// (`None` if it dropped).
+    ///
+    /// At or above `required` the position is closed and its slot freed, and
+    /// with an `exit_price` (quote units, like the entry) the realized PnL is
+    /// added to the day's total as in
+    /// [`record_close_with_price`](Self::record_close_with_price). Otherwise
+    /// the position is restored to `Open` so the exit is retried.
+    pub fn settle_close(
+        &self,
+        wallet: &str,
+        mint: &str,
+        reached: Option<SellCommitment>,
+        required: SellCommitment,
+        exit_price: Option<f64>,
+    ) -> Result<CloseSettlement, PositionError> {
+        let mut guard = self.inner.lock().map_err(|e| {
+            PositionError::Internal(format!("Mutex poisoned in settle_close: {}", e))
+        })?;
+
+        match guard.get_mut(&(wallet.to_string(), mint.to_string())) {
+            Some(pos) if pos.status == PositionStatus::Closing => {
+                if matches!(reached, Some(r) if r >= required) {
+                    let now = now_ts();
+                    let pnl = exit_price.and_then(|price| self.realized_pnl_usd(pos, price));
+                    self.mark_closed(pos, now, exit_price);
+                    drop(guard);
+                    if let Some(pnl) = pnl {
+                        self.daily_pnl.record(wallet, pnl, now);
+                    }
+                    self.unsubscribe(mint);
+                    Ok(CloseSettlement::Closed)
+                } else {
+                    pos.status = PositionStatus::Open;
+                    Ok(CloseSettlement::Restored)
+                }
+            }
+            _ => Err(PositionError::NotOpen(wallet.into(), mint.into())),
+        }
+    }
+
+    /// Returns true if there is currently an open (or closing) position for
+    /// the given (wallet, mint) pair. If the internal mutex is poisoned, this
+    /// will return `false` as a conservative default.
     pub fn has_open_position(&self, wallet: &str, mint: &str) -> bool {
         let guard = match self.inner.lock() {
             Ok(guard) => guard,
@@ -98,7 +376,7 @@ impl PositionsRegistry {
 
         guard
             .get(&(wallet.to_string(), mint.to_string()))
-            .map(|p| p.status == PositionStatus::Open)
+            .map(|p| p.status.is_active())
             .unwrap_or(false)
     }
 
@@ -124,10 +402,25 @@ impl PositionsRegistry {
 
         guard
             .values()
-            .filter(|p| p.wallet == wallet && p.status == PositionStatus::Open)
+            .filter(|p| p.wallet == wallet && p.status.is_active())
             .cloned()
             .collect()
     }
+
+    /// Number of open positions in `mint` across all wallets. Returns 0 if
+    /// the mutex is poisoned.
+    pub fn count_open_for_mint(&self, mint: &str) -> usize {
+        let guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(_) => return 0,
+        };
+
+        guard
+            .values()
+            .filter(|p| p.mint == mint && p.status.is_active())
+            .count()
+    }
+
     /// Marks all positions for the given wallet as closed.
     ///
     /// This is intended to support higher-level controls such as:
@@ -143,17 +436,160 @@ impl PositionsRegistry {
             Err(_) => return 0,
         };
 
+        let now = now_ts();
+        let mut closed_mints = Vec::new();
+        for state in guard.values_mut() {
+            if state.wallet == wallet && state.status.is_active() {
+                self.mark_closed(state, now, None);
+                closed_mints.push(state.mint.clone());
+            }
+        }
+        drop(guard);
+
+        for mint in &closed_mints {
+            self.unsubscribe(mint);
+        }
+        closed_mints.len()
+    }
+
+    /// Marks every open position in `mint` (across all wallets) as closed.
+    ///
+    /// Returns the number of positions closed; 0 if the mutex is poisoned.
+    pub fn close_all_for_mint(&self, mint: &str) -> usize {
+        let mut guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(_) => return 0,
+        };
+
+        let now = now_ts();
         let mut closed_count = 0usize;
         for state in guard.values_mut() {
-            if state.wallet == wallet && state.status == PositionStatus::Open {
-                state.status = PositionStatus::Closed;
+            if state.mint == mint && state.status.is_active() {
+                self.mark_closed(state, now, None);
                 closed_count += 1;
             }
         }
+        drop(guard);
 
+        for _ in 0..closed_count {
+            self.unsubscribe(mint);
+        }
         closed_count
     }
 
+    /// Snapshot of every open position across all wallets, for global
+    /// views such as `/positions` without a wallet. Returns an empty list if
+    /// the mutex is poisoned.
+    pub fn all_open_positions(&self) -> Vec<PositionState> {
+        let guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(_) => return Vec::new(),
+        };
+
+        guard
+            .values()
+            .filter(|p| p.status.is_active())
+            .cloned()
+            .collect()
+    }
+
+    /// Put back active positions saved by an earlier run (see
+    /// [`save_positions`](super::save_positions)). Closed entries and
+    /// (wallet, mint) pairs already active here are skipped. Restored
+    /// positions keep their state and are not counted as new opens. Returns
+    /// how many were restored.
+    pub fn restore_positions(&self, positions: Vec<PositionState>) -> usize {
+        let mut restored = Vec::new();
+        {
+            let mut guard = match self.inner.lock() {
+                Ok(guard) => guard,
+                Err(_) => return 0,
+            };
+            for pos in positions {
+                if !pos.status.is_active() {
+                    continue;
+                }
+                let key = (pos.wallet.clone(), pos.mint.clone());
+                if guard.get(&key).is_some_and(|p| p.status.is_active()) {
+                    continue;
+                }
+                restored.push(pos.mint.clone());
+                guard.insert(key, pos);
+            }
+        }
+
+        for mint in &restored {
+            self.subscribe(mint);
+        }
+        restored.len()
+    }
+
+    /// Number of open positions across all wallets. Returns 0 if the mutex
+    /// is poisoned.
+    pub fn open_count(&self) -> usize {
+        let guard = match self.inner.lock() {
+            Ok(guard) => guard,
+            Err(_) => return 0,
+        };
+
+        guard.values().filter(|p| p.status.is_active()).count()
+    }
+
+    /// Write every closed position to `writer` in `format`, oldest close
+    /// first, and return how many were written. Returns an error without
+    /// writing anything if the mutex is poisoned.
+    pub fn export_closed<W: Write>(&self, writer: W, format: ExportFormat) -> io::Result<usize> {
+        let records = {
+            let guard = self.inner.lock().map_err(|e| {
+                io::Error::other(format!("Mutex poisoned in export_closed: {}", e))
+            })?;
+            closed_records(guard.values())
+        };
+        write_closed_positions(writer, format, &records)?;
+        Ok(records.len())
+    }
+
+    /// Like [`export_closed`](Self::export_closed), then drop the exported
+    /// positions from the registry so the next export only has new closes.
+    /// Nothing is dropped if writing fails.
+    pub fn export_closed_and_prune<W: Write>(
+        &self,
+        writer: W,
+        format: ExportFormat,
+    ) -> io::Result<usize> {
+        let mut guard = self.inner.lock().map_err(|e| {
+            io::Error::other(format!("Mutex poisoned in export_closed: {}", e))
+        })?;
+        let records = closed_records(guard.values());
+        write_closed_positions(writer, format, &records)?;
+        guard.retain(|_, p| p.status.is_active());
+        Ok(records.len())
+    }
+}
+
+/// Export rows for the closed positions in `positions`, ordered by close
+/// time, then wallet and mint.
+fn closed_records<'a>(
+    positions: impl Iterator<Item = &'a PositionState>,
+) -> Vec<ClosedPositionRecord> {
+    let mut records: Vec<_> = positions
+        .filter_map(ClosedPositionRecord::from_position)
+        .collect();
+    records.sort_by(|a, b| {
+        a.closed_ts
+            .cmp(&b.closed_ts)
+            .then_with(|| a.wallet.cmp(&b.wallet))
+            .then_with(|| a.mint.cmp(&b.mint))
+    });
+    records
+}
+
+fn now_ts() -> i64 {
+    chrono::Utc::now().timestamp()
+}
 
+impl Default for PositionsRegistry {
+    fn default() -> Self {
+        Self::new()
     }
 }
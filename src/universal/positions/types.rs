@@ -1,9 +1,82 @@
+use std::env;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub enum PositionStatus {
     Open,
+    /// Exit sent but not yet confirmed; the position still holds its slot.
+    Closing,
+    Closed,
+}
+
+impl PositionStatus {
+    /// Whether the position still occupies a slot (open or closing).
+    pub fn is_active(&self) -> bool {
+        !matches!(self, PositionStatus::Closed)
+    }
+}
+
+/// Commitment a sell must reach before its position counts as closed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SellCommitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl Default for SellCommitment {
+    fn default() -> Self {
+        SellCommitment::Confirmed
+    }
+}
+
+impl FromStr for SellCommitment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "processed" => Ok(SellCommitment::Processed),
+            "confirmed" => Ok(SellCommitment::Confirmed),
+            "finalized" => Ok(SellCommitment::Finalized),
+            other => Err(format!("unknown commitment '{}'", other)),
+        }
+    }
+}
+
+impl SellCommitment {
+    /// Read SELL_MIN_COMMITMENT (processed|confirmed|finalized, default confirmed).
+    pub fn from_env() -> Self {
+        if let Ok(v) = env::var("SELL_MIN_COMMITMENT") {
+            match v.parse() {
+                Ok(parsed) => return parsed,
+                Err(e) => eprintln!("Invalid SELL_MIN_COMMITMENT: {}", e),
+            }
+        }
+        Self::default()
+    }
+}
+
+/// What happened to a closing position once its sell settled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CloseSettlement {
+    /// The sell reached the required commitment; the slot is freed.
     Closed,
+    /// The sell dropped or fell short; the position is open again so the
+    /// monitor retries the exit.
+    Restored,
+}
+
+/// Fill details recorded when a position opens.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OpenParams {
+    /// Position size in tokens.
+    pub size: Option<f64>,
+    /// Entry price (in quote units).
+    pub entry_price: Option<f64>,
+    /// Slippage tolerance the buy was sent with, in basis points.
+    pub slippage_bps: Option<f64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -14,12 +87,27 @@ pub struct PositionState {
     pub mint: String,
     /// Unix timestamp (seconds) when the position was opened.
     pub opened_ts: i64,
-    /// Optional position size (tokens or quote units). Reserved for later use.
+    /// Position size in tokens, if known.
     pub size: Option<f64>,
-    /// Optional entry price (in quote units). Reserved for later use.
+    /// Entry price (in quote units), from `record_open_with` or
+    /// `record_entry`. Positions without one are skipped by the TP/SL monitor.
     pub entry_price: Option<f64>,
-    /// Optional slippage in basis points. Reserved for later use.
+    /// Slippage tolerance of the buy in basis points, if known.
     pub slippage_bps: Option<f64>,
+    /// Highest price seen since entry, for trailing stops. Starts at the
+    /// entry price and is raised by `update_high_water`.
+    #[serde(default)]
+    pub high_water_price: Option<f64>,
     /// Current status of the position.
     pub status: PositionStatus,
+    /// Unix timestamp (seconds) when the position closed.
+    #[serde(default)]
+    pub closed_ts: Option<i64>,
+    /// Exit price (in quote units), from `record_close_with_price` or
+    /// `settle_close`.
+    #[serde(default)]
+    pub exit_price: Option<f64>,
+    /// Paper position opened by dry-run; no tokens were actually bought.
+    #[serde(default)]
+    pub simulated: bool,
 }
@@ -0,0 +1,223 @@
+//! Prometheus counters for gate rejections (per gate and per reason code),
+//! trades and RPC failovers.
+//!
+//! [`COUNTERS`] is incremented by `run_gates`, the positions registry and
+//! the RPC failover rotation. When METRICS_PORT is set,
+//! [`spawn_metrics_server_from_env`] serves them in the Prometheus text format
+//! on `GET /metrics`. The server is a bare tokio listener answering one
+//! request per connection, which is all a scraper needs.
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::universal::gates::RejectReason;
+
+/// Process-wide counters.
+#[derive(Debug, Default)]
+pub struct Counters {
+    gate_rejections: Mutex<BTreeMap<String, u64>>,
+    reject_reasons: Mutex<BTreeMap<RejectReason, u64>>,
+    trades_opened: AtomicU64,
+    trades_closed: AtomicU64,
+    rpc_failovers: AtomicU64,
+}
+
+impl Counters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn inc_gate_rejection(&self, gate: &str) {
+        let mut guard = match self.gate_rejections.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *guard.entry(gate.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn inc_reject_reason(&self, code: RejectReason) {
+        let mut guard = match self.reject_reasons.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        *guard.entry(code).or_insert(0) += 1;
+    }
+
+    pub fn inc_trades_opened(&self) {
+        self.trades_opened.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn inc_trades_closed(&self) {
+        self.trades_closed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn inc_rpc_failovers(&self) {
+        self.rpc_failovers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn gate_rejections(&self, gate: &str) -> u64 {
+        match self.gate_rejections.lock() {
+            Ok(guard) => guard.get(gate).copied().unwrap_or(0),
+            Err(poisoned) => poisoned.into_inner().get(gate).copied().unwrap_or(0),
+        }
+    }
+
+    pub fn reject_reasons(&self, code: RejectReason) -> u64 {
+        match self.reject_reasons.lock() {
+            Ok(guard) => guard.get(&code).copied().unwrap_or(0),
+            Err(poisoned) => poisoned.into_inner().get(&code).copied().unwrap_or(0),
+        }
+    }
+
+    pub fn trades_opened(&self) -> u64 {
+        self.trades_opened.load(Ordering::Relaxed)
+    }
+
+    pub fn trades_closed(&self) -> u64 {
+        self.trades_closed.load(Ordering::Relaxed)
+    }
+
+    pub fn rpc_failovers(&self) -> u64 {
+        self.rpc_failovers.load(Ordering::Relaxed)
+    }
+
+    /// All counters in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let rejections = match self.gate_rejections.lock() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        let reasons = match self.reject_reasons.lock() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        let mut out = String::new();
+        out.push_str("# HELP gate_rejections_total Trades rejected, by gate.\n");
+        out.push_str("# TYPE gate_rejections_total counter\n");
+        for (gate, n) in &rejections {
+            out.push_str(&format!(
+                "gate_rejections_total{{gate=\"{}\"}} {}\n",
+                escape_label(gate),
+                n
+            ));
+        }
+        out.push_str("# HELP gate_reject_reasons_total Trades rejected, by reason code.\n");
+        out.push_str("# TYPE gate_reject_reasons_total counter\n");
+        for (code, n) in &reasons {
+            out.push_str(&format!(
+                "gate_reject_reasons_total{{reason=\"{}\"}} {}\n",
+                code.as_str(),
+                n
+            ));
+        }
+        for (name, help, value) in [
+            (
+                "trades_opened_total",
+                "Positions opened.",
+                self.trades_opened(),
+            ),
+            (
+                "trades_closed_total",
+                "Positions closed.",
+                self.trades_closed(),
+            ),
+            (
+                "rpc_failovers_total",
+                "RPC calls that moved on to the next endpoint.",
+                self.rpc_failovers(),
+            ),
+        ] {
+            out.push_str(&format!("# HELP {} {}\n", name, help));
+            out.push_str(&format!("# TYPE {} counter\n", name));
+            out.push_str(&format!("{} {}\n", name, value));
+        }
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+pub static COUNTERS: Lazy<Counters> = Lazy::new(Counters::new);
+
+/// Answer one HTTP request on `stream`: the rendered counters for
+/// `GET /metrics`, 404 for anything else.
+async fn handle(mut stream: TcpStream, counters: &Counters) -> std::io::Result<()> {
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    let mut parts = request.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some(path)) if path == "/metrics" || path.starts_with("/metrics?") => {
+            ("200 OK", "text/plain; version=0.0.4", counters.render())
+        }
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Serve `counters` on `listener` until the task is dropped.
+pub async fn serve_metrics(listener: TcpListener, counters: &'static Counters) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                eprintln!("[METRICS] accept failed: {}", e);
+                continue;
+            }
+        };
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, counters).await {
+                eprintln!("[METRICS] request failed: {}", e);
+            }
+        });
+    }
+}
+
+/// Spawn the `/metrics` endpoint on 0.0.0.0:METRICS_PORT if METRICS_PORT is
+/// set. Returns the server task, or `None` if disabled or the port could not
+/// be bound.
+pub async fn spawn_metrics_server_from_env() -> Option<tokio::task::JoinHandle<()>> {
+    let port = match std::env::var("METRICS_PORT") {
+        Ok(v) => match v.trim().parse::<u16>() {
+            Ok(port) => port,
+            Err(_) => {
+                eprintln!(
+                    "METRICS_PORT={} is not a valid port; metrics endpoint disabled",
+                    v
+                );
+                return None;
+            }
+        },
+        Err(_) => return None,
+    };
+    let listener = match TcpListener::bind(("0.0.0.0", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!(
+                "METRICS_PORT={}: bind failed: {}; metrics endpoint disabled",
+                port, e
+            );
+            return None;
+        }
+    };
+    println!("Serving Prometheus metrics on :{}/metrics", port);
+    Some(tokio::spawn(serve_metrics(listener, &COUNTERS)))
+}
@@ -0,0 +1,147 @@
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::universal::blacklist::blacklist_mint;
+
+/// What counts as a rug: price falls by at least `price_drop_pct` from its
+/// peak within `window_secs`, while liquidity falls by at least
+/// `liq_drop_pct` from its peak over the same window.
+#[derive(Debug, Clone)]
+pub struct RugConfig {
+    pub price_drop_pct: f64,
+    pub window_secs: i64,
+    pub liq_drop_pct: f64,
+}
+
+impl Default for RugConfig {
+    fn default() -> Self {
+        Self {
+            price_drop_pct: 90.0,
+            window_secs: 60,
+            liq_drop_pct: 80.0,
+        }
+    }
+}
+
+impl RugConfig {
+    /// Build from env vars:
+    ///
+    /// - RUG_PRICE_DROP_PCT (default 90)
+    /// - RUG_WINDOW_SECS    (default 60)
+    /// - RUG_LIQ_DROP_PCT   (default 80)
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = env::var("RUG_PRICE_DROP_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.price_drop_pct = parsed;
+            }
+        }
+
+        if let Ok(v) = env::var("RUG_WINDOW_SECS") {
+            if let Ok(parsed) = v.parse::<i64>() {
+                cfg.window_secs = parsed.max(1);
+            }
+        }
+
+        if let Ok(v) = env::var("RUG_LIQ_DROP_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.liq_drop_pct = parsed;
+            }
+        }
+
+        cfg
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    ts: i64,
+    price: f64,
+    liq: f64,
+}
+
+/// Tracks recent price / liquidity per held mint and flags abrupt collapses.
+pub struct RugDetector {
+    cfg: RugConfig,
+    samples: Mutex<HashMap<String, VecDeque<Sample>>>,
+}
+
+impl RugDetector {
+    pub fn new(cfg: RugConfig) -> Self {
+        Self {
+            cfg,
+            samples: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &RugConfig {
+        &self.cfg
+    }
+
+    /// Record a price / liquidity observation; returns true if it completes
+    /// a rug pattern against the peaks seen within the window.
+    pub fn observe(&self, mint: &str, ts: i64, price: f64, liq: f64) -> bool {
+        let mut guard = match self.samples.lock() {
+            Ok(guard) => guard,
+            Err(_) => return false,
+        };
+        let window = guard.entry(mint.to_string()).or_default();
+
+        while let Some(front) = window.front() {
+            if front.ts < ts - self.cfg.window_secs {
+                window.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let peak_price = window.iter().map(|s| s.price).fold(0.0, f64::max);
+        let peak_liq = window.iter().map(|s| s.liq).fold(0.0, f64::max);
+        window.push_back(Sample { ts, price, liq });
+
+        if peak_price <= 0.0 || peak_liq <= 0.0 {
+            return false;
+        }
+        let price_drop = (peak_price - price) / peak_price * 100.0;
+        let liq_drop = (peak_liq - liq) / peak_liq * 100.0;
+
+        price_drop >= self.cfg.price_drop_pct && liq_drop >= self.cfg.liq_drop_pct
+    }
+
+    /// Drop tracked samples for a mint (e.g. once the position is closed).
+    pub fn forget(&self, mint: &str) {
+        if let Ok(mut guard) = self.samples.lock() {
+            guard.remove(mint);
+        }
+    }
+}
+
+pub static GLOBAL_RUG_DETECTOR: Lazy<RugDetector> =
+    Lazy::new(|| RugDetector::new(RugConfig::from_env()));
+
+/// Feed an observation to `detector`; on a rug, add the mint to the session
+/// blacklist and log a notification. Returns true only when the mint was
+/// newly blacklisted, so callers can notify once.
+pub fn observe_and_blacklist(
+    detector: &RugDetector,
+    mint: &str,
+    ts: i64,
+    price: f64,
+    liq: f64,
+) -> bool {
+    if !detector.observe(mint, ts, price, liq) {
+        return false;
+    }
+    if !blacklist_mint(mint) {
+        return false;
+    }
+    eprintln!(
+        "[RUG] {} collapsed (price -{}%+ and liquidity -{}%+ within {}s); added to session blacklist",
+        mint, detector.cfg.price_drop_pct, detector.cfg.liq_drop_pct, detector.cfg.window_secs
+    );
+    true
+}
@@ -0,0 +1,205 @@
+//! Graceful shutdown on SIGINT / SIGTERM.
+//!
+//! Without it, Ctrl-C or a deploy kills the process while the positions
+//! registry only exists in memory. [`spawn_shutdown_handler`] waits for
+//! either signal and runs [`shutdown`]: new opens are refused (sells still
+//! go through), in-flight sends get up to SHUTDOWN_DRAIN_SECS to finish,
+//! then the positions registry (POSITIONS_FILE) and session blacklist
+//! (BLACKLIST_FILE) are written to disk and a `shutdown` event is emitted
+//! before the process exits. A second signal exits immediately. With
+//! SHUTDOWN_FORCE_EXIT set, every open position is force-sold first.
+
+use std::env;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::common::bot_config;
+use crate::universal::blacklist::persist_session_blacklist;
+use crate::universal::events::{emit, Event};
+use crate::universal::force_sell::{force_exit, force_seller, live_open_mints};
+use crate::universal::positions::{persist_positions, GLOBAL_POSITIONS_REGISTRY};
+
+/// Default time [`shutdown`] waits for in-flight sends.
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+const DRAIN_POLL: Duration = Duration::from_millis(50);
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+static IN_FLIGHT_SENDS: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether a graceful shutdown has started; no new positions open after.
+pub fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// Refuse new opens from now on. Unlike a pause, this cannot be resumed.
+pub fn begin_shutdown() {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+}
+
+/// Counts a transaction send as in flight until dropped.
+#[must_use]
+pub struct SendGuard {
+    _private: (),
+}
+
+impl Drop for SendGuard {
+    fn drop(&mut self) {
+        IN_FLIGHT_SENDS.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Mark a send as in flight for as long as the returned guard lives.
+pub fn track_send() -> SendGuard {
+    IN_FLIGHT_SENDS.fetch_add(1, Ordering::SeqCst);
+    SendGuard { _private: () }
+}
+
+/// Number of sends currently in flight.
+pub fn in_flight_sends() -> usize {
+    IN_FLIGHT_SENDS.load(Ordering::SeqCst)
+}
+
+/// SHUTDOWN_DRAIN_SECS (default 10): how long a shutdown waits for
+/// in-flight sends.
+pub fn drain_timeout_from_env() -> Duration {
+    let mut timeout = DEFAULT_DRAIN_TIMEOUT;
+    if let Ok(v) = env::var("SHUTDOWN_DRAIN_SECS") {
+        if let Ok(parsed) = v.parse::<u64>() {
+            timeout = Duration::from_secs(parsed);
+        }
+    }
+    timeout
+}
+
+/// Wait until no send is in flight, for at most `timeout`. Returns whether
+/// every send finished.
+pub async fn wait_for_sends(timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if in_flight_sends() == 0 {
+            return true;
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return false;
+        }
+        tokio::time::sleep(DRAIN_POLL.min(deadline - now)).await;
+    }
+}
+
+/// Outcome of [`shutdown`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShutdownReport {
+    pub signal: String,
+    pub open_positions: usize,
+    /// Sends still running when the drain gave up (0 if drained).
+    pub in_flight_sends: usize,
+    pub drained: bool,
+}
+
+/// SHUTDOWN_FORCE_EXIT (default false): force-sell every open position on
+/// shutdown instead of carrying it over to the next run.
+pub fn force_exit_on_shutdown() -> bool {
+    let mut force = false;
+    if let Ok(v) = bot_config::var("SHUTDOWN_FORCE_EXIT") {
+        if let Ok(parsed) = v.parse::<bool>() {
+            force = parsed;
+        }
+    }
+    force
+}
+
+/// Stop new opens, force-sell open positions if SHUTDOWN_FORCE_EXIT is set,
+/// wait up to `drain_timeout` for in-flight sends, persist the positions
+/// registry and session blacklist, and emit a `shutdown` event. Persisting
+/// after the drain captures the sends' final state.
+pub async fn shutdown(signal: &str, drain_timeout: Duration) -> ShutdownReport {
+    begin_shutdown();
+    if force_exit_on_shutdown() {
+        match force_seller() {
+            Some(seller) => {
+                force_exit(
+                    seller.as_ref(),
+                    &live_open_mints(&GLOBAL_POSITIONS_REGISTRY),
+                )
+                .await;
+            }
+            None => eprintln!("Shutdown: SHUTDOWN_FORCE_EXIT set but no force seller installed"),
+        }
+    }
+    let drained = wait_for_sends(drain_timeout).await;
+    persist_positions(&GLOBAL_POSITIONS_REGISTRY);
+    persist_session_blacklist();
+
+    let report = ShutdownReport {
+        signal: signal.to_string(),
+        open_positions: GLOBAL_POSITIONS_REGISTRY.open_count(),
+        in_flight_sends: in_flight_sends(),
+        drained,
+    };
+    emit(Event::Shutdown {
+        ts: chrono::Utc::now().timestamp(),
+        signal: report.signal.clone(),
+        open_positions: report.open_positions,
+        in_flight_sends: report.in_flight_sends,
+        drained: report.drained,
+    });
+    report
+}
+
+/// Resolve on SIGINT or (on unix) SIGTERM, with the signal's name.
+async fn wait_for_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut term) => tokio::select! {
+                _ = tokio::signal::ctrl_c() => "SIGINT",
+                _ = term.recv() => "SIGTERM",
+            },
+            Err(e) => {
+                eprintln!("Failed to install SIGTERM handler: {}", e);
+                let _ = tokio::signal::ctrl_c().await;
+                "SIGINT"
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "SIGINT"
+    }
+}
+
+/// Run [`shutdown`] on the first SIGINT / SIGTERM and exit the process.
+pub fn spawn_shutdown_handler() -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async {
+        let signal = wait_for_signal().await;
+        let timeout = drain_timeout_from_env();
+        println!(
+            "{} received; shutting down (waiting up to {:?} for in-flight sends)",
+            signal, timeout
+        );
+        tokio::select! {
+            report = shutdown(signal, timeout) => {
+                if !report.drained {
+                    eprintln!(
+                        "Shutdown: {} send(s) still in flight after {:?}",
+                        report.in_flight_sends, timeout
+                    );
+                }
+                println!(
+                    "Shutdown complete with {} open position(s)",
+                    report.open_positions
+                );
+                std::process::exit(0);
+            }
+            second = wait_for_signal() => {
+                eprintln!("{} received again; exiting without waiting", second);
+                std::process::exit(1);
+            }
+        }
+    })
+}
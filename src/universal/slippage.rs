@@ -0,0 +1,53 @@
+//! Slippage tolerance to swap-instruction bounds.
+//!
+//! Wallet configs give slippage as a fraction (0.02 = 2%) and `SwapConfig`
+//! carries it in basis points; swap instructions want raw amounts. These
+//! helpers turn an expected output into a `minimum_amount_out` and an input
+//! into a maximum spend. Math is done in `u128` so large amounts neither
+//! overflow nor lose precision, and rounding always favors us: minimum
+//! outputs round up, maximum inputs round down.
+
+const BPS_DENOMINATOR: u128 = 10_000;
+/// Fractions are resolved to parts per billion.
+const PPB_DENOMINATOR: u128 = 1_000_000_000;
+
+fn scale_up(amount: u64, keep: u128, denominator: u128) -> u64 {
+    // keep <= denominator, so the result never exceeds `amount`.
+    ((amount as u128 * keep).div_ceil(denominator)) as u64
+}
+
+/// Least output to accept for `expected_out` at `slippage` (a fraction;
+/// clamped to `[0, 1]`, NaN counts as 0). 100% slippage accepts anything.
+pub fn min_out_with_slippage(expected_out: u64, slippage: f64) -> u64 {
+    let slippage = if slippage.is_nan() {
+        0.0
+    } else {
+        slippage.clamp(0.0, 1.0)
+    };
+    // Truncating the slippage keeps the bound on the protective side.
+    let slippage_ppb = (slippage * PPB_DENOMINATOR as f64) as u128;
+    scale_up(
+        expected_out,
+        PPB_DENOMINATOR - slippage_ppb.min(PPB_DENOMINATOR),
+        PPB_DENOMINATOR,
+    )
+}
+
+/// [`min_out_with_slippage`] with the tolerance in basis points; 10_000 or
+/// more accepts anything.
+pub fn min_out_with_slippage_bps(expected_out: u64, slippage_bps: u64) -> u64 {
+    let slippage_bps = (slippage_bps as u128).min(BPS_DENOMINATOR);
+    scale_up(
+        expected_out,
+        BPS_DENOMINATOR - slippage_bps,
+        BPS_DENOMINATOR,
+    )
+}
+
+/// Most input to spend on a swap quoted at `amount_in` with `slippage_bps`
+/// tolerance, saturating at `u64::MAX`.
+pub fn max_in_with_slippage_bps(amount_in: u64, slippage_bps: u64) -> u64 {
+    let max = (amount_in as u128).saturating_mul(BPS_DENOMINATOR + slippage_bps as u128)
+        / BPS_DENOMINATOR;
+    max.min(u64::MAX as u128) as u64
+}
@@ -1,5 +1,12 @@
+use std::str::FromStr;
 
-use std::collections::HashMap;
+use solana_sdk::pubkey::Pubkey;
+
+use crate::universal::blacklist::{blacklist_mint, persist_session_blacklist, SESSION_BLACKLIST};
+use crate::universal::control;
+use crate::universal::force_sell::{force_seller, live_open_mints, spawn_force_exit};
+use crate::universal::positions::{PositionState, GLOBAL_POSITIONS_REGISTRY};
+use crate::universal::wallet_config::ConfigChange;
 
 #[derive(Clone, Debug)]
 pub enum Ctrl {
@@ -7,29 +14,267 @@ pub enum Ctrl {
     ResumeAll,
     AddWallet(String),
     PauseWallet{wallet:String, what:String},
+    DisableWallet{wallet:String},
+    EnableWallet{wallet:String},
     ExitPosition{mint:String},
+    /// Kill switch: pause new opens and force-sell every open position.
+    ExitAll,
     SetSlippage{wallet:String, bps:u64},
     SetTp{wallet:String, pct:f64},
     SetSl{wallet:String, pct:f64},
     SetRetries{wallet:String, n:u32},
     SetMaxPos{wallet:String, n:u32},
+    ReloadConfig,
+    GateTest{mint:String, size_usd:f64},
+    ListPositions{wallet:Option<String>},
+    Pnl{wallet:Option<String>},
+    Blacklist{mint:String},
+    Unblacklist{mint:String},
 }
 
 /// Parse very simple slash commands. Wire this to your telegram bot update stream.
+///
+/// Commands whose numeric argument does not parse return `None` rather than
+/// falling back to a default, so a typo never applies an unintended value.
 pub fn parse_command(text: &str) -> Option<Ctrl> {
     let t = text.trim();
     let parts: Vec<&str> = t.split_whitespace().collect();
-    match parts.get(0).copied().unwrap_or("") {
+    match parts.first().copied().unwrap_or("") {
         "/pause_all" => Some(Ctrl::PauseAll),
         "/resume_all" => Some(Ctrl::ResumeAll),
         "/add_wallet" if parts.len()>=2 => Some(Ctrl::AddWallet(parts[1].to_string())),
         "/pause" if parts.len()>=3 => Some(Ctrl::PauseWallet{wallet:parts[1].into(), what:parts[2].into()}),
+        "/disable_wallet" if parts.len()>=2 => Some(Ctrl::DisableWallet{wallet:parts[1].into()}),
+        "/enable_wallet" if parts.len()>=2 => Some(Ctrl::EnableWallet{wallet:parts[1].into()}),
         "/exit" if parts.len()>=2 => Some(Ctrl::ExitPosition{mint:parts[1].into()}),
-        "/slip" if parts.len()>=3 => Some(Ctrl::SetSlippage{wallet:parts[1].into(), bps:parts[2].parse().unwrap_or(250)}),
-        "/tp" if parts.len()>=3 => Some(Ctrl::SetTp{wallet:parts[1].into(), pct:parts[2].parse().unwrap_or(5.0)}),
-        "/sl" if parts.len()>=3 => Some(Ctrl::SetSl{wallet:parts[1].into(), pct:parts[2].parse().unwrap_or(12.0)}),
-        "/retries" if parts.len()>=3 => Some(Ctrl::SetRetries{wallet:parts[1].into(), n:parts[2].parse().unwrap_or(3)}),
-        "/maxpos" if parts.len()>=3 => Some(Ctrl::SetMaxPos{wallet:parts[1].into(), n:parts[2].parse().unwrap_or(3)}),
+        "/exit_all" => Some(Ctrl::ExitAll),
+        "/slip" if parts.len()>=3 => parts[2].parse().ok().map(|bps| Ctrl::SetSlippage{wallet:parts[1].into(), bps}),
+        "/tp" if parts.len()>=3 => parts[2].parse::<f64>().ok().filter(|p| p.is_finite()).map(|pct| Ctrl::SetTp{wallet:parts[1].into(), pct}),
+        "/sl" if parts.len()>=3 => parts[2].parse::<f64>().ok().filter(|p| p.is_finite()).map(|pct| Ctrl::SetSl{wallet:parts[1].into(), pct}),
+        "/retries" if parts.len()>=3 => parts[2].parse().ok().map(|n| Ctrl::SetRetries{wallet:parts[1].into(), n}),
+        "/maxpos" if parts.len()>=3 => parts[2].parse().ok().map(|n| Ctrl::SetMaxPos{wallet:parts[1].into(), n}),
+        "/reload_config" => Some(Ctrl::ReloadConfig),
+        "/gate_test" if parts.len()>=3 => parts[2].parse().ok().map(|size_usd| Ctrl::GateTest{mint:parts[1].into(), size_usd}),
+        "/positions" => Some(Ctrl::ListPositions{wallet:parts.get(1).map(|w| w.to_string())}),
+        "/pnl" => Some(Ctrl::Pnl{wallet:parts.get(1).map(|w| w.to_string())}),
+        "/blacklist" if parts.len()>=2 => Some(Ctrl::Blacklist{mint:parts[1].into()}),
+        "/unblacklist" if parts.len()>=2 => Some(Ctrl::Unblacklist{mint:parts[1].into()}),
         _ => None
     }
 }
+
+/// Open positions for `wallet`, or for every wallet when `None`, oldest first.
+fn open_positions_for(wallet: Option<&str>) -> Vec<PositionState> {
+    let mut positions = match wallet {
+        Some(w) => GLOBAL_POSITIONS_REGISTRY.list_open_positions_for_wallet(w),
+        None => GLOBAL_POSITIONS_REGISTRY.all_open_positions(),
+    };
+    positions.sort_by(|a, b| a.opened_ts.cmp(&b.opened_ts).then_with(|| a.mint.cmp(&b.mint)));
+    positions
+}
+
+fn fmt_opt(v: Option<f64>) -> String {
+    v.map(|x| format!("{}", x)).unwrap_or_else(|| "n/a".to_string())
+}
+
+/// Render positions as a multi-line `/positions` reply, one line per position.
+pub fn format_positions(positions: &[PositionState]) -> String {
+    if positions.is_empty() {
+        return "No open positions".to_string();
+    }
+    let mut out = format!("Open positions ({}):", positions.len());
+    for p in positions {
+        out.push_str(&format!(
+            "\n{} {} opened={} size={} entry={}{}",
+            p.wallet,
+            p.mint,
+            p.opened_ts,
+            fmt_opt(p.size),
+            fmt_opt(p.entry_price),
+            if p.simulated { " [sim]" } else { "" }
+        ));
+    }
+    out
+}
+
+/// Render a `/reload_config` reply listing what changed.
+pub fn format_reload(wallets: usize, changes: &[ConfigChange]) -> String {
+    let mut out = format!("Wallet config reloaded ({} wallets)", wallets);
+    if changes.is_empty() {
+        out.push_str(", no changes");
+    }
+    for change in changes {
+        out.push_str(&format!("\n{}", change));
+    }
+    out
+}
+
+/// Render unrealized PnL for `positions` as a `/pnl` reply.
+///
+/// `price_of` returns the current price of a mint (same quote units as
+/// `entry_price`). Positions missing a size, entry or current price are listed
+/// as `n/a` and left out of the total.
+pub fn format_pnl<F>(positions: &[PositionState], price_of: F) -> String
+where
+    F: Fn(&str) -> Option<f64>,
+{
+    if positions.is_empty() {
+        return "No open positions".to_string();
+    }
+    let mut total = 0.0;
+    let mut lines = Vec::with_capacity(positions.len());
+    for p in positions {
+        let line = match (p.size, p.entry_price, price_of(&p.mint)) {
+            (Some(size), Some(entry), Some(price)) if entry > 0.0 => {
+                let pnl = (price - entry) * size;
+                total += pnl;
+                format!("{} {} pnl={:.6} ({:+.2}%)", p.wallet, p.mint, pnl, (price / entry - 1.0) * 100.0)
+            }
+            _ => format!("{} {} pnl=n/a", p.wallet, p.mint),
+        };
+        lines.push(line);
+    }
+    format!("Unrealized PnL: {:.6}\n{}", total, lines.join("\n"))
+}
+
+/// Handle `/positions [wallet]`.
+pub fn handle_positions(wallet: Option<&str>) -> String {
+    format_positions(&open_positions_for(wallet))
+}
+
+/// Handle `/pnl [wallet]` using `price_of` for current mint prices.
+pub fn handle_pnl<F>(wallet: Option<&str>, price_of: F) -> String
+where
+    F: Fn(&str) -> Option<f64>,
+{
+    format_pnl(&open_positions_for(wallet), price_of)
+}
+
+/// Handle `/reload_config`: re-read WALLET_CONFIG_TOML and report the outcome.
+/// A failed reload keeps the previous wallet config in place.
+pub fn handle_reload_config() -> String {
+    match apply_command(Ctrl::ReloadConfig) {
+        Ok(msg) => msg,
+        Err(e) => e,
+    }
+}
+
+/// Execute a parsed command against the live registries.
+///
+/// Returns a human-readable confirmation for the bot to echo, or `Err` with
+/// the reason when the command is unsupported or fails.
+pub fn apply_command(ctrl: Ctrl) -> Result<String, String> {
+    use crate::universal::wallet_config::{
+        reload_from_env_with_diff, set_wallet_enabled, track_wallet, update_wallet_config,
+    };
+
+    match ctrl {
+        Ctrl::ExitPosition { mint } => {
+            // Real positions are force-sold at market, and settled by the
+            // sell, once a force seller is installed. Paper positions, or
+            // any without a seller (tools, tests), are only closed in the
+            // registry.
+            if force_seller().is_some() && live_open_mints(&GLOBAL_POSITIONS_REGISTRY).contains(&mint) {
+                return spawn_force_exit(vec![mint.clone()])
+                    .map(|_| format!("Force selling {}", mint))
+                    .map_err(|e| format!("Could not force sell {}: {}", mint, e));
+            }
+            match GLOBAL_POSITIONS_REGISTRY.close_all_for_mint(&mint) {
+                0 => Err(format!("No open position for {}", mint)),
+                n => Ok(format!("Closed {} position(s) for {}", n, mint)),
+            }
+        }
+        Ctrl::ExitAll => {
+            control::pause_all();
+            let mints = live_open_mints(&GLOBAL_POSITIONS_REGISTRY);
+            if mints.is_empty() {
+                return Ok("Paused; no open positions to sell".to_string());
+            }
+            let n = mints.len();
+            spawn_force_exit(mints)
+                .map(|_| format!("Paused; force selling {} mint(s)", n))
+                .map_err(|e| format!("Paused, but could not force sell: {}", e))
+        }
+        Ctrl::SetSlippage { wallet, bps } => {
+            update_wallet_config(&wallet, |c| c.slippage = Some(bps as f64 / 10_000.0))
+                .map(|_| format!("Slippage for {} set to {} bps", wallet, bps))
+                .map_err(|e| format!("Rejected slippage for {}: {}", wallet, e))
+        }
+        Ctrl::SetTp { wallet, pct } => {
+            // `pct` is the profit target in percent; config stores a multiple of entry.
+            update_wallet_config(&wallet, |c| c.tp = Some(1.0 + pct / 100.0))
+                .map(|_| format!("Take-profit for {} set to +{}%", wallet, pct))
+                .map_err(|e| format!("Rejected take-profit for {}: {}", wallet, e))
+        }
+        Ctrl::SetSl { wallet, pct } => {
+            // `pct` is the tolerated loss in percent; config stores the kept fraction.
+            update_wallet_config(&wallet, |c| c.sl = Some(1.0 - pct / 100.0))
+                .map(|_| format!("Stop-loss for {} set to -{}%", wallet, pct))
+                .map_err(|e| format!("Rejected stop-loss for {}: {}", wallet, e))
+        }
+        Ctrl::SetMaxPos { wallet, n } => update_wallet_config(&wallet, |c| c.max_positions = Some(n))
+            .map(|_| format!("Max positions for {} set to {}", wallet, n))
+            .map_err(|e| format!("Rejected max positions for {}: {}", wallet, e)),
+        Ctrl::AddWallet(wallet) => {
+            if Pubkey::from_str(&wallet).is_err() {
+                return Err(format!("Invalid wallet address: {}", wallet));
+            }
+            if track_wallet(&wallet) {
+                Ok(format!("Now tracking {}", wallet))
+            } else {
+                Ok(format!("Already tracking {}", wallet))
+            }
+        }
+        Ctrl::ReloadConfig => reload_from_env_with_diff()
+            .map(|(n, changes)| format_reload(n, &changes))
+            .map_err(|e| format!("Wallet config reload failed, keeping previous config: {}", e)),
+        Ctrl::SetRetries { wallet, n } => update_wallet_config(&wallet, |c| c.retries = Some(n))
+            .map(|_| format!("Buy retries for {} set to {}", wallet, n))
+            .map_err(|e| format!("Rejected retries for {}: {}", wallet, e)),
+        Ctrl::PauseAll => {
+            control::pause_all();
+            Ok("Paused: no new positions will be opened".to_string())
+        }
+        Ctrl::ResumeAll => {
+            control::resume_all();
+            Ok("Resumed".to_string())
+        }
+        Ctrl::PauseWallet { wallet, what } => match what.as_str() {
+            "on" => {
+                control::pause_wallet(&wallet);
+                Ok(format!("Paused new positions for {}", wallet))
+            }
+            "off" => {
+                control::resume_wallet(&wallet);
+                Ok(format!("Resumed {}", wallet))
+            }
+            other => Err(format!("Unknown pause state '{}', expected on|off", other)),
+        },
+        Ctrl::DisableWallet { wallet } => set_wallet_enabled(&wallet, false)
+            .map(|_| format!("Disabled {}: its trades are no longer copied", wallet))
+            .map_err(|e| format!("Could not disable {}: {}", wallet, e)),
+        Ctrl::EnableWallet { wallet } => set_wallet_enabled(&wallet, true)
+            .map(|_| format!("Enabled {}", wallet))
+            .map_err(|e| format!("Could not enable {}: {}", wallet, e)),
+        Ctrl::GateTest { .. } => {
+            Err("/gate_test needs live market data; run it through GateTestReport".to_string())
+        }
+        Ctrl::ListPositions { wallet } => Ok(handle_positions(wallet.as_deref())),
+        Ctrl::Pnl { .. } => Err("/pnl needs a price lookup; use handle_pnl".to_string()),
+        Ctrl::Blacklist { mint } => {
+            if blacklist_mint(&mint) {
+                Ok(format!("Blacklisted {}", mint))
+            } else {
+                Ok(format!("{} already blacklisted", mint))
+            }
+        }
+        Ctrl::Unblacklist { mint } => {
+            if SESSION_BLACKLIST.remove(&mint) {
+                persist_session_blacklist();
+                Ok(format!("Removed {} from blacklist", mint))
+            } else {
+                Err(format!("{} is not blacklisted", mint))
+            }
+        }
+    }
+}
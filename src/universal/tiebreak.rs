@@ -0,0 +1,65 @@
+//! Deterministic ordering of competing copy signals.
+//!
+//! When several followed wallets buy at (nearly) the same moment and there
+//! are fewer free position slots than signals, the winner must not depend on
+//! channel delivery order. Signals are ranked by wallet trust (higher first),
+//! then slot (earlier first), then wallet pubkey as a final stable key.
+
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+use crate::universal::wallet_config::get_wallet_config_map;
+
+/// A buy signal copied from a followed wallet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CopySignal {
+    pub wallet: String,
+    pub mint: String,
+    pub slot: u64,
+    /// Tie-break priority, usually from the wallet's `trust` config.
+    pub trust: f64,
+}
+
+impl CopySignal {
+    /// Build a signal with `trust` looked up in the global wallet config.
+    pub fn from_config(wallet: &str, mint: &str, slot: u64) -> Self {
+        Self {
+            wallet: wallet.to_string(),
+            mint: mint.to_string(),
+            slot,
+            trust: get_wallet_config_map().get_trust(wallet),
+        }
+    }
+}
+
+/// Total order used for tie-breaking: `Less` means `a` wins over `b`.
+pub fn compare_signals(a: &CopySignal, b: &CopySignal) -> Ordering {
+    b.trust
+        .total_cmp(&a.trust)
+        .then_with(|| a.slot.cmp(&b.slot))
+        .then_with(|| a.wallet.cmp(&b.wallet))
+}
+
+/// Pick which of a batch of simultaneous signals to execute given
+/// `free_slots` open position slots.
+///
+/// At most one signal per mint is executed. Returns `(execute, dropped)`,
+/// each in tie-break order, so the result is independent of input order.
+pub fn resolve_signals(
+    mut signals: Vec<CopySignal>,
+    free_slots: usize,
+) -> (Vec<CopySignal>, Vec<CopySignal>) {
+    signals.sort_by(compare_signals);
+
+    let mut execute = Vec::new();
+    let mut dropped = Vec::new();
+    let mut mints = HashSet::new();
+    for signal in signals {
+        if execute.len() < free_slots && mints.insert(signal.mint.clone()) {
+            execute.push(signal);
+        } else {
+            dropped.push(signal);
+        }
+    }
+    (execute, dropped)
+}
@@ -0,0 +1,293 @@
+//! Take-profit / stop-loss monitor for open positions.
+//!
+//! Every poll the monitor prices each `Open` position with a recorded entry
+//! and compares it against `entry_price * tp` and `entry_price * sl`, using
+//! the wallet's overrides from the wallet config on top of the TAKE_PROFIT /
+//! STOP_LOSS defaults. With a trailing stop configured, each poll also raises
+//! the position's high-water price and exits once the price retraces that
+//! percentage below it. A breached position is marked `Closing`, an
+//! [`Event::ExitTrigger`](crate::universal::events::Event) is emitted, and the
+//! close is handed to an [`ExitExecutor`]. The position is closed once the
+//! sell reaches SELL_MIN_COMMITMENT and reopened otherwise. Prices come from a
+//! pluggable [`PriceSource`].
+
+use std::env;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::BoxFuture;
+
+use crate::universal::events::{emit, Event};
+use crate::universal::positions::{PositionState, PositionStatus, PositionsRegistry, SellCommitment};
+use crate::universal::wallet_config::get_wallet_config_map;
+
+/// Current price for a mint, in the same units as the positions' entry price.
+pub trait PriceSource: Send + Sync {
+    fn price(&self, mint: &str) -> Option<f64>;
+}
+
+impl<F> PriceSource for F
+where
+    F: Fn(&str) -> Option<f64> + Send + Sync,
+{
+    fn price(&self, mint: &str) -> Option<f64> {
+        self(mint)
+    }
+}
+
+/// Which threshold a position crossed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitTrigger {
+    TakeProfit,
+    StopLoss,
+    TrailingStop,
+}
+
+impl fmt::Display for ExitTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExitTrigger::TakeProfit => write!(f, "take_profit"),
+            ExitTrigger::StopLoss => write!(f, "stop_loss"),
+            ExitTrigger::TrailingStop => write!(f, "trailing_stop"),
+        }
+    }
+}
+
+/// A position that crossed its TP, SL or trailing stop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExitSignal {
+    pub wallet: String,
+    pub mint: String,
+    pub trigger: ExitTrigger,
+    pub entry_price: f64,
+    pub price: f64,
+    /// The price level that was crossed.
+    pub threshold: f64,
+}
+
+/// Sells a position the monitor flagged and has already marked `Closing`.
+/// Resolve with the commitment the sell reached (`None` if it never landed);
+/// short of SELL_MIN_COMMITMENT, or on `Err`, the position is reopened and
+/// retried next poll.
+pub trait ExitExecutor: Send + Sync {
+    fn close<'a>(
+        &'a self,
+        signal: &'a ExitSignal,
+    ) -> BoxFuture<'a, Result<Option<SellCommitment>, String>>;
+}
+
+/// Check one position against multiples `tp` / `sl` of its entry price and,
+/// if `trailing` is `Some((high_water, pct))`, against a stop `pct` percent
+/// below the high-water price. Stop-loss wins over the trailing stop, which
+/// wins over take-profit.
+pub fn check_exit(
+    entry_price: f64,
+    price: f64,
+    tp: f64,
+    sl: f64,
+    trailing: Option<(f64, f64)>,
+) -> Option<(ExitTrigger, f64)> {
+    if entry_price <= 0.0 || !price.is_finite() {
+        return None;
+    }
+    let sl_level = entry_price * sl;
+    let tp_level = entry_price * tp;
+    let trail_level = trailing
+        .filter(|(high, pct)| *high > 0.0 && *pct > 0.0)
+        .map(|(high, pct)| high * (1.0 - pct / 100.0));
+    if sl > 0.0 && price <= sl_level {
+        Some((ExitTrigger::StopLoss, sl_level))
+    } else if let Some(level) = trail_level.filter(|level| price <= *level) {
+        Some((ExitTrigger::TrailingStop, level))
+    } else if tp > 0.0 && price >= tp_level {
+        Some((ExitTrigger::TakeProfit, tp_level))
+    } else {
+        None
+    }
+}
+
+/// Monitor settings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TpSlConfig {
+    pub interval: Duration,
+    /// Default take-profit multiple (e.g. 1.25 = +25%).
+    pub tp: f64,
+    /// Default stop-loss multiple (e.g. 0.7 = -30%).
+    pub sl: f64,
+    /// Default trailing stop, in percent below the high-water price.
+    pub trailing_stop_pct: Option<f64>,
+}
+
+impl Default for TpSlConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            tp: 1.25,
+            sl: 0.7,
+            trailing_stop_pct: None,
+        }
+    }
+}
+
+impl TpSlConfig {
+    /// Build from TP_SL_POLL_SECS (default 5), the global TAKE_PROFIT /
+    /// STOP_LOSS percentages (defaults 25 / -30) and TRAILING_STOP_PCT
+    /// (default off).
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+        if let Ok(v) = env::var("TP_SL_POLL_SECS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                cfg.interval = Duration::from_secs(parsed.max(1));
+            }
+        }
+        if let Ok(v) = env::var("TAKE_PROFIT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.tp = 1.0 + parsed / 100.0;
+            }
+        }
+        if let Ok(v) = env::var("STOP_LOSS") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.sl = 1.0 + parsed / 100.0;
+            }
+        }
+        if let Ok(v) = env::var("TRAILING_STOP_PCT") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                if parsed > 0.0 && parsed < 100.0 {
+                    cfg.trailing_stop_pct = Some(parsed);
+                }
+            }
+        }
+        cfg
+    }
+}
+
+/// Whether TP_SL_MONITOR is enabled (`1` or `true`; default off).
+pub fn tp_sl_monitor_enabled() -> bool {
+    match env::var("TP_SL_MONITOR") {
+        Ok(v) => matches!(v.trim(), "1" | "true"),
+        Err(_) => false,
+    }
+}
+
+pub struct TpSlMonitor {
+    registry: &'static PositionsRegistry,
+    prices: Arc<dyn PriceSource>,
+    executor: Arc<dyn ExitExecutor>,
+    cfg: TpSlConfig,
+}
+
+impl TpSlMonitor {
+    pub fn new(
+        registry: &'static PositionsRegistry,
+        prices: Arc<dyn PriceSource>,
+        executor: Arc<dyn ExitExecutor>,
+        cfg: TpSlConfig,
+    ) -> Self {
+        Self {
+            registry,
+            prices,
+            executor,
+            cfg,
+        }
+    }
+
+    pub fn config(&self) -> &TpSlConfig {
+        &self.cfg
+    }
+
+    fn signal_for(&self, pos: &PositionState) -> Option<ExitSignal> {
+        if pos.status != PositionStatus::Open {
+            return None;
+        }
+        let entry_price = pos.entry_price?;
+        let price = self.prices.price(&pos.mint)?;
+        let params = get_wallet_config_map().resolve_params_for_wallet(
+            &pos.wallet,
+            0.0,
+            self.cfg.tp,
+            self.cfg.sl,
+            None,
+        );
+        let trailing = params
+            .trailing_stop_pct
+            .or(self.cfg.trailing_stop_pct)
+            .and_then(|pct| {
+                self.registry
+                    .update_high_water(&pos.wallet, &pos.mint, price)
+                    .map(|high| (high, pct))
+            });
+        let (trigger, threshold) =
+            check_exit(entry_price, price, params.tp, params.sl, trailing)?;
+        Some(ExitSignal {
+            wallet: pos.wallet.clone(),
+            mint: pos.mint.clone(),
+            trigger,
+            entry_price,
+            price,
+            threshold,
+        })
+    }
+
+    /// Positions currently past their TP, SL or trailing stop, without
+    /// closing them. Raises high-water prices as a side effect.
+    pub fn scan(&self) -> Vec<ExitSignal> {
+        self.registry
+            .all_open_positions()
+            .iter()
+            .filter_map(|pos| self.signal_for(pos))
+            .collect()
+    }
+
+    /// One poll: mark each breached position `Closing`, emit its trigger
+    /// event, sell it through the executor and settle it on the commitment
+    /// the sell reached. Returns the signals acted on.
+    pub async fn tick(&self) -> Vec<ExitSignal> {
+        let mut fired = Vec::new();
+        for signal in self.scan() {
+            // Another exit path may have claimed it since the scan.
+            if self.registry.begin_close(&signal.wallet, &signal.mint).is_err() {
+                continue;
+            }
+            emit(Event::ExitTrigger {
+                ts: chrono::Utc::now().timestamp(),
+                wallet: signal.wallet.clone(),
+                mint: signal.mint.clone(),
+                trigger: signal.trigger.to_string(),
+                entry_price: signal.entry_price,
+                price: signal.price,
+                threshold: signal.threshold,
+            });
+            let reached = match self.executor.close(&signal).await {
+                Ok(reached) => reached,
+                Err(e) => {
+                    eprintln!(
+                        "[TP/SL] {} exit for {} failed: {}; will retry",
+                        signal.trigger, signal.mint, e
+                    );
+                    None
+                }
+            };
+            let _ = self.registry.settle_close(
+                &signal.wallet,
+                &signal.mint,
+                reached,
+                SellCommitment::from_env(),
+                Some(signal.price),
+            );
+            fired.push(signal);
+        }
+        fired
+    }
+}
+
+/// Run `monitor` on its configured interval in a background tokio task.
+pub fn spawn_tp_sl_monitor(monitor: Arc<TpSlMonitor>) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(monitor.config().interval);
+        loop {
+            ticker.tick().await;
+            monitor.tick().await;
+        }
+    })
+}
@@ -5,22 +5,150 @@
 
 pub mod types;
 
-pub use types::{WalletConfig, WalletConfigMap, EffectiveWalletParams};
+pub use types::{ConfigChange, CopyMode, WalletConfig, WalletConfigMap, EffectiveWalletParams};
 use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
 
 /// Global wallet-configuration map.
 ///
-/// Phase 1: populated via `from_env_or_empty()`, which currently returns an empty map.
-/// Later phases will load from env/config (e.g. WALLET_CONFIG_TOML) and possibly
-/// expose additional helper accessors.
-pub static GLOBAL_WALLET_CONFIGS: Lazy<WalletConfigMap> =
-    Lazy::new(|| WalletConfigMap::from_env_or_empty());
+/// Populated via `from_env_or_empty()` at first use and swapped atomically by
+/// [`reload_from_env`] / [`reload_from_str`], so per-wallet slippage/TP/SL can
+/// change without a restart. Readers take a cheap `Arc` snapshot.
+pub static GLOBAL_WALLET_CONFIGS: Lazy<RwLock<Arc<WalletConfigMap>>> =
+    Lazy::new(|| RwLock::new(Arc::new(WalletConfigMap::from_env_or_empty())));
 
-/// Accessor for the global wallet-configuration map.
+/// Snapshot of the global wallet-configuration map.
 #[inline]
-pub fn get_wallet_config_map() -> &'static WalletConfigMap {
-    &GLOBAL_WALLET_CONFIGS
+pub fn get_wallet_config_map() -> Arc<WalletConfigMap> {
+    match GLOBAL_WALLET_CONFIGS.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Re-parse `raw` and atomically swap it in as the global map.
+///
+/// On bad TOML the existing map is left untouched and the parse error is
+/// returned. Returns the number of wallets in the new map.
+pub fn reload_from_str(raw: &str) -> Result<usize, String> {
+    reload_from_str_with_diff(raw).map(|(count, _)| count)
+}
+
+/// Like [`reload_from_str`], but also returns what changed relative to the
+/// previous map. Each change is logged as an audit trail of live edits.
+pub fn reload_from_str_with_diff(raw: &str) -> Result<(usize, Vec<ConfigChange>), String> {
+    let map = WalletConfigMap::from_toml_str(raw)
+        .map_err(|e| format!("Failed to parse wallet config: {e}"))?;
+    let count = map.len();
+    let mut guard = match GLOBAL_WALLET_CONFIGS.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    let changes = guard.diff(&map);
+    *guard = Arc::new(map);
+    drop(guard);
+    for change in &changes {
+        println!("[CONFIG] {change}");
+    }
+    Ok((count, changes))
+}
+
+/// Apply `f` to one wallet's config (created empty if missing) and swap the
+/// updated map in atomically.
+///
+/// The edited entry must pass [`WalletConfig::validate`]; otherwise the map is
+/// left untouched and the problems are returned.
+pub fn update_wallet_config<F>(wallet: &str, f: F) -> Result<WalletConfig, String>
+where
+    F: FnOnce(&mut WalletConfig),
+{
+    let mut guard = match GLOBAL_WALLET_CONFIGS.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    let mut cfg = guard.get_or_default(wallet);
+    f(&mut cfg);
+    let problems = cfg.validate();
+    if !problems.is_empty() {
+        return Err(problems.join("; "));
+    }
+
+    let mut map = (**guard).clone();
+    map.insert(wallet.to_string(), cfg.clone());
+    *guard = Arc::new(map);
+    Ok(cfg)
+}
+
+/// Wallets added at runtime (e.g. via Telegram `/add_wallet`) to copy-trade.
+///
+/// Whether each one is actually copied is its `enabled` config override;
+/// see [`set_wallet_enabled`].
+pub static TRACKED_WALLETS: Lazy<RwLock<HashSet<String>>> =
+    Lazy::new(|| RwLock::new(HashSet::new()));
+
+/// Add a wallet to the tracked set; returns true if it was newly added.
+pub fn track_wallet(wallet: &str) -> bool {
+    match TRACKED_WALLETS.write() {
+        Ok(mut guard) => guard.insert(wallet.to_string()),
+        Err(poisoned) => poisoned.into_inner().insert(wallet.to_string()),
+    }
 }
+
+/// Snapshot of the tracked-wallet set.
+pub fn tracked_wallets() -> Vec<String> {
+    TRACKED_WALLETS
+        .read()
+        .map(|g| g.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Turn copying from `wallet` on or off, keeping the rest of its config.
+///
+/// The flag is stored as the wallet's `enabled` override, so it lives next
+/// to slippage, TP/SL etc. and a config file can set it too.
+pub fn set_wallet_enabled(wallet: &str, enabled: bool) -> Result<WalletConfig, String> {
+    update_wallet_config(wallet, |c| c.enabled = Some(enabled))
+}
+
+/// Whether trades from `wallet` are copied; true unless it was disabled.
+#[inline]
+pub fn is_wallet_enabled(wallet: &str) -> bool {
+    get_wallet_config_map().is_enabled(wallet)
+}
+
+/// Tracked wallets with their enabled flag, sorted by wallet.
+pub fn tracked_wallet_states() -> Vec<(String, bool)> {
+    let map = get_wallet_config_map();
+    let mut states: Vec<(String, bool)> = tracked_wallets()
+        .into_iter()
+        .map(|wallet| {
+            let enabled = map.is_enabled(&wallet);
+            (wallet, enabled)
+        })
+        .collect();
+    states.sort();
+    states
+}
+
+/// Re-read WALLET_CONFIG_TOML / WALLET_CONFIG_PATH and swap it in; see
+/// [`reload_from_str`].
+///
+/// A missing source or unreadable file is reported as an error rather than
+/// clearing the current map.
+pub fn reload_from_env() -> Result<usize, String> {
+    reload_from_env_with_diff().map(|(count, _)| count)
+}
+
+/// Like [`reload_from_env`], returning the changes; see [`reload_from_str_with_diff`].
+pub fn reload_from_env_with_diff() -> Result<(usize, Vec<ConfigChange>), String> {
+    match WalletConfigMap::toml_source_from_env()? {
+        Some(raw) => reload_from_str_with_diff(&raw),
+        None => Err("neither WALLET_CONFIG_TOML nor WALLET_CONFIG_PATH is set".to_string()),
+    }
+}
+
 /// Return the configured `max_positions` for this wallet, if any.
 ///
 /// This is just a convenience wrapper around the global wallet-config map.
@@ -29,12 +157,12 @@ pub fn effective_max_positions_for_wallet(wallet: &str) -> Option<u32> {
     get_wallet_config_map().get_max_positions(wallet)
 }
 
-/// Count how many open positions this wallet currently has.
-///
-/// Phase 1: this is a stub that always returns 0. Later phases can wire this
-/// into the real positions registry once the module path is stable.
-pub fn open_position_count_for_wallet(_wallet: &str) -> usize {
-    0
+/// Count how many open positions this wallet currently has in the global
+/// positions registry.
+pub fn open_position_count_for_wallet(wallet: &str) -> usize {
+    crate::universal::positions::GLOBAL_POSITIONS_REGISTRY
+        .list_open_positions_for_wallet(wallet)
+        .len()
 }
 
 /// Whether this wallet is allowed to open at least one more position under the
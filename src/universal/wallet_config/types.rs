@@ -1,18 +1,72 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::env;
+use std::fmt;
+use std::fs;
 
 use serde::Deserialize;
 
-/// Per-wallet configuration (slippage, TP/SL, max open positions).
+use crate::common::bot_config::FileSettings;
+
+/// Buy attempts when a wallet sets no `retries` override.
+pub const DEFAULT_RETRIES: u32 = 3;
+
+/// Accepted values of `landing_mode`.
+pub const LANDING_MODES: [&str; 2] = ["zeroslot", "normal"];
+
+/// Which of a leader's trades are copied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyMode {
+    /// Mirror both buys and sells.
+    #[default]
+    All,
+    /// Mirror buys only; exits are left to TP/SL and the other exit rules.
+    BuysOnly,
+    /// Mirror sells only, for positions opened some other way.
+    SellsOnly,
+    /// Never mirror buys; close the whole position whenever the leader sells.
+    InverseSells,
+}
+
+impl CopyMode {
+    /// Snake-case name, as written in the wallet config.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CopyMode::All => "all",
+            CopyMode::BuysOnly => "buys_only",
+            CopyMode::SellsOnly => "sells_only",
+            CopyMode::InverseSells => "inverse_sells",
+        }
+    }
+}
+
+impl fmt::Display for CopyMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Per-wallet configuration (slippage, TP/SL, max open positions, sizing).
 ///
-/// Loaded from a TOML blob in the WALLET_CONFIG_TOML environment variable,
-/// shaped like:
+/// Loaded from a TOML blob in the WALLET_CONFIG_TOML environment variable
+/// (or a file named by WALLET_CONFIG_PATH), shaped like:
 ///
 /// [wallets.<pubkey>]
 /// slippage = 0.02
 /// tp       = 1.25
 /// sl       = 0.20
 /// max_positions = 2
+/// notional_usd = 50.0            # fixed buy size, or
+/// notional_pct_of_leader = 25.0  # mirror 25% of the leader's trade size
+/// trust = 0.9                    # tie-break priority vs. other wallets
+/// trailing_stop_pct = 15.0       # exit 15% below the position's high-water price
+/// max_daily_loss_usd = 200.0     # stop copy-buys after losing $200 today (UTC)
+/// max_buys_per_min = 5           # at most 5 copy-buys from this wallet per minute
+/// retries = 5                    # buy attempts before giving up
+/// landing_mode = "normal"        # send copies through normal RPC ("zeroslot" | "normal")
+/// copy_mode = "buys_only"        # "all" | "buys_only" | "sells_only" | "inverse_sells"
+/// min_leader_trade_usd = 20.0    # ignore leader trades worth less than $20
+/// enabled = false                # keep the config but stop copying this wallet
 #[derive(Debug, Clone, Deserialize)]
 pub struct WalletConfig {
     /// Per-wallet slippage as a fraction, e.g. 0.02 = 2%.
@@ -23,6 +77,34 @@ pub struct WalletConfig {
     pub sl: Option<f64>,
     /// Maximum simultaneously open positions for this wallet.
     pub max_positions: Option<u32>,
+    /// Fixed buy size in USD when copying this wallet.
+    pub notional_usd: Option<f64>,
+    /// Buy size as a percentage of the leader's trade size, e.g. 25.0 = 25%.
+    /// Ignored when `notional_usd` is also set.
+    pub notional_pct_of_leader: Option<f64>,
+    /// Priority when several wallets compete for the same position slot;
+    /// higher wins. Unset counts as 0.0.
+    pub trust: Option<f64>,
+    /// Trailing stop as a percentage below the position's high-water price,
+    /// e.g. 15.0 = exit after a 15% retrace from the peak.
+    pub trailing_stop_pct: Option<f64>,
+    /// Realized loss (USD, positive) after which new buys copied from this
+    /// wallet stop for the rest of the UTC day.
+    pub max_daily_loss_usd: Option<f64>,
+    /// Maximum copy-buys from this wallet per 60s window, overriding
+    /// MAX_BUYS_PER_MIN.
+    pub max_buys_per_min: Option<u32>,
+    /// Buy attempts for trades copied from this wallet.
+    pub retries: Option<u32>,
+    /// Landing path for this wallet's copies: "zeroslot" or "normal".
+    pub landing_mode: Option<String>,
+    /// Which of this wallet's trades are copied; [`CopyMode::All`] when unset.
+    pub copy_mode: Option<CopyMode>,
+    /// Leader trades below this notional (USD) are not copied.
+    pub min_leader_trade_usd: Option<f64>,
+    /// `false` stops copying this wallet without dropping its config;
+    /// enabled when unset. Unlike a pause this is part of the config.
+    pub enabled: Option<bool>,
 }
 
 impl WalletConfig {
@@ -34,8 +116,100 @@ impl WalletConfig {
             tp: None,
             sl: None,
             max_positions: None,
+            notional_usd: None,
+            notional_pct_of_leader: None,
+            trust: None,
+            trailing_stop_pct: None,
+            max_daily_loss_usd: None,
+            max_buys_per_min: None,
+            retries: None,
+            landing_mode: None,
+            copy_mode: None,
+            min_leader_trade_usd: None,
+            enabled: None,
         }
     }
+
+    /// Check overrides for nonsensical values, returning one message per problem.
+    ///
+    /// - slippage must be in (0.0, 1.0)
+    /// - tp must be > 1.0
+    /// - sl must be in (0.0, 1.0)
+    /// - max_positions must be > 0
+    /// - trust must be finite
+    /// - trailing_stop_pct must be in (0.0, 100.0)
+    /// - max_daily_loss_usd must be > 0.0
+    /// - max_buys_per_min must be > 0
+    /// - retries must be > 0
+    /// - landing_mode must be "zeroslot" or "normal"
+    /// - min_leader_trade_usd must be >= 0.0
+    pub fn validate(&self) -> Vec<String> {
+        // NaN fails every comparison, so it is rejected by `in_open_unit`.
+        fn in_open_unit(v: f64) -> bool {
+            v > 0.0 && v < 1.0
+        }
+
+        let mut problems = Vec::new();
+
+        if let Some(slippage) = self.slippage {
+            if !in_open_unit(slippage) {
+                problems.push(format!("slippage {} not in (0.0, 1.0)", slippage));
+            }
+        }
+        if let Some(tp) = self.tp {
+            if tp.is_nan() || tp <= 1.0 {
+                problems.push(format!("tp {} must be > 1.0", tp));
+            }
+        }
+        if let Some(sl) = self.sl {
+            if !in_open_unit(sl) {
+                problems.push(format!("sl {} not in (0.0, 1.0)", sl));
+            }
+        }
+        if let Some(max_positions) = self.max_positions {
+            if max_positions == 0 {
+                problems.push("max_positions must be > 0".to_string());
+            }
+        }
+        if let Some(trust) = self.trust {
+            if !trust.is_finite() {
+                problems.push(format!("trust {} must be finite", trust));
+            }
+        }
+        if let Some(pct) = self.trailing_stop_pct {
+            if !in_open_unit(pct / 100.0) {
+                problems.push(format!("trailing_stop_pct {} not in (0.0, 100.0)", pct));
+            }
+        }
+        if let Some(limit) = self.max_daily_loss_usd {
+            if limit.is_nan() || limit <= 0.0 {
+                problems.push(format!("max_daily_loss_usd {} must be > 0.0", limit));
+            }
+        }
+        if let Some(max_buys) = self.max_buys_per_min {
+            if max_buys == 0 {
+                problems.push("max_buys_per_min must be > 0".to_string());
+            }
+        }
+        if let Some(retries) = self.retries {
+            if retries == 0 {
+                problems.push("retries must be > 0".to_string());
+            }
+        }
+        if let Some(mode) = &self.landing_mode {
+            if !LANDING_MODES.contains(&mode.as_str()) {
+                problems.push(format!("landing_mode {:?} not one of {:?}", mode, LANDING_MODES));
+            }
+        }
+
+        if let Some(min_usd) = self.min_leader_trade_usd {
+            if !min_usd.is_finite() || min_usd < 0.0 {
+                problems.push(format!("min_leader_trade_usd {} must be >= 0.0", min_usd));
+            }
+        }
+
+        problems
+    }
 }
 
 /// Helper struct that mirrors the TOML layout:
@@ -48,8 +222,63 @@ struct WalletConfigFile {
     wallets: HashMap<String, WalletConfig>,
 }
 
+impl WalletConfig {
+    /// Each override as `(name, value)`, formatted for display.
+    fn fields(&self) -> [(&'static str, Option<String>); 15] {
+        fn show<T: ToString>(v: Option<T>) -> Option<String> {
+            v.map(|v| v.to_string())
+        }
+        [
+            ("slippage", show(self.slippage)),
+            ("tp", show(self.tp)),
+            ("sl", show(self.sl)),
+            ("max_positions", show(self.max_positions)),
+            ("notional_usd", show(self.notional_usd)),
+            ("notional_pct_of_leader", show(self.notional_pct_of_leader)),
+            ("trust", show(self.trust)),
+            ("trailing_stop_pct", show(self.trailing_stop_pct)),
+            ("max_daily_loss_usd", show(self.max_daily_loss_usd)),
+            ("max_buys_per_min", show(self.max_buys_per_min)),
+            ("retries", show(self.retries)),
+            ("landing_mode", show(self.landing_mode.clone())),
+            ("copy_mode", show(self.copy_mode)),
+            ("min_leader_trade_usd", show(self.min_leader_trade_usd)),
+            ("enabled", show(self.enabled)),
+        ]
+    }
+}
+
+/// One difference between two wallet-config maps; see [`WalletConfigMap::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigChange {
+    WalletAdded { wallet: String },
+    WalletRemoved { wallet: String },
+    /// An override changed; `None` means unset.
+    FieldChanged {
+        wallet: String,
+        field: &'static str,
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigChange::WalletAdded { wallet } => write!(f, "+ wallet {wallet}"),
+            ConfigChange::WalletRemoved { wallet } => write!(f, "- wallet {wallet}"),
+            ConfigChange::FieldChanged { wallet, field, from, to } => write!(
+                f,
+                "~ {wallet}.{field}: {} -> {}",
+                from.as_deref().unwrap_or("unset"),
+                to.as_deref().unwrap_or("unset")
+            ),
+        }
+    }
+}
+
 /// Map from wallet pubkey (string) to its configuration.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct WalletConfigMap {
     inner: HashMap<String, WalletConfig>,
 }
@@ -113,35 +342,177 @@ impl WalletConfigMap {
         self.get(wallet).and_then(|cfg| cfg.max_positions)
     }
 
-    /// Load from WALLET_CONFIG_TOML env var, or return an empty map on failure.
-    ///
-    /// Expected TOML shape:
+    /// Convenience: get just the daily loss limit override for a wallet, if any.
+    #[inline]
+    pub fn get_max_daily_loss_usd(&self, wallet: &str) -> Option<f64> {
+        self.get(wallet).and_then(|cfg| cfg.max_daily_loss_usd)
+    }
+
+    /// Convenience: get just the buy rate limit override for a wallet, if any.
+    #[inline]
+    pub fn get_max_buys_per_min(&self, wallet: &str) -> Option<u32> {
+        self.get(wallet).and_then(|cfg| cfg.max_buys_per_min)
+    }
+
+    /// Convenience: copy mode for a wallet ([`CopyMode::All`] when unset).
+    #[inline]
+    pub fn get_copy_mode(&self, wallet: &str) -> CopyMode {
+        self.get(wallet).and_then(|cfg| cfg.copy_mode).unwrap_or_default()
+    }
+
+    /// Convenience: minimum leader trade notional for a wallet (0.0 when unset).
+    #[inline]
+    pub fn get_min_leader_trade_usd(&self, wallet: &str) -> f64 {
+        self.get(wallet).and_then(|cfg| cfg.min_leader_trade_usd).unwrap_or(0.0)
+    }
+
+    /// Convenience: whether copying from a wallet is enabled (true when unset).
+    #[inline]
+    pub fn is_enabled(&self, wallet: &str) -> bool {
+        self.get(wallet).and_then(|cfg| cfg.enabled).unwrap_or(true)
+    }
+
+    /// Convenience: tie-break trust for a wallet (0.0 when unset).
+    #[inline]
+    pub fn get_trust(&self, wallet: &str) -> f64 {
+        self.get(wallet).and_then(|cfg| cfg.trust).unwrap_or(0.0)
+    }
+
+    /// Parse a TOML blob shaped like the example below. Wallet entries that
+    /// fail [`WalletConfig::validate`] are logged and skipped.
     ///
     /// [wallets.<pubkey>]
     /// slippage = 0.02
     /// tp       = 1.25
     /// sl       = 0.20
     /// max_positions = 2
+    /// notional_usd = 50.0
+    pub fn from_toml_str(raw: &str) -> Result<Self, toml::de::Error> {
+        let file = toml::from_str::<WalletConfigFile>(raw)?;
+        let mut map = WalletConfigMap::new();
+        for (wallet, cfg) in file.wallets {
+            let problems = cfg.validate();
+            if !problems.is_empty() {
+                eprintln!(
+                    "Skipping invalid wallet config for {wallet}: {}",
+                    problems.join("; ")
+                );
+                continue;
+            }
+            if cfg.notional_usd.is_some() && cfg.notional_pct_of_leader.is_some() {
+                eprintln!(
+                    "Wallet {wallet} sets both notional_usd and notional_pct_of_leader; using notional_usd"
+                );
+            }
+            map.insert(wallet, cfg);
+        }
+        Ok(map)
+    }
+
+    /// Locate the wallet-config TOML from the environment.
+    ///
+    /// - WALLET_CONFIG_TOML: inline TOML blob
+    /// - WALLET_CONFIG_PATH: path to a `.toml` file with the same shape
+    /// - otherwise the `[wallets]` section of the BOT_CONFIG_PATH file
+    ///
+    /// If both env vars are set the inline blob wins and a warning is logged.
+    /// Returns `Ok(None)` when none is set and `Err` when a file cannot be
+    /// read (missing, permissions, ...).
+    pub fn toml_source_from_env() -> Result<Option<String>, String> {
+        let inline = env::var("WALLET_CONFIG_TOML")
+            .ok()
+            .filter(|raw| !raw.trim().is_empty());
+        let path = env::var("WALLET_CONFIG_PATH")
+            .ok()
+            .filter(|p| !p.trim().is_empty());
+
+        match (inline, path) {
+            (Some(raw), Some(path)) => {
+                eprintln!(
+                    "Both WALLET_CONFIG_TOML and WALLET_CONFIG_PATH are set; using inline WALLET_CONFIG_TOML and ignoring {path}"
+                );
+                Ok(Some(raw))
+            }
+            (Some(raw), None) => Ok(Some(raw)),
+            (None, Some(path)) => fs::read_to_string(path.trim())
+                .map(Some)
+                .map_err(|e| format!("Failed to read WALLET_CONFIG_PATH {path}: {e}")),
+            (None, None) => {
+                FileSettings::from_env_path().map(|file| file.wallets_toml().map(String::from))
+            }
+        }
+    }
+
+    /// Load from WALLET_CONFIG_TOML / WALLET_CONFIG_PATH or the bot config
+    /// file's `[wallets]`; empty when none is set. Unlike
+    /// [`from_env_or_empty`](Self::from_env_or_empty), an unreadable or
+    /// invalid source is an error.
+    pub fn from_config() -> Result<Self, String> {
+        match Self::toml_source_from_env()? {
+            Some(raw) => Self::from_toml_str(&raw)
+                .map_err(|e| format!("invalid wallet config TOML: {}", e)),
+            None => Ok(Self::empty()),
+        }
+    }
+
+    /// Load from WALLET_CONFIG_TOML / WALLET_CONFIG_PATH, or return an empty
+    /// map on failure.
+    ///
+    /// See [`WalletConfigMap::from_toml_str`] for the expected TOML shape.
     pub fn from_env_or_empty() -> Self {
-        match env::var("WALLET_CONFIG_TOML") {
-            Ok(raw) if !raw.trim().is_empty() => {
-                match toml::from_str::<WalletConfigFile>(&raw) {
-                    Ok(file) => {
-                        let mut map = WalletConfigMap::new();
-                        for (wallet, cfg) in file.wallets {
-                            map.insert(wallet, cfg);
+        match Self::toml_source_from_env() {
+            Ok(Some(raw)) => match Self::from_toml_str(&raw) {
+                Ok(map) => map,
+                Err(err) => {
+                    // Avoid panicking on bad config; just log and fallback.
+                    eprintln!("Failed to parse wallet config TOML: {err}");
+                    WalletConfigMap::empty()
+                }
+            },
+            Ok(None) => WalletConfigMap::empty(),
+            Err(err) => {
+                eprintln!("{err}");
+                WalletConfigMap::empty()
+            }
+        }
+    }
+
+    /// Number of wallets with overrides.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Changes going from `self` to `other`, ordered by wallet: added and
+    /// removed wallets, then each changed field of wallets present in both.
+    pub fn diff(&self, other: &WalletConfigMap) -> Vec<ConfigChange> {
+        let wallets: BTreeSet<&String> = self.inner.keys().chain(other.inner.keys()).collect();
+        let mut changes = Vec::new();
+        for wallet in wallets {
+            match (self.inner.get(wallet), other.inner.get(wallet)) {
+                (None, Some(_)) => changes.push(ConfigChange::WalletAdded { wallet: wallet.clone() }),
+                (Some(_), None) => {
+                    changes.push(ConfigChange::WalletRemoved { wallet: wallet.clone() })
+                }
+                (Some(old), Some(new)) => {
+                    for ((field, from), (_, to)) in old.fields().into_iter().zip(new.fields()) {
+                        if from != to {
+                            changes.push(ConfigChange::FieldChanged {
+                                wallet: wallet.clone(),
+                                field,
+                                from,
+                                to,
+                            });
                         }
-                        map
-                    }
-                    Err(err) => {
-                        // Avoid panicking on bad config; just log and fallback.
-                        eprintln!("Failed to parse WALLET_CONFIG_TOML: {err}");
-                        WalletConfigMap::empty()
                     }
                 }
+                (None, None) => {}
             }
-            _ => WalletConfigMap::empty(),
         }
+        changes
     }
 }
 /// Fully-resolved per-wallet parameters combining defaults and overrides.
@@ -160,6 +531,24 @@ pub struct EffectiveWalletParams {
     /// Final max open-positions limit for this wallet.
     /// None means "no explicit limit" at the config layer.
     pub max_positions: Option<u32>,
+    /// Fixed buy size in USD. At most one of `notional_usd` and
+    /// `notional_pct_of_leader` is set; both None means "use the global
+    /// buy amount".
+    pub notional_usd: Option<f64>,
+    /// Buy size as a percentage of the leader's trade size.
+    pub notional_pct_of_leader: Option<f64>,
+    /// Trailing stop below the high-water price, in percent. None = no
+    /// trailing stop from the wallet config.
+    pub trailing_stop_pct: Option<f64>,
+    /// Buy attempts before giving up; [`DEFAULT_RETRIES`] unless overridden.
+    pub retries: u32,
+    /// Landing path override ("zeroslot" | "normal"). None = the caller's
+    /// default path.
+    pub landing_mode: Option<String>,
+    /// Which of the leader's trades to copy.
+    pub copy_mode: CopyMode,
+    /// Minimum leader trade notional (USD) worth copying; 0.0 copies all.
+    pub min_leader_trade_usd: f64,
 }
 
 impl WalletConfigMap {
@@ -169,6 +558,12 @@ impl WalletConfigMap {
     /// For each field:
     /// - If the wallet has an override, it wins.
     /// - Otherwise, the default_* argument is used.
+    ///
+    /// Sizing has no global default here: the absolute `notional_usd` wins
+    /// over `notional_pct_of_leader`, and both stay None when unset.
+    /// Retries fall back to [`DEFAULT_RETRIES`]; the landing mode stays None
+    /// when unset so the send path keeps its own default, and the copy mode
+    /// falls back to [`CopyMode::All`] and the minimum leader trade to 0.0.
     pub fn resolve_params_for_wallet(
         &self,
         wallet: &str,
@@ -179,6 +574,13 @@ impl WalletConfigMap {
     ) -> EffectiveWalletParams {
         let cfg = self.get(wallet);
 
+        let notional_usd = cfg.and_then(|c| c.notional_usd);
+        let notional_pct_of_leader = if notional_usd.is_some() {
+            None
+        } else {
+            cfg.and_then(|c| c.notional_pct_of_leader)
+        };
+
         EffectiveWalletParams {
             slippage: cfg
                 .and_then(|c| c.slippage)
@@ -192,6 +594,13 @@ impl WalletConfigMap {
             max_positions: cfg
                 .and_then(|c| c.max_positions)
                 .or(default_max_positions),
+            notional_usd,
+            notional_pct_of_leader,
+            trailing_stop_pct: cfg.and_then(|c| c.trailing_stop_pct),
+            retries: cfg.and_then(|c| c.retries).unwrap_or(DEFAULT_RETRIES),
+            landing_mode: cfg.and_then(|c| c.landing_mode.clone()),
+            copy_mode: cfg.and_then(|c| c.copy_mode).unwrap_or_default(),
+            min_leader_trade_usd: cfg.and_then(|c| c.min_leader_trade_usd).unwrap_or(0.0),
         }
     }
 }
@@ -1,14 +1,596 @@
+//! Periodic withdrawals of trading profits to a vault wallet.
+//!
+//! Every `interval_secs` the [`WithdrawalScheduler`] values the wallet's
+//! holdings. Once they exceed `threshold_usd` it plans a withdrawal: SOL and
+//! USDC are transferred to `destination` as-is, anything else is swapped to
+//! the configured output asset through Jupiter and the swap's minimum output
+//! is forwarded. `retain_usd` stays in the wallet (in SOL first, for fees),
+//! and nothing is withdrawn while live positions are open. Outside
+//! EXECUTION_MODE=LIVE the scheduler only logs what it would withdraw. The transactions are built by a [`WithdrawalBuilder`]
+//! ([`JupiterWithdrawalBuilder`] in production) and handed, in order, to the
+//! caller's `do_withdraw` closure to sign-check and send.
 
-use std::time::Duration;
-use tokio::time::interval;
+use std::future::Future;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// Periodic withdrawals placeholder
-pub async fn run_withdrawals_task(_vault: String, _min_daily_usd: f64) {
-    let mut ticker = interval(Duration::from_secs(60 * 60)); // hourly for now
+use anchor_client::solana_client::nonblocking::rpc_client::RpcClient;
+use anchor_client::solana_sdk::{
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, VersionedTransaction},
+};
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+use futures::future::BoxFuture;
+use spl_associated_token_account::{
+    get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+
+use crate::common::bot_config;
+use crate::common::logger::Logger;
+use crate::common::price_oracle;
+use crate::common::quote::{USDC_MINT, WSOL_MINT};
+use crate::library::jupiter_api::JupiterClient;
+use crate::universal::dry_run::ExecutionMode;
+use crate::universal::positions::{PositionsRegistry, GLOBAL_POSITIONS_REGISTRY};
+
+const SOL_DECIMALS: u8 = 9;
+const USDC_DECIMALS: u8 = 6;
+
+/// Asset withdrawals are paid out in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WithdrawAsset {
+    #[default]
+    Sol,
+    Usdc,
+}
+
+impl WithdrawAsset {
+    pub fn mint(&self) -> &'static str {
+        match self {
+            WithdrawAsset::Sol => WSOL_MINT,
+            WithdrawAsset::Usdc => USDC_MINT,
+        }
+    }
+
+    pub fn decimals(&self) -> u8 {
+        match self {
+            WithdrawAsset::Sol => SOL_DECIMALS,
+            WithdrawAsset::Usdc => USDC_DECIMALS,
+        }
+    }
+}
+
+/// Withdrawal settings.
+#[derive(Clone, Debug)]
+pub struct WithdrawConfig {
+    /// Vault wallet receiving withdrawals.
+    pub destination: String,
+    /// Only withdraw once holdings are worth more than this.
+    pub threshold_usd: f64,
+    /// Value left in the wallet after a withdrawal, for fees and new entries.
+    pub retain_usd: f64,
+    /// Seconds between withdrawal checks.
+    pub interval_secs: u64,
+    /// What non-SOL/USDC holdings are swapped into.
+    pub output: WithdrawAsset,
+    pub slippage_bps: u64,
+}
+
+impl Default for WithdrawConfig {
+    fn default() -> Self {
+        Self {
+            destination: String::new(),
+            threshold_usd: 100.0,
+            retain_usd: 0.0,
+            interval_secs: 60 * 60,
+            output: WithdrawAsset::Sol,
+            slippage_bps: 100,
+        }
+    }
+}
+
+impl WithdrawConfig {
+    /// Build from env vars:
+    ///
+    /// - WITHDRAW_DESTINATION   (vault wallet; withdrawals are off without it)
+    /// - WITHDRAW_THRESHOLD_USD (default 100)
+    /// - WITHDRAW_RETAIN_USD    (default 0)
+    /// - WITHDRAW_INTERVAL_SECS (default 3600)
+    /// - WITHDRAW_OUTPUT        (sol | usdc, default sol)
+    /// - WITHDRAW_SLIPPAGE_BPS  (default 100)
+    pub fn from_env() -> Self {
+        let mut cfg = Self::default();
+
+        if let Ok(v) = bot_config::var("WITHDRAW_DESTINATION") {
+            cfg.destination = v.trim().to_string();
+        }
+
+        if let Ok(v) = bot_config::var("WITHDRAW_THRESHOLD_USD") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.threshold_usd = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("WITHDRAW_RETAIN_USD") {
+            if let Ok(parsed) = v.parse::<f64>() {
+                cfg.retain_usd = parsed;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("WITHDRAW_INTERVAL_SECS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                cfg.interval_secs = parsed.max(1);
+            }
+        }
+
+        if let Ok(v) = bot_config::var("WITHDRAW_OUTPUT") {
+            if v.trim().eq_ignore_ascii_case("usdc") {
+                cfg.output = WithdrawAsset::Usdc;
+            }
+        }
+
+        if let Ok(v) = bot_config::var("WITHDRAW_SLIPPAGE_BPS") {
+            if let Ok(parsed) = v.parse::<u64>() {
+                cfg.slippage_bps = parsed;
+            }
+        }
+
+        cfg
+    }
+}
+
+/// One token balance of the trading wallet, in raw units, and its USD value.
+/// For SOL, `amount` should already exclude whatever must stay for fees.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Holding {
+    pub mint: String,
+    pub amount: u64,
+    pub decimals: u8,
+    pub value_usd: f64,
+}
+
+impl Holding {
+    /// A SOL balance of `lamports` valued at `sol_usd`.
+    pub fn sol_at(lamports: u64, sol_usd: f64) -> Self {
+        Self {
+            mint: WSOL_MINT.to_string(),
+            amount: lamports,
+            decimals: SOL_DECIMALS,
+            value_usd: lamports as f64 / 10f64.powi(SOL_DECIMALS as i32) * sol_usd,
+        }
+    }
+
+    /// A SOL balance of `lamports` valued at the SOL/USD oracle price, or
+    /// `None` when the oracle is stale and no SOL_USD_FALLBACK is set.
+    pub fn sol(lamports: u64) -> Option<Self> {
+        price_oracle::sol_usd_price().map(|sol_usd| Self::sol_at(lamports, sol_usd))
+    }
+}
+
+/// One step of a withdrawal.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WithdrawalLeg {
+    /// Send `amount` of `mint` (SOL or USDC) straight to the destination.
+    Transfer {
+        mint: String,
+        amount: u64,
+        decimals: u8,
+    },
+    /// Swap `amount` of `mint` to the output asset, then send the proceeds.
+    Swap { mint: String, amount: u64 },
+}
+
+/// What a withdrawal will move.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WithdrawalPlan {
+    pub destination: String,
+    pub output: WithdrawAsset,
+    pub legs: Vec<WithdrawalLeg>,
+    /// USD value of everything being withdrawn.
+    pub value_usd: f64,
+}
+
+/// Withdrawal order: other tokens first, then USDC, then SOL, so the
+/// retained balance stays in the assets fees are paid with.
+fn withdraw_rank(mint: &str) -> u8 {
+    match mint {
+        WSOL_MINT => 2,
+        USDC_MINT => 1,
+        _ => 0,
+    }
+}
+
+/// Plan a withdrawal of `holdings`, or `None` if they are not worth more
+/// than `threshold_usd` or nothing is left after keeping `retain_usd`.
+///
+/// Only `balance_usd - retain_usd` is withdrawn, taken in [`withdraw_rank`]
+/// order; the holding that straddles the limit is withdrawn pro rata. Empty
+/// balances are skipped.
+pub fn plan_withdrawal(cfg: &WithdrawConfig, holdings: &[Holding]) -> Option<WithdrawalPlan> {
+    let mut held: Vec<&Holding> = holdings.iter().filter(|h| h.amount > 0).collect();
+    let balance_usd: f64 = held.iter().map(|h| h.value_usd).sum();
+    if held.is_empty() || balance_usd <= cfg.threshold_usd {
+        return None;
+    }
+    let withdrawable_usd = balance_usd - cfg.retain_usd.max(0.0);
+    if withdrawable_usd <= 0.0 {
+        return None;
+    }
+    held.sort_by_key(|h| withdraw_rank(&h.mint));
+
+    let mut remaining = withdrawable_usd;
+    let mut value_usd = 0.0;
+    let mut legs = Vec::new();
+    for h in held {
+        if remaining <= 0.0 {
+            break;
+        }
+        let (amount, value) = if h.value_usd <= remaining {
+            (h.amount, h.value_usd)
+        } else {
+            let share = remaining / h.value_usd;
+            ((h.amount as f64 * share).floor() as u64, remaining)
+        };
+        if amount == 0 {
+            continue;
+        }
+        remaining -= value;
+        value_usd += value;
+        legs.push(if withdraw_rank(&h.mint) > 0 {
+            WithdrawalLeg::Transfer {
+                mint: h.mint.clone(),
+                amount,
+                decimals: h.decimals,
+            }
+        } else {
+            WithdrawalLeg::Swap {
+                mint: h.mint.clone(),
+                amount,
+            }
+        });
+    }
+    if legs.is_empty() {
+        return None;
+    }
+    Some(WithdrawalPlan {
+        destination: cfg.destination.clone(),
+        output: cfg.output,
+        legs,
+        value_usd,
+    })
+}
+
+/// Turns a [`WithdrawalPlan`] into signed transactions, in send order.
+pub trait WithdrawalBuilder: Send + Sync {
+    fn build<'a>(
+        &'a self,
+        plan: &'a WithdrawalPlan,
+    ) -> BoxFuture<'a, Result<Vec<VersionedTransaction>>>;
+}
+
+/// Builds withdrawals from Jupiter swaps plus SOL / SPL token transfers,
+/// signed by the trading wallet.
+pub struct JupiterWithdrawalBuilder {
+    jupiter: JupiterClient,
+    rpc_client: Arc<RpcClient>,
+    keypair: Arc<Keypair>,
+    slippage_bps: u64,
+}
+
+impl JupiterWithdrawalBuilder {
+    pub fn new(rpc_client: Arc<RpcClient>, keypair: Arc<Keypair>, slippage_bps: u64) -> Self {
+        Self {
+            jupiter: JupiterClient::new(rpc_client.clone()),
+            rpc_client,
+            keypair,
+            slippage_bps,
+        }
+    }
+
+    /// Instructions moving `amount` of `mint` from the wallet to `destination`.
+    /// Native SOL is a system transfer; anything else an SPL transfer into the
+    /// destination's associated token account, created if missing.
+    fn transfer_instructions(
+        &self,
+        destination: &Pubkey,
+        mint: &str,
+        amount: u64,
+        decimals: u8,
+    ) -> Result<Vec<Instruction>> {
+        let owner = self.keypair.pubkey();
+        if mint == WSOL_MINT {
+            return Ok(vec![system_instruction::transfer(
+                &owner,
+                destination,
+                amount,
+            )]);
+        }
+        let mint = Pubkey::from_str(mint)
+            .map_err(|e| anyhow!("withdraw: invalid mint {}: {}", mint, e))?;
+        let source = get_associated_token_address(&owner, &mint);
+        let dest_ata = get_associated_token_address(destination, &mint);
+        Ok(vec![
+            create_associated_token_account_idempotent(
+                &owner,
+                destination,
+                &mint,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::transfer_checked(
+                &spl_token::id(),
+                &source,
+                &mint,
+                &dest_ata,
+                &owner,
+                &[],
+                amount,
+                decimals,
+            )?,
+        ])
+    }
+
+    fn sign(
+        &self,
+        instructions: &[Instruction],
+        blockhash: anchor_client::solana_sdk::hash::Hash,
+    ) -> VersionedTransaction {
+        let owner = self.keypair.pubkey();
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&owner),
+            &[self.keypair.as_ref()],
+            blockhash,
+        );
+        VersionedTransaction::from(tx)
+    }
+}
+
+impl WithdrawalBuilder for JupiterWithdrawalBuilder {
+    fn build<'a>(
+        &'a self,
+        plan: &'a WithdrawalPlan,
+    ) -> BoxFuture<'a, Result<Vec<VersionedTransaction>>> {
+        Box::pin(async move {
+            let destination = Pubkey::from_str(&plan.destination).map_err(|e| {
+                anyhow!("withdraw: invalid destination {}: {}", plan.destination, e)
+            })?;
+            let blockhash = self.rpc_client.get_latest_blockhash().await?;
+            let owner = self.keypair.pubkey();
+            let output_mint = plan.output.mint();
+
+            let mut txs = Vec::new();
+            let mut transfers = Vec::new();
+            let mut swapped_out: u64 = 0;
+            for leg in &plan.legs {
+                match leg {
+                    WithdrawalLeg::Transfer {
+                        mint,
+                        amount,
+                        decimals,
+                    } => {
+                        transfers.extend(self.transfer_instructions(
+                            &destination,
+                            mint,
+                            *amount,
+                            *decimals,
+                        )?);
+                    }
+                    WithdrawalLeg::Swap { mint, amount } => {
+                        let quote = self
+                            .jupiter
+                            .get_quote(mint, output_mint, *amount, self.slippage_bps)
+                            .await?;
+                        // Forward only what the swap is guaranteed to return.
+                        let min_out = quote
+                            .other_amount_threshold
+                            .parse::<u64>()
+                            .map_err(|e| anyhow!("withdraw: bad Jupiter min output: {}", e))?;
+                        let unsigned = self.jupiter.get_swap_transaction(quote, &owner).await?;
+                        let mut message = unsigned.message;
+                        message.set_recent_blockhash(blockhash);
+                        txs.push(VersionedTransaction::try_new(
+                            message,
+                            &[self.keypair.as_ref()],
+                        )?);
+                        swapped_out = swapped_out.saturating_add(min_out);
+                    }
+                }
+            }
+            if swapped_out > 0 {
+                transfers.extend(self.transfer_instructions(
+                    &destination,
+                    output_mint,
+                    swapped_out,
+                    plan.output.decimals(),
+                )?);
+            }
+            if !transfers.is_empty() {
+                txs.push(self.sign(&transfers, blockhash));
+            }
+            Ok(txs)
+        })
+    }
+}
+
+/// Runs withdrawals no more often than every `interval_secs`, and not at all
+/// while live (non-simulated) positions are open in `positions`.
+pub struct WithdrawalScheduler {
+    cfg: WithdrawConfig,
+    last: Option<Instant>,
+    positions: &'static PositionsRegistry,
+    mode: ExecutionMode,
+    logger: Logger,
+}
+
+impl WithdrawalScheduler {
+    pub fn new(cfg: WithdrawConfig) -> Self {
+        Self {
+            cfg,
+            last: None,
+            positions: &GLOBAL_POSITIONS_REGISTRY,
+            mode: ExecutionMode::from_env(),
+            logger: Logger::new("[WITHDRAW] => ".cyan().to_string()),
+        }
+    }
+
+    /// Check open positions in `registry` instead of the global one.
+    pub fn with_positions(mut self, registry: &'static PositionsRegistry) -> Self {
+        self.positions = registry;
+        self
+    }
+
+    /// Override the EXECUTION_MODE the scheduler was created with.
+    pub fn with_mode(mut self, mode: ExecutionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn mode(&self) -> ExecutionMode {
+        self.mode
+    }
+
+    pub fn config(&self) -> &WithdrawConfig {
+        &self.cfg
+    }
+
+    /// When the last check ran, if any.
+    pub fn last(&self) -> Option<Instant> {
+        self.last
+    }
+
+    /// Whether a check is due at `now`.
+    pub fn is_due(&self, now: Instant) -> bool {
+        match self.last {
+            Some(last) => now.duration_since(last) >= Duration::from_secs(self.cfg.interval_secs),
+            None => true,
+        }
+    }
+
+    /// One check: if due and `holdings` are over the threshold, build the
+    /// withdrawal and pass each transaction to `do_withdraw`, which returns
+    /// its signature. In `DryRun` / `Simulate` mode the intended transfers
+    /// are only logged and nothing is built or sent; the timer still
+    /// advances. Returns the plan that was (or would have been) executed.
+    pub async fn tick<B, F, Fut>(
+        &mut self,
+        now: Instant,
+        holdings: &[Holding],
+        builder: &B,
+        mut do_withdraw: F,
+    ) -> Result<Option<WithdrawalPlan>>
+    where
+        B: WithdrawalBuilder + ?Sized,
+        F: FnMut(VersionedTransaction) -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        if !self.is_due(now) {
+            return Ok(None);
+        }
+        self.last = Some(now);
+        // Exits need the balance for fees and slippage; wait until flat.
+        let open = self
+            .positions
+            .all_open_positions()
+            .iter()
+            .filter(|p| !p.simulated)
+            .count();
+        if open > 0 {
+            self.logger.log(format!(
+                "Skipping withdrawal: {} position(s) still open",
+                open
+            ));
+            return Ok(None);
+        }
+        let plan = match plan_withdrawal(&self.cfg, holdings) {
+            Some(plan) => plan,
+            None => return Ok(None),
+        };
+
+        if !self.mode.is_live() {
+            self.log_legs(&plan, "[DRY RUN] would ");
+            self.logger.log(format!(
+                "[DRY RUN] would withdraw ~${:.2} to {} ({:?}, nothing sent)",
+                plan.value_usd, plan.destination, self.mode
+            ));
+            return Ok(Some(plan));
+        }
+
+        let txs = builder.build(&plan).await?;
+        let mut signatures = Vec::with_capacity(txs.len());
+        for tx in txs {
+            signatures.push(do_withdraw(tx).await?);
+        }
+        self.log_legs(&plan, "");
+        self.logger.log(
+            format!(
+                "Withdrew ~${:.2} to {} ({})",
+                plan.value_usd,
+                plan.destination,
+                signatures.join(", ")
+            )
+            .green()
+            .to_string(),
+        );
+        Ok(Some(plan))
+    }
+
+    fn log_legs(&self, plan: &WithdrawalPlan, prefix: &str) {
+        for leg in &plan.legs {
+            let line = match leg {
+                WithdrawalLeg::Transfer { mint, amount, .. } => {
+                    format!("transfer {} of {}", amount, mint)
+                }
+                WithdrawalLeg::Swap { mint, amount } => {
+                    format!("swap {} of {} to {:?}", amount, mint, plan.output)
+                }
+            };
+            self.logger
+                .log(format!("{}{} -> {}", prefix, line, plan.destination));
+        }
+    }
+}
+
+/// Check for withdrawals every `interval_secs`, valuing holdings with
+/// `holdings` and sending through `do_withdraw`. Failures are logged and
+/// retried on the next interval.
+pub async fn run_withdrawals_task<H, HFut, B, F, Fut>(
+    cfg: WithdrawConfig,
+    holdings: H,
+    builder: B,
+    mut do_withdraw: F,
+) where
+    H: Fn() -> HFut,
+    HFut: Future<Output = Result<Vec<Holding>>>,
+    B: WithdrawalBuilder,
+    F: FnMut(VersionedTransaction) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let mut ticker = tokio::time::interval(Duration::from_secs(cfg.interval_secs.max(1)));
+    let mut scheduler = WithdrawalScheduler::new(cfg);
     loop {
-        ticker.tick().await;
-        // TODO: compute unsettled PnL & balances, if > threshold, craft tx to vault
-        // Use Jupiter swap if tokens != SOL/USDC
-        // Persist outcomes to logs
+        // The scheduled tick time, so `is_due` sees exact intervals.
+        let at = ticker.tick().await.into_std();
+        let balances = match holdings().await {
+            Ok(balances) => balances,
+            Err(e) => {
+                scheduler
+                    .logger
+                    .log(format!("Failed to read holdings: {}", e).red().to_string());
+                continue;
+            }
+        };
+        if let Err(e) = scheduler
+            .tick(at, &balances, &builder, &mut do_withdraw)
+            .await
+        {
+            scheduler
+                .logger
+                .log(format!("Withdrawal failed: {}", e).red().to_string());
+        }
     }
 }
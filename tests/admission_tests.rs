@@ -0,0 +1,121 @@
+//! Tests for the single pre-open admission check.
+
+use std::time::Duration;
+
+use solana_vntr_sniper::universal::control::{
+    admission_check, admission_check_in, check_can_open, pause_all, pause_wallet,
+    record_send_result, resume_all, resume_wallet, AdmissionReject, SEND_BREAKER,
+};
+use solana_vntr_sniper::universal::dedup::SeenSignatures;
+use solana_vntr_sniper::universal::positions::PositionsRegistry;
+use solana_vntr_sniper::universal::shutdown::begin_shutdown;
+use solana_vntr_sniper::universal::wallet_config::{set_wallet_enabled, update_wallet_config};
+
+// Pause state, wallet configs and env are process-wide, so every rejection
+// path is checked in a single test.
+#[test]
+fn admission_check_returns_first_failing_reason() {
+    let registry = PositionsRegistry::new();
+    let seen = SeenSignatures::new(Duration::from_secs(60), 100);
+    let check = |wallet: &str, mint: &str, sig: Option<&str>| {
+        admission_check_in(&registry, &seen, wallet, wallet, mint, sig)
+    };
+
+    // All pass; the signature is recorded on admission.
+    assert_eq!(check("walletA", "mint1", Some("sig1")), Ok(()));
+    assert_eq!(admission_check("walletZ", "walletZ", "mintZ", None), Ok(()));
+    assert_eq!(
+        check("walletA", "mint1", Some("sig1")),
+        Err(AdmissionReject::DuplicateSignal)
+    );
+
+    // An open (wallet, mint) position blocks a second open. A rejected
+    // trade does not consume its signature.
+    registry.record_open("walletA", "mint1", 100).unwrap();
+    assert_eq!(
+        check("walletA", "mint1", Some("sig2")),
+        Err(AdmissionReject::AlreadyOpen)
+    );
+
+    // Per-wallet max_positions.
+    update_wallet_config("walletA", |c| c.max_positions = Some(1)).unwrap();
+    let reject = check("walletA", "mint2", Some("sig2")).unwrap_err();
+    assert_eq!(
+        reject,
+        AdmissionReject::WalletMaxPositions { open: 1, max: 1 }
+    );
+    assert_eq!(reject.to_string(), "wallet_max_positions 1 >= 1");
+    assert_eq!(check("walletB", "mint2", None), Ok(()));
+
+    // Global cap across wallets.
+    registry.record_open("walletB", "mint2", 100).unwrap();
+    std::env::set_var("MAX_OPEN_POSITIONS", "2");
+    assert_eq!(
+        check("walletC", "mint3", Some("sig2")),
+        Err(AdmissionReject::MaxOpenPositions { open: 2, max: 2 })
+    );
+    std::env::remove_var("MAX_OPEN_POSITIONS");
+    assert_eq!(check("walletC", "mint3", Some("sig2")), Ok(()));
+
+    // Positions are looked up under our wallet, not the leader's.
+    registry.record_open("ourWallet", "mint6", 100).unwrap();
+    assert_eq!(
+        admission_check_in(&registry, &seen, "walletC", "ourWallet", "mint6", None),
+        Err(AdmissionReject::AlreadyOpen)
+    );
+    assert_eq!(
+        admission_check_in(&registry, &seen, "walletA", "ourWallet", "mint7", None),
+        Err(AdmissionReject::WalletMaxPositions { open: 1, max: 1 })
+    );
+
+    // A disabled wallet is skipped, but it is not paused.
+    set_wallet_enabled("walletD", false).unwrap();
+    assert_eq!(
+        check("walletD", "mint5", None),
+        Err(AdmissionReject::WalletDisabled)
+    );
+    assert_eq!(check_can_open("walletD"), Ok(()));
+    pause_wallet("walletD");
+    assert_eq!(
+        check("walletD", "mint5", None),
+        Err(AdmissionReject::WalletPaused)
+    );
+    resume_wallet("walletD");
+    set_wallet_enabled("walletD", true).unwrap();
+    assert_eq!(check("walletD", "mint5", None), Ok(()));
+
+    // Pause state comes first, ahead of the position checks.
+    pause_wallet("walletA");
+    assert_eq!(
+        check("walletA", "mint1", None),
+        Err(AdmissionReject::WalletPaused)
+    );
+    resume_wallet("walletA");
+
+    if SEND_BREAKER.fail_threshold > 0 {
+        for _ in 0..SEND_BREAKER.fail_threshold {
+            record_send_result(false);
+        }
+        assert_eq!(
+            check("walletC", "mint4", None),
+            Err(AdmissionReject::CircuitBreakerOpen)
+        );
+    }
+
+    pause_all();
+    assert_eq!(
+        check("walletC", "mint4", None),
+        Err(AdmissionReject::Paused)
+    );
+    assert_eq!(check_can_open("walletC"), Err("paused".to_string()));
+    resume_all();
+    assert_eq!(check("walletC", "mint4", None), Ok(()));
+
+    // Shutdown cannot be undone, so it goes last.
+    begin_shutdown();
+    assert_eq!(
+        check("walletC", "mint4", None),
+        Err(AdmissionReject::ShuttingDown)
+    );
+    assert_eq!(check_can_open("walletC"), Err("shutting_down".to_string()));
+}
@@ -0,0 +1,90 @@
+//! Tests for the offline gate backtest.
+
+use solana_vntr_sniper::universal::backtest::{backtest, read_inputs};
+use solana_vntr_sniper::universal::executor::{SimBackend, SimConfig, SimulationAction};
+use solana_vntr_sniper::universal::gates::TradeContext;
+
+fn ctx(mint: &str, mcap: Option<f64>, depth_multiple: f64) -> TradeContext {
+    TradeContext {
+        mint: mint.into(),
+        target_wallet: "backtestWallet".into(),
+        price_usd: 1.0,
+        est_cost_bps: 50.0,
+        window5m_usd: 1_000_000.0,
+        window15m_usd: 3_000_000.0,
+        depth_multiple,
+        est_mcap_usd: mcap,
+        window_vol_pct: 1.0,
+        metrics_age_secs: Some(0),
+        requested_size_usd: Some(100.0),
+        ..TradeContext::default()
+    }
+}
+
+fn backend() -> SimBackend {
+    SimBackend::new(SimConfig {
+        exclude_non_migrated: false,
+        ..SimConfig::default()
+    })
+}
+
+#[test]
+fn summary_counts_fills_rejections_and_pnl() {
+    let inputs = vec![
+        ctx("btDeep", Some(10_000_000.0), 500.0),
+        ctx("btShallow", Some(10_000_000.0), 2.5),
+        ctx("btNoMcap", None, 500.0),
+        ctx("btTiny", Some(1_000.0), 500.0),
+    ];
+    let report = backtest(&backend(), &inputs, SimulationAction::Buy);
+    let s = &report.summary;
+    assert_eq!(s.total, 4);
+    assert_eq!(s.passed, 2);
+    assert_eq!(s.rejected, 2);
+    assert_eq!(s.rejected_by_gate.values().sum::<usize>(), 2);
+
+    let fills: f64 = report.rows.iter().map(|r| r.est_fill_pct).sum();
+    assert!((s.filled_orders - fills).abs() < 1e-12);
+    assert!((s.filled_usd - 100.0 * fills).abs() < 1e-9);
+    let pnl: f64 = report.rows.iter().filter_map(|r| r.est_pnl_usd).sum();
+    assert!((s.est_pnl_usd - pnl).abs() < 1e-9);
+
+    let rejected = &report.rows[2];
+    assert!(!rejected.passed);
+    assert!(rejected.gate.is_some());
+    assert_eq!(rejected.est_fill_pct, 0.0);
+}
+
+#[test]
+fn reads_jsonl_inputs_and_writes_reports() {
+    let dir = std::env::temp_dir().join(format!("backtest_tests_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("inputs.jsonl");
+    let lines: Vec<String> = ["btA", "btB"]
+        .iter()
+        .map(|m| serde_json::to_string(&ctx(m, Some(10_000_000.0), 500.0)).unwrap())
+        .collect();
+    std::fs::write(&input, format!("{}\n\n{}\n", lines[0], lines[1])).unwrap();
+
+    let inputs = read_inputs(&input).unwrap();
+    assert_eq!(inputs.len(), 2);
+    let report = backtest(&backend(), &inputs, SimulationAction::Buy);
+
+    let csv = dir.join("report.csv");
+    report.write_to(&csv).unwrap();
+    let text = std::fs::read_to_string(&csv).unwrap();
+    assert!(text.starts_with("mint,target_wallet,passed,"));
+    assert_eq!(text.lines().count(), 3);
+
+    let json = dir.join("report.json");
+    report.write_to(&json).unwrap();
+    let value: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json).unwrap()).unwrap();
+    assert_eq!(value["summary"]["total"], 2);
+
+    std::fs::write(&input, "{not json}\n").unwrap();
+    let err = read_inputs(&input).unwrap_err();
+    assert!(err.to_string().contains("line 1"));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
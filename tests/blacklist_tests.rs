@@ -0,0 +1,104 @@
+//! Tests for the blacklist and its gate.
+
+use std::sync::Arc;
+
+use solana_vntr_sniper::universal::blacklist::{Blacklist, BlacklistGate, SESSION_BLACKLIST};
+use solana_vntr_sniper::universal::gates::{run_gates, Gate, GateDecision, TradeContext};
+use solana_vntr_sniper::universal::telegram::{apply_command, parse_command, Ctrl};
+
+fn ctx(mint: &str, wallet: &str) -> TradeContext {
+    TradeContext {
+        mint: mint.into(),
+        target_wallet: wallet.into(),
+        price_usd: 1.0,
+        ..TradeContext::default()
+    }
+}
+
+#[test]
+fn gate_rejects_blacklisted_mint_and_wallet() {
+    let blacklist = Arc::new(Blacklist::from_csv("badMint, otherBad"));
+    blacklist.insert_wallet("badWallet");
+    let gates: Vec<Box<dyn Gate>> = vec![Box::new(BlacklistGate::new(blacklist.clone()))];
+
+    let (ok, reasons) = run_gates(&ctx("badMint", "goodWallet"), &gates);
+    assert!(!ok);
+    assert_eq!(
+        reasons,
+        vec![("BlacklistGate".to_string(), "blacklisted_mint".to_string())]
+    );
+
+    let (ok, reasons) = run_gates(&ctx("goodMint", "badWallet"), &gates);
+    assert!(!ok);
+    assert_eq!(reasons[0].1, "blacklisted_wallet");
+
+    assert!(run_gates(&ctx("goodMint", "goodWallet"), &gates).0);
+}
+
+#[test]
+fn runtime_insert_and_remove_are_seen_by_shared_gate() {
+    let blacklist = Arc::new(Blacklist::new());
+    let gate = BlacklistGate::new(blacklist.clone());
+    let trade = ctx("liveMint", "wallet");
+
+    assert!(matches!(gate.check(&trade), GateDecision::Passed));
+    assert!(blacklist.insert("liveMint"));
+    assert!(!blacklist.insert("liveMint"));
+    assert!(matches!(gate.check(&trade), GateDecision::Rejected { .. }));
+
+    assert!(blacklist.remove("liveMint"));
+    assert!(!blacklist.remove("liveMint"));
+    assert!(matches!(gate.check(&trade), GateDecision::Passed));
+}
+
+#[test]
+fn telegram_blacklist_commands_edit_session_blacklist() {
+    let mint = "telegramBlacklistMint";
+    let cmd = parse_command(&format!("/blacklist {}", mint)).expect("parses");
+    assert!(matches!(cmd, Ctrl::Blacklist { .. }));
+
+    apply_command(cmd).unwrap();
+    assert!(SESSION_BLACKLIST.contains(mint));
+    assert!(matches!(
+        BlacklistGate::session().check(&ctx(mint, "wallet")),
+        GateDecision::Rejected { .. }
+    ));
+
+    apply_command(Ctrl::Unblacklist { mint: mint.into() }).unwrap();
+    assert!(!SESSION_BLACKLIST.contains(mint));
+    assert!(apply_command(Ctrl::Unblacklist { mint: mint.into() }).is_err());
+}
+
+#[test]
+fn file_round_trip_trims_dedupes_and_skips_comments() {
+    let path = std::env::temp_dir().join(format!("blacklist_{}.txt", std::process::id()));
+    std::fs::write(&path, "# rugs\n  mintA  \nmintB,mintA\n\n# mintC\nmintB\n").unwrap();
+
+    let blacklist = Blacklist::from_file(&path).unwrap();
+    assert_eq!(blacklist.len(), 2);
+    assert!(blacklist.contains("mintA"));
+    assert!(!blacklist.contains("mintC"));
+
+    // Clones share the live set, so a gate built from one sees edits via the other.
+    let gate = BlacklistGate::new(Arc::new(blacklist.clone()));
+    blacklist.insert("mintC");
+    blacklist.remove("mintA");
+    assert!(matches!(
+        gate.check(&ctx("mintC", "wallet")),
+        GateDecision::Rejected { .. }
+    ));
+    assert!(matches!(
+        gate.check(&ctx("mintA", "wallet")),
+        GateDecision::Passed
+    ));
+
+    blacklist.save_to_file(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "mintB\nmintC\n");
+    let reloaded = Blacklist::from_file(&path).unwrap();
+    let mut mints = reloaded.mints();
+    mints.sort();
+    assert_eq!(mints, vec!["mintB".to_string(), "mintC".to_string()]);
+
+    let _ = std::fs::remove_file(&path);
+    assert!(Blacklist::from_file(&path).is_err());
+}
@@ -0,0 +1,90 @@
+//! Tests for the cached recent blockhash.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use futures::future::BoxFuture;
+use solana_sdk::hash::Hash;
+use solana_vntr_sniper::library::blockhash_cache::{
+    BlockhashCache, BlockhashInfo, BlockhashSource, EXPIRY_MARGIN_BLOCKS, SLOT_TIME,
+};
+
+struct StubSource {
+    info: BlockhashInfo,
+    fetches: AtomicUsize,
+}
+
+impl StubSource {
+    fn new(info: BlockhashInfo) -> Self {
+        Self {
+            info,
+            fetches: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl BlockhashSource for StubSource {
+    fn fetch_blockhash(&self) -> BoxFuture<'_, Result<BlockhashInfo, String>> {
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+        let info = self.info;
+        Box::pin(async move { Ok(info) })
+    }
+}
+
+fn info(seed: u8, block_height: u64) -> BlockhashInfo {
+    BlockhashInfo {
+        hash: Hash::new_from_array([seed; 32]),
+        last_valid_block_height: block_height + 150,
+        block_height,
+    }
+}
+
+#[test]
+fn serves_cached_hash_until_max_age() {
+    let cache = BlockhashCache::new(Duration::from_secs(10));
+    let t0 = Instant::now();
+    assert_eq!(cache.cached_at(t0), None);
+
+    cache.record_at(info(1, 1_000), t0);
+    assert_eq!(cache.cached_at(t0), Some(Hash::new_from_array([1; 32])));
+    assert_eq!(
+        cache.cached_at(t0 + Duration::from_secs(10)),
+        Some(Hash::new_from_array([1; 32]))
+    );
+    assert_eq!(cache.cached_at(t0 + Duration::from_secs(11)), None);
+}
+
+#[test]
+fn hash_near_last_valid_block_height_is_not_served() {
+    // Generous max age so only the block-height bound applies.
+    let cache = BlockhashCache::new(Duration::from_secs(600));
+    let t0 = Instant::now();
+    cache.record_at(info(2, 1_000), t0);
+
+    // 150 valid blocks minus the margin leaves this many blocks of use.
+    let usable = 150 - EXPIRY_MARGIN_BLOCKS;
+    let before = SLOT_TIME * (usable as u32 - 1);
+    let after = SLOT_TIME * usable as u32;
+    assert!(cache.cached_at(t0 + before).is_some());
+    assert_eq!(cache.cached_at(t0 + after), None);
+}
+
+#[tokio::test]
+async fn get_or_fetch_only_hits_source_when_stale() {
+    let cache = BlockhashCache::new(Duration::from_secs(10));
+    let source = StubSource::new(info(3, 5_000));
+
+    let hash = cache.get_or_fetch(&source).await.unwrap();
+    assert_eq!(hash, Hash::new_from_array([3; 32]));
+    assert_eq!(source.fetches.load(Ordering::SeqCst), 1);
+
+    // Served from the cache now.
+    cache.get_or_fetch(&source).await.unwrap();
+    assert_eq!(source.fetches.load(Ordering::SeqCst), 1);
+
+    // An aged-out entry is refetched.
+    cache.record_at(info(4, 5_000), Instant::now() - Duration::from_secs(30));
+    let hash = cache.get_or_fetch(&source).await.unwrap();
+    assert_eq!(hash, Hash::new_from_array([3; 32]));
+    assert_eq!(source.fetches.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,102 @@
+//! Tests for the unified bot config file and its env > file > default
+//! precedence.
+
+use solana_vntr_sniper::common::bot_config::{install_file_settings, var, FileSettings};
+use solana_vntr_sniper::universal::wallet_config::WalletConfigMap;
+use solana_vntr_sniper::universal::withdraw::WithdrawConfig;
+
+const SAMPLE: &str = r#"
+[gates]
+liq_5m_min_usd = 20000
+require_renounced = true
+
+[mev]
+mev_route = "jito"
+
+[withdraw]
+threshold_usd = 250.5
+slippage_bps = 50
+
+[rpc]
+endpoints = ["us|https://a.example", "eu|https://b.example"]
+
+[wallets.leader]
+slippage = 0.02
+retries = 4
+"#;
+
+#[test]
+fn sections_map_keys_to_env_names() {
+    let file = FileSettings::from_toml_str(SAMPLE).unwrap();
+    assert_eq!(file.get("LIQ_5M_MIN_USD"), Some("20000"));
+    assert_eq!(file.get("REQUIRE_RENOUNCED"), Some("true"));
+    assert_eq!(file.get("MEV_ROUTE"), Some("jito"));
+    assert_eq!(file.get("WITHDRAW_THRESHOLD_USD"), Some("250.5"));
+    assert_eq!(file.get("WITHDRAW_SLIPPAGE_BPS"), Some("50"));
+    assert_eq!(
+        file.get("RPC_ENDPOINTS"),
+        Some("us|https://a.example,eu|https://b.example")
+    );
+    assert_eq!(file.get("COOLDOWN_SECS"), None);
+}
+
+#[test]
+fn wallets_section_loads_as_wallet_config() {
+    let file = FileSettings::from_toml_str(SAMPLE).unwrap();
+    let map = WalletConfigMap::from_toml_str(file.wallets_toml().unwrap()).unwrap();
+    let cfg = map.get("leader").unwrap();
+    assert_eq!(cfg.slippage, Some(0.02));
+    assert_eq!(cfg.retries, Some(4));
+}
+
+#[test]
+fn unknown_sections_and_keys_are_rejected() {
+    let err = FileSettings::from_toml_str("[gatez]\ncooldown_secs = 5\n").unwrap_err();
+    assert!(err.contains("unknown section [gatez]"), "{}", err);
+
+    let err = FileSettings::from_toml_str("[gates]\ncooldown_sec = 5\n").unwrap_err();
+    assert!(err.contains("unknown key gates.cooldown_sec"), "{}", err);
+
+    // Section prefixes are implied, not repeated.
+    let err = FileSettings::from_toml_str("[withdraw]\nwithdraw_output = \"usdc\"\n").unwrap_err();
+    assert!(
+        err.contains("unknown key withdraw.withdraw_output"),
+        "{}",
+        err
+    );
+
+    assert!(FileSettings::from_toml_str("[gates]\ncooldown_secs = { a = 1 }\n").is_err());
+    assert!(FileSettings::from_toml_str("not toml =").is_err());
+}
+
+// Env and the installed file settings are process-wide, so every assertion
+// reading them lives in one test.
+#[test]
+fn env_beats_file_beats_default() {
+    install_file_settings(
+        FileSettings::from_toml_str(
+            r#"
+            [withdraw]
+            retain_usd = 5
+            slippage_bps = 250
+            "#,
+        )
+        .unwrap(),
+    );
+    std::env::set_var("WITHDRAW_SLIPPAGE_BPS", "300");
+    std::env::remove_var("WITHDRAW_RETAIN_USD");
+    std::env::remove_var("WITHDRAW_THRESHOLD_USD");
+
+    assert_eq!(var("WITHDRAW_SLIPPAGE_BPS").as_deref(), Ok("300"));
+    assert_eq!(var("WITHDRAW_RETAIN_USD").as_deref(), Ok("5"));
+    assert!(var("WITHDRAW_THRESHOLD_USD").is_err());
+
+    let cfg = WithdrawConfig::from_env();
+    let defaults = WithdrawConfig::default();
+    assert_eq!(cfg.slippage_bps, 300);
+    assert_eq!(cfg.retain_usd, 5.0);
+    assert_eq!(cfg.threshold_usd, defaults.threshold_usd);
+
+    install_file_settings(FileSettings::empty());
+    assert_eq!(WithdrawConfig::from_env().retain_usd, defaults.retain_usd);
+}
@@ -0,0 +1,92 @@
+//! Tests for assembling a TradeContext from metrics, positions and wallet config.
+
+use solana_vntr_sniper::universal::context::{build_trade_context, ContextError};
+use solana_vntr_sniper::universal::impact::estimate_impact_bps;
+use solana_vntr_sniper::universal::metrics::{clock, MetricsStore, TokenMetrics, VolumeWindows};
+use solana_vntr_sniper::universal::positions::{OpenParams, PositionsRegistry};
+
+fn setup() -> (MetricsStore, PositionsRegistry) {
+    // Every test sets the same value, so running them in parallel is fine.
+    std::env::set_var("SLIPPAGE", "100");
+    let store = MetricsStore::new(VolumeWindows::default());
+    store.upsert(TokenMetrics {
+        mint: "ctxMint".to_string(),
+        price_usd: 2.0,
+        liq_usd: Some(100_000.0),
+        mcap_usd: Some(1_000_000.0),
+        vol_5m_usd: 5_000.0,
+        vol_15m_usd: 12_000.0,
+        amihud_5m: Some(0.001),
+        range_eff_5m: Some(0.4),
+        last_update: clock::now_secs(),
+    });
+    (store, PositionsRegistry::new())
+}
+
+#[test]
+fn fills_fields_from_metrics_and_wallet_slippage() {
+    let (store, registry) = setup();
+    let ctx =
+        build_trade_context("ctxMint", "ctxLeader", "ctxOwner", &store, &registry, 500.0).unwrap();
+
+    assert_eq!(ctx.mint, "ctxMint");
+    assert_eq!(ctx.target_wallet, "ctxLeader");
+    assert_eq!(ctx.owner_wallet.as_deref(), Some("ctxOwner"));
+    assert_eq!(ctx.price_usd, 2.0);
+    assert_eq!(ctx.window5m_usd, 5_000.0);
+    assert_eq!(ctx.window15m_usd, 12_000.0);
+    assert_eq!(ctx.est_mcap_usd, Some(1_000_000.0));
+    assert_eq!(ctx.amihud_5m, Some(0.001));
+    assert_eq!(ctx.range_eff_5m, Some(0.4));
+    assert_eq!(ctx.liq_usd, Some(100_000.0));
+    assert_eq!(ctx.requested_size_usd, Some(500.0));
+    assert!((ctx.depth_multiple - 200.0).abs() < 1e-9);
+    let expected_cost = estimate_impact_bps(500.0, 100_000.0) + 100.0;
+    assert!((ctx.est_cost_bps - expected_cost).abs() < 1e-9);
+    assert!(ctx.metrics_age_secs.unwrap() <= 1);
+    assert_eq!(ctx.first_seen_ts, store.first_seen("ctxMint"));
+    assert!(ctx.first_seen_ts.is_some());
+}
+
+#[test]
+fn existing_position_counts_against_depth() {
+    let (store, registry) = setup();
+    registry
+        .record_open_with(
+            "ctxOwner",
+            "ctxMint",
+            0,
+            OpenParams {
+                size: Some(250.0),
+                entry_price: Some(1.0),
+                slippage_bps: None,
+            },
+        )
+        .unwrap();
+    // 250 tokens at $2 already held under our wallet, plus the new $500.
+    let ctx =
+        build_trade_context("ctxMint", "ctxLeader", "ctxOwner", &store, &registry, 500.0).unwrap();
+    assert!((ctx.depth_multiple - 100.0).abs() < 1e-9);
+}
+
+#[test]
+fn errors_name_the_missing_input() {
+    let (store, registry) = setup();
+    let err = build_trade_context(
+        "unknownMint",
+        "ctxLeader",
+        "ctxOwner",
+        &store,
+        &registry,
+        500.0,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContextError::MissingMetrics("unknownMint".to_string()));
+    assert_eq!(err.to_string(), "no metrics for mint=unknownMint");
+
+    assert_eq!(
+        build_trade_context("ctxMint", "ctxLeader", "ctxOwner", &store, &registry, 0.0)
+            .unwrap_err(),
+        ContextError::InvalidSize(0.0)
+    );
+}
@@ -0,0 +1,205 @@
+//! Tests for the global / per-wallet pause state and the send circuit breaker.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+use solana_vntr_sniper::universal::control::{
+    check_can_open, is_paused, is_wallet_paused, pause_all, pause_wallet, resume_all,
+    resume_wallet, BreakerState, CircuitBreaker,
+};
+use solana_vntr_sniper::universal::executor::{
+    ExecutionSimulator, SimBackend, SimConfig, SimulationAction,
+};
+use solana_vntr_sniper::universal::gates::stats::Clock;
+use solana_vntr_sniper::universal::gates::TradeContext;
+use solana_vntr_sniper::universal::telegram::{apply_command, Ctrl};
+
+fn ctx(wallet: &str) -> TradeContext {
+    TradeContext {
+        mint: "mintX".into(),
+        target_wallet: wallet.into(),
+        price_usd: 1.0,
+        window5m_usd: 1_000_000.0,
+        window15m_usd: 3_000_000.0,
+        depth_multiple: 10.0,
+        est_mcap_usd: Some(10_000_000.0),
+        window_vol_pct: 1.0,
+        amihud_5m: Some(1e-7),
+        range_eff_5m: Some(0.6),
+        metrics_age_secs: Some(0),
+        ..TradeContext::default()
+    }
+}
+
+fn sim() -> SimBackend {
+    SimBackend::new(SimConfig {
+        exclude_non_migrated: false,
+        ..SimConfig::default()
+    })
+}
+
+// The global pause is process-wide, so all assertions touching it live in
+// one test to avoid racing other tests in this binary.
+#[test]
+fn global_pause_blocks_opens_but_allows_closes() {
+    let backend = sim();
+    let wallet = "global-wallet";
+    assert!(backend.simulate(&ctx(wallet), SimulationAction::Buy).passed);
+
+    apply_command(Ctrl::PauseAll).unwrap();
+    assert!(is_paused());
+    assert_eq!(check_can_open(wallet), Err("paused".to_string()));
+
+    let buy = backend.simulate(&ctx(wallet), SimulationAction::Buy);
+    assert!(!buy.passed);
+    assert_eq!(
+        buy.gate_reasons,
+        vec![("Control".to_string(), "paused".to_string())]
+    );
+    assert!(
+        backend
+            .simulate(&ctx(wallet), SimulationAction::Sell)
+            .passed
+    );
+
+    apply_command(Ctrl::ResumeAll).unwrap();
+    assert!(!is_paused());
+    assert!(backend.simulate(&ctx(wallet), SimulationAction::Buy).passed);
+
+    pause_wallet(wallet);
+    assert_eq!(check_can_open(wallet), Err("wallet_paused".to_string()));
+    pause_all();
+    assert_eq!(check_can_open(wallet), Err("paused".to_string()));
+    resume_all();
+    resume_wallet(wallet);
+    assert_eq!(check_can_open(wallet), Ok(()));
+
+    // A buy the gates reject does not consume its leader signature, so the
+    // trade can be evaluated again; once admitted it is a duplicate.
+    let signed = |window5m_usd: f64| TradeContext {
+        leader_signature: Some("globalLeaderSig".into()),
+        window5m_usd,
+        ..ctx(wallet)
+    };
+    assert!(!backend.simulate(&signed(0.0), SimulationAction::Buy).passed);
+    assert!(
+        backend
+            .simulate(&signed(1_000_000.0), SimulationAction::Buy)
+            .passed
+    );
+    let again = backend.simulate(&signed(1_000_000.0), SimulationAction::Buy);
+    assert_eq!(
+        again.gate_reasons,
+        vec![("Control".to_string(), "duplicate_signal".to_string())]
+    );
+}
+
+#[test]
+fn wallet_pause_only_affects_that_wallet() {
+    let backend = sim();
+    let paused = "paused-wallet";
+    let other = "other-wallet";
+
+    pause_wallet(paused);
+    assert!(is_wallet_paused(paused));
+
+    assert!(!backend.simulate(&ctx(paused), SimulationAction::Buy).passed);
+    assert!(
+        backend
+            .simulate(&ctx(paused), SimulationAction::Sell)
+            .passed
+    );
+    assert!(!is_wallet_paused(other));
+
+    resume_wallet(paused);
+    assert!(!is_wallet_paused(paused));
+}
+
+#[test]
+fn telegram_pause_wallet_toggles_on_and_off() {
+    let wallet = "telegram-paused-wallet";
+    apply_command(Ctrl::PauseWallet {
+        wallet: wallet.into(),
+        what: "on".into(),
+    })
+    .unwrap();
+    assert!(is_wallet_paused(wallet));
+
+    apply_command(Ctrl::PauseWallet {
+        wallet: wallet.into(),
+        what: "off".into(),
+    })
+    .unwrap();
+    assert!(!is_wallet_paused(wallet));
+}
+
+#[derive(Clone)]
+struct FakeClock(Arc<AtomicI64>);
+
+impl FakeClock {
+    fn advance(&self, secs: i64) {
+        self.0.fetch_add(secs, Ordering::SeqCst);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now_secs(&self) -> i64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[test]
+fn breaker_opens_after_failure_streak() {
+    let clock = FakeClock(Arc::new(AtomicI64::new(1_000)));
+    let breaker = CircuitBreaker::with_clock(3, 60, clock);
+
+    breaker.record_failure();
+    breaker.record_failure();
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    // A success in between resets the streak.
+    breaker.record_success();
+    breaker.record_failure();
+    breaker.record_failure();
+    assert!(!breaker.is_open());
+
+    breaker.record_failure();
+    assert!(breaker.is_open());
+    assert_eq!(breaker.consecutive_failures(), 3);
+}
+
+#[test]
+fn breaker_half_opens_after_cooldown() {
+    let clock = FakeClock(Arc::new(AtomicI64::new(1_000)));
+    let breaker = CircuitBreaker::with_clock(2, 60, clock.clone());
+    breaker.record_failure();
+    breaker.record_failure();
+    assert!(breaker.is_open());
+
+    clock.advance(59);
+    assert!(breaker.is_open());
+    clock.advance(1);
+    assert_eq!(breaker.state(), BreakerState::HalfOpen);
+    assert!(!breaker.is_open());
+
+    // One failure while half-open reopens it for another cooldown.
+    breaker.record_failure();
+    assert!(breaker.is_open());
+    clock.advance(60);
+    assert_eq!(breaker.state(), BreakerState::HalfOpen);
+
+    // A success closes it and clears the streak.
+    breaker.record_success();
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    assert_eq!(breaker.consecutive_failures(), 0);
+    breaker.record_failure();
+    assert!(!breaker.is_open());
+}
+
+#[test]
+fn breaker_with_zero_threshold_never_opens() {
+    let breaker = CircuitBreaker::new(0, 60);
+    for _ in 0..100 {
+        breaker.record_failure();
+    }
+    assert!(!breaker.is_open());
+}
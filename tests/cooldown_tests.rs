@@ -0,0 +1,79 @@
+//! Tests for the re-buy cooldown gate.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+use solana_vntr_sniper::universal::gates::stats::Clock;
+use solana_vntr_sniper::universal::gates::{
+    CooldownGate, Gate, GateDecision, RejectReason, TradeContext,
+};
+use solana_vntr_sniper::universal::positions::PositionsRegistry;
+
+#[derive(Clone)]
+struct FakeClock(Arc<AtomicI64>);
+
+impl FakeClock {
+    fn set(&self, ts: i64) {
+        self.0.store(ts, Ordering::SeqCst);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now_secs(&self) -> i64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+fn ctx(mint: &str) -> TradeContext {
+    TradeContext {
+        mint: mint.into(),
+        target_wallet: "walletA".into(),
+        price_usd: 1.0,
+        ..TradeContext::default()
+    }
+}
+
+#[test]
+fn rejects_rebuy_within_cooldown_after_registry_close() {
+    let registry = PositionsRegistry::new();
+    let clock = FakeClock(Arc::new(AtomicI64::new(1_000)));
+    let gate = CooldownGate::with_clock(120, registry.close_log(), clock.clone());
+
+    // Never traded: no cooldown.
+    assert!(matches!(gate.check(&ctx("mintX")), GateDecision::Passed));
+
+    registry.record_open("walletA", "mintX", 900).unwrap();
+    registry.record_close_at("walletA", "mintX", 1_000).unwrap();
+
+    clock.set(1_030);
+    match gate.check(&ctx("mintX")) {
+        GateDecision::Rejected { code, reason } => {
+            assert_eq!(code, RejectReason::Cooldown);
+            assert_eq!(reason, "cooldown 90s");
+        }
+        GateDecision::Passed => panic!("expected cooldown rejection"),
+    }
+    // Other mints are unaffected.
+    assert!(matches!(gate.check(&ctx("mintY")), GateDecision::Passed));
+
+    clock.set(1_120);
+    assert!(matches!(gate.check(&ctx("mintX")), GateDecision::Passed));
+}
+
+#[test]
+fn latest_close_across_wallets_drives_cooldown() {
+    let registry = PositionsRegistry::new();
+    let clock = FakeClock(Arc::new(AtomicI64::new(0)));
+    let gate = CooldownGate::with_clock(60, registry.close_log(), clock.clone());
+
+    registry.record_open("walletA", "mintX", 0).unwrap();
+    registry.record_open("walletB", "mintX", 0).unwrap();
+    registry.record_close_at("walletB", "mintX", 500).unwrap();
+    registry.record_close_at("walletA", "mintX", 400).unwrap();
+
+    clock.set(530);
+    assert!(matches!(
+        gate.check(&ctx("mintX")),
+        GateDecision::Rejected { .. }
+    ));
+}
@@ -0,0 +1,121 @@
+//! Tests for the per-wallet copy mode, the minimum leader trade size and the
+//! executor's copy decision.
+
+use solana_vntr_sniper::universal::executor::{
+    copy_action, copy_action_for, copy_action_for_wallet, leader_trade_too_small,
+    leader_trade_too_small_for_wallet, CopyAction,
+};
+use solana_vntr_sniper::universal::models::SimInput;
+use solana_vntr_sniper::universal::wallet_config::{reload_from_str, CopyMode};
+
+fn buy(wallet: &str, qty: f64, price_usd: f64) -> SimInput {
+    SimInput {
+        ts: 0,
+        target_wallet: wallet.into(),
+        mint: "mintX".into(),
+        side: "buy".into(),
+        qty,
+        price_usd,
+    }
+}
+
+#[test]
+fn all_mirrors_buys_and_sells() {
+    assert_eq!(copy_action(CopyMode::All, "buy"), CopyAction::Buy);
+    assert_eq!(copy_action(CopyMode::All, "sell"), CopyAction::Sell);
+}
+
+#[test]
+fn buys_only_skips_sells() {
+    assert_eq!(copy_action(CopyMode::BuysOnly, "buy"), CopyAction::Buy);
+    assert_eq!(copy_action(CopyMode::BuysOnly, "sell"), CopyAction::Skip);
+}
+
+#[test]
+fn sells_only_skips_buys() {
+    assert_eq!(copy_action(CopyMode::SellsOnly, "buy"), CopyAction::Skip);
+    assert_eq!(copy_action(CopyMode::SellsOnly, "sell"), CopyAction::Sell);
+}
+
+#[test]
+fn inverse_sells_closes_on_leader_sell() {
+    assert_eq!(copy_action(CopyMode::InverseSells, "buy"), CopyAction::Skip);
+    assert_eq!(
+        copy_action(CopyMode::InverseSells, "SELL"),
+        CopyAction::Close
+    );
+    assert!(CopyAction::Close.simulation_action().is_some());
+    assert!(CopyAction::Skip.simulation_action().is_none());
+}
+
+#[test]
+fn unknown_side_is_skipped() {
+    assert_eq!(copy_action(CopyMode::All, "transfer"), CopyAction::Skip);
+}
+
+// The wallet config is process-wide, so every assertion reading it lives in
+// one test.
+#[test]
+fn leader_events_use_the_wallet_config() {
+    reload_from_str(
+        r#"
+        [wallets.copyModeLeader]
+        copy_mode = "buys_only"
+
+        [wallets.dustLeader]
+        min_leader_trade_usd = 20.0
+
+        [wallets.disabledLeader]
+        enabled = false
+        "#,
+    )
+    .unwrap();
+    let event = |wallet: &str, side: &str| SimInput {
+        ts: 0,
+        target_wallet: wallet.into(),
+        mint: "mintX".into(),
+        side: side.into(),
+        qty: 1.0,
+        price_usd: 1.0,
+    };
+
+    assert_eq!(
+        copy_action_for(&event("copyModeLeader", "buy")),
+        CopyAction::Buy
+    );
+    assert_eq!(
+        copy_action_for(&event("copyModeLeader", "sell")),
+        CopyAction::Skip
+    );
+    assert_eq!(
+        copy_action_for(&event("otherLeader", "sell")),
+        CopyAction::Sell
+    );
+
+    // A disabled wallet's buys are skipped, its sells still mirrored.
+    assert_eq!(
+        copy_action_for_wallet("disabledLeader", "buy"),
+        CopyAction::Skip
+    );
+    assert_eq!(
+        copy_action_for(&event("disabledLeader", "sell")),
+        CopyAction::Sell
+    );
+    assert_eq!(
+        copy_action_for_wallet("otherLeader", "buy"),
+        CopyAction::Buy
+    );
+
+    assert!(leader_trade_too_small_for_wallet(&buy("dustLeader", 0.05, 150.0)).is_some());
+    assert!(leader_trade_too_small_for_wallet(&buy("dustLeader", 1.0, 150.0)).is_none());
+    assert!(leader_trade_too_small_for_wallet(&buy("otherLeader", 0.05, 150.0)).is_none());
+}
+
+#[test]
+fn sub_threshold_leader_buy_is_skipped() {
+    let reason = leader_trade_too_small(&buy("w", 0.05, 150.0), 20.0).unwrap();
+    assert!(reason.starts_with("leader_trade_too_small "), "{}", reason);
+    assert_eq!(leader_trade_too_small(&buy("w", 0.5, 150.0), 20.0), None);
+    // The default of 0 copies even dust.
+    assert_eq!(leader_trade_too_small(&buy("w", 0.0001, 150.0), 0.0), None);
+}
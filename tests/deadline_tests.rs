@@ -0,0 +1,102 @@
+//! Tests for the decision deadline wrapper.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use solana_vntr_sniper::universal::executor::{run_gates_blocking, with_deadline, DeadlineResult};
+use solana_vntr_sniper::universal::gates::{Gate, GateDecision, TradeContext};
+
+fn ctx() -> TradeContext {
+    TradeContext {
+        mint: "deadlineMint".into(),
+        target_wallet: "deadlineWallet".into(),
+        price_usd: 1.0,
+        est_cost_bps: 50.0,
+        window5m_usd: 1_000_000.0,
+        window15m_usd: 3_000_000.0,
+        depth_multiple: 10.0,
+        est_mcap_usd: Some(10_000_000.0),
+        window_vol_pct: 1.0,
+        metrics_age_secs: Some(0),
+        ..TradeContext::default()
+    }
+}
+
+/// A gate that blocks its thread for `delay` before passing.
+struct SlowGate {
+    delay: Duration,
+}
+
+impl Gate for SlowGate {
+    fn name(&self) -> &'static str {
+        "SlowGate"
+    }
+
+    fn check(&self, _ctx: &TradeContext) -> GateDecision {
+        std::thread::sleep(self.delay);
+        GateDecision::Passed
+    }
+}
+
+fn slow_gates(delay: Duration) -> Vec<Box<dyn Gate>> {
+    vec![Box::new(SlowGate { delay })]
+}
+
+#[tokio::test]
+async fn deadline_fires_on_slow_gate() {
+    let sent = AtomicBool::new(false);
+    let sent_ref = &sent;
+    let result = with_deadline(Duration::from_millis(50), |timer| async move {
+        timer
+            .time("metrics", async {
+                tokio::time::sleep(Duration::from_millis(1)).await
+            })
+            .await;
+        let (ok, _) = timer
+            .time(
+                "gates",
+                run_gates_blocking(ctx(), slow_gates(Duration::from_millis(500))),
+            )
+            .await;
+        if ok {
+            sent_ref.store(true, Ordering::SeqCst);
+        }
+        ok
+    })
+    .await;
+
+    match result {
+        DeadlineResult::TimedOut { deadline, phases } => {
+            assert_eq!(deadline, Duration::from_millis(50));
+            let names: Vec<&str> = phases.iter().map(|(name, _)| *name).collect();
+            assert_eq!(names, vec!["metrics"]);
+        }
+        DeadlineResult::Completed { .. } => panic!("expected the deadline to fire"),
+    }
+    assert!(!sent.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn fast_decision_completes_with_phase_timings() {
+    let result = with_deadline(Duration::from_secs(5), |timer| async move {
+        let (ok, _) = timer
+            .time(
+                "gates",
+                run_gates_blocking(ctx(), slow_gates(Duration::ZERO)),
+            )
+            .await;
+        timer.time("send", async { "sig" }).await;
+        ok
+    })
+    .await;
+
+    assert!(!result.is_timed_out());
+    match result {
+        DeadlineResult::Completed { value, phases } => {
+            assert!(value);
+            let names: Vec<&str> = phases.iter().map(|(name, _)| *name).collect();
+            assert_eq!(names, vec!["gates", "send"]);
+        }
+        DeadlineResult::TimedOut { .. } => panic!("decision should finish in time"),
+    }
+}
@@ -0,0 +1,46 @@
+//! Tests for the leader-trade signature dedup.
+
+use std::time::{Duration, Instant};
+
+use solana_vntr_sniper::universal::dedup::SeenSignatures;
+
+#[test]
+fn same_signature_is_only_processed_once() {
+    let seen = SeenSignatures::new(Duration::from_secs(60), 100);
+    let t0 = Instant::now();
+
+    // e.g. the gRPC stream and an RPC poll both report the same buy
+    assert!(seen.first_seen_at("sigA", t0));
+    assert!(!seen.first_seen_at("sigA", t0 + Duration::from_secs(1)));
+    assert!(seen.contains_at("sigA", t0 + Duration::from_secs(1)));
+    assert!(seen.first_seen_at("sigB", t0 + Duration::from_secs(1)));
+    assert_eq!(seen.len(), 2);
+}
+
+#[test]
+fn signatures_expire_after_ttl() {
+    let seen = SeenSignatures::new(Duration::from_secs(60), 100);
+    let t0 = Instant::now();
+    assert!(seen.first_seen_at("sigA", t0));
+
+    let later = t0 + Duration::from_secs(60);
+    assert!(!seen.contains_at("sigA", later));
+    assert!(seen.first_seen_at("sigA", later));
+}
+
+#[test]
+fn oldest_signature_is_evicted_at_capacity() {
+    let seen = SeenSignatures::new(Duration::from_secs(60), 2);
+    let t0 = Instant::now();
+    assert!(seen.first_seen_at("sigA", t0));
+    assert!(seen.first_seen_at("sigB", t0));
+    assert!(seen.first_seen_at("sigC", t0));
+
+    assert_eq!(seen.len(), 2);
+    assert!(!seen.contains_at("sigA", t0));
+    assert!(seen.contains_at("sigB", t0));
+    assert!(seen.contains_at("sigC", t0));
+
+    seen.clear();
+    assert!(seen.is_empty());
+}
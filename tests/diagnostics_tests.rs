@@ -0,0 +1,67 @@
+//! Tests for the `/gate_test` diagnostic.
+
+use solana_vntr_sniper::universal::diagnostics::GateTestReport;
+use solana_vntr_sniper::universal::gates::{Gate, McapGate, TradeContext};
+use solana_vntr_sniper::universal::impact::estimate_impact_bps;
+use solana_vntr_sniper::universal::telegram::{parse_command, Ctrl};
+
+fn ctx_with_liquidity(liq_usd: f64) -> TradeContext {
+    TradeContext {
+        mint: "mintX".into(),
+        target_wallet: "walletA".into(),
+        price_usd: 0.5,
+        est_cost_bps: 50.0,
+        window5m_usd: 20_000.0,
+        window15m_usd: 60_000.0,
+        depth_multiple: 5.0,
+        est_mcap_usd: Some(10_000_000.0),
+        window_vol_pct: 10.0,
+        metrics_age_secs: Some(5),
+        liq_usd: Some(liq_usd),
+        ..TradeContext::default()
+    }
+}
+
+fn gates() -> Vec<Box<dyn Gate>> {
+    vec![Box::new(McapGate {
+        min_mcap: 1_000_000.0,
+    })]
+}
+
+#[test]
+fn impact_grows_with_size() {
+    // $1k into a $200k pool (100k quote reserve) ≈ 99 bps.
+    let small = estimate_impact_bps(1_000.0, 200_000.0);
+    assert!((small - 99.0099).abs() < 1e-3);
+    assert!(estimate_impact_bps(10_000.0, 200_000.0) > small);
+    assert_eq!(estimate_impact_bps(1_000.0, 0.0), 10_000.0);
+}
+
+#[test]
+fn gate_test_reports_impact_and_min_out_for_size() {
+    let report = GateTestReport::run(&ctx_with_liquidity(200_000.0), &gates(), 1_000.0, 100);
+
+    assert!(report.passed);
+    let impact = report.est_impact_bps.expect("impact estimated");
+    assert!((impact - 99.0099).abs() < 1e-3);
+    // 2000 tokens at spot, minus ~0.99% impact, minus 1% slippage.
+    let min_out = report.est_min_out.expect("min out estimated");
+    assert!((min_out - 2_000.0 * (1.0 - impact / 10_000.0) * 0.99).abs() < 1e-6);
+
+    let text = report.render();
+    assert!(text.contains("PASS"));
+    assert!(text.contains("est_impact: 99.0 bps"));
+    assert!(text.contains("min_out:"));
+}
+
+#[test]
+fn gate_test_command_parses_mint_and_size() {
+    match parse_command("/gate_test mintX 250") {
+        Some(Ctrl::GateTest { mint, size_usd }) => {
+            assert_eq!(mint, "mintX");
+            assert_eq!(size_usd, 250.0);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(parse_command("/gate_test mintX abc").is_none());
+}
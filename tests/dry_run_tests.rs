@@ -0,0 +1,90 @@
+//! Tests for dry-run paper trading.
+
+use solana_vntr_sniper::universal::dry_run::{
+    dry_run_send, paper_buy, paper_sell, DryRunSignature,
+};
+use solana_vntr_sniper::universal::executor::{
+    ExecutionSimulator, SimulationAction, SimulationResult,
+};
+use solana_vntr_sniper::universal::gates::TradeContext;
+use solana_vntr_sniper::universal::positions::{PositionError, PositionsRegistry};
+
+/// Passes every mint except those starting with "bad".
+struct StubSim;
+
+impl ExecutionSimulator for StubSim {
+    fn simulate(&self, ctx: &TradeContext, action: SimulationAction) -> SimulationResult {
+        let passed = !ctx.mint.starts_with("bad");
+        SimulationResult {
+            mint: ctx.mint.clone(),
+            action,
+            passed,
+            gate_reasons: if passed {
+                vec![]
+            } else {
+                vec![("Stub".into(), "bad_mint".into())]
+            },
+            est_fill_pct: if passed { 1.0 } else { 0.0 },
+            est_pnl_usd: None,
+        }
+    }
+}
+
+fn ctx(mint: &str) -> TradeContext {
+    TradeContext {
+        mint: mint.into(),
+        target_wallet: "leader".into(),
+        price_usd: 1.0,
+        ..TradeContext::default()
+    }
+}
+
+#[test]
+fn passing_paper_buy_opens_a_simulated_position() {
+    let registry = PositionsRegistry::new();
+    let result = paper_buy(&registry, &StubSim, "me", &ctx("goodMint"), 100);
+    assert!(result.passed);
+
+    let pos = registry.get_open_position("me", "goodMint").unwrap();
+    assert!(pos.simulated);
+    assert_eq!(pos.opened_ts, 100);
+    // It holds the slot like a real position.
+    assert!(registry.can_open("me", "goodMint").is_err());
+
+    let rejected = paper_buy(&registry, &StubSim, "me", &ctx("badMint"), 100);
+    assert!(!rejected.passed);
+    assert!(!registry.has_open_position("me", "badMint"));
+}
+
+#[test]
+fn paper_sell_closes_only_simulated_positions() {
+    let registry = PositionsRegistry::new();
+    paper_buy(&registry, &StubSim, "me", &ctx("paperMint"), 100);
+    registry.record_open("me", "realMint", 100).unwrap();
+
+    paper_sell(&registry, "me", "paperMint").unwrap();
+    assert!(!registry.has_open_position("me", "paperMint"));
+
+    assert!(matches!(
+        paper_sell(&registry, "me", "realMint"),
+        Err(PositionError::NotOpen(_, _))
+    ));
+    let real = registry.get_open_position("me", "realMint").unwrap();
+    assert!(!real.simulated);
+}
+
+#[test]
+fn dry_run_signatures_identify_each_send() {
+    let ts = 1_700_000_000_000_000_000;
+    let buy = DryRunSignature::derive("buy mintA", ts, 0);
+    assert_eq!(buy, DryRunSignature::derive("buy mintA", ts, 0));
+    assert_ne!(buy, DryRunSignature::derive("sell mintA", ts, 0));
+    assert_ne!(buy, DryRunSignature::derive("buy mintA", ts + 1, 0));
+    assert_ne!(buy, DryRunSignature::derive("buy mintA", ts, 1));
+    assert_eq!(buy.as_ref().len(), 64);
+    assert_ne!(buy, DryRunSignature::mock());
+
+    // Repeated sends of the same label still get distinct ids.
+    assert_ne!(dry_run_send("priority_sell"), dry_run_send("priority_sell"));
+    assert_ne!(dry_run_send("buy mintA"), dry_run_send("buy mintB"));
+}
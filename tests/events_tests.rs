@@ -0,0 +1,64 @@
+//! Tests for structured JSONL event output.
+
+use serde_json::Value;
+use solana_vntr_sniper::universal::events::{install_emitter, EventEmitter};
+use solana_vntr_sniper::universal::executor::{
+    ExecutionSimulator, SimBackend, SimConfig, SimulationAction,
+};
+use solana_vntr_sniper::universal::gates::{run_gates, Gate, McapGate, TradeContext};
+
+fn ctx() -> TradeContext {
+    TradeContext {
+        mint: "mintX".into(),
+        target_wallet: "walletA".into(),
+        price_usd: 1.0,
+        window5m_usd: 1_000_000.0,
+        window15m_usd: 3_000_000.0,
+        depth_multiple: 10.0,
+        est_mcap_usd: Some(10_000.0),
+        window_vol_pct: 1.0,
+        metrics_age_secs: Some(0),
+        ..TradeContext::default()
+    }
+}
+
+// The emitter is process-wide, so everything is checked in a single test.
+#[test]
+fn gate_rejections_and_simulations_are_written_as_jsonl() {
+    let path = std::env::temp_dir().join(format!("events_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    install_emitter(EventEmitter::open(&path).unwrap());
+
+    let gates: Vec<Box<dyn Gate>> = vec![Box::new(McapGate { min_mcap: 50_000.0 })];
+    let (ok, _) = run_gates(&ctx(), &gates);
+    assert!(!ok);
+
+    let result = SimBackend::new(SimConfig::default()).simulate(&ctx(), SimulationAction::Buy);
+    assert!(!result.passed);
+
+    let events: Vec<Value> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("one JSON object per line"))
+        .collect();
+
+    let first = &events[0];
+    assert_eq!(first["event"], "gate_rejection");
+    assert_eq!(first["mint"], "mintX");
+    assert_eq!(first["target_wallet"], "walletA");
+    assert_eq!(first["gate"], "McapGate");
+    assert_eq!(first["reason"], "mcap_usd 10000 < 50000");
+    assert!(first["ts"].as_i64().unwrap() > 0);
+
+    let sim = events.last().unwrap();
+    assert_eq!(sim["event"], "simulation");
+    assert_eq!(sim["mint"], "mintX");
+    assert_eq!(sim["target_wallet"], "walletA");
+    assert_eq!(sim["result"]["passed"], false);
+    assert_eq!(sim["result"]["action"], "Buy");
+
+    // The simulation's own gate rejection is logged before its result.
+    assert_eq!(events[events.len() - 2]["event"], "gate_rejection");
+
+    let _ = std::fs::remove_file(&path);
+}
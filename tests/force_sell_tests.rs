@@ -0,0 +1,91 @@
+//! Tests for the forced (panic / kill-switch) sell path.
+
+use futures::future::BoxFuture;
+use solana_vntr_sniper::universal::force_sell::{
+    force_exit, live_open_mints, run_sell_gates, sell_slippage_bps, ForceSeller,
+};
+use solana_vntr_sniper::universal::gates::{Gate, McapGate, TradeContext};
+use solana_vntr_sniper::universal::positions::{OpenParams, PositionsRegistry};
+
+fn sell_ctx() -> TradeContext {
+    TradeContext {
+        mint: "mintX".into(),
+        target_wallet: "walletA".into(),
+        price_usd: 1.0,
+        est_cost_bps: 50.0,
+        window5m_usd: 20_000.0,
+        window15m_usd: 60_000.0,
+        depth_multiple: 5.0,
+        est_mcap_usd: Some(10_000.0),
+        window_vol_pct: 10.0,
+        metrics_age_secs: Some(5),
+        ..TradeContext::default()
+    }
+}
+
+fn strict_gates() -> Vec<Box<dyn Gate>> {
+    vec![Box::new(McapGate {
+        min_mcap: 1_000_000.0,
+    })]
+}
+
+#[test]
+fn normal_sell_is_blocked_by_gate() {
+    let (proceed, reasons) = run_sell_gates(&sell_ctx(), &strict_gates(), false);
+    assert!(!proceed);
+    assert_eq!(reasons[0].0, "McapGate");
+}
+
+#[test]
+fn force_sell_proceeds_even_when_gate_rejects() {
+    let (proceed, bypassed) = run_sell_gates(&sell_ctx(), &strict_gates(), true);
+    assert!(proceed);
+    // The rejection is still reported so it can be logged.
+    assert_eq!(bypassed.len(), 1);
+    assert_eq!(bypassed[0].0, "McapGate");
+}
+
+#[test]
+fn force_sell_widens_but_never_tightens_slippage() {
+    assert_eq!(sell_slippage_bps(1_000, false), 1_000);
+    assert_eq!(sell_slippage_bps(1_000, true), 10_000);
+}
+
+/// Fails mints starting with "fail", otherwise "signs" the sell.
+struct StubSeller;
+
+impl ForceSeller for StubSeller {
+    fn force_sell<'a>(&'a self, mint: &'a str) -> BoxFuture<'a, Result<String, String>> {
+        Box::pin(async move {
+            if mint.starts_with("fail") {
+                Err("send failed".to_string())
+            } else {
+                Ok(format!("sig-{}", mint))
+            }
+        })
+    }
+}
+
+#[tokio::test]
+async fn force_exit_sells_every_live_mint_past_failures() {
+    let registry = PositionsRegistry::new();
+    registry.record_open("walletA", "failMint", 1).unwrap();
+    registry.record_open("walletA", "goodMint", 1).unwrap();
+    registry.record_open("walletB", "goodMint", 1).unwrap();
+    registry
+        .record_open_simulated("walletA", "paperMint", 1, OpenParams::default())
+        .unwrap();
+
+    // Paper positions have nothing to sell; a mint held twice sells once.
+    let mints = live_open_mints(&registry);
+    assert_eq!(mints, vec!["failMint".to_string(), "goodMint".to_string()]);
+
+    let results = force_exit(&StubSeller, &mints).await;
+    assert_eq!(
+        results,
+        vec![
+            ("failMint".to_string(), Err("send failed".to_string())),
+            ("goodMint".to_string(), Ok("sig-goodMint".to_string())),
+        ]
+    );
+}
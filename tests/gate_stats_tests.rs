@@ -0,0 +1,75 @@
+//! Tests for gate stats counters and the periodic JSONL report.
+
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+
+use solana_vntr_sniper::universal::gates::stats::{
+    Clock, GateStats, GateStatsReport, GateStatsReportConfig, GateStatsReporter,
+};
+
+#[derive(Clone)]
+struct FakeClock(Arc<AtomicI64>);
+
+impl FakeClock {
+    fn advance(&self, secs: i64) {
+        self.0.fetch_add(secs, Ordering::SeqCst);
+    }
+}
+
+impl Clock for FakeClock {
+    fn now_secs(&self) -> i64 {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+#[test]
+fn writes_one_report_per_interval_with_deltas() {
+    let path = std::env::temp_dir().join(format!("gate_stats_{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let clock = FakeClock(Arc::new(AtomicI64::new(1_000)));
+    let stats = GateStats::new();
+    let mut reporter = GateStatsReporter::new(
+        GateStatsReportConfig {
+            path: path.clone(),
+            interval_secs: 60,
+        },
+        clock.clone(),
+    );
+
+    stats.record(None);
+    stats.record(Some("LiquidityGate"));
+    stats.record(Some("LiquidityGate"));
+
+    clock.advance(30);
+    assert!(reporter.tick(&stats).is_none(), "not due yet");
+
+    clock.advance(30);
+    let first = reporter.tick(&stats).expect("first report");
+    assert_eq!(first.ts, 1_060);
+    assert_eq!(first.delta.evaluated, 3);
+    assert_eq!(first.delta.passed, 1);
+    assert_eq!(first.delta.rejected.get("LiquidityGate"), Some(&2));
+
+    stats.record(Some("McapGate"));
+    stats.record(None);
+
+    clock.advance(60);
+    let second = reporter.tick(&stats).expect("second report");
+    assert_eq!(second.ts, 1_120);
+    assert_eq!(second.interval_secs, 60);
+    assert_eq!(second.totals.evaluated, 5);
+    assert_eq!(second.delta.evaluated, 2);
+    assert_eq!(second.delta.passed, 1);
+    assert_eq!(second.delta.rejected.get("McapGate"), Some(&1));
+    assert_eq!(second.delta.rejected.get("LiquidityGate"), None);
+
+    let written: Vec<GateStatsReport> = std::fs::read_to_string(&path)
+        .expect("report file written")
+        .lines()
+        .map(|l| serde_json::from_str(l).expect("valid json line"))
+        .collect();
+    assert_eq!(written, vec![first, second]);
+
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,774 @@
+//! Tests for the universal trade gates.
+
+use solana_vntr_sniper::universal::gates::authority::{
+    mint_authorities, MintAuthorities, MintAuthorityCache, MintAuthorityGate,
+};
+use solana_vntr_sniper::universal::gates::curve_progress::{
+    curve_progress_pct, INITIAL_REAL_TOKEN_RESERVES,
+};
+use solana_vntr_sniper::universal::gates::holders::top10_pct;
+use solana_vntr_sniper::universal::gates::liquidity::{
+    evaluate_guard, evaluate_guard_for_size, weighted_liquidity_score, GuardThresholds,
+    LiquidityGateConfig, VolumeMode,
+};
+use solana_vntr_sniper::universal::gates::stats::Clock;
+use solana_vntr_sniper::universal::gates::transfer_hook::transfer_hook_program;
+use solana_vntr_sniper::universal::gates::{
+    enabled_gates, gate_enabled, run_gates, run_gates_batch, run_gates_collect, run_scored_gates,
+    score_at_least, score_at_most, BondingCurveProgressGate, Gate, GateDecision,
+    HolderConcentrationGate, LiquidityGate, McapGate, MinAgeGate, RejectReason, ScoredGate,
+    TradeContext, TransferHookGate, VolatilityGate,
+};
+use solana_vntr_sniper::universal::impact::{estimate_impact_bps, estimate_price_impact_bps};
+use solana_vntr_sniper::universal::metrics::TokenMetrics;
+use spl_token_2022::extension::transfer_hook::TransferHook;
+use spl_token_2022::extension::{
+    BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+};
+use spl_token_2022::solana_program::program_option::COption;
+use spl_token_2022::solana_program::program_pack::Pack;
+use spl_token_2022::solana_program::pubkey::Pubkey;
+use spl_token_2022::state::Mint;
+
+/// A context that comfortably passes the default liquidity thresholds.
+fn healthy_ctx() -> TradeContext {
+    TradeContext {
+        mint: "mintX".into(),
+        target_wallet: "walletA".into(),
+        price_usd: 1.0,
+        est_cost_bps: 50.0,
+        window5m_usd: 20_000.0,
+        window15m_usd: 60_000.0,
+        depth_multiple: 5.0,
+        est_mcap_usd: Some(10_000_000.0),
+        window_vol_pct: 10.0,
+        amihud_5m: Some(1e-7),
+        range_eff_5m: Some(0.6),
+        metrics_age_secs: Some(5),
+        ..TradeContext::default()
+    }
+}
+
+fn rejection(decision: GateDecision) -> String {
+    match decision {
+        GateDecision::Rejected { reason, .. } => reason,
+        GateDecision::Passed => panic!("expected rejection"),
+    }
+}
+
+fn rejection_code(decision: GateDecision) -> RejectReason {
+    match decision {
+        GateDecision::Rejected { code, .. } => code,
+        GateDecision::Passed => panic!("expected rejection"),
+    }
+}
+
+fn micro_cfg() -> LiquidityGateConfig {
+    LiquidityGateConfig {
+        amihud_max: Some(1e-6),
+        range_eff_min: Some(0.3),
+        max_age_secs: Some(30),
+        ..LiquidityGateConfig::default()
+    }
+}
+
+#[test]
+fn liquidity_gate_passes_healthy_context_with_micro_checks() {
+    let gate = LiquidityGate::new(micro_cfg());
+    assert!(matches!(gate.check(&healthy_ctx()), GateDecision::Passed));
+}
+
+#[test]
+fn liquidity_gate_rejects_high_amihud() {
+    let gate = LiquidityGate::new(micro_cfg());
+    let mut ctx = healthy_ctx();
+    ctx.amihud_5m = Some(1e-5);
+
+    assert!(rejection(gate.check(&ctx)).starts_with("amihud "));
+}
+
+#[test]
+fn liquidity_gate_rejects_low_range_efficiency() {
+    let gate = LiquidityGate::new(micro_cfg());
+    let mut ctx = healthy_ctx();
+    ctx.range_eff_5m = Some(0.1);
+
+    assert_eq!(rejection(gate.check(&ctx)), "range_eff 0.1 < 0.3");
+}
+
+#[test]
+fn liquidity_gate_rejects_stale_metrics() {
+    let gate = LiquidityGate::new(micro_cfg());
+    let mut ctx = healthy_ctx();
+    ctx.metrics_age_secs = Some(120);
+
+    assert_eq!(rejection(gate.check(&ctx)), "stale_metrics 120s > 30s");
+}
+
+#[test]
+fn liquidity_rejections_carry_reason_codes() {
+    let gate = LiquidityGate::new(micro_cfg());
+
+    let mut ctx = healthy_ctx();
+    ctx.metrics_age_secs = Some(120);
+    assert_eq!(rejection_code(gate.check(&ctx)), RejectReason::Stale);
+
+    let mut ctx = healthy_ctx();
+    ctx.amihud_5m = Some(1e-5);
+    assert_eq!(rejection_code(gate.check(&ctx)), RejectReason::Amihud);
+
+    let mut ctx = healthy_ctx();
+    ctx.range_eff_5m = Some(0.1);
+    assert_eq!(rejection_code(gate.check(&ctx)), RejectReason::RangeEff);
+
+    let mut ctx = healthy_ctx();
+    ctx.est_mcap_usd = None;
+    assert_eq!(rejection_code(gate.check(&ctx)), RejectReason::NoMcap);
+
+    assert_eq!(RejectReason::LowLiq5m.to_string(), "low_liq5m");
+    assert_eq!(
+        serde_json::to_string(&RejectReason::DepthMultiple).unwrap(),
+        "\"depth_multiple\""
+    );
+}
+
+#[test]
+fn liquidity_gate_ignores_micro_fields_when_unconfigured() {
+    let gate = LiquidityGate::new(LiquidityGateConfig::default());
+    let mut ctx = healthy_ctx();
+    ctx.amihud_5m = None;
+    ctx.range_eff_5m = None;
+    ctx.metrics_age_secs = None;
+
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+}
+
+#[test]
+fn gate_and_metrics_guard_reject_stale_input_identically() {
+    let thresholds = GuardThresholds {
+        max_age_secs: Some(30),
+        ..GuardThresholds::default()
+    };
+    let now = 1_700_000_000;
+
+    let metrics = TokenMetrics {
+        mint: "mintX".into(),
+        price_usd: 1.0,
+        liq_usd: Some(500_000.0),
+        mcap_usd: Some(10_000_000.0),
+        vol_5m_usd: 20_000.0,
+        vol_15m_usd: 60_000.0,
+        amihud_5m: None,
+        range_eff_5m: None,
+        last_update: now - 90,
+    };
+    let mut ctx = healthy_ctx();
+    ctx.metrics_age_secs = Some(90);
+
+    let from_metrics = rejection(evaluate_guard(&metrics, &thresholds, now));
+    let from_gate = rejection(LiquidityGate::new(thresholds).check(&ctx));
+
+    assert_eq!(from_metrics, "stale_metrics 90s > 30s");
+    assert_eq!(from_metrics, from_gate);
+}
+
+#[test]
+fn liquidity_gate_rejects_order_large_relative_to_liquidity() {
+    let gate = LiquidityGate::new(LiquidityGateConfig::default());
+    let mut ctx = healthy_ctx();
+    ctx.liq_usd = Some(10_000.0);
+    ctx.requested_size_usd = Some(2_000.0);
+
+    assert_eq!(rejection(gate.check(&ctx)), "size_over_liq 0.2 > 0.1");
+
+    ctx.requested_size_usd = Some(500.0);
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+}
+
+#[test]
+fn size_over_liq_is_skipped_without_size() {
+    let gate = LiquidityGate::new(LiquidityGateConfig {
+        max_size_over_liq: 0.0001,
+        ..LiquidityGateConfig::default()
+    });
+    let mut ctx = healthy_ctx();
+    ctx.liq_usd = Some(10_000.0);
+
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+}
+
+#[test]
+fn weighted_volume_mode_compares_single_score() {
+    assert_eq!(
+        weighted_liquidity_score(10_000.0, 20_000.0, 0.7, 0.3),
+        13_000.0
+    );
+
+    let gate = LiquidityGate::new(LiquidityGateConfig {
+        volume_mode: VolumeMode::Weighted {
+            w5m: 0.7,
+            w15m: 0.3,
+            min_score: 12_000.0,
+        },
+        ..LiquidityGateConfig::default()
+    });
+    let mut ctx = healthy_ctx();
+
+    // Both windows below their independent floors, but the score clears.
+    ctx.window5m_usd = 10_000.0;
+    ctx.window15m_usd = 20_000.0;
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+
+    ctx.window5m_usd = 5_000.0;
+    assert_eq!(rejection(gate.check(&ctx)), "low_liq_score 9500 < 12000");
+}
+
+#[test]
+fn price_impact_grows_with_size_and_is_capped_by_depth() {
+    let liq = 200_000.0;
+    let mut last = 0.0;
+    for size in [100.0, 1_000.0, 10_000.0, 100_000.0, 1_000_000.0] {
+        // Depth from liquidity alone matches the plain constant-product model.
+        let impact = estimate_price_impact_bps(size, liq, liq / size);
+        assert!((impact - estimate_impact_bps(size, liq)).abs() < 1e-9);
+        assert!(impact > last && impact < 10_000.0);
+        last = impact;
+    }
+    // Half of the liquidity is the quote reserve: 10k into 100k moves 909 bps.
+    assert!((estimate_price_impact_bps(10_000.0, liq, 20.0) - 909.09).abs() < 0.01);
+    assert!((estimate_price_impact_bps(100_000.0, liq, 2.0) - 5_000.0).abs() < 1e-9);
+
+    // Exposure already held shrinks the depth: 1k at depth 50 sees 50k, not 200k.
+    let held = estimate_price_impact_bps(1_000.0, liq, 50.0);
+    assert!((held - 1_000.0 / 26_000.0 * 10_000.0).abs() < 1e-9);
+    assert!(held > estimate_price_impact_bps(1_000.0, liq, f64::INFINITY));
+
+    assert_eq!(estimate_price_impact_bps(0.0, liq, 5.0), 0.0);
+    assert_eq!(estimate_price_impact_bps(1_000.0, liq, 0.0), 10_000.0);
+    assert_eq!(estimate_price_impact_bps(1_000.0, 0.0, 5.0), 10_000.0);
+    assert_eq!(
+        estimate_price_impact_bps(1_000.0, liq, f64::NAN),
+        estimate_impact_bps(1_000.0, liq)
+    );
+}
+
+#[test]
+fn liquidity_gate_rejects_high_price_impact() {
+    let gate = LiquidityGate::new(LiquidityGateConfig {
+        max_impact_bps: Some(500.0),
+        ..LiquidityGateConfig::default()
+    });
+    let mut ctx = healthy_ctx();
+    ctx.liq_usd = Some(200_000.0);
+
+    ctx.requested_size_usd = Some(1_000.0);
+    ctx.depth_multiple = 200.0;
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+
+    // Held exposure leaves depth 5x: the same 1k order now moves 2857 bps.
+    ctx.depth_multiple = 5.0;
+    assert_eq!(rejection_code(gate.check(&ctx)), RejectReason::PriceImpact);
+
+    ctx.requested_size_usd = Some(10_000.0);
+    ctx.depth_multiple = 20.0;
+    assert_eq!(
+        rejection(gate.check(&ctx)),
+        "price_impact 909.1bps > 500bps"
+    );
+
+    // Without liquidity the impact cannot be estimated and is not enforced.
+    ctx.liq_usd = None;
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+}
+
+#[test]
+fn guard_for_size_reports_estimated_impact() {
+    let now = 1_700_000_000;
+    let mut metrics = TokenMetrics {
+        mint: "mintX".into(),
+        price_usd: 1.0,
+        liq_usd: Some(200_000.0),
+        mcap_usd: Some(10_000_000.0),
+        vol_5m_usd: 20_000.0,
+        vol_15m_usd: 60_000.0,
+        amihud_5m: None,
+        range_eff_5m: None,
+        last_update: now,
+    };
+    let thresholds = GuardThresholds {
+        max_impact_bps: Some(500.0),
+        ..GuardThresholds::default()
+    };
+
+    let small = evaluate_guard_for_size(&metrics, &thresholds, 1_000.0, now);
+    assert!(small.passed());
+    assert!((small.est_impact_bps.unwrap() - 99.01).abs() < 0.01);
+
+    let large = evaluate_guard_for_size(&metrics, &thresholds, 10_000.0, now);
+    assert!(!large.passed());
+    assert!((large.est_impact_bps.unwrap() - 909.09).abs() < 0.01);
+    assert_eq!(rejection_code(large.decision), RejectReason::PriceImpact);
+
+    metrics.liq_usd = None;
+    let unknown = evaluate_guard_for_size(&metrics, &thresholds, 10_000.0, now);
+    assert!(unknown.passed());
+    assert_eq!(unknown.est_impact_bps, None);
+}
+
+/// Raw Token-2022 mint account data, with a transfer hook if `hook` is set.
+fn token_2022_mint_data(hook: Option<Pubkey>) -> Vec<u8> {
+    let extensions: Vec<ExtensionType> = hook.iter().map(|_| ExtensionType::TransferHook).collect();
+    let len = if extensions.is_empty() {
+        Mint::LEN
+    } else {
+        ExtensionType::try_calculate_account_len::<Mint>(&extensions).unwrap()
+    };
+    let mut data = vec![0u8; len];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+    if let Some(program) = hook {
+        let ext = state.init_extension::<TransferHook>(true).unwrap();
+        ext.program_id.0 = program;
+    }
+    state.base = Mint {
+        decimals: 6,
+        is_initialized: true,
+        ..Mint::default()
+    };
+    state.pack_base();
+    if !extensions.is_empty() {
+        state.init_account_type().unwrap();
+    }
+    data
+}
+
+#[test]
+fn hook_bearing_mint_is_rejected_and_hook_free_mint_passes() {
+    let hook = Pubkey::new_unique();
+    let gate = TransferHookGate::default();
+
+    let mut ctx = healthy_ctx();
+    ctx.transfer_hook_program = transfer_hook_program(&token_2022_mint_data(Some(hook)));
+    assert_eq!(ctx.transfer_hook_program, Some(hook.to_string()));
+    assert_eq!(
+        rejection(gate.check(&ctx)),
+        format!("transfer_hook {} not allowlisted", hook)
+    );
+
+    ctx.transfer_hook_program = transfer_hook_program(&token_2022_mint_data(None));
+    assert_eq!(ctx.transfer_hook_program, None);
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+}
+
+#[test]
+fn allowlisted_hook_program_passes() {
+    let hook = Pubkey::new_unique();
+    let gate = TransferHookGate::new([hook.to_string()].into_iter().collect());
+
+    let mut ctx = healthy_ctx();
+    ctx.transfer_hook_program = transfer_hook_program(&token_2022_mint_data(Some(hook)));
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+}
+
+#[test]
+fn dead_liquidity_ratio_rejects_fake_pools() {
+    let gate = LiquidityGate::new(LiquidityGateConfig {
+        min_vol_liq_ratio: Some(0.01),
+        ..LiquidityGateConfig::default()
+    });
+
+    // 20k traded in 5m against 500k TVL: healthy.
+    let mut ctx = healthy_ctx();
+    ctx.liq_usd = Some(500_000.0);
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+
+    // Same volume against 10M claimed TVL: implausibly quiet.
+    ctx.liq_usd = Some(10_000_000.0);
+    assert_eq!(
+        rejection(gate.check(&ctx)),
+        "dead_liquidity ratio=0.002 < 0.01"
+    );
+}
+
+/// A context failing the market-cap and volatility gates but not liquidity.
+fn mcap_and_vol_failing_ctx() -> TradeContext {
+    TradeContext {
+        est_mcap_usd: Some(1_000.0),
+        window_vol_pct: 90.0,
+        ..healthy_ctx()
+    }
+}
+
+fn ordered_gates() -> Vec<Box<dyn Gate>> {
+    vec![
+        Box::new(LiquidityGate::new(LiquidityGateConfig {
+            min_mcap_usd: 0.0,
+            ..LiquidityGateConfig::default()
+        })),
+        Box::new(McapGate { min_mcap: 50_000.0 }),
+        Box::new(VolatilityGate { max_pct: 50.0 }),
+    ]
+}
+
+#[test]
+fn collect_all_returns_every_failing_reason() {
+    let ctx = mcap_and_vol_failing_ctx();
+
+    let (ok, reasons) = run_gates_collect(&ctx, &ordered_gates());
+    assert!(!ok);
+    let names: Vec<&str> = reasons.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["McapGate", "VolatilityGate"]);
+
+    let (ok, reasons) = run_gates(&ctx, &ordered_gates());
+    assert!(!ok);
+    assert_eq!(reasons.len(), 1);
+    assert_eq!(reasons[0].0, "McapGate");
+}
+
+#[test]
+fn caller_controls_gate_order() {
+    let ctx = mcap_and_vol_failing_ctx();
+    let mut gates = ordered_gates();
+    gates.reverse();
+
+    let (_, reasons) = run_gates(&ctx, &gates);
+    assert_eq!(reasons[0].0, "VolatilityGate");
+
+    let (_, reasons) = run_gates_collect(&ctx, &gates);
+    let names: Vec<&str> = reasons.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["VolatilityGate", "McapGate"]);
+}
+
+// The only test here touching MCAP_GATE_ENABLED, since env is process-wide.
+#[test]
+fn disabled_gate_is_dropped_and_never_rejects() {
+    let ctx = mcap_and_vol_failing_ctx();
+    let mcap_only = || -> Vec<Box<dyn Gate>> { vec![Box::new(McapGate { min_mcap: 50_000.0 })] };
+
+    std::env::set_var("MCAP_GATE_ENABLED", "false");
+    assert!(!gate_enabled("McapGate"));
+    assert!(gate_enabled("VolatilityGate"));
+    assert!(enabled_gates(mcap_only()).is_empty());
+    let (ok, reasons) = run_gates_collect(&ctx, &enabled_gates(ordered_gates()));
+    assert!(!ok);
+    let names: Vec<&str> = reasons.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["VolatilityGate"]);
+
+    std::env::set_var("MCAP_GATE_ENABLED", "OFF");
+    assert!(!gate_enabled("McapGate"));
+
+    std::env::set_var("MCAP_GATE_ENABLED", "true");
+    assert!(gate_enabled("McapGate"));
+
+    std::env::remove_var("MCAP_GATE_ENABLED");
+    let (ok, reasons) = run_gates(&ctx, &enabled_gates(mcap_only()));
+    assert!(!ok);
+    assert_eq!(reasons[0].0, "McapGate");
+}
+
+#[test]
+fn collect_all_passes_when_no_gate_rejects() {
+    let (ok, reasons) = run_gates_collect(&healthy_ctx(), &ordered_gates());
+    assert!(ok);
+    assert!(reasons.is_empty());
+}
+
+#[test]
+fn batch_matches_per_context_results_in_order() {
+    let gates = ordered_gates();
+    let contexts: Vec<TradeContext> = (0..1000)
+        .map(|i| {
+            let mut ctx = if i % 3 == 0 {
+                mcap_and_vol_failing_ctx()
+            } else {
+                healthy_ctx()
+            };
+            ctx.mint = format!("mint{}", i);
+            ctx
+        })
+        .collect();
+
+    let started = std::time::Instant::now();
+    let batch = run_gates_batch(&contexts, &gates);
+    let batch_elapsed = started.elapsed();
+
+    let started = std::time::Instant::now();
+    let single: Vec<_> = contexts.iter().map(|ctx| run_gates(ctx, &gates)).collect();
+    let single_elapsed = started.elapsed();
+    println!(
+        "1000 contexts: batch {:?}, one at a time {:?}",
+        batch_elapsed, single_elapsed
+    );
+
+    assert_eq!(batch, single);
+    for (i, (ok, reasons)) in batch.iter().enumerate() {
+        assert_eq!(*ok, i % 3 != 0, "context {}", i);
+        if !ok {
+            assert_eq!(reasons[0].0, "McapGate");
+        }
+    }
+    assert!(run_gates_batch(&[], &gates).is_empty());
+}
+
+#[test]
+fn threshold_scores_at_boundaries() {
+    assert_eq!(score_at_least(0.0, 100.0), 0.0);
+    assert_eq!(score_at_least(100.0, 100.0), 0.5);
+    assert_eq!(score_at_least(200.0, 100.0), 1.0);
+    assert_eq!(score_at_least(1_000.0, 100.0), 1.0);
+    assert_eq!(score_at_least(5.0, 0.0), 1.0);
+    assert_eq!(score_at_least(f64::NAN, 100.0), 0.0);
+
+    assert_eq!(score_at_most(0.0, 50.0), 1.0);
+    assert_eq!(score_at_most(50.0, 50.0), 0.5);
+    assert_eq!(score_at_most(100.0, 50.0), 0.0);
+    assert_eq!(score_at_most(500.0, 50.0), 0.0);
+}
+
+#[test]
+fn narrow_miss_scores_just_under_half() {
+    let gate = VolatilityGate { max_pct: 50.0 };
+    let ctx = TradeContext {
+        window_vol_pct: 51.0,
+        ..healthy_ctx()
+    };
+    assert!(matches!(
+        Gate::check(&gate, &ctx),
+        GateDecision::Rejected { .. }
+    ));
+    let score = ScoredGate::score(&gate, &ctx);
+    assert!(score > 0.45 && score < 0.5, "score {}", score);
+}
+
+#[test]
+fn liquidity_score_is_weakest_threshold() {
+    let gate = LiquidityGate::new(LiquidityGateConfig {
+        liq_5m_min_usd: 10_000.0,
+        liq_15m_min_usd: 30_000.0,
+        min_mcap_usd: 1_000_000.0,
+        ..LiquidityGateConfig::default()
+    });
+    // 5m volume sits exactly on its floor; everything else is well clear.
+    let ctx = TradeContext {
+        window5m_usd: 10_000.0,
+        ..healthy_ctx()
+    };
+    assert_eq!(ScoredGate::score(&gate, &ctx), 0.5);
+
+    let no_mcap = TradeContext {
+        est_mcap_usd: None,
+        ..healthy_ctx()
+    };
+    assert_eq!(ScoredGate::score(&gate, &no_mcap), 0.0);
+}
+
+#[test]
+fn aggregate_score_takes_the_minimum() {
+    let gates: Vec<Box<dyn ScoredGate>> = vec![
+        Box::new(LiquidityGate::new(LiquidityGateConfig {
+            liq_5m_min_usd: 5_000.0,
+            liq_15m_min_usd: 15_000.0,
+            min_mcap_usd: 1_000_000.0,
+            ..LiquidityGateConfig::default()
+        })),
+        Box::new(VolatilityGate { max_pct: 20.0 }),
+    ];
+    // Liquidity comfortably clears (score 1.0); volatility 10% of 20% max
+    // scores 0.75.
+    assert_eq!(run_scored_gates(&healthy_ctx(), &gates), 0.75);
+    assert_eq!(run_scored_gates(&healthy_ctx(), &[]), 1.0);
+}
+
+struct FixedClock(i64);
+
+impl Clock for FixedClock {
+    fn now_secs(&self) -> i64 {
+        self.0
+    }
+}
+
+#[test]
+fn min_age_gate_rejects_tokens_younger_than_threshold() {
+    let gate = MinAgeGate::with_clock(300, FixedClock(10_000));
+    let seen_at = |ts: i64| TradeContext {
+        first_seen_ts: Some(ts),
+        ..healthy_ctx()
+    };
+
+    assert_eq!(
+        rejection(gate.check(&seen_at(10_000 - 42))),
+        "too_new 42s < 300"
+    );
+    assert!(matches!(
+        gate.check(&seen_at(10_000 - 300)),
+        GateDecision::Passed
+    ));
+    assert_eq!(rejection(gate.check(&healthy_ctx())), "no_first_seen");
+}
+
+#[test]
+fn min_age_gate_is_permissive_by_default() {
+    let gate = MinAgeGate::with_clock(0, FixedClock(10_000));
+    let brand_new = TradeContext {
+        first_seen_ts: Some(10_000),
+        ..healthy_ctx()
+    };
+    assert!(matches!(gate.check(&brand_new), GateDecision::Passed));
+    assert!(matches!(gate.check(&healthy_ctx()), GateDecision::Passed));
+}
+
+#[test]
+fn top10_pct_sums_the_ten_largest_balances() {
+    let mut balances = vec![1u64; 5];
+    balances.extend([100, 90, 80, 70, 60, 50, 40, 30, 20, 10]);
+    assert_eq!(top10_pct(&balances, 1_000), Some(55.0));
+    assert_eq!(top10_pct(&[5, 5], 100), Some(10.0));
+    assert_eq!(top10_pct(&[5], 0), None);
+}
+
+#[test]
+fn holder_concentration_gate_rejects_concentrated_and_missing_data() {
+    let gate = HolderConcentrationGate::new(40.0);
+    let with_pct = |pct: Option<f64>| TradeContext {
+        top10_holder_pct: pct,
+        ..healthy_ctx()
+    };
+
+    assert_eq!(
+        rejection(gate.check(&with_pct(Some(55.0)))),
+        "top10 55% > 40%"
+    );
+    assert!(matches!(
+        gate.check(&with_pct(Some(40.0))),
+        GateDecision::Passed
+    ));
+    assert_eq!(rejection(gate.check(&with_pct(None))), "no_holder_data");
+
+    let disabled = HolderConcentrationGate::new(0.0);
+    assert!(matches!(
+        disabled.check(&with_pct(None)),
+        GateDecision::Passed
+    ));
+}
+
+fn legacy_mint_data(mint_authority: Option<Pubkey>, freeze_authority: Option<Pubkey>) -> Vec<u8> {
+    let mut data = vec![0u8; Mint::LEN];
+    let mint = Mint {
+        mint_authority: mint_authority.map_or(COption::None, COption::Some),
+        freeze_authority: freeze_authority.map_or(COption::None, COption::Some),
+        decimals: 6,
+        is_initialized: true,
+        ..Mint::default()
+    };
+    Mint::pack(mint, &mut data).unwrap();
+    data
+}
+
+#[test]
+fn mint_authorities_read_from_legacy_and_token_2022_mints() {
+    let authority = Pubkey::new_unique();
+    let parsed = mint_authorities(&legacy_mint_data(Some(authority), None)).unwrap();
+    assert_eq!(parsed.mint_authority, Some(authority.to_string()));
+    assert_eq!(parsed.freeze_authority, None);
+
+    let renounced = mint_authorities(&token_2022_mint_data(Some(Pubkey::new_unique()))).unwrap();
+    assert_eq!(renounced, MintAuthorities::default());
+
+    assert_eq!(mint_authorities(&[0u8; 10]), None);
+}
+
+#[test]
+fn mint_authority_gate_requires_renounced_authorities() {
+    let cache: &'static MintAuthorityCache = Box::leak(Box::new(MintAuthorityCache::new()));
+    let gate = MintAuthorityGate::new(true, cache);
+    let ctx_for = |mint: &str| TradeContext {
+        mint: mint.into(),
+        ..healthy_ctx()
+    };
+    let active = Some(Pubkey::new_unique().to_string());
+
+    cache.insert(
+        "inflatable",
+        MintAuthorities {
+            mint_authority: active.clone(),
+            freeze_authority: None,
+        },
+    );
+    cache.insert(
+        "freezable",
+        MintAuthorities {
+            mint_authority: None,
+            freeze_authority: active,
+        },
+    );
+    cache.insert("renounced", MintAuthorities::default());
+
+    assert_eq!(
+        rejection(gate.check(&ctx_for("inflatable"))),
+        "mint_authority_active"
+    );
+    assert_eq!(
+        rejection(gate.check(&ctx_for("freezable"))),
+        "freeze_authority_active"
+    );
+    assert!(matches!(
+        gate.check(&ctx_for("renounced")),
+        GateDecision::Passed
+    ));
+    assert_eq!(rejection(gate.check(&ctx_for("uncached"))), "no_mint_data");
+
+    let off = MintAuthorityGate::new(false, cache);
+    assert!(matches!(
+        off.check(&ctx_for("inflatable")),
+        GateDecision::Passed
+    ));
+}
+
+#[test]
+fn curve_progress_tracks_real_token_reserves() {
+    assert_eq!(curve_progress_pct(INITIAL_REAL_TOKEN_RESERVES, false), 0.0);
+    assert_eq!(
+        curve_progress_pct(INITIAL_REAL_TOKEN_RESERVES / 4, false),
+        75.0
+    );
+    assert_eq!(curve_progress_pct(0, false), 100.0);
+    assert_eq!(curve_progress_pct(INITIAL_REAL_TOKEN_RESERVES, true), 100.0);
+}
+
+#[test]
+fn curve_progress_gate_accepts_pumpfun_tokens_within_band() {
+    let gate = BondingCurveProgressGate::new(20.0, 80.0);
+    let pumpfun = |pct: Option<f64>, migrated: Option<bool>| TradeContext {
+        is_pumpfun: true,
+        pumpfun_migrated: migrated,
+        bonding_curve_progress_pct: pct,
+        ..healthy_ctx()
+    };
+
+    assert!(matches!(
+        gate.check(&pumpfun(Some(50.0), Some(false))),
+        GateDecision::Passed
+    ));
+    assert_eq!(
+        rejection(gate.check(&pumpfun(Some(10.0), Some(false)))),
+        "curve_progress 10"
+    );
+    assert_eq!(
+        rejection_code(gate.check(&pumpfun(Some(90.0), None))),
+        RejectReason::CurveProgress
+    );
+    assert_eq!(
+        rejection(gate.check(&pumpfun(Some(50.0), Some(true)))),
+        "curve_progress 100"
+    );
+    assert_eq!(
+        rejection_code(gate.check(&pumpfun(None, None))),
+        RejectReason::NoCurveProgress
+    );
+
+    // Non-PumpFun tokens and the full band pass untouched.
+    assert!(matches!(gate.check(&healthy_ctx()), GateDecision::Passed));
+    assert!(matches!(
+        BondingCurveProgressGate::default().check(&pumpfun(None, None)),
+        GateDecision::Passed
+    ));
+}
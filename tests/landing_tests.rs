@@ -0,0 +1,118 @@
+//! Tests for the structured landing-mode outcome and blockhash-expiry
+//! retries.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use solana_sdk::hash::Hash;
+use solana_vntr_sniper::block_engine::tx::{
+    is_blockhash_expired_error, record_landing, send_with_blockhash_retry,
+};
+use solana_vntr_sniper::common::config::TransactionLandingMode;
+
+#[tokio::test]
+async fn outcome_records_mode_endpoint_signatures_and_timing() {
+    let outcome = record_landing(
+        TransactionLandingMode::Zeroslot,
+        "https://zeroslot.example".to_string(),
+        async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(vec!["sigA".to_string()])
+        },
+    )
+    .await
+    .expect("send succeeds");
+
+    assert!(matches!(outcome.mode, TransactionLandingMode::Zeroslot));
+    assert_eq!(outcome.endpoint, "https://zeroslot.example");
+    assert_eq!(outcome.signatures, vec!["sigA".to_string()]);
+    assert!(outcome.elapsed >= Duration::from_millis(20));
+}
+
+#[tokio::test]
+async fn send_failure_is_propagated() {
+    let result = record_landing(
+        TransactionLandingMode::Normal,
+        "http://rpc.example".to_string(),
+        async { Err(anyhow::anyhow!("blockhash not found")) },
+    )
+    .await;
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn only_blockhash_errors_are_retryable() {
+    assert!(is_blockhash_expired_error(
+        "Failed to send normal transaction: Transaction simulation failed: Blockhash not found"
+    ));
+    assert!(is_blockhash_expired_error("BlockhashNotFound"));
+    assert!(is_blockhash_expired_error("block height exceeded"));
+    assert!(!is_blockhash_expired_error(
+        "Transaction simulation failed: Attempt to debit an account but found no record of a prior credit"
+    ));
+    assert!(!is_blockhash_expired_error("insufficient funds for fee"));
+}
+
+#[tokio::test]
+async fn expired_blockhash_is_retried_with_a_fresh_one() {
+    let stale = Hash::new_from_array([1; 32]);
+    let fresh = Hash::new_from_array([2; 32]);
+    let sent = Mutex::new(Vec::new());
+
+    let result = send_with_blockhash_retry(
+        stale,
+        2,
+        || async { Ok(fresh) },
+        |blockhash| {
+            sent.lock().unwrap().push(blockhash);
+            async move {
+                if blockhash == stale {
+                    Err(anyhow::anyhow!("Blockhash not found"))
+                } else {
+                    Ok(vec!["sigB".to_string()])
+                }
+            }
+        },
+    )
+    .await;
+
+    assert_eq!(result.unwrap(), vec!["sigB".to_string()]);
+    assert_eq!(*sent.lock().unwrap(), vec![stale, fresh]);
+}
+
+#[tokio::test]
+async fn non_retryable_error_is_not_retried() {
+    let attempts = Mutex::new(0);
+    let result = send_with_blockhash_retry(
+        Hash::default(),
+        2,
+        || async { Err(anyhow::anyhow!("no fresh blockhash needed")) },
+        |_| {
+            *attempts.lock().unwrap() += 1;
+            async { Err(anyhow::anyhow!("insufficient funds for fee")) }
+        },
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(*attempts.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn blockhash_retries_are_bounded() {
+    let attempts = Mutex::new(0);
+    let result = send_with_blockhash_retry(
+        Hash::default(),
+        2,
+        || async { Ok(Hash::default()) },
+        |_| {
+            *attempts.lock().unwrap() += 1;
+            async { Err(anyhow::anyhow!("block height exceeded")) }
+        },
+    )
+    .await;
+
+    assert!(result.is_err());
+    assert_eq!(*attempts.lock().unwrap(), 3);
+}
@@ -0,0 +1,42 @@
+//! Tests for parked limit entries.
+
+use solana_vntr_sniper::universal::limit_entry::{LimitEntryBook, THESIS_INVALIDATED};
+
+#[test]
+fn source_sell_cancels_parked_entry_before_target_is_reached() {
+    let book = LimitEntryBook::new(true);
+    let id = book.park("leader", "mintX", 0.5, 100.0, 1_000);
+    let other = book.park("otherLeader", "mintX", 0.5, 100.0, 1_000);
+
+    // Price still above target: nothing fills.
+    assert!(book.on_price("mintX", 0.8).is_empty());
+
+    let cancelled = book.on_source_sell("leader", "mintX");
+    assert_eq!(cancelled.len(), 1);
+    assert_eq!(cancelled[0].entry.id, id);
+    assert_eq!(cancelled[0].reason, THESIS_INVALIDATED);
+
+    // The cancelled entry can no longer fill; the other leader's still can.
+    let filled = book.on_price("mintX", 0.4);
+    assert_eq!(filled.len(), 1);
+    assert_eq!(filled[0].id, other);
+    assert!(book.pending().is_empty());
+}
+
+#[test]
+fn source_sell_of_other_mint_keeps_entry() {
+    let book = LimitEntryBook::new(true);
+    book.park("leader", "mintX", 0.5, 100.0, 1_000);
+
+    assert!(book.on_source_sell("leader", "mintY").is_empty());
+    assert_eq!(book.pending().len(), 1);
+}
+
+#[test]
+fn cancellation_can_be_disabled() {
+    let book = LimitEntryBook::new(false);
+    book.park("leader", "mintX", 0.5, 100.0, 1_000);
+
+    assert!(book.on_source_sell("leader", "mintX").is_empty());
+    assert_eq!(book.pending().len(), 1);
+}
@@ -0,0 +1,356 @@
+//! Tests for the per-mint metrics store.
+
+use std::sync::{Arc, Mutex};
+
+use solana_vntr_sniper::universal::gates::stats::Clock;
+use solana_vntr_sniper::universal::metrics::birdeye_rest::parse_token_overview;
+use solana_vntr_sniper::universal::metrics::birdeye_ws::{
+    parse_frame, subscription_messages, BirdeyeWsClient, WsUpdate,
+};
+use solana_vntr_sniper::universal::metrics::clock::{
+    clear_event_clock, event_time, install_event_clock, now_secs, EventClock, MetricsClock,
+};
+use solana_vntr_sniper::universal::metrics::{
+    MetricsCalc, MetricsStore, SubscriptionSink, TokenMetrics, VolumeWindows, WindowPair,
+};
+
+const NOW: i64 = 1_700_000_000;
+
+fn feed(store: &MetricsStore, mint: &str) {
+    // (seconds ago, volume_usd)
+    for (ago, vol) in [(2_000, 800.0), (1_000, 400.0), (120, 200.0), (30, 100.0)] {
+        store.upsert_tick(mint, NOW - ago, 1.0, vol);
+    }
+}
+
+#[test]
+fn default_windows_are_5m_and_15m() {
+    let store = MetricsStore::new(VolumeWindows::default());
+    feed(&store, "mintX");
+
+    let m = store.get("mintX").expect("metrics recorded");
+    assert_eq!(m.vol_5m_usd, 300.0);
+    assert_eq!(m.vol_15m_usd, 300.0);
+    assert_eq!(m.last_update, NOW - 30);
+}
+
+#[test]
+fn configured_windows_drive_computed_volumes() {
+    let store = MetricsStore::new(VolumeWindows::new(WindowPair {
+        primary_secs: 60,
+        secondary_secs: 1_800,
+    }));
+    feed(&store, "mintX");
+
+    let m = store.get("mintX").expect("metrics recorded");
+    // 1m window sees only the tick 30s before the last one; 30m sees all but the oldest.
+    assert_eq!(m.vol_5m_usd, 100.0);
+    assert_eq!(m.vol_15m_usd, 700.0);
+}
+
+#[test]
+fn per_mint_override_beats_global_windows() {
+    let mut windows = VolumeWindows::default();
+    windows.set_for_mint(
+        "fastMint",
+        WindowPair {
+            primary_secs: 60,
+            secondary_secs: 300,
+        },
+    );
+    let store = MetricsStore::new(windows);
+    feed(&store, "fastMint");
+    feed(&store, "slowMint");
+
+    assert_eq!(store.get("fastMint").unwrap().vol_5m_usd, 100.0);
+    assert_eq!(store.get("slowMint").unwrap().vol_5m_usd, 300.0);
+}
+
+#[test]
+fn get_fresh_rejects_stale_entries_and_prune_drops_them() {
+    let store = MetricsStore::new(VolumeWindows::default());
+    store.upsert(TokenMetrics {
+        mint: "oldMint".into(),
+        price_usd: 1.0,
+        last_update: NOW - 600,
+        ..TokenMetrics::default()
+    });
+    feed(&store, "liveMint");
+
+    assert!(store.get_fresh("oldMint", 120, NOW).is_none());
+    assert!(store.get("oldMint").is_some());
+    assert!(store.get_fresh("liveMint", 120, NOW).is_some());
+
+    assert_eq!(store.prune_stale(120, NOW), 1);
+    assert!(store.get("oldMint").is_none());
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn on_missing_hook_fires_for_unknown_and_stale_mints() {
+    let store = Arc::new(MetricsStore::new(VolumeWindows::default()));
+    let missed = Arc::new(Mutex::new(Vec::new()));
+    {
+        let missed = missed.clone();
+        let refill = Arc::downgrade(&store);
+        store.on_missing(move |mint| {
+            missed.lock().unwrap().push(mint.to_string());
+            // The hook runs outside the store lock, so it may refill it directly.
+            if let Some(store) = refill.upgrade() {
+                store.upsert(TokenMetrics {
+                    mint: mint.into(),
+                    last_update: NOW,
+                    ..TokenMetrics::default()
+                });
+            }
+        });
+    }
+    store.upsert(TokenMetrics {
+        mint: "staleMint".into(),
+        last_update: NOW - 600,
+        ..TokenMetrics::default()
+    });
+
+    assert!(store.get("newMint").is_none());
+    assert!(store.get_fresh("staleMint", 60, NOW).is_none());
+    assert_eq!(*missed.lock().unwrap(), vec!["newMint", "staleMint"]);
+
+    // Refreshed by the hook; hits do not fire it again.
+    assert!(store.get("newMint").is_some());
+    assert!(store.get_fresh("staleMint", 60, NOW).is_some());
+    assert_eq!(missed.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn birdeye_overview_maps_into_token_metrics() {
+    let body = r#"{"success":true,"data":{"address":"mintB","price":0.0025,
+        "liquidity":52000.5,"mc":250000.0,"v30mUSD":9000.0,"v1hUSD":15000.0}}"#;
+    let m = parse_token_overview("mintB", body, NOW).unwrap();
+    assert_eq!(m.mint, "mintB");
+    assert_eq!(m.price_usd, 0.0025);
+    assert_eq!(m.liq_usd, Some(52000.5));
+    assert_eq!(m.mcap_usd, Some(250000.0));
+    // Prorated from the 30m bucket.
+    assert!((m.vol_5m_usd - 1500.0).abs() < 1e-9);
+    assert!((m.vol_15m_usd - 4500.0).abs() < 1e-9);
+    assert_eq!(m.amihud_5m, None);
+    assert_eq!(m.last_update, NOW);
+
+    // Falls back to the 1h bucket and accepts `marketCap`.
+    let body = r#"{"success":true,"data":{"price":1.0,"marketCap":10.0,"v1hUSD":600.0}}"#;
+    let m = parse_token_overview("mintC", body, NOW).unwrap();
+    assert_eq!(m.mcap_usd, Some(10.0));
+    assert_eq!(m.liq_usd, None);
+    assert!((m.vol_5m_usd - 50.0).abs() < 1e-9);
+}
+
+#[test]
+fn birdeye_overview_errors_are_reported() {
+    assert!(parse_token_overview("m", "not json", NOW).is_err());
+    let failed = r#"{"success":false,"message":"Unauthorized"}"#;
+    let err = parse_token_overview("m", failed, NOW).unwrap_err();
+    assert!(err.to_string().contains("Unauthorized"));
+    let no_price = r#"{"success":true,"data":{"liquidity":1.0}}"#;
+    assert!(parse_token_overview("m", no_price, NOW).is_err());
+}
+
+#[test]
+fn birdeye_ws_frames_parse_into_updates() {
+    let txs = r#"{"type":"TXS_DATA","data":{"blockUnixTime":1700000100,"tokenAddress":"mintW",
+        "volumeUSD":250.5,"from":{"address":"So11111111111111111111111111111111111111112",
+        "price":150.0},"to":{"address":"mintW","price":0.002}}}"#;
+    assert_eq!(
+        parse_frame(txs, NOW).unwrap(),
+        Some(WsUpdate::Trade {
+            mint: "mintW".to_string(),
+            ts: NOW + 100,
+            price_usd: 0.002,
+            volume_usd: 250.5,
+        })
+    );
+
+    let price = r#"{"type":"PRICE_DATA","data":{"o":1.0,"h":1.2,"l":0.9,"c":1.1,
+        "eventType":"ohlcv","type":"1m","address":"mintW","v":10.0}}"#;
+    assert_eq!(
+        parse_frame(price, NOW).unwrap(),
+        Some(WsUpdate::Price {
+            mint: "mintW".to_string(),
+            ts: NOW,
+            price_usd: 1.1,
+        })
+    );
+}
+
+#[test]
+fn birdeye_ws_skips_heartbeats_and_rejects_partial_frames() {
+    for frame in [
+        "",
+        "ping",
+        r#"{"type":"WELCOME"}"#,
+        r#"{"type":"PRICE_PONG"}"#,
+    ] {
+        assert_eq!(parse_frame(frame, NOW).unwrap(), None, "{:?}", frame);
+    }
+    assert!(parse_frame(r#"{"type":"TXS_DATA","data":{"tokenAdd"#, NOW).is_err());
+    let no_price = r#"{"type":"TXS_DATA","data":{"tokenAddress":"mintW","volumeUSD":1.0}}"#;
+    assert!(parse_frame(no_price, NOW).is_err());
+    let error = r#"{"type":"ERROR","data":{"message":"invalid api key"}}"#;
+    assert!(parse_frame(error, NOW)
+        .unwrap_err()
+        .to_string()
+        .contains("invalid api key"));
+}
+
+#[test]
+fn birdeye_ws_subscribes_the_full_mint_set() {
+    let msgs = subscription_messages(&["mintA".to_string(), "mintB".to_string()]);
+    assert_eq!(msgs.len(), 2);
+    assert!(msgs[0].contains("SUBSCRIBE_PRICE"));
+    assert!(msgs[0].contains(
+        "(address = mintA AND chartType = 1m AND currency = usd) OR (address = mintB AND chartType = 1m AND currency = usd)"
+    ));
+    assert!(msgs[1].contains("SUBSCRIBE_TXS"));
+    assert!(msgs[1].contains("address = mintA OR address = mintB"));
+
+    let msgs = subscription_messages(&[]);
+    assert!(msgs[0].contains("UNSUBSCRIBE_PRICE"));
+    assert!(msgs[1].contains("UNSUBSCRIBE_TXS"));
+}
+
+#[test]
+fn birdeye_ws_client_taps_lines_and_feeds_the_store() {
+    let store = Arc::new(MetricsStore::new(VolumeWindows::default()));
+    let client = BirdeyeWsClient::new("ws://localhost".to_string(), Arc::clone(&store));
+    let tapped = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&tapped);
+    client.set_line_tap(move |line| sink.lock().unwrap().push(line.to_string()));
+
+    client.subscribe("mintB");
+    client.subscribe("mintA");
+    client.subscribe("mintA");
+    assert_eq!(client.tracked_mints(), vec!["mintA", "mintB"]);
+    client.unsubscribe("mintB");
+    assert_eq!(client.tracked_mints(), vec!["mintA"]);
+
+    let trade = |ts: i64, vol: f64| {
+        format!(
+            r#"{{"type":"TXS_DATA","data":{{"blockUnixTime":{},"tokenAddress":"mintA","tokenPrice":2.0,"volumeUSD":{}}}}}"#,
+            ts, vol
+        )
+    };
+    assert!(client.handle_line(r#"{"type":"WELCOME"}"#).is_none());
+    client.handle_line(&trade(NOW - 60, 100.0));
+    client.handle_line(&trade(NOW - 30, 50.0));
+    let m = store.get("mintA").expect("trades recorded");
+    assert_eq!(m.price_usd, 2.0);
+    assert_eq!(m.vol_5m_usd, 150.0);
+    assert_eq!(m.last_update, NOW - 30);
+
+    // A newer candle moves the price without adding volume; an older one is ignored.
+    let candle = |ts: i64, close: f64| {
+        format!(
+            r#"{{"type":"PRICE_DATA","data":{{"address":"mintA","c":{},"unixTime":{}}}}}"#,
+            close, ts
+        )
+    };
+    client.handle_line(&candle(NOW - 120, 9.0));
+    assert_eq!(store.get("mintA").unwrap().price_usd, 2.0);
+    client.handle_line(&candle(NOW, 2.5));
+    let m = store.get("mintA").unwrap();
+    assert_eq!(m.price_usd, 2.5);
+    assert_eq!(m.vol_5m_usd, 150.0);
+    assert_eq!(m.last_update, NOW);
+
+    assert_eq!(tapped.lock().unwrap().len(), 5);
+}
+
+#[test]
+fn calc_computes_amihud_and_range_efficiency() {
+    let mut calc = MetricsCalc::new(300);
+    assert_eq!(calc.amihud(), None);
+    assert_eq!(calc.range_efficiency(), None);
+
+    // 1.0 -> 1.1 (+10% on $100) -> 1.0 (-9.09% on $200) -> 1.2 (+20% on $400)
+    calc.push(NOW, 1.0, 50.0);
+    calc.push(NOW + 10, 1.1, 100.0);
+    calc.push(NOW + 20, 1.0, 200.0);
+    calc.push(NOW + 30, 1.2, 400.0);
+    let expected = (0.1 / 100.0 + (1.0 / 1.1 - 1.0f64).abs() / 200.0 + 0.2 / 400.0) / 3.0;
+    assert!((calc.amihud().unwrap() - expected).abs() < 1e-12);
+    // Net 0.2 over a 0.1 + 0.1 + 0.2 path.
+    assert!((calc.range_efficiency().unwrap() - 0.5).abs() < 1e-9);
+
+    // Only the last two ticks remain in the window, with a flat price.
+    calc.push(NOW + 320, 1.2, 10.0);
+    assert_eq!(calc.len(), 2);
+    assert_eq!(calc.range_efficiency(), None);
+    assert_eq!(calc.amihud(), Some(0.0));
+}
+
+#[test]
+fn first_seen_is_the_earliest_observation() {
+    let store = MetricsStore::new(VolumeWindows::default());
+    assert_eq!(store.first_seen("fresh"), None);
+
+    store.upsert_tick("fresh", NOW - 30, 1.0, 10.0);
+    store.upsert_tick("fresh", NOW, 1.0, 10.0);
+    // A late, out-of-order tick moves first_seen back.
+    store.upsert_tick("fresh", NOW - 90, 1.0, 10.0);
+    assert_eq!(store.first_seen("fresh"), Some(NOW - 90));
+
+    store.upsert(TokenMetrics {
+        mint: "snap".into(),
+        last_update: NOW - 10,
+        ..TokenMetrics::default()
+    });
+    store.upsert(TokenMetrics {
+        mint: "snap".into(),
+        last_update: NOW,
+        ..TokenMetrics::default()
+    });
+    assert_eq!(store.first_seen("snap"), Some(NOW - 10));
+}
+
+#[test]
+fn upsert_tick_fills_derived_metrics() {
+    let store = MetricsStore::new(VolumeWindows::default());
+    store.upsert_tick("trend", NOW - 60, 1.0, 100.0);
+    assert_eq!(store.get("trend").unwrap().amihud_5m, None);
+
+    store.upsert_tick("trend", NOW - 30, 1.05, 100.0);
+    store.upsert_tick("trend", NOW, 1.1, 100.0);
+    let m = store.get("trend").unwrap();
+    assert!(m.amihud_5m.unwrap() > 0.0);
+    assert!((m.range_eff_5m.unwrap() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn event_time_reads_recorded_timestamps() {
+    assert_eq!(event_time(r#"{"ts":1700000000}"#), Some(1_700_000_000));
+    assert_eq!(
+        event_time(r#"{"type":"TXS_DATA","data":{"blockUnixTime":1700000123}}"#),
+        Some(1_700_000_123)
+    );
+    // Milliseconds are converted to seconds.
+    assert_eq!(
+        event_time(r#"{"timestamp":1700000000456}"#),
+        Some(1_700_000_000)
+    );
+    assert_eq!(event_time(r#"{"type":"PRICE_DATA"}"#), None);
+    assert_eq!(event_time("not json"), None);
+}
+
+#[test]
+fn event_clock_drives_metrics_time() {
+    let clock = Arc::new(EventClock::new(NOW));
+    assert_eq!(clock.observe(NOW + 30), NOW + 30);
+    // Out-of-order frames never rewind the clock.
+    assert_eq!(clock.observe(NOW + 10), NOW + 30);
+    assert_eq!(clock.advance(5), NOW + 35);
+
+    install_event_clock(clock.clone());
+    assert_eq!(now_secs(), NOW + 35);
+    assert_eq!(MetricsClock.now_secs(), NOW + 35);
+    clear_event_clock();
+    assert!(now_secs() > NOW + 35);
+}
@@ -0,0 +1,147 @@
+//! Tests for MEV-protected submission: caps and routing.
+
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use solana_vntr_sniper::universal::mev_submit::{
+    check_mev_limits, clamp_to_cap, compute_unit_price, submit_with_protection, tip_lamports,
+    LandingRoute, MevConfig, RawSubmit, Submitted, JITO_TIP_ACCOUNTS,
+};
+
+fn tip_account() -> Pubkey {
+    Pubkey::from_str(JITO_TIP_ACCOUNTS[0]).unwrap()
+}
+
+fn tx(cu_price: u64, tip: u64) -> Transaction {
+    let payer = Pubkey::new_unique();
+    let ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(200_000),
+        ComputeBudgetInstruction::set_compute_unit_price(cu_price),
+        // Not a tip: goes to an ordinary account.
+        system_instruction::transfer(&payer, &Pubkey::new_unique(), 1_000_000),
+        system_instruction::transfer(&payer, &tip_account(), tip),
+    ];
+    Transaction::new_with_payer(&ixs, Some(&payer))
+}
+
+#[test]
+fn reads_cu_price_and_tip_from_instructions() {
+    let t = tx(5_000, 10_000);
+    assert_eq!(compute_unit_price(&t), Some(5_000));
+    assert_eq!(tip_lamports(&t, &MevConfig::default().tip_accounts), 10_000);
+    assert_eq!(tip_lamports(&t, &[]), 0);
+}
+
+#[test]
+fn caps_reject_overpriced_transactions() {
+    let cfg = MevConfig {
+        cu_price_max_lamports: 10_000,
+        tip_cap_lamports: 50_000,
+        ..MevConfig::default()
+    };
+    assert!(check_mev_limits(&cfg, &tx(10_000, 50_000)).is_ok());
+    let err = check_mev_limits(&cfg, &tx(10_001, 0)).unwrap_err();
+    assert!(err.to_string().contains("compute-unit price"));
+    let err = check_mev_limits(&cfg, &tx(0, 50_001)).unwrap_err();
+    assert!(err.to_string().contains("tip"));
+    // Zero caps are off.
+    assert!(check_mev_limits(&MevConfig::default(), &tx(u64::MAX, u64::MAX)).is_ok());
+}
+
+#[test]
+fn send_path_values_are_clamped_to_caps() {
+    let cfg = MevConfig {
+        cu_price_max_lamports: 50_000,
+        tip_cap_lamports: 1_000_000,
+        ..MevConfig::default()
+    };
+    assert_eq!(cfg.clamp_cu_price(2_000_000), 50_000);
+    assert_eq!(cfg.clamp_cu_price(20_000), 20_000);
+    assert_eq!(cfg.clamp_tip_lamports(10_000_000), 1_000_000);
+    assert_eq!(cfg.clamp_tip_lamports(500), 500);
+    // 0 = uncapped.
+    assert_eq!(clamp_to_cap(u64::MAX, 0), u64::MAX);
+}
+
+#[test]
+fn route_parses_from_env_names() {
+    assert_eq!("jito".parse::<LandingRoute>(), Ok(LandingRoute::JitoBundle));
+    assert_eq!("Private".parse::<LandingRoute>(), Ok(LandingRoute::Private));
+    assert!("carrier-pigeon".parse::<LandingRoute>().is_err());
+}
+
+/// Records which path was used.
+#[derive(Default)]
+struct StubSubmitter {
+    calls: Mutex<Vec<&'static str>>,
+}
+
+impl RawSubmit for StubSubmitter {
+    type Sig = String;
+
+    fn submit_via_rpc<'a>(&'a self, _tx: &'a Transaction) -> BoxFuture<'a, Result<String>> {
+        self.calls.lock().unwrap().push("rpc");
+        Box::pin(async { Ok("rpcSig".to_string()) })
+    }
+
+    fn submit_via_private<'a>(&'a self, _tx: &'a Transaction) -> BoxFuture<'a, Result<String>> {
+        self.calls.lock().unwrap().push("private");
+        Box::pin(async { Ok("privateSig".to_string()) })
+    }
+
+    fn submit_via_jito_bundle<'a>(
+        &'a self,
+        txs: &'a [Transaction],
+    ) -> BoxFuture<'a, Result<String>> {
+        self.calls.lock().unwrap().push("jito");
+        let n = txs.len();
+        Box::pin(async move { Ok(format!("bundle-{}", n)) })
+    }
+}
+
+#[tokio::test]
+async fn submits_on_the_configured_route() {
+    let submitter = StubSubmitter::default();
+    let t = tx(1_000, 1_000);
+
+    let mut cfg = MevConfig::default();
+    assert_eq!(
+        submit_with_protection(&submitter, &cfg, &t).await.unwrap(),
+        Submitted::Signature("rpcSig".to_string())
+    );
+    cfg.route = LandingRoute::Private;
+    assert_eq!(
+        submit_with_protection(&submitter, &cfg, &t).await.unwrap(),
+        Submitted::Signature("privateSig".to_string())
+    );
+    cfg.route = LandingRoute::JitoBundle;
+    assert_eq!(
+        submit_with_protection(&submitter, &cfg, &t).await.unwrap(),
+        Submitted::Bundle {
+            bundle_id: "bundle-1".to_string()
+        }
+    );
+    assert_eq!(
+        *submitter.calls.lock().unwrap(),
+        vec!["rpc", "private", "jito"]
+    );
+}
+
+#[tokio::test]
+async fn nothing_is_sent_when_a_cap_is_exceeded() {
+    let submitter = StubSubmitter::default();
+    let cfg = MevConfig {
+        tip_cap_lamports: 100,
+        ..MevConfig::default()
+    };
+    assert!(submit_with_protection(&submitter, &cfg, &tx(0, 101))
+        .await
+        .is_err());
+    assert!(submitter.calls.lock().unwrap().is_empty());
+}
@@ -0,0 +1,181 @@
+//! Tests for MEV protection helpers.
+
+use solana_vntr_sniper::universal::mev::{
+    AdaptiveTip, AdaptiveTipConfig, BlockTx, FillObservation, MevProtection, QuoteSnapshot,
+    SandwichConfig, SandwichDetector, SandwichVerdict, TradeSide,
+};
+
+fn tx(signer: &str, side: TradeSide, tx_index: usize) -> BlockTx {
+    BlockTx {
+        signer: signer.into(),
+        mint: "mintX".into(),
+        side,
+        tx_index,
+    }
+}
+
+fn our_buy(price: f64) -> FillObservation {
+    FillObservation {
+        mint: "mintX".into(),
+        side: TradeSide::Buy,
+        price,
+        tx_index: 5,
+    }
+}
+
+#[test]
+fn bracketed_adverse_fill_is_flagged_as_sandwich() {
+    let detector = SandwichDetector::new(SandwichConfig::default());
+    let block = vec![
+        tx("attacker", TradeSide::Buy, 4),
+        tx("someone", TradeSide::Buy, 2),
+        tx("attacker", TradeSide::Sell, 6),
+    ];
+
+    // Filled 5% above the quote.
+    let verdict = detector.analyze(&our_buy(1.05), &QuoteSnapshot { price: 1.0 }, &block);
+
+    match verdict {
+        SandwichVerdict::Sandwiched {
+            attacker,
+            front_index,
+            back_index,
+            adverse_bps,
+        } => {
+            assert_eq!(attacker, "attacker");
+            assert_eq!(front_index, 4);
+            assert_eq!(back_index, 6);
+            assert!((adverse_bps - 500.0).abs() < 1e-6);
+        }
+        other => panic!("expected sandwich, got {:?}", other),
+    }
+    assert!(detector.prefer_private_landing());
+}
+
+#[test]
+fn clean_fill_is_not_flagged() {
+    let detector = SandwichDetector::new(SandwichConfig::default());
+    let block = vec![
+        tx("attacker", TradeSide::Buy, 4),
+        tx("attacker", TradeSide::Sell, 6),
+    ];
+
+    // Filled within tolerance of the quote.
+    let verdict = detector.analyze(&our_buy(1.001), &QuoteSnapshot { price: 1.0 }, &block);
+
+    assert_eq!(verdict, SandwichVerdict::Clean);
+    assert!(!detector.prefer_private_landing());
+}
+
+#[test]
+fn adverse_fill_without_bracket_is_not_a_sandwich() {
+    let detector = SandwichDetector::new(SandwichConfig::default());
+    let block = vec![
+        tx("buyer", TradeSide::Buy, 4),
+        tx("seller", TradeSide::Sell, 6),
+    ];
+
+    let verdict = detector.analyze(&our_buy(1.05), &QuoteSnapshot { price: 1.0 }, &block);
+
+    assert!(matches!(verdict, SandwichVerdict::AdverseFill { .. }));
+    assert!(!verdict.is_sandwiched());
+    assert_eq!(detector.sandwiches_seen(), 0);
+}
+
+fn adaptive_cfg() -> AdaptiveTipConfig {
+    AdaptiveTipConfig {
+        enabled: true,
+        window: 10,
+        min_samples: 5,
+        target_land_rate: 0.8,
+        relax_land_rate: 0.95,
+        step_pct: 50.0,
+        min_tip: 0.001,
+        max_tip: 0.004,
+    }
+}
+
+#[test]
+fn low_land_rate_raises_tip_up_to_mev_cap() {
+    let tip = AdaptiveTip::new(adaptive_cfg(), 0.002);
+
+    // Too few samples: no change yet.
+    for _ in 0..4 {
+        assert_eq!(tip.record(false), 0.002);
+    }
+    assert_eq!(tip.land_rate(), Some(0.0));
+    // 5th miss: 0.002 -> 0.003.
+    assert!((tip.record(false) - 0.003).abs() < 1e-12);
+    assert_eq!(tip.land_rate(), None);
+
+    // Another window at 40% landing: raised again, clamped at the cap.
+    for landed in [true, false, true, false] {
+        tip.record(landed);
+    }
+    assert_eq!(tip.record(false), 0.004);
+    for _ in 0..10 {
+        tip.record(false);
+    }
+    assert_eq!(tip.current_tip(), 0.004);
+}
+
+#[test]
+fn high_land_rate_lowers_tip_down_to_floor() {
+    let tip = AdaptiveTip::new(adaptive_cfg(), 0.004);
+
+    for _ in 0..5 {
+        tip.record(true);
+    }
+    assert!((tip.current_tip() - 0.002).abs() < 1e-12);
+
+    for _ in 0..20 {
+        tip.record(true);
+    }
+    assert_eq!(tip.current_tip(), 0.001);
+}
+
+#[test]
+fn land_rate_in_band_keeps_tip() {
+    let tip = AdaptiveTip::new(adaptive_cfg(), 0.002);
+    // 9/10 landed: between target (0.8) and relax (0.95).
+    for landed in [true, true, true, true, false, true, true, true, true, true] {
+        tip.record(landed);
+    }
+    assert_eq!(tip.current_tip(), 0.002);
+    // Initial tip outside the bounds is clamped.
+    assert_eq!(AdaptiveTip::new(adaptive_cfg(), 1.0).current_tip(), 0.004);
+}
+
+fn protection() -> MevProtection {
+    // 1% of notional, at most 0.005 SOL.
+    MevProtection {
+        tip_bps_cap: 100,
+        tip_lamports_cap: 5_000_000,
+    }
+}
+
+#[test]
+fn small_trade_tip_is_capped_by_notional_bps() {
+    let mev = protection();
+    // 0.1 SOL trade: 1% = 1_000_000 lamports, below the absolute cap.
+    assert_eq!(mev.bound_tip_lamports(3_000_000, 100_000_000), 1_000_000);
+    assert_eq!(mev.bound_tip_lamports(400_000, 100_000_000), 400_000);
+}
+
+#[test]
+fn large_trade_tip_is_capped_by_absolute_lamports() {
+    let mev = protection();
+    // 10 SOL trade: 1% = 0.1 SOL, so the absolute cap wins.
+    assert_eq!(
+        mev.bound_tip_lamports(50_000_000, 10_000_000_000),
+        5_000_000
+    );
+    assert_eq!(mev.bound_tip_lamports(2_000_000, 10_000_000_000), 2_000_000);
+    // No overflow at the top of the range.
+    assert_eq!(mev.bound_tip_lamports(u64::MAX, u64::MAX), 5_000_000);
+}
+
+#[test]
+fn zero_notional_allows_no_tip() {
+    assert_eq!(protection().bound_tip_lamports(1_000, 0), 0);
+}
@@ -0,0 +1,78 @@
+//! Tests for mint decimals handling via the `MintInfo` cache.
+
+use std::cell::Cell;
+
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::common::cache::{MintInfo, MintInfoCache};
+use spl_token_2022::solana_program::program_pack::Pack;
+use spl_token_2022::state::Mint;
+
+fn mint_data(decimals: u8) -> Vec<u8> {
+    let mut data = vec![0u8; Mint::LEN];
+    Mint {
+        decimals,
+        is_initialized: true,
+        ..Mint::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+#[test]
+fn zero_decimal_mint_uses_whole_units() {
+    let info = MintInfo::from_account(Pubkey::new_unique(), &mint_data(0)).unwrap();
+    assert_eq!(info.decimals, 0);
+
+    assert_eq!(info.ui_amount(1_500), 1_500.0);
+    // 3 SOL for 1_500 whole tokens.
+    assert_eq!(info.ui_price(3.0, 1_500), Some(0.002));
+    // A 1000-token threshold is 1000 raw units; fractional UI rounds down.
+    assert_eq!(info.raw_amount(1_000.0), 1_000);
+    assert_eq!(info.raw_amount(0.9), 0);
+    assert!(info.ui_amount(999) < 1_000.0);
+}
+
+#[test]
+fn twelve_decimal_mint_scales_amounts_and_prices() {
+    let info = MintInfo::from_account(Pubkey::new_unique(), &mint_data(12)).unwrap();
+    assert_eq!(info.decimals, 12);
+
+    let raw = 1_500_000_000_000_000; // 1500 tokens
+    assert_eq!(info.ui_amount(raw), 1_500.0);
+    assert_eq!(info.ui_price(3.0, raw), Some(0.002));
+    assert_eq!(info.raw_amount(1_000.0), 1_000_000_000_000_000);
+    // Under a 9-decimal assumption this balance would read as 1.5M tokens.
+    assert!(info.ui_amount(raw) < 1_000_000.0);
+    assert_eq!(info.ui_price(3.0, 0), None);
+}
+
+#[test]
+fn cache_fetches_each_mint_once() {
+    let cache = MintInfoCache::new();
+    let mint = Pubkey::new_unique();
+    let program = Pubkey::new_unique();
+    let fetches = Cell::new(0);
+    let fetch = || {
+        fetches.set(fetches.get() + 1);
+        Ok(MintInfo::new(6, program))
+    };
+
+    assert_eq!(cache.get_or_insert_with(&mint, fetch).unwrap().decimals, 6);
+    assert_eq!(
+        cache.get_or_insert_with(&mint, fetch).unwrap().program,
+        program
+    );
+    assert_eq!(fetches.get(), 1);
+    assert_eq!(cache.size(), 1);
+}
+
+#[test]
+fn failed_fetch_is_not_cached() {
+    let cache = MintInfoCache::new();
+    let mint = Pubkey::new_unique();
+    assert!(cache
+        .get_or_insert_with(&mint, || Err(anyhow::anyhow!("rpc down")))
+        .is_err());
+    assert!(cache.get(&mint).is_none());
+    assert!(MintInfo::from_account(Pubkey::new_unique(), &[0u8; 10]).is_none());
+}
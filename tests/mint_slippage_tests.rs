@@ -0,0 +1,109 @@
+//! Tests for learned per-mint slippage.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use solana_vntr_sniper::common::config::SwapConfig;
+use solana_vntr_sniper::processor::sniper_bot::attempt_swap_config;
+use solana_vntr_sniper::processor::swap::{SwapDirection, SwapInType};
+use solana_vntr_sniper::universal::mint_slippage::{MintSlippageBook, MintSlippageConfig};
+
+fn temp_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "mint_slippage_{}_{}.json",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn enabled_cfg(path: PathBuf) -> MintSlippageConfig {
+    MintSlippageConfig {
+        enabled: true,
+        failure_threshold: 2,
+        step_bps: 200,
+        max_bps: 1_000,
+        path: Some(path),
+    }
+}
+
+#[test]
+fn repeated_failures_raise_slippage_for_next_send() {
+    let book = MintSlippageBook::new(enabled_cfg(temp_path("raise")));
+
+    // Below the threshold nothing changes.
+    assert_eq!(book.record_failure("mintX", 500), None);
+    assert_eq!(book.effective_slippage_bps("mintX", 500), 500);
+
+    // Second consecutive failure bumps the learned floor.
+    assert_eq!(book.record_failure("mintX", 500), Some(700));
+    assert_eq!(book.effective_slippage_bps("mintX", 500), 700);
+
+    // Other mints are unaffected.
+    assert_eq!(book.effective_slippage_bps("mintY", 500), 500);
+}
+
+#[test]
+fn learned_slippage_is_bounded_by_max() {
+    let book = MintSlippageBook::new(enabled_cfg(temp_path("bounded")));
+
+    for _ in 0..20 {
+        book.record_failure("mintX", 900);
+    }
+
+    assert_eq!(book.learned_slippage_bps("mintX"), Some(1_000));
+    assert_eq!(book.effective_slippage_bps("mintX", 500), 1_000);
+}
+
+#[test]
+fn learned_slippage_survives_restart() {
+    let path = temp_path("persist");
+
+    {
+        let book = MintSlippageBook::new(enabled_cfg(path.clone()));
+        book.record_failure("mintX", 500);
+        book.record_failure("mintX", 500);
+    }
+
+    let reloaded = MintSlippageBook::new(enabled_cfg(path.clone()));
+    assert_eq!(reloaded.learned_slippage_bps("mintX"), Some(700));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn disabled_book_never_changes_slippage() {
+    let mut cfg = enabled_cfg(temp_path("disabled"));
+    cfg.enabled = false;
+    let book = MintSlippageBook::new(cfg);
+
+    assert_eq!(book.record_failure("mintX", 500), None);
+    assert_eq!(book.record_failure("mintX", 500), None);
+    assert_eq!(book.effective_slippage_bps("mintX", 500), 500);
+}
+
+#[test]
+fn retry_attempt_after_failures_uses_raised_floor() {
+    let book = MintSlippageBook::new(enabled_cfg(temp_path("attempt")));
+    let configured = Arc::new(SwapConfig {
+        swap_direction: SwapDirection::Buy,
+        in_type: SwapInType::Qty,
+        amount_in: 0.1,
+        slippage: 500,
+    });
+
+    // First attempt goes out at the configured slippage, unchanged.
+    let first = attempt_swap_config(&book, "mintX", &configured);
+    assert!(Arc::ptr_eq(&first, &configured));
+
+    // Failures recorded at that attempt's slippage raise the floor...
+    book.record_failure("mintX", first.slippage);
+    book.record_failure("mintX", first.slippage);
+
+    // ...and the next attempt is sent with it.
+    let second = attempt_swap_config(&book, "mintX", &configured);
+    assert_eq!(second.slippage, 700);
+    assert_eq!(second.amount_in, configured.amount_in);
+    assert_eq!(configured.slippage, 500);
+}
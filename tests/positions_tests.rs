@@ -3,7 +3,20 @@
 //! These tests exercise the core invariant we care about in Phase 1A:
 //! - No duplicate open positions per (wallet, mint) until the position is closed.
 
-use solana_vntr_sniper::universal::positions::{PositionError, PositionsRegistry};
+use std::sync::{Arc, Mutex};
+
+use solana_vntr_sniper::universal::gates::stats::Clock;
+use solana_vntr_sniper::universal::gates::{
+    DailyLossGate, DuplicatePositionGate, Gate, GateDecision, MintConcurrencyGate, RejectReason,
+    TradeContext,
+};
+use solana_vntr_sniper::universal::metrics::{MetricsSubscriptions, SubscriptionSink};
+use solana_vntr_sniper::universal::positions::{
+    load_positions, reap_expired_positions, reconcile_positions, save_positions, BalanceSource,
+    CloseSettlement, DailyLossTracker, ExportFormat, OpenParams, PositionError, PositionStatus,
+    PositionsRegistry, SellCommitment,
+};
+use solana_vntr_sniper::universal::wallet_config::update_wallet_config;
 
 #[test]
 fn fresh_registry_allows_opening_position() {
@@ -68,3 +81,640 @@ fn closing_non_open_position_returns_not_open() {
         if wallet == "walletA" && mint == "mintX"
     ));
 }
+
+struct FakeBalances(std::collections::HashMap<(String, String), f64>);
+
+impl BalanceSource for FakeBalances {
+    fn token_balance(&self, wallet: &str, mint: &str) -> Result<f64, String> {
+        Ok(self
+            .0
+            .get(&(wallet.to_string(), mint.to_string()))
+            .copied()
+            .unwrap_or(0.0))
+    }
+}
+
+#[test]
+fn all_open_positions_spans_every_wallet() {
+    let registry = PositionsRegistry::new();
+    registry
+        .record_open("walletA", "mintX", 1_700_000_000)
+        .unwrap();
+    registry
+        .record_open("walletA", "mintY", 1_700_000_001)
+        .unwrap();
+    registry
+        .record_open("walletB", "mintX", 1_700_000_002)
+        .unwrap();
+    registry.record_close("walletA", "mintY").unwrap();
+
+    let mut open: Vec<_> = registry
+        .all_open_positions()
+        .into_iter()
+        .map(|p| (p.wallet, p.mint))
+        .collect();
+    open.sort();
+    assert_eq!(
+        open,
+        vec![
+            ("walletA".to_string(), "mintX".to_string()),
+            ("walletB".to_string(), "mintX".to_string()),
+        ]
+    );
+    assert_eq!(registry.open_count(), 2);
+}
+
+#[test]
+fn reaper_closes_positions_older_than_max_age() {
+    let registry = PositionsRegistry::new();
+    let now = 1_700_010_000;
+    registry
+        .record_open("walletA", "oldMint", now - 7_200)
+        .unwrap();
+    registry
+        .record_open("walletA", "newMint", now - 60)
+        .unwrap();
+
+    let expired = reap_expired_positions(&registry, 3_600, now);
+    assert_eq!(
+        expired,
+        vec![("walletA".to_string(), "oldMint".to_string())]
+    );
+
+    let old = registry.get_open_position("walletA", "oldMint").unwrap();
+    assert_eq!(old.status, PositionStatus::Closed);
+    assert!(registry.has_open_position("walletA", "newMint"));
+    assert!(registry.can_open("walletA", "oldMint").is_ok());
+}
+
+#[test]
+fn reconcile_closes_positions_without_onchain_balance() {
+    let registry = PositionsRegistry::new();
+    registry
+        .record_open("walletA", "soldMint", 1_700_000_000)
+        .unwrap();
+    registry
+        .record_open("walletA", "heldMint", 1_700_000_000)
+        .unwrap();
+
+    let mut balances = std::collections::HashMap::new();
+    balances.insert(("walletA".to_string(), "heldMint".to_string()), 42.0);
+
+    let report = reconcile_positions(&registry, &FakeBalances(balances));
+
+    assert_eq!(report.checked, 2);
+    assert_eq!(
+        report.closed,
+        vec![("walletA".to_string(), "soldMint".to_string())]
+    );
+    assert!(report.discrepancies.is_empty());
+    assert!(!registry.has_open_position("walletA", "soldMint"));
+    assert!(registry.has_open_position("walletA", "heldMint"));
+}
+
+#[test]
+fn saved_positions_are_restored_into_a_new_registry() {
+    let path = std::env::temp_dir().join(format!("positions_{}.json", std::process::id()));
+    let registry = PositionsRegistry::new();
+    let params = OpenParams {
+        size: Some(1_000.0),
+        entry_price: Some(0.01),
+        slippage_bps: None,
+    };
+    registry
+        .record_open_with("walletA", "mintA", 100, params)
+        .unwrap();
+    registry.record_open("walletB", "mintB", 200).unwrap();
+    registry.begin_close("walletB", "mintB").unwrap();
+    registry.record_open("walletC", "mintC", 300).unwrap();
+    registry.record_close("walletC", "mintC").unwrap();
+
+    // Only active (open or closing) positions are saved.
+    assert_eq!(save_positions(&registry, &path).unwrap(), 2);
+
+    let subs = Arc::new(MetricsSubscriptions::new());
+    let restored = PositionsRegistry::new().with_subscriptions(Arc::clone(&subs));
+    restored.record_open("walletA", "mintA", 999).unwrap();
+    // walletA/mintA is already open here, so only walletB's is restored.
+    assert_eq!(load_positions(&restored, &path).unwrap(), 1);
+    assert_eq!(
+        restored
+            .get_open_position("walletA", "mintA")
+            .unwrap()
+            .opened_ts,
+        999
+    );
+    let closing = restored.get_open_position("walletB", "mintB").unwrap();
+    assert_eq!(closing.status, PositionStatus::Closing);
+    assert_eq!(closing.opened_ts, 200);
+    assert_eq!(subs.refcount("mintB"), 1);
+
+    let fresh = PositionsRegistry::new();
+    assert_eq!(load_positions(&fresh, &path).unwrap(), 2);
+    assert_eq!(
+        fresh.get_open_position("walletA", "mintA").unwrap().size,
+        Some(1_000.0)
+    );
+
+    std::fs::remove_file(&path).ok();
+    // A missing file restores nothing.
+    assert_eq!(load_positions(&PositionsRegistry::new(), &path).unwrap(), 0);
+}
+
+#[test]
+fn open_params_round_trip_through_get_open_position() {
+    let registry = PositionsRegistry::new();
+    let params = OpenParams {
+        size: Some(1_500.0),
+        entry_price: Some(0.002),
+        slippage_bps: Some(300.0),
+    };
+    registry
+        .record_open_with("walletP", "mintP", 42, params)
+        .unwrap();
+
+    let pos = registry.get_open_position("walletP", "mintP").unwrap();
+    assert_eq!(pos.opened_ts, 42);
+    assert_eq!(pos.size, Some(1_500.0));
+    assert_eq!(pos.entry_price, Some(0.002));
+    assert_eq!(pos.slippage_bps, Some(300.0));
+    assert!(!pos.simulated);
+
+    // The 3-arg form leaves them unset.
+    registry.record_open("walletP", "mintQ", 43).unwrap();
+    let bare = registry.get_open_position("walletP", "mintQ").unwrap();
+    assert_eq!(bare.size, None);
+    assert_eq!(bare.entry_price, None);
+    assert_eq!(bare.slippage_bps, None);
+
+    // Still one position per (wallet, mint).
+    assert!(matches!(
+        registry.record_open_with("walletP", "mintP", 44, params),
+        Err(PositionError::AlreadyOpen(_, _))
+    ));
+}
+
+fn trade_ctx(mint: &str) -> TradeContext {
+    TradeContext {
+        mint: mint.into(),
+        target_wallet: "wallet3".into(),
+        price_usd: 1.0,
+        ..TradeContext::default()
+    }
+}
+
+#[test]
+fn mint_concurrency_gate_caps_wallets_per_mint() {
+    let registry: &'static PositionsRegistry = Box::leak(Box::new(PositionsRegistry::new()));
+    let gate = MintConcurrencyGate::new(2, registry);
+    let ctx = trade_ctx("mintX");
+
+    registry.record_open("wallet1", "mintX", 1).unwrap();
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+
+    registry.record_open("wallet2", "mintX", 2).unwrap();
+    registry.record_open("wallet3", "mintY", 3).unwrap();
+    assert_eq!(registry.count_open_for_mint("mintX"), 2);
+    match gate.check(&ctx) {
+        GateDecision::Rejected { code, reason } => {
+            assert_eq!(code, RejectReason::MintConcurrency);
+            assert_eq!(reason, "mint_concurrency 2 >= 2");
+        }
+        GateDecision::Passed => panic!("third wallet should be rejected"),
+    }
+
+    registry.record_close("wallet1", "mintX").unwrap();
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+}
+
+#[test]
+fn duplicate_position_gate_blocks_second_buy_until_closed() {
+    let registry: &'static PositionsRegistry = Box::leak(Box::new(PositionsRegistry::new()));
+    let gate = DuplicatePositionGate::new(true, registry);
+    let ctx = trade_ctx("mintD");
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+
+    registry.record_open("wallet3", "mintD", 1).unwrap();
+    match gate.check(&ctx) {
+        GateDecision::Rejected { code, reason } => {
+            assert_eq!(code, RejectReason::DuplicatePosition);
+            assert_eq!(reason, "duplicate_position");
+        }
+        GateDecision::Passed => panic!("duplicate buy should be rejected"),
+    }
+    // Another leader's position in the same mint is not a duplicate.
+    let mut other = trade_ctx("mintD");
+    other.target_wallet = "wallet4".into();
+    assert!(matches!(gate.check(&other), GateDecision::Passed));
+    // Switched off, the gate never blocks.
+    assert!(matches!(
+        DuplicatePositionGate::new(false, registry).check(&ctx),
+        GateDecision::Passed
+    ));
+
+    registry.record_close("wallet3", "mintD").unwrap();
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+}
+
+#[test]
+fn duplicate_position_gate_checks_our_wallet_not_the_leader() {
+    let registry: &'static PositionsRegistry = Box::leak(Box::new(PositionsRegistry::new()));
+    let gate = DuplicatePositionGate::new(true, registry);
+    let copy_of = |leader: &str| TradeContext {
+        target_wallet: leader.into(),
+        owner_wallet: Some("ourWallet".into()),
+        ..trade_ctx("mintO")
+    };
+    assert!(matches!(
+        gate.check(&copy_of("leader1")),
+        GateDecision::Passed
+    ));
+
+    // Positions are recorded under our signing wallet, whichever leader we
+    // copied, so a second leader buying the mint is still a duplicate.
+    registry.record_open("ourWallet", "mintO", 1).unwrap();
+    assert!(matches!(
+        gate.check(&copy_of("leader1")),
+        GateDecision::Rejected { .. }
+    ));
+    assert!(matches!(
+        gate.check(&copy_of("leader2")),
+        GateDecision::Rejected { .. }
+    ));
+}
+
+#[test]
+fn dropped_sell_restores_position_for_retry() {
+    let registry = PositionsRegistry::new();
+    registry.record_open("walletA", "mintX", 1).unwrap();
+
+    registry.begin_close("walletA", "mintX").unwrap();
+    // In flight: slot still held, and a second sell cannot start.
+    assert!(registry.has_open_position("walletA", "mintX"));
+    assert!(registry.begin_close("walletA", "mintX").is_err());
+
+    let settled = registry
+        .settle_close("walletA", "mintX", None, SellCommitment::Confirmed, None)
+        .unwrap();
+    assert_eq!(settled, CloseSettlement::Restored);
+    assert!(registry.has_open_position("walletA", "mintX"));
+
+    // The monitor retries the exit.
+    registry.begin_close("walletA", "mintX").unwrap();
+}
+
+#[test]
+fn sell_short_of_required_commitment_is_restored_then_closes_when_confirmed() {
+    let registry = PositionsRegistry::new();
+    registry.record_open("walletA", "mintX", 1).unwrap();
+
+    registry.begin_close("walletA", "mintX").unwrap();
+    let settled = registry
+        .settle_close(
+            "walletA",
+            "mintX",
+            Some(SellCommitment::Processed),
+            SellCommitment::Confirmed,
+            None,
+        )
+        .unwrap();
+    assert_eq!(settled, CloseSettlement::Restored);
+
+    registry.begin_close("walletA", "mintX").unwrap();
+    let settled = registry
+        .settle_close(
+            "walletA",
+            "mintX",
+            Some(SellCommitment::Finalized),
+            SellCommitment::Confirmed,
+            None,
+        )
+        .unwrap();
+    assert_eq!(settled, CloseSettlement::Closed);
+    assert!(!registry.has_open_position("walletA", "mintX"));
+    assert!(registry.can_open("walletA", "mintX").is_ok());
+    assert!(registry.close_log().last_close("mintX").is_some());
+}
+
+#[test]
+fn sell_commitment_parses_case_insensitively() {
+    assert_eq!(
+        "Finalized".parse::<SellCommitment>(),
+        Ok(SellCommitment::Finalized)
+    );
+    assert!("soon".parse::<SellCommitment>().is_err());
+}
+
+#[derive(Default)]
+struct RecordingSink {
+    calls: Mutex<Vec<String>>,
+}
+
+impl SubscriptionSink for RecordingSink {
+    fn subscribe(&self, mint: &str) {
+        self.calls.lock().unwrap().push(format!("sub {}", mint));
+    }
+    fn unsubscribe(&self, mint: &str) {
+        self.calls.lock().unwrap().push(format!("unsub {}", mint));
+    }
+}
+
+#[test]
+fn open_subscribes_mint_and_close_releases_it() {
+    let sink = Arc::new(RecordingSink::default());
+    let subs = Arc::new(MetricsSubscriptions::new());
+    subs.set_sink(sink.clone());
+    let registry = PositionsRegistry::new().with_subscriptions(subs.clone());
+
+    registry.record_open("walletA", "mintX", 1).unwrap();
+    registry.record_open("walletB", "mintX", 2).unwrap();
+    assert_eq!(subs.refcount("mintX"), 2);
+    assert_eq!(*sink.calls.lock().unwrap(), vec!["sub mintX"]);
+
+    // A pending sell keeps the feed; a confirmed one drops this position's ref.
+    registry.begin_close("walletA", "mintX").unwrap();
+    assert_eq!(subs.refcount("mintX"), 2);
+    registry
+        .settle_close(
+            "walletA",
+            "mintX",
+            Some(SellCommitment::Finalized),
+            SellCommitment::Confirmed,
+            None,
+        )
+        .unwrap();
+    assert_eq!(subs.refcount("mintX"), 1);
+
+    registry.record_close("walletB", "mintX").unwrap();
+    assert_eq!(subs.refcount("mintX"), 0);
+    assert_eq!(
+        *sink.calls.lock().unwrap(),
+        vec!["sub mintX", "unsub mintX"]
+    );
+}
+
+#[test]
+fn bulk_close_releases_every_position_and_late_sink_catches_up() {
+    let subs = Arc::new(MetricsSubscriptions::new());
+    let registry = PositionsRegistry::new().with_subscriptions(subs.clone());
+    registry.record_open("walletA", "mintX", 1).unwrap();
+    registry.record_open("walletA", "mintY", 1).unwrap();
+    registry.record_open("walletB", "mintY", 1).unwrap();
+
+    // Sink attached after positions opened still gets their mints.
+    let sink = Arc::new(RecordingSink::default());
+    subs.set_sink(sink.clone());
+    assert_eq!(*sink.calls.lock().unwrap(), vec!["sub mintX", "sub mintY"]);
+
+    assert_eq!(registry.close_all_for_mint("mintY"), 2);
+    assert_eq!(registry.close_all_for_wallet("walletA"), 1);
+    assert!(subs.subscribed_mints().is_empty());
+    assert_eq!(
+        *sink.calls.lock().unwrap(),
+        vec!["sub mintX", "sub mintY", "unsub mintY", "unsub mintX"]
+    );
+}
+
+struct FixedClock(i64);
+
+impl Clock for FixedClock {
+    fn now_secs(&self) -> i64 {
+        self.0
+    }
+}
+
+fn open_at_entry(registry: &PositionsRegistry, wallet: &str, mint: &str) {
+    registry
+        .record_open_with(
+            wallet,
+            mint,
+            0,
+            OpenParams {
+                size: Some(100.0),
+                entry_price: Some(1.0),
+                slippage_bps: None,
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+fn close_with_price_records_realized_pnl() {
+    let registry = PositionsRegistry::new();
+    open_at_entry(&registry, "pnlWallet", "pnlMint");
+    let pnl = registry
+        .record_close_with_price("pnlWallet", "pnlMint", 0.4)
+        .unwrap();
+    assert!((pnl.unwrap() + 60.0).abs() < 1e-9);
+
+    let now = chrono::Utc::now().timestamp();
+    let tracker = registry.daily_pnl();
+    assert!((tracker.today_pnl_usd(now) + 60.0).abs() < 1e-9);
+    assert!((tracker.wallet_pnl_usd("pnlWallet", now) + 60.0).abs() < 1e-9);
+
+    // No entry price or size: closes, but realizes nothing.
+    registry.record_open("pnlWallet", "bareMint", 0).unwrap();
+    assert!(matches!(
+        registry.record_close_with_price("pnlWallet", "bareMint", 2.0),
+        Ok(None)
+    ));
+}
+
+#[test]
+fn confirmed_sell_books_pnl_in_usd() {
+    // Entries are SOL per token; at $150/SOL a 0.6 SOL loss is -$90.
+    let registry = PositionsRegistry::new().with_quote_usd(|| Some(150.0));
+    open_at_entry(&registry, "settleWallet", "settleMint");
+    registry.begin_close("settleWallet", "settleMint").unwrap();
+    let settled = registry
+        .settle_close(
+            "settleWallet",
+            "settleMint",
+            Some(SellCommitment::Confirmed),
+            SellCommitment::Confirmed,
+            Some(0.994),
+        )
+        .unwrap();
+    assert_eq!(settled, CloseSettlement::Closed);
+
+    let now = chrono::Utc::now().timestamp();
+    let tracker = registry.daily_pnl();
+    assert!((tracker.wallet_pnl_usd("settleWallet", now) + 90.0).abs() < 1e-6);
+
+    // No SOL/USD quote: the close stands, but nothing is booked.
+    let unquoted = PositionsRegistry::new().with_quote_usd(|| None);
+    open_at_entry(&unquoted, "settleWallet", "settleMint");
+    assert!(matches!(
+        unquoted.record_close_with_price("settleWallet", "settleMint", 0.4),
+        Ok(None)
+    ));
+    assert!(!unquoted.has_open_position("settleWallet", "settleMint"));
+}
+
+#[test]
+fn daily_loss_tracker_resets_at_utc_midnight() {
+    let tracker = DailyLossTracker::new();
+    let day = 1_700_000_000 - 1_700_000_000 % 86_400;
+    tracker.record("w", -50.0, day + 10);
+    tracker.record("w", 20.0, day + 86_399);
+    assert_eq!(tracker.today_pnl_usd(day + 86_399), -30.0);
+    assert_eq!(tracker.today_pnl_usd(day + 86_400), 0.0);
+    assert_eq!(tracker.wallet_pnl_usd("w", day + 86_400), 0.0);
+}
+
+#[test]
+fn daily_loss_gate_blocks_buys_past_the_limit() {
+    let tracker = Arc::new(DailyLossTracker::new());
+    let now = 1_700_000_000;
+    let gate = DailyLossGate::with_clock(100.0, tracker.clone(), FixedClock(now));
+
+    tracker.record("lossWallet", -100.0, now);
+    assert!(matches!(
+        gate.check(&trade_ctx("lossMint")),
+        GateDecision::Passed
+    ));
+
+    tracker.record("lossWallet", -0.01, now);
+    match gate.check(&trade_ctx("lossMint")) {
+        GateDecision::Rejected { code, reason } => {
+            assert_eq!(code, RejectReason::DailyLossLimit);
+            assert_eq!(reason, "daily_loss_limit_hit");
+        }
+        GateDecision::Passed => panic!("expected daily loss limit to trip"),
+    }
+
+    // The next UTC day starts from zero.
+    let tomorrow = DailyLossGate::with_clock(100.0, tracker, FixedClock(now + 86_400));
+    assert!(matches!(
+        tomorrow.check(&trade_ctx("lossMint")),
+        GateDecision::Passed
+    ));
+}
+
+#[test]
+fn daily_loss_gate_applies_per_wallet_limit() {
+    let wallet = "dailyLossLimitedWallet";
+    update_wallet_config(wallet, |c| c.max_daily_loss_usd = Some(25.0)).unwrap();
+    let tracker = Arc::new(DailyLossTracker::new());
+    let now = 1_700_000_000;
+    // No global limit; only the wallet's own limit applies.
+    let gate = DailyLossGate::with_clock(0.0, tracker.clone(), FixedClock(now));
+    let ctx = TradeContext {
+        target_wallet: wallet.into(),
+        ..trade_ctx("lossMint")
+    };
+
+    tracker.record("otherWallet", -1_000.0, now);
+    assert!(matches!(gate.check(&ctx), GateDecision::Passed));
+
+    tracker.record(wallet, -30.0, now);
+    assert!(matches!(gate.check(&ctx), GateDecision::Rejected { .. }));
+}
+
+#[test]
+fn daily_loss_gate_reads_pnl_booked_under_our_wallet() {
+    let leader = "dailyLossLeader";
+    update_wallet_config(leader, |c| c.max_daily_loss_usd = Some(25.0)).unwrap();
+    let tracker = Arc::new(DailyLossTracker::new());
+    let now = 1_700_000_000;
+    let gate = DailyLossGate::with_clock(0.0, tracker.clone(), FixedClock(now));
+    let ctx = TradeContext {
+        target_wallet: leader.into(),
+        owner_wallet: Some("dailyLossOwner".into()),
+        ..trade_ctx("lossMint")
+    };
+
+    // Realized PnL is booked under the wallet the position was opened with.
+    tracker.record("dailyLossOwner", -30.0, now);
+    match gate.check(&ctx) {
+        GateDecision::Rejected { code, .. } => assert_eq!(code, RejectReason::DailyLossLimit),
+        GateDecision::Passed => panic!("expected the leader's limit to trip on our loss"),
+    }
+}
+
+fn closed_history() -> PositionsRegistry {
+    let registry = PositionsRegistry::new();
+    registry
+        .record_open_with(
+            "walletA",
+            "mintX",
+            1_700_000_000,
+            OpenParams {
+                size: Some(100.0),
+                entry_price: Some(0.5),
+                slippage_bps: None,
+            },
+        )
+        .unwrap();
+    registry
+        .record_close_with_price("walletA", "mintX", 0.75)
+        .unwrap();
+    registry
+        .record_open("walletB", "mintY", 1_700_000_100)
+        .unwrap();
+    registry
+        .record_close_at("walletB", "mintY", 1_700_000_200)
+        .unwrap();
+    registry
+        .record_open("walletB", "mintZ", 1_700_000_300)
+        .unwrap();
+    registry
+}
+
+#[test]
+fn export_closed_writes_csv_rows_for_closed_positions_only() {
+    let registry = closed_history();
+    let mut out = Vec::new();
+    assert_eq!(
+        registry.export_closed(&mut out, ExportFormat::Csv).unwrap(),
+        2
+    );
+
+    let csv = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "wallet,mint,opened_ts,closed_ts,size,entry_price,exit_price,pnl"
+    );
+    assert_eq!(lines[1], "walletB,mintY,1700000100,1700000200,,,,");
+    assert!(lines[2].starts_with("walletA,mintX,1700000000,"));
+    assert!(lines[2].ends_with(",100,0.5,0.75,25"));
+    assert_eq!(lines.len(), 3);
+}
+
+#[test]
+fn export_closed_writes_json_lines_and_prunes_on_request() {
+    let registry = closed_history();
+    let mut out = Vec::new();
+    assert_eq!(
+        registry
+            .export_closed_and_prune(&mut out, ExportFormat::JsonLines)
+            .unwrap(),
+        2
+    );
+
+    let rows: Vec<serde_json::Value> = String::from_utf8(out)
+        .unwrap()
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["mint"], "mintY");
+    assert_eq!(rows[0]["closed_ts"], 1_700_000_200);
+    assert!(rows[0]["pnl"].is_null());
+    assert_eq!(rows[1]["mint"], "mintX");
+    assert_eq!(rows[1]["exit_price"], 0.75);
+    assert_eq!(rows[1]["pnl"], 25.0);
+
+    // Pruned: closed rows are gone, the open one stays.
+    let mut again = Vec::new();
+    assert_eq!(
+        registry
+            .export_closed(&mut again, ExportFormat::JsonLines)
+            .unwrap(),
+        0
+    );
+    assert!(again.is_empty());
+    assert!(registry.has_open_position("walletB", "mintZ"));
+    assert!(registry.get_open_position("walletA", "mintX").is_none());
+}
@@ -0,0 +1,118 @@
+//! Tests for the SOL/USD price oracle, using a mocked feed.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use solana_vntr_sniper::common::price_oracle::{
+    parse_birdeye_price, parse_pyth_price, PriceFeed, PriceOracle,
+};
+use solana_vntr_sniper::universal::withdraw::Holding;
+
+/// Returns the queued prices in order (an error once they run out) and
+/// counts fetches.
+struct MockFeed {
+    prices: Mutex<Vec<f64>>,
+    fetches: AtomicUsize,
+}
+
+impl MockFeed {
+    fn new(prices: &[f64]) -> Arc<Self> {
+        Arc::new(Self {
+            prices: Mutex::new(prices.iter().rev().copied().collect()),
+            fetches: AtomicUsize::new(0),
+        })
+    }
+}
+
+impl PriceFeed for MockFeed {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn fetch(&self) -> BoxFuture<'_, Result<f64>> {
+        self.fetches.fetch_add(1, Ordering::SeqCst);
+        let next = self.prices.lock().unwrap().pop();
+        Box::pin(async move { next.ok_or_else(|| anyhow!("mock feed down")) })
+    }
+}
+
+fn oracle(feed: Arc<MockFeed>, fallback: Option<f64>) -> PriceOracle {
+    PriceOracle::new(
+        Some(feed),
+        Duration::from_secs(10),
+        Duration::from_secs(60),
+        fallback,
+    )
+}
+
+#[tokio::test]
+async fn caches_within_ttl_and_refreshes_from_the_feed() {
+    let feed = MockFeed::new(&[150.0, 160.0]);
+    let oracle = oracle(feed.clone(), None);
+
+    assert_eq!(oracle.price(), None);
+    assert_eq!(oracle.current().await, Some(150.0));
+    assert_eq!(oracle.current().await, Some(150.0));
+    assert_eq!(feed.fetches.load(Ordering::SeqCst), 1);
+
+    assert_eq!(oracle.refresh().await.unwrap(), 160.0);
+    assert_eq!(oracle.price(), Some(160.0));
+
+    // A failed refresh keeps serving the cached price.
+    assert!(oracle.refresh().await.is_err());
+    assert_eq!(oracle.price(), Some(160.0));
+}
+
+#[test]
+fn stale_price_falls_back() {
+    let now = Instant::now();
+    let with_fallback = oracle(MockFeed::new(&[]), Some(140.0));
+    with_fallback.record_at(150.0, now);
+
+    assert_eq!(
+        with_fallback.price_at(now + Duration::from_secs(30)),
+        Some(150.0)
+    );
+    assert!(with_fallback.needs_refresh_at(now + Duration::from_secs(30)));
+    assert_eq!(
+        with_fallback.price_at(now + Duration::from_secs(61)),
+        Some(140.0)
+    );
+
+    let without = oracle(MockFeed::new(&[]), None);
+    without.record_at(150.0, now);
+    assert_eq!(without.price_at(now + Duration::from_secs(61)), None);
+
+    // Invalid prices are never cached.
+    without.record_at(f64::NAN, now + Duration::from_secs(61));
+    without.record_at(-1.0, now + Duration::from_secs(61));
+    assert_eq!(without.price_at(now + Duration::from_secs(61)), None);
+}
+
+#[tokio::test]
+async fn dead_feed_serves_fallback() {
+    let oracle = oracle(MockFeed::new(&[]), Some(140.0));
+    assert_eq!(oracle.current().await, Some(140.0));
+}
+
+#[test]
+fn parses_feed_responses() {
+    let pyth = r#"{"parsed":[{"id":"ef0d","price":{"price":"14286000000","conf":"1","expo":-8,"publish_time":1}}]}"#;
+    assert!((parse_pyth_price(pyth).unwrap() - 142.86).abs() < 1e-9);
+    assert!(parse_pyth_price(r#"{"parsed":[]}"#).is_err());
+
+    let birdeye = r#"{"success":true,"data":{"value":142.5,"updateUnixTime":1}}"#;
+    assert_eq!(parse_birdeye_price(birdeye).unwrap(), 142.5);
+    assert!(parse_birdeye_price(r#"{"success":false}"#).is_err());
+    assert!(parse_birdeye_price(r#"{"success":true,"data":{"value":0}}"#).is_err());
+}
+
+#[test]
+fn sol_holding_is_valued_in_usd() {
+    let holding = Holding::sol_at(2_500_000_000, 150.0);
+    assert_eq!(holding.amount, 2_500_000_000);
+    assert_eq!(holding.value_usd, 375.0);
+}
@@ -0,0 +1,62 @@
+//! Tests for dynamic priority-fee helpers in the send path.
+
+use std::time::Duration;
+
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_vntr_sniper::block_engine::tx::{percentile_fee, writable_accounts, PriorityFeeCache};
+
+#[test]
+fn percentile_uses_nearest_rank() {
+    let fees = vec![10, 40, 20, 30];
+
+    assert_eq!(percentile_fee(fees.clone(), 50.0), Some(20));
+    assert_eq!(percentile_fee(fees.clone(), 75.0), Some(30));
+    assert_eq!(percentile_fee(fees.clone(), 100.0), Some(40));
+    assert_eq!(percentile_fee(fees, 0.0), Some(10));
+}
+
+#[test]
+fn percentile_of_no_samples_is_none() {
+    assert_eq!(percentile_fee(Vec::new(), 75.0), None);
+}
+
+#[test]
+fn writable_accounts_are_deduplicated_and_exclude_readonly() {
+    let program = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+
+    let instructions = vec![
+        Instruction::new_with_bytes(
+            program,
+            &[],
+            vec![
+                AccountMeta::new(pool, false),
+                AccountMeta::new(user, true),
+                AccountMeta::new_readonly(mint, false),
+            ],
+        ),
+        Instruction::new_with_bytes(program, &[], vec![AccountMeta::new(pool, false)]),
+    ];
+
+    assert_eq!(writable_accounts(&instructions), vec![pool, user]);
+}
+
+#[test]
+fn priority_fee_cache_is_keyed_by_account_set() {
+    let pool_a = Pubkey::new_unique();
+    let pool_b = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let ttl = Duration::from_secs(60);
+
+    let cache = PriorityFeeCache::new();
+    cache.insert(&[pool_a, user], 5_000, ttl);
+
+    // Same accounts in any order hit; another pool's accounts miss.
+    assert_eq!(cache.get(&[user, pool_a], ttl), Some(5_000));
+    assert_eq!(cache.get(&[pool_b, user], ttl), None);
+    // Past the TTL the price is resampled.
+    assert_eq!(cache.get(&[pool_a, user], Duration::ZERO), None);
+}
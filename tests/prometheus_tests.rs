@@ -0,0 +1,110 @@
+//! Tests for the Prometheus counters and `/metrics` endpoint.
+
+use solana_vntr_sniper::universal::gates::{run_gates, Gate, McapGate, RejectReason, TradeContext};
+use solana_vntr_sniper::universal::positions::{PositionsRegistry, SellCommitment};
+use solana_vntr_sniper::universal::prometheus::{serve_metrics, Counters, COUNTERS};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+fn ctx() -> TradeContext {
+    TradeContext {
+        mint: "promMint".into(),
+        target_wallet: "promWallet".into(),
+        price_usd: 1.0,
+        est_cost_bps: 50.0,
+        ..TradeContext::default()
+    }
+}
+
+#[test]
+fn render_uses_prometheus_text_format() {
+    let counters = Counters::new();
+    counters.inc_gate_rejection("McapGate");
+    counters.inc_gate_rejection("McapGate");
+    counters.inc_gate_rejection("Weird\"Gate");
+    counters.inc_trades_opened();
+    counters.inc_rpc_failovers();
+    counters.inc_reject_reason(RejectReason::LowMcap);
+
+    let text = counters.render();
+    assert!(text.contains("# TYPE gate_rejections_total counter\n"));
+    assert!(text.contains("gate_rejections_total{gate=\"McapGate\"} 2\n"));
+    assert!(text.contains("gate_rejections_total{gate=\"Weird\\\"Gate\"} 1\n"));
+    assert!(text.contains("trades_opened_total 1\n"));
+    assert!(text.contains("trades_closed_total 0\n"));
+    assert!(text.contains("rpc_failovers_total 1\n"));
+    assert!(text.contains("# TYPE gate_reject_reasons_total counter\n"));
+    assert!(text.contains("gate_reject_reasons_total{reason=\"low_mcap\"} 1\n"));
+}
+
+#[test]
+fn gates_and_registry_increment_global_counters() {
+    let rejections = COUNTERS.gate_rejections("McapGate");
+    let no_mcap = COUNTERS.reject_reasons(RejectReason::NoMcap);
+    let gates: Vec<Box<dyn Gate>> = vec![Box::new(McapGate { min_mcap: 1.0 })];
+    let (ok, _) = run_gates(&ctx(), &gates);
+    assert!(!ok);
+    assert!(COUNTERS.gate_rejections("McapGate") > rejections);
+    assert!(COUNTERS.reject_reasons(RejectReason::NoMcap) > no_mcap);
+
+    let opened = COUNTERS.trades_opened();
+    let closed = COUNTERS.trades_closed();
+    let registry = PositionsRegistry::new();
+    registry.record_open("promWallet", "promMint", 0).unwrap();
+    registry.record_close("promWallet", "promMint").unwrap();
+    assert!(COUNTERS.trades_opened() > opened);
+    assert!(COUNTERS.trades_closed() > closed);
+}
+
+#[test]
+fn every_close_path_counts_a_closed_trade() {
+    let registry = PositionsRegistry::new();
+    for mint in ["settled", "walletWide", "mintWide"] {
+        registry.record_open("countWallet", mint, 0).unwrap();
+    }
+    registry.record_open("otherWallet", "mintWide", 0).unwrap();
+    let closed = COUNTERS.trades_closed();
+
+    registry.begin_close("countWallet", "settled").unwrap();
+    registry
+        .settle_close(
+            "countWallet",
+            "settled",
+            Some(SellCommitment::Confirmed),
+            SellCommitment::Confirmed,
+            None,
+        )
+        .unwrap();
+    assert_eq!(registry.close_all_for_mint("mintWide"), 2);
+    assert_eq!(registry.close_all_for_wallet("countWallet"), 1);
+
+    // Other tests bump the global counter concurrently, so only a lower bound.
+    assert!(COUNTERS.trades_closed() >= closed + 4);
+}
+
+async fn get(addr: std::net::SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let request = format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path);
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn metrics_endpoint_serves_counters() {
+    let counters: &'static Counters = Box::leak(Box::new(Counters::new()));
+    counters.inc_trades_closed();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(serve_metrics(listener, counters));
+
+    let response = get(addr, "/metrics").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("trades_closed_total 1\n"));
+
+    let response = get(addr, "/other").await;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+    server.abort();
+}
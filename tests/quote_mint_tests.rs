@@ -0,0 +1,108 @@
+//! Tests for quote-mint (SOL vs USDC) detection and conversion.
+
+use solana_vntr_sniper::common::quote::{
+    set_sol_usd_price, QuoteConverter, QuoteMint, USDC_MINT, WSOL_MINT,
+};
+use solana_vntr_sniper::processor::transaction_parser::{parse_transaction_data, DexType};
+use yellowstone_grpc_proto::geyser::{SubscribeUpdateTransaction, SubscribeUpdateTransactionInfo};
+use yellowstone_grpc_proto::prelude::{TokenBalance, TransactionStatusMeta};
+
+const TOKEN_MINT: &str = "2ivzYvjnKqA4X3dVvPKr7bctGpbxwrXbbxm44TJCpump";
+
+fn put_u64(buf: &mut [u8], offset: usize, value: u64) {
+    buf[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+fn pump_swap_buy_txn(quote_mint: &str) -> SubscribeUpdateTransaction {
+    let balance = |mint: &str| TokenBalance {
+        mint: mint.to_string(),
+        ..Default::default()
+    };
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            meta: Some(TransactionStatusMeta {
+                log_messages: vec!["Program log: Instruction: Buy".to_string()],
+                post_token_balances: vec![balance(TOKEN_MINT), balance(quote_mint)],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        slot: 0,
+    }
+}
+
+/// 368-byte PumpSwap buy event.
+fn pump_swap_buy_event(
+    base_amount_out: u64,
+    pool_base_reserves: u64,
+    pool_quote_reserves: u64,
+    quote_amount_in: u64,
+) -> Vec<u8> {
+    let mut buf = vec![0u8; 368];
+    put_u64(&mut buf, 16, 1_700_000_000);
+    put_u64(&mut buf, 24, base_amount_out);
+    put_u64(&mut buf, 56, pool_base_reserves);
+    put_u64(&mut buf, 64, pool_quote_reserves);
+    put_u64(&mut buf, 72, quote_amount_in);
+    buf
+}
+
+#[test]
+fn detects_quote_mint_from_touched_mints() {
+    assert_eq!(QuoteMint::detect([TOKEN_MINT, WSOL_MINT]), QuoteMint::Sol);
+    assert_eq!(QuoteMint::detect([TOKEN_MINT, USDC_MINT]), QuoteMint::Usdc);
+    assert_eq!(QuoteMint::detect([TOKEN_MINT]), QuoteMint::Sol);
+}
+
+#[test]
+fn converter_handles_both_quotes() {
+    let conv = QuoteConverter::new(200.0);
+    assert_eq!(conv.to_usd(1.5, QuoteMint::Sol), 300.0);
+    assert_eq!(conv.to_sol(1.5, QuoteMint::Sol), 1.5);
+    assert_eq!(conv.to_usd(100.0, QuoteMint::Usdc), 100.0);
+    assert_eq!(conv.to_sol(100.0, QuoteMint::Usdc), 0.5);
+}
+
+#[test]
+fn decodes_usdc_quoted_pool_buy_in_sol_and_usd() {
+    set_sol_usd_price(200.0);
+
+    // 100 USDC spent into a pool holding 20,000 USDC (6 decimals).
+    let buffer = pump_swap_buy_event(
+        5_000_000_000,
+        1_000_000_000_000_000,
+        20_000_000_000,
+        100_000_000,
+    );
+    let txn = pump_swap_buy_txn(USDC_MINT);
+
+    let info = parse_transaction_data(&txn, &buffer).expect("decodes");
+
+    assert_eq!(info.dex_type, DexType::PumpSwap);
+    assert_eq!(info.mint, TOKEN_MINT);
+    assert_eq!(info.quote_mint, QuoteMint::Usdc);
+    assert!(info.is_buy);
+    assert!((info.sol_change + 0.5).abs() < 1e-9);
+    assert!((info.liquidity - 100.0).abs() < 1e-9);
+
+    let conv = QuoteConverter::new(200.0);
+    assert!((conv.to_usd(info.sol_change.abs(), QuoteMint::Sol) - 100.0).abs() < 1e-6);
+    assert!((conv.to_usd(info.liquidity, QuoteMint::Sol) - 20_000.0).abs() < 1e-6);
+}
+
+#[test]
+fn sol_quoted_pool_buy_is_unchanged() {
+    let buffer = pump_swap_buy_event(
+        5_000_000_000,
+        1_000_000_000_000_000,
+        100_000_000_000,
+        500_000_000,
+    );
+    let txn = pump_swap_buy_txn(WSOL_MINT);
+
+    let info = parse_transaction_data(&txn, &buffer).expect("decodes");
+
+    assert_eq!(info.quote_mint, QuoteMint::Sol);
+    assert!((info.sol_change + 0.5).abs() < 1e-9);
+    assert!((info.liquidity - 100.0).abs() < 1e-9);
+}
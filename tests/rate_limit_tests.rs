@@ -0,0 +1,95 @@
+//! Tests for the per-wallet buy rate limiter.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use solana_vntr_sniper::universal::gates::rate_limit::{
+    BuyRateLimiter, MonotonicClock, RATE_WINDOW,
+};
+use solana_vntr_sniper::universal::gates::{
+    BuyRateGate, Gate, GateDecision, RejectReason, TradeContext,
+};
+
+#[derive(Clone)]
+struct FakeClock(Arc<Mutex<Instant>>);
+
+impl FakeClock {
+    fn new() -> Self {
+        FakeClock(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    fn advance(&self, by: Duration) {
+        *self.0.lock().unwrap() += by;
+    }
+}
+
+impl MonotonicClock for FakeClock {
+    fn now(&self) -> Instant {
+        *self.0.lock().unwrap()
+    }
+}
+
+fn ctx(wallet: &str) -> TradeContext {
+    TradeContext {
+        mint: "mintX".into(),
+        target_wallet: wallet.into(),
+        price_usd: 1.0,
+        ..TradeContext::default()
+    }
+}
+
+#[test]
+fn rejects_buy_over_limit_until_window_passes() {
+    let clock = FakeClock::new();
+    let limiter = Arc::new(BuyRateLimiter::with_clock(RATE_WINDOW, clock.clone()));
+    let gate = BuyRateGate::new(3, limiter);
+
+    for _ in 0..3 {
+        assert!(matches!(gate.check(&ctx("walletA")), GateDecision::Passed));
+    }
+    match gate.check(&ctx("walletA")) {
+        GateDecision::Rejected { code, reason } => {
+            assert_eq!(code, RejectReason::RateLimited);
+            assert_eq!(reason, "rate_limited 20s");
+        }
+        GateDecision::Passed => panic!("expected rate limit rejection"),
+    }
+    // Buckets are per wallet.
+    assert!(matches!(gate.check(&ctx("walletB")), GateDecision::Passed));
+
+    clock.advance(RATE_WINDOW);
+    for _ in 0..3 {
+        assert!(matches!(gate.check(&ctx("walletA")), GateDecision::Passed));
+    }
+}
+
+#[test]
+fn bucket_refills_gradually() {
+    let clock = FakeClock::new();
+    let limiter = BuyRateLimiter::with_clock(RATE_WINDOW, clock.clone());
+
+    assert!(limiter.try_acquire("walletA", 2).is_ok());
+    assert!(limiter.try_acquire("walletA", 2).is_ok());
+    assert_eq!(
+        limiter.try_acquire("walletA", 2),
+        Err(Duration::from_secs(30))
+    );
+
+    clock.advance(Duration::from_secs(10));
+    assert_eq!(
+        limiter.try_acquire("walletA", 2),
+        Err(Duration::from_secs(20))
+    );
+    clock.advance(Duration::from_secs(20));
+    assert!(limiter.try_acquire("walletA", 2).is_ok());
+    assert!(limiter.try_acquire("walletA", 2).is_err());
+}
+
+#[test]
+fn zero_limit_is_unlimited() {
+    let limiter = Arc::new(BuyRateLimiter::with_clock(RATE_WINDOW, FakeClock::new()));
+    let gate = BuyRateGate::new(0, limiter);
+    for _ in 0..100 {
+        assert!(matches!(gate.check(&ctx("walletA")), GateDecision::Passed));
+    }
+}
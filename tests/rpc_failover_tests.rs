@@ -0,0 +1,349 @@
+//! Tests for endpoint selection in the RPC failover clients.
+
+use std::time::{Duration, Instant};
+
+use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anchor_client::solana_sdk::transaction::Transaction;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use solana_client::client_error::{ClientError, ClientErrorKind};
+use solana_vntr_sniper::common::config::{commitment_from_env, parse_commitment};
+use solana_vntr_sniper::common::rpc_failover::{
+    AsyncRpcFailover, EndpointStats, RetryBackoff, RpcEndpoint, RpcError, RpcFailover,
+};
+use solana_vntr_sniper::library::rpc_failover::RpcClientSet;
+
+fn regional_failover() -> RpcFailover {
+    let endpoints = [
+        "ny|https://ny.example",
+        "fra|https://fra1.example",
+        "fra|https://fra2.example",
+    ]
+    .iter()
+    .filter_map(|s| RpcEndpoint::parse(s))
+    .collect();
+    RpcFailover::with_endpoints(endpoints, Some("fra".into()), 1, Duration::from_secs(1))
+        .expect("endpoints configured")
+}
+
+#[test]
+fn endpoint_parse_reads_optional_region() {
+    assert_eq!(
+        RpcEndpoint::parse("FRA|https://a.example"),
+        Some(RpcEndpoint {
+            url: "https://a.example".into(),
+            region: Some("fra".into()),
+        })
+    );
+    assert_eq!(
+        RpcEndpoint::parse("https://b.example"),
+        Some(RpcEndpoint {
+            url: "https://b.example".into(),
+            region: None,
+        })
+    );
+    assert_eq!(RpcEndpoint::parse("  "), None);
+}
+
+#[test]
+fn local_region_endpoints_are_tried_first() {
+    let failover = regional_failover();
+
+    assert_eq!(
+        failover.selection_order(),
+        vec![
+            "https://fra1.example",
+            "https://fra2.example",
+            "https://ny.example"
+        ]
+    );
+    assert_eq!(failover.current_endpoint(), "https://fra1.example");
+}
+
+#[test]
+fn remote_region_is_used_only_after_local_failures() {
+    let failover = regional_failover();
+
+    failover.mark_unhealthy("https://fra1.example");
+    assert_eq!(failover.current_endpoint(), "https://fra2.example");
+
+    failover.mark_unhealthy("https://fra2.example");
+    assert_eq!(failover.current_endpoint(), "https://ny.example");
+
+    // A recovered local endpoint wins again.
+    failover.mark_healthy("https://fra1.example");
+    assert_eq!(failover.current_endpoint(), "https://fra1.example");
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn async_failover_rotates_through_endpoints() {
+    // Nothing listens on port 1, so every call fails fast with connection refused.
+    let endpoints = ["fra|http://127.0.0.1:1", "ny|http://127.0.0.2:1"]
+        .iter()
+        .filter_map(|s| RpcEndpoint::parse(s))
+        .collect();
+    let failover =
+        RpcFailover::with_endpoints(endpoints, Some("fra".into()), 2, Duration::from_secs(1))
+            .unwrap();
+    let client = AsyncRpcFailover::new(failover.clone());
+
+    let err = client.get_account(&Pubkey::new_unique()).await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "get_account failed: all rpc endpoints failed after retries"
+    );
+    // Connection refused: the last attempt is an upstream transport error,
+    // which is worth retrying later.
+    match &err {
+        RpcError::AllEndpointsFailed { op, last } => {
+            assert_eq!(*op, "get_account");
+            assert!(matches!(last.as_deref(), Some(RpcError::Upstream { .. })));
+        }
+        other => panic!("unexpected error {:?}", other),
+    }
+    assert!(err.is_retryable());
+
+    // Both endpoints were marked unhealthy on the shared failover state, so
+    // the local-region preference decides the order again.
+    assert_eq!(failover.current_endpoint(), "http://127.0.0.1:1");
+    assert_eq!(
+        client.failover().selection_order(),
+        vec!["http://127.0.0.1:1", "http://127.0.0.2:1"]
+    );
+}
+
+#[test]
+fn client_errors_are_classified_for_retry_decisions() {
+    let io = |kind| ClientError::from(std::io::Error::new(kind, "io"));
+
+    let timeout = RpcError::from_client_error("get_account", io(std::io::ErrorKind::TimedOut));
+    assert!(matches!(
+        timeout,
+        RpcError::Timeout {
+            source: Some(_),
+            ..
+        }
+    ));
+    assert!(timeout.is_retryable());
+
+    let refused =
+        RpcError::from_client_error("get_account", io(std::io::ErrorKind::ConnectionRefused));
+    assert!(matches!(refused, RpcError::Upstream { .. }));
+    assert!(refused.is_retryable());
+
+    let bad_json = serde_json::from_str::<u64>("nope").unwrap_err();
+    let serialization = RpcError::from_client_error(
+        "get_account",
+        ClientError::from(ClientErrorKind::SerdeJson(bad_json)),
+    );
+    assert!(matches!(serialization, RpcError::Serialization { .. }));
+    assert!(!serialization.is_retryable());
+
+    let rejected = RpcError::from_client_error(
+        "send_and_confirm_transaction",
+        ClientError::from(ClientErrorKind::Custom("blockhash not found".into())),
+    );
+    assert!(matches!(rejected, RpcError::Upstream { .. }));
+    assert!(!rejected.is_retryable());
+    assert_eq!(
+        rejected.to_string(),
+        "send_and_confirm_transaction failed: blockhash not found"
+    );
+
+    // Messages match the former string errors.
+    let budget = RpcError::Timeout {
+        op: "get_latest_blockhash",
+        source: None,
+    };
+    assert_eq!(
+        budget.to_string(),
+        "get_latest_blockhash failed: rpc retry budget exhausted"
+    );
+    assert_eq!(
+        RpcError::NoEndpoints { op: "get_account" }.to_string(),
+        "get_account failed: no rpc endpoints configured"
+    );
+    let all_failed = RpcError::AllEndpointsFailed {
+        op: "get_account",
+        last: Some(Box::new(serialization)),
+    };
+    assert!(!all_failed.is_retryable());
+    assert!(std::error::Error::source(&all_failed).is_some());
+}
+
+#[test]
+fn probes_rank_healthy_endpoints_by_latency() {
+    let failover = regional_failover();
+    let ms = Duration::from_millis;
+
+    failover.record_probe("https://fra1.example", Ok(ms(80)));
+    failover.record_probe("https://fra2.example", Ok(ms(20)));
+    failover.record_probe("https://ny.example", Ok(ms(5)));
+    // Faster local endpoint first; the remote one stays behind both.
+    assert_eq!(
+        failover.selection_order(),
+        vec![
+            "https://fra2.example",
+            "https://fra1.example",
+            "https://ny.example"
+        ]
+    );
+
+    failover.record_probe("https://fra2.example", Err("timeout".into()));
+    assert_eq!(failover.current_endpoint(), "https://fra1.example");
+
+    // The faster endpoint recovers and is preferred again.
+    failover.record_probe("https://fra2.example", Ok(ms(15)));
+    assert_eq!(failover.current_endpoint(), "https://fra2.example");
+
+    let stats = failover.endpoint_stats();
+    assert_eq!(stats[2].0, "https://fra2.example");
+    assert_eq!(
+        stats[2].1,
+        EndpointStats {
+            successes: 2,
+            failures: 1,
+            latency: Some(ms(15)),
+        }
+    );
+}
+
+#[test]
+fn all_unhealthy_falls_back_to_every_endpoint() {
+    let failover = regional_failover();
+    for url in [
+        "https://ny.example",
+        "https://fra1.example",
+        "https://fra2.example",
+    ] {
+        failover.record_probe(url, Err("down".into()));
+    }
+    assert_eq!(failover.selection_order().len(), 3);
+    assert_eq!(failover.current_endpoint(), "https://fra1.example");
+}
+
+#[test]
+fn probe_all_marks_unreachable_endpoint_unhealthy() {
+    let endpoints = ["http://127.0.0.1:1", "http://127.0.0.2:1"]
+        .iter()
+        .filter_map(|s| RpcEndpoint::parse(s))
+        .collect();
+    let failover = RpcFailover::with_endpoints(endpoints, None, 1, Duration::from_secs(1)).unwrap();
+    failover.record_probe("http://127.0.0.2:1", Ok(Duration::from_millis(1)));
+
+    failover.probe_all();
+    let stats = failover.endpoint_stats();
+    assert!(stats.iter().all(|(_, s)| s.failures == 1));
+    assert_eq!(stats[0].1.latency, None);
+}
+
+#[test]
+fn commitment_defaults_to_confirmed_and_is_configurable() {
+    let failover = regional_failover();
+    assert_eq!(failover.commitment(), CommitmentConfig::confirmed());
+    let failover = failover.with_commitment(CommitmentConfig::finalized());
+    assert_eq!(failover.commitment(), CommitmentConfig::finalized());
+}
+
+#[test]
+fn commitment_env_maps_to_commitment_config() {
+    for (value, expected) in [
+        ("processed", CommitmentConfig::processed()),
+        ("confirmed", CommitmentConfig::confirmed()),
+        (" Finalized ", CommitmentConfig::finalized()),
+        ("bogus", CommitmentConfig::confirmed()),
+    ] {
+        std::env::set_var("COMMITMENT", value);
+        assert_eq!(commitment_from_env(), expected, "COMMITMENT={}", value);
+    }
+    std::env::remove_var("COMMITMENT");
+    assert_eq!(commitment_from_env(), CommitmentConfig::confirmed());
+    assert_eq!(parse_commitment("recent"), None);
+}
+
+#[test]
+fn unreachable_endpoint_fails_within_configured_timeout() {
+    // Non-routable address: connects hang until the client timeout fires.
+    let failover = RpcFailover::new(
+        vec!["http://10.255.255.1:8899".into()],
+        1,
+        Duration::from_secs(1),
+    )
+    .unwrap();
+
+    let started = Instant::now();
+    assert!(failover.get_latest_blockhash().is_err());
+    assert!(
+        started.elapsed() < Duration::from_secs(5),
+        "took {:?}",
+        started.elapsed()
+    );
+}
+
+#[test]
+fn backoff_is_jittered_exponential_and_budgeted() {
+    let mut backoff = RetryBackoff::new(100, 1_000, Duration::from_millis(1_500));
+    assert_eq!(backoff.ceiling(0), Duration::from_millis(100));
+    assert_eq!(backoff.ceiling(3), Duration::from_millis(800));
+    assert_eq!(backoff.ceiling(4), Duration::from_millis(1_000));
+    assert_eq!(backoff.ceiling(80), Duration::from_millis(1_000));
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut delays = Vec::new();
+    while let Some(delay) = backoff.next_delay(&mut rng) {
+        assert!(delay <= backoff.ceiling(delays.len() as u32));
+        delays.push(delay);
+        assert!(delays.len() < 100, "budget never ran out");
+    }
+    // Total sleep never exceeds the budget; jitter spreads the delays out.
+    assert!(backoff.slept() <= Duration::from_millis(1_500));
+    assert_eq!(delays.iter().sum::<Duration>(), backoff.slept());
+}
+
+#[test]
+fn backoff_settings_are_exposed_on_the_client() {
+    let failover = regional_failover().with_backoff(50, 2_000, Duration::from_secs(3));
+    assert_eq!(failover.rpc_backoff_base_ms(), 50);
+    assert_eq!(failover.rpc_backoff_max_ms(), 2_000);
+    assert_eq!(
+        failover.retry_backoff().ceiling(2),
+        Duration::from_millis(200)
+    );
+}
+
+#[test]
+fn client_set_tries_every_backup_and_reports_all_errors() {
+    let set = RpcClientSet::from_urls(&[
+        "http://127.0.0.1:1",
+        "http://127.0.0.2:1",
+        "http://127.0.0.3:1",
+    ]);
+    assert_eq!(set.backups.len(), 2);
+    assert_eq!(set.backups[1].url(), "http://127.0.0.3:1");
+
+    let err = set
+        .send_and_confirm_transaction_with_failover(&Transaction::default())
+        .unwrap_err()
+        .to_string();
+    assert!(err.starts_with("all 3 RPCs failed"), "{}", err);
+    for part in [
+        "primary (http://127.0.0.1:1)",
+        "backup #1 (http://127.0.0.2:1)",
+        "backup #2 (http://127.0.0.3:1)",
+    ] {
+        assert!(err.contains(part), "missing {} in {}", part, err);
+    }
+
+    // The single-backup constructor still works.
+    assert_eq!(
+        RpcClientSet::new("http://127.0.0.1:1", None).backups.len(),
+        0
+    );
+    assert_eq!(
+        RpcClientSet::new("http://127.0.0.1:1", Some("http://127.0.0.2:1"))
+            .backups
+            .len(),
+        1
+    );
+}
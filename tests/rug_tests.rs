@@ -0,0 +1,41 @@
+//! Tests for rug detection and the session blacklist.
+
+use solana_vntr_sniper::universal::blacklist::is_blacklisted;
+use solana_vntr_sniper::universal::rug::{observe_and_blacklist, RugConfig, RugDetector};
+
+#[test]
+fn rug_collapse_lands_mint_on_blacklist() {
+    let detector = RugDetector::new(RugConfig::default());
+    let mint = "rugMint111";
+
+    assert!(!observe_and_blacklist(&detector, mint, 1_000, 1.0, 50.0));
+    assert!(!observe_and_blacklist(&detector, mint, 1_010, 1.05, 52.0));
+    assert!(!is_blacklisted(mint));
+
+    // 97% price drop and liquidity pulled within 30s.
+    assert!(observe_and_blacklist(&detector, mint, 1_030, 0.03, 1.0));
+    assert!(is_blacklisted(mint));
+
+    // Already listed: no second notification.
+    assert!(!observe_and_blacklist(&detector, mint, 1_031, 0.02, 0.5));
+}
+
+#[test]
+fn price_drop_with_liquidity_intact_is_not_a_rug() {
+    let detector = RugDetector::new(RugConfig::default());
+    let mint = "dumpMint111";
+
+    detector.observe(mint, 1_000, 1.0, 50.0);
+    assert!(!detector.observe(mint, 1_020, 0.05, 45.0));
+}
+
+#[test]
+fn slow_decline_outside_window_is_not_a_rug() {
+    let detector = RugDetector::new(RugConfig::default());
+    let mint = "slowMint111";
+
+    detector.observe(mint, 1_000, 1.0, 50.0);
+    // Peak has aged out of the 60s window by the time of the collapse.
+    assert!(!observe_and_blacklist(&detector, mint, 1_200, 0.01, 0.5));
+    assert!(!is_blacklisted(mint));
+}
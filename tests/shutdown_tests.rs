@@ -0,0 +1,77 @@
+//! Tests for the graceful shutdown sequence.
+
+use std::time::Duration;
+
+use serde_json::Value;
+use solana_vntr_sniper::universal::control::{check_can_open, is_paused};
+use solana_vntr_sniper::universal::events::{install_emitter, EventEmitter};
+use solana_vntr_sniper::universal::positions::{
+    load_positions, PositionsRegistry, GLOBAL_POSITIONS_REGISTRY,
+};
+use solana_vntr_sniper::universal::shutdown::{
+    drain_timeout_from_env, in_flight_sends, is_shutting_down, shutdown, track_send,
+    wait_for_sends, DEFAULT_DRAIN_TIMEOUT,
+};
+
+// Shutdown state, the registry, the emitter and env are process-wide, so the
+// whole sequence is checked in a single test.
+#[tokio::test]
+async fn shutdown_drains_sends_and_saves_positions() {
+    let dir = std::env::temp_dir().join(format!("shutdown_tests_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let positions = dir.join("positions.json");
+    let events = dir.join("events.jsonl");
+    let _ = std::fs::remove_file(&positions);
+    let _ = std::fs::remove_file(&events);
+    std::env::set_var("POSITIONS_FILE", &positions);
+    install_emitter(EventEmitter::open(&events).unwrap());
+
+    assert_eq!(drain_timeout_from_env(), DEFAULT_DRAIN_TIMEOUT);
+    std::env::set_var("SHUTDOWN_DRAIN_SECS", "3");
+    assert_eq!(drain_timeout_from_env(), Duration::from_secs(3));
+
+    // A send that outlives the drain timeout is reported, not waited for.
+    let stuck = track_send();
+    assert_eq!(in_flight_sends(), 1);
+    assert!(!wait_for_sends(Duration::from_millis(20)).await);
+
+    GLOBAL_POSITIONS_REGISTRY
+        .record_open("walletA", "mintA", 100)
+        .unwrap();
+    assert!(check_can_open("walletA").is_ok());
+
+    // The send finishes while shutdown is draining.
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(stuck);
+    });
+    let report = shutdown("SIGTERM", Duration::from_secs(5)).await;
+    assert!(report.drained);
+    assert_eq!(report.in_flight_sends, 0);
+    assert_eq!(report.open_positions, 1);
+
+    assert!(is_shutting_down());
+    assert!(is_paused());
+    assert_eq!(check_can_open("walletA"), Err("shutting_down".to_string()));
+
+    let restored = PositionsRegistry::new();
+    assert_eq!(load_positions(&restored, &positions).unwrap(), 1);
+    assert!(restored.has_open_position("walletA", "mintA"));
+
+    let last: Value = serde_json::from_str(
+        std::fs::read_to_string(&events)
+            .unwrap()
+            .lines()
+            .last()
+            .expect("shutdown event written"),
+    )
+    .unwrap();
+    assert_eq!(last["event"], "shutdown");
+    assert_eq!(last["signal"], "SIGTERM");
+    assert_eq!(last["open_positions"], 1);
+    assert_eq!(last["drained"], true);
+
+    std::env::remove_var("POSITIONS_FILE");
+    std::env::remove_var("SHUTDOWN_DRAIN_SECS");
+    std::fs::remove_dir_all(&dir).ok();
+}
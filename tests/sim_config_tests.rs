@@ -0,0 +1,61 @@
+//! Tests for building SimConfig from env and wallet config.
+
+use solana_vntr_sniper::universal::executor::SimConfig;
+use solana_vntr_sniper::universal::wallet_config::WalletConfigMap;
+
+// Env is process-wide, so every assertion reading it lives in one test.
+#[test]
+fn env_overrides_beat_defaults() {
+    let defaults = SimConfig::default();
+    std::env::set_var("LIQ_5M_MIN_USD", "2500");
+    std::env::set_var("LIQ_15M_MIN_USD", "7500");
+    std::env::set_var("DEPTH_MULT", "4.5");
+    std::env::set_var("MIN_MCAP_USD", "1000");
+    std::env::set_var("MCAP_MIN_USD", "90000");
+    std::env::set_var("VOL_MAX_PCT", "not-a-number");
+    std::env::set_var("EXCLUDE_NON_MIGRATED", "false");
+
+    let cfg = SimConfig::from_env();
+    assert_eq!(cfg.liq5m, 2500.0);
+    assert_eq!(cfg.liq15m, 7500.0);
+    assert_eq!(cfg.depth_mult, 4.5);
+    // MCAP_MIN_USD wins over the live gate's MIN_MCAP_USD.
+    assert_eq!(cfg.mcap_min, 90000.0);
+    // Unparsable values keep the default.
+    assert_eq!(cfg.vol_max_pct, defaults.vol_max_pct);
+    assert!(!cfg.exclude_non_migrated);
+
+    std::env::remove_var("MCAP_MIN_USD");
+    assert_eq!(SimConfig::from_env().mcap_min, 1000.0);
+
+    // Unconfigured wallets keep the env-derived thresholds and defaults.
+    let wallet = SimConfig::for_wallet("sim-config-unknown-wallet");
+    assert_eq!(wallet.liq5m, 2500.0);
+    assert_eq!(wallet.max_slippage_bps, defaults.max_slippage_bps);
+    assert_eq!(wallet.tp_mult, defaults.tp_mult);
+}
+
+#[test]
+fn wallet_params_set_slippage_and_exits() {
+    let map = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.simWallet]
+        slippage = 0.02
+        tp = 2.0
+        "#,
+    )
+    .unwrap();
+    let defaults = SimConfig::default();
+    let params = map.resolve_params_for_wallet(
+        "simWallet",
+        defaults.max_slippage_bps / 10_000.0,
+        defaults.tp_mult,
+        defaults.sl_mult,
+        None,
+    );
+    let cfg = defaults.clone().with_wallet_params(&params);
+    assert!((cfg.max_slippage_bps - 200.0).abs() < 1e-9);
+    assert_eq!(cfg.tp_mult, 2.0);
+    assert_eq!(cfg.sl_mult, defaults.sl_mult);
+    assert_eq!(cfg.liq5m, defaults.liq5m);
+}
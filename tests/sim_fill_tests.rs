@@ -0,0 +1,88 @@
+//! Tests for the SimBackend fill / P&L model.
+
+use solana_vntr_sniper::universal::executor::{
+    estimate_fill, estimate_pnl_usd, ExecutionSimulator, SimBackend, SimConfig, SimulationAction,
+};
+use solana_vntr_sniper::universal::gates::TradeContext;
+
+fn ctx(depth_multiple: f64, size_usd: Option<f64>) -> TradeContext {
+    TradeContext {
+        mint: "simFillMint".into(),
+        target_wallet: "simFillWallet".into(),
+        price_usd: 1.0,
+        est_cost_bps: 50.0,
+        window5m_usd: 1_000_000.0,
+        window15m_usd: 3_000_000.0,
+        depth_multiple,
+        est_mcap_usd: Some(10_000_000.0),
+        window_vol_pct: 1.0,
+        amihud_5m: Some(1e-7),
+        range_eff_5m: Some(0.6),
+        metrics_age_secs: Some(0),
+        requested_size_usd: size_usd,
+        ..TradeContext::default()
+    }
+}
+
+fn sim() -> SimBackend {
+    SimBackend::new(SimConfig {
+        exclude_non_migrated: false,
+        ..SimConfig::default()
+    })
+}
+
+#[test]
+fn deep_pool_fills_fully_with_small_impact() {
+    let fill = estimate_fill(50.0, 1_000.0, 500.0);
+    assert_eq!(fill.fill_pct, 1.0);
+    // 1 / (1000 + 1) of impact on top of the 50 bps cost.
+    assert!((fill.slippage_bps - (50.0 + 10_000.0 / 1_001.0)).abs() < 1e-9);
+}
+
+#[test]
+fn shallow_pool_fills_partially() {
+    // 450 bps of impact budget against a pool 3x the order.
+    let fill = estimate_fill(50.0, 3.0, 500.0);
+    let s = 0.045;
+    assert!((fill.fill_pct - 3.0 * s / (1.0 - s)).abs() < 1e-12);
+    assert!(fill.fill_pct < 1.0);
+    // The filled part spends exactly the slippage budget.
+    assert!((fill.slippage_bps - 500.0).abs() < 1e-6);
+
+    assert_eq!(estimate_fill(600.0, 3.0, 500.0).fill_pct, 0.0);
+    assert_eq!(estimate_fill(50.0, 0.0, 500.0).fill_pct, 0.0);
+}
+
+#[test]
+fn pnl_weighs_tp_and_sl_exits_net_of_costs() {
+    // No costs: half at +50%, half at -50% nets zero.
+    assert!(estimate_pnl_usd(100.0, 0.0, 1.5, 0.5, 0.5, 0.0).abs() < 1e-9);
+    // Fees are paid on entry and exit.
+    assert!((estimate_pnl_usd(100.0, 0.0, 1.5, 0.5, 0.5, 1.0) + 2.0).abs() < 1e-9);
+    // Slippage drags a sure TP below its gross profit.
+    let pnl = estimate_pnl_usd(100.0, 100.0, 1.5, 0.5, 1.0, 0.0);
+    assert!(pnl < 50.0 && pnl > 45.0);
+}
+
+#[test]
+fn simulate_reports_partial_fill_and_pnl_for_shallow_buys() {
+    let backend = sim();
+    let shallow = backend.simulate(&ctx(2.5, Some(100.0)), SimulationAction::Buy);
+    assert!(shallow.passed, "{:?}", shallow.gate_reasons);
+    assert!(shallow.est_fill_pct < 1.0);
+    assert!(shallow.est_fill_pct > 0.0);
+    assert!(shallow.est_pnl_usd.is_some());
+
+    let deep = backend.simulate(&ctx(500.0, Some(100.0)), SimulationAction::Buy);
+    assert_eq!(deep.est_fill_pct, 1.0);
+
+    // Sells and unsized buys have no P&L estimate.
+    assert!(backend
+        .simulate(&ctx(500.0, Some(100.0)), SimulationAction::Sell)
+        .est_pnl_usd
+        .is_none());
+    assert!(backend
+        .simulate(&ctx(500.0, None), SimulationAction::Buy)
+        .est_pnl_usd
+        .is_none());
+}
@@ -0,0 +1,49 @@
+//! Tests for slippage-to-bound conversions.
+
+use solana_vntr_sniper::universal::slippage::{
+    max_in_with_slippage_bps, min_out_with_slippage, min_out_with_slippage_bps,
+};
+
+#[test]
+fn min_out_at_zero_two_and_hundred_percent() {
+    assert_eq!(min_out_with_slippage(1_000, 0.0), 1_000);
+    assert_eq!(min_out_with_slippage(1_000, 0.02), 980);
+    assert_eq!(min_out_with_slippage(1_000, 1.0), 0);
+
+    assert_eq!(min_out_with_slippage_bps(1_000, 0), 1_000);
+    assert_eq!(min_out_with_slippage_bps(1_000, 200), 980);
+    assert_eq!(min_out_with_slippage_bps(1_000, 10_000), 0);
+}
+
+#[test]
+fn min_out_rounds_up() {
+    // 999 * 0.98 = 979.02 -> 980: never accept less than the tolerance allows.
+    assert_eq!(min_out_with_slippage(999, 0.02), 980);
+    assert_eq!(min_out_with_slippage_bps(999, 200), 980);
+    assert_eq!(min_out_with_slippage_bps(1, 9_999), 1);
+}
+
+#[test]
+fn out_of_range_slippage_is_clamped() {
+    assert_eq!(min_out_with_slippage(1_000, -0.5), 1_000);
+    assert_eq!(min_out_with_slippage(1_000, 1.5), 0);
+    assert_eq!(min_out_with_slippage(1_000, f64::NAN), 1_000);
+    assert_eq!(min_out_with_slippage(1_000, f64::INFINITY), 0);
+    assert_eq!(min_out_with_slippage_bps(1_000, u64::MAX), 0);
+}
+
+#[test]
+fn large_amounts_do_not_overflow() {
+    assert_eq!(min_out_with_slippage(u64::MAX, 0.0), u64::MAX);
+    assert_eq!(min_out_with_slippage_bps(u64::MAX, 0), u64::MAX);
+    let expected = (u64::MAX as u128 * 9_800).div_ceil(10_000) as u64;
+    assert_eq!(min_out_with_slippage_bps(u64::MAX, 200), expected);
+    assert_eq!(min_out_with_slippage(u64::MAX, 0.02), expected);
+
+    assert_eq!(max_in_with_slippage_bps(1_000, 500), 1_050);
+    assert_eq!(max_in_with_slippage_bps(1_000, 0), 1_000);
+    assert_eq!(max_in_with_slippage_bps(u64::MAX, 1), u64::MAX);
+    assert_eq!(max_in_with_slippage_bps(u64::MAX, u64::MAX), u64::MAX);
+    // Rounds down: 999 * 1.05 = 1048.95.
+    assert_eq!(max_in_with_slippage_bps(999, 500), 1_048);
+}
@@ -0,0 +1,53 @@
+//! Tests for the fee-aware take-profit adjustment.
+
+use solana_vntr_sniper::processor::selling_strategy::FeeAwareTpConfig;
+
+fn cfg() -> FeeAwareTpConfig {
+    FeeAwareTpConfig {
+        enabled: true,
+        min_net_profit_pct: 2.0,
+        fee_per_tx_sol: 0.001,
+        swap_fee_bps: 100.0,
+    }
+}
+
+/// Net SOL profit of exiting a `position_sol` position at `tp_pct`.
+fn net_profit(cfg: &FeeAwareTpConfig, position_sol: f64, tp_pct: f64) -> f64 {
+    let bps = cfg.swap_fee_bps / 10_000.0;
+    let tp = tp_pct / 100.0;
+    position_sol * tp
+        - 2.0 * cfg.fee_per_tx_sol
+        - position_sol * bps
+        - position_sol * (1.0 + tp) * bps
+}
+
+#[test]
+fn small_position_tp_is_raised_to_cover_fees() {
+    let cfg = cfg();
+    let position = 0.05;
+    // At +5% the round trip loses money on 0.05 SOL.
+    assert!(net_profit(&cfg, position, 5.0) < 0.0);
+
+    let tp = cfg.effective_take_profit(5.0, position);
+    assert!(tp > 5.0);
+    assert!((tp - 8.0808).abs() < 1e-3, "tp = {}", tp);
+    let min_net = position * cfg.min_net_profit_pct / 100.0;
+    assert!((net_profit(&cfg, position, tp) - min_net).abs() < 1e-9);
+}
+
+#[test]
+fn large_position_tp_is_unchanged() {
+    let cfg = cfg();
+    assert_eq!(cfg.effective_take_profit(5.0, 10.0), 5.0);
+    assert!(net_profit(&cfg, 10.0, 5.0) > 10.0 * 0.02);
+}
+
+#[test]
+fn disabled_or_unknown_size_keeps_configured_tp() {
+    let disabled = FeeAwareTpConfig {
+        enabled: false,
+        ..cfg()
+    };
+    assert_eq!(disabled.effective_take_profit(5.0, 0.05), 5.0);
+    assert_eq!(cfg().effective_take_profit(5.0, 0.0), 5.0);
+}
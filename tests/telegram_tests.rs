@@ -0,0 +1,308 @@
+//! Tests for applying Telegram control commands to the live registries.
+
+use solana_vntr_sniper::universal::positions::{
+    PositionState, PositionStatus, GLOBAL_POSITIONS_REGISTRY,
+};
+use solana_vntr_sniper::universal::telegram::{
+    apply_command, format_pnl, format_positions, handle_positions, parse_command, Ctrl,
+};
+use solana_vntr_sniper::universal::wallet_config::{
+    get_wallet_config_map, is_wallet_enabled, tracked_wallet_states, tracked_wallets,
+};
+
+const WALLET: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+
+#[test]
+fn exit_position_closes_all_wallets_holding_mint() {
+    GLOBAL_POSITIONS_REGISTRY
+        .record_open("exit-w1", "exit-mint", 1)
+        .unwrap();
+    GLOBAL_POSITIONS_REGISTRY
+        .record_open("exit-w2", "exit-mint", 1)
+        .unwrap();
+
+    let msg = apply_command(Ctrl::ExitPosition {
+        mint: "exit-mint".into(),
+    })
+    .expect("closes");
+    assert!(msg.contains("Closed 2"));
+    assert!(!GLOBAL_POSITIONS_REGISTRY.has_open_position("exit-w1", "exit-mint"));
+    assert!(!GLOBAL_POSITIONS_REGISTRY.has_open_position("exit-w2", "exit-mint"));
+
+    assert!(apply_command(Ctrl::ExitPosition {
+        mint: "exit-mint".into()
+    })
+    .is_err());
+}
+
+#[test]
+fn setters_update_wallet_config() {
+    let wallet = "setter-wallet";
+    apply_command(Ctrl::SetSlippage {
+        wallet: wallet.into(),
+        bps: 250,
+    })
+    .unwrap();
+    apply_command(Ctrl::SetTp {
+        wallet: wallet.into(),
+        pct: 50.0,
+    })
+    .unwrap();
+    apply_command(Ctrl::SetSl {
+        wallet: wallet.into(),
+        pct: 20.0,
+    })
+    .unwrap();
+    apply_command(Ctrl::SetMaxPos {
+        wallet: wallet.into(),
+        n: 3,
+    })
+    .unwrap();
+    apply_command(Ctrl::SetRetries {
+        wallet: wallet.into(),
+        n: 5,
+    })
+    .unwrap();
+
+    let cfg = get_wallet_config_map().get_or_default(wallet);
+    assert!((cfg.slippage.unwrap() - 0.025).abs() < 1e-12);
+    assert!((cfg.tp.unwrap() - 1.5).abs() < 1e-12);
+    assert!((cfg.sl.unwrap() - 0.8).abs() < 1e-12);
+    assert_eq!(cfg.max_positions, Some(3));
+    assert_eq!(cfg.retries, Some(5));
+
+    assert!(apply_command(Ctrl::SetRetries {
+        wallet: wallet.into(),
+        n: 0,
+    })
+    .is_err());
+}
+
+#[test]
+fn invalid_setting_is_rejected_and_not_applied() {
+    let wallet = "invalid-setter-wallet";
+    apply_command(Ctrl::SetSl {
+        wallet: wallet.into(),
+        pct: 10.0,
+    })
+    .unwrap();
+
+    let err = apply_command(Ctrl::SetSl {
+        wallet: wallet.into(),
+        pct: 150.0,
+    })
+    .unwrap_err();
+    assert!(err.contains("sl"));
+    assert!(apply_command(Ctrl::SetMaxPos {
+        wallet: wallet.into(),
+        n: 0
+    })
+    .is_err());
+
+    let cfg = get_wallet_config_map().get_or_default(wallet);
+    assert!((cfg.sl.unwrap() - 0.9).abs() < 1e-12);
+    assert_eq!(cfg.max_positions, None);
+}
+
+#[test]
+fn add_wallet_tracks_valid_pubkeys_only() {
+    assert!(apply_command(Ctrl::AddWallet("not-a-pubkey".into())).is_err());
+
+    let first = apply_command(Ctrl::AddWallet(WALLET.into())).unwrap();
+    assert!(first.contains("Now tracking"));
+    let second = apply_command(Ctrl::AddWallet(WALLET.into())).unwrap();
+    assert!(second.contains("Already tracking"));
+    assert!(tracked_wallets().contains(&WALLET.to_string()));
+}
+
+#[test]
+fn enable_and_disable_wallet_parse() {
+    match parse_command("/disable_wallet walletX") {
+        Some(Ctrl::DisableWallet { wallet }) => assert_eq!(wallet, "walletX"),
+        other => panic!("unexpected {:?}", other),
+    }
+    match parse_command("/enable_wallet walletX") {
+        Some(Ctrl::EnableWallet { wallet }) => assert_eq!(wallet, "walletX"),
+        other => panic!("unexpected {:?}", other),
+    }
+    assert!(parse_command("/disable_wallet").is_none());
+    assert!(parse_command("/enable_wallet").is_none());
+}
+
+#[test]
+fn exit_all_parses() {
+    assert!(matches!(parse_command("/exit_all"), Some(Ctrl::ExitAll)));
+    match parse_command("/exit mintX") {
+        Some(Ctrl::ExitPosition { mint }) => assert_eq!(mint, "mintX"),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn disable_wallet_keeps_its_config() {
+    let wallet = "toggle-wallet";
+    apply_command(Ctrl::SetMaxPos {
+        wallet: wallet.into(),
+        n: 3,
+    })
+    .unwrap();
+    assert!(is_wallet_enabled(wallet));
+
+    let msg = apply_command(Ctrl::DisableWallet {
+        wallet: wallet.into(),
+    })
+    .unwrap();
+    assert!(msg.contains("Disabled"));
+    assert!(!is_wallet_enabled(wallet));
+    let cfg = get_wallet_config_map().get_or_default(wallet);
+    assert_eq!(cfg.enabled, Some(false));
+    assert_eq!(cfg.max_positions, Some(3));
+
+    apply_command(Ctrl::EnableWallet {
+        wallet: wallet.into(),
+    })
+    .unwrap();
+    assert!(is_wallet_enabled(wallet));
+    assert_eq!(
+        get_wallet_config_map().get_or_default(wallet).max_positions,
+        Some(3)
+    );
+}
+
+#[test]
+fn tracked_wallet_states_report_enabled_flag() {
+    let wallet = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    apply_command(Ctrl::AddWallet(wallet.into())).unwrap();
+    assert!(tracked_wallet_states().contains(&(wallet.to_string(), true)));
+
+    apply_command(Ctrl::DisableWallet {
+        wallet: wallet.into(),
+    })
+    .unwrap();
+    assert!(tracked_wallet_states().contains(&(wallet.to_string(), false)));
+    // Still tracked, just not copied.
+    assert!(tracked_wallets().contains(&wallet.to_string()));
+}
+
+#[test]
+fn unsupported_commands_return_err() {
+    assert!(apply_command(Ctrl::PauseWallet {
+        wallet: WALLET.into(),
+        what: "maybe".into()
+    })
+    .is_err());
+}
+
+fn position(wallet: &str, mint: &str, size: f64, entry: f64) -> PositionState {
+    PositionState {
+        wallet: wallet.into(),
+        mint: mint.into(),
+        opened_ts: 100,
+        size: Some(size),
+        entry_price: Some(entry),
+        slippage_bps: None,
+        high_water_price: Some(entry),
+        status: PositionStatus::Open,
+        closed_ts: None,
+        exit_price: None,
+        simulated: false,
+    }
+}
+
+#[test]
+fn parses_positions_and_pnl_with_optional_wallet() {
+    match parse_command("/positions") {
+        Some(Ctrl::ListPositions { wallet: None }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match parse_command("/positions walletX") {
+        Some(Ctrl::ListPositions { wallet: Some(w) }) => assert_eq!(w, "walletX"),
+        other => panic!("unexpected {:?}", other),
+    }
+    match parse_command("  /pnl ") {
+        Some(Ctrl::Pnl { wallet: None }) => {}
+        other => panic!("unexpected {:?}", other),
+    }
+    match parse_command("/pnl walletX") {
+        Some(Ctrl::Pnl { wallet: Some(w) }) => assert_eq!(w, "walletX"),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn positions_reply_lists_wallet_positions() {
+    GLOBAL_POSITIONS_REGISTRY
+        .record_open("list-wallet", "list-mint-a", 10)
+        .unwrap();
+    GLOBAL_POSITIONS_REGISTRY
+        .record_open("list-wallet", "list-mint-b", 20)
+        .unwrap();
+
+    let reply = handle_positions(Some("list-wallet"));
+    assert!(reply.starts_with("Open positions (2):"));
+    let a = reply.find("list-mint-a opened=10").expect("mint a listed");
+    let b = reply.find("list-mint-b opened=20").expect("mint b listed");
+    assert!(a < b);
+
+    assert_eq!(handle_positions(Some("nobody")), "No open positions");
+}
+
+#[test]
+fn formats_size_and_entry() {
+    let reply = format_positions(&[position("w", "m", 1000.0, 0.5)]);
+    assert!(reply.contains("w m opened=100 size=1000 entry=0.5"));
+    assert!(!reply.contains("[sim]"));
+
+    let mut paper = position("w", "p", 1000.0, 0.5);
+    paper.simulated = true;
+    assert!(format_positions(&[paper]).ends_with("entry=0.5 [sim]"));
+}
+
+#[test]
+fn pnl_uses_price_lookup_and_skips_unknown_prices() {
+    let positions = vec![
+        position("w", "up", 100.0, 1.0),
+        position("w", "down", 10.0, 2.0),
+        position("w", "unpriced", 10.0, 2.0),
+    ];
+    let reply = format_pnl(&positions, |mint| match mint {
+        "up" => Some(1.5),
+        "down" => Some(1.0),
+        _ => None,
+    });
+
+    assert!(reply.starts_with("Unrealized PnL: 40.000000"));
+    assert!(reply.contains("w up pnl=50.000000 (+50.00%)"));
+    assert!(reply.contains("w down pnl=-10.000000 (-50.00%)"));
+    assert!(reply.contains("w unpriced pnl=n/a"));
+}
+
+#[test]
+fn malformed_numeric_args_are_rejected() {
+    assert!(parse_command("/slip walletX notanumber").is_none());
+    assert!(parse_command("/slip walletX -5").is_none());
+    assert!(parse_command("/tp walletX 5%").is_none());
+    assert!(parse_command("/tp walletX NaN").is_none());
+    assert!(parse_command("/sl walletX inf").is_none());
+    assert!(parse_command("/retries walletX 1.5").is_none());
+    assert!(parse_command("/maxpos walletX many").is_none());
+}
+
+#[test]
+fn well_formed_numeric_args_parse() {
+    match parse_command("/slip walletX 300") {
+        Some(Ctrl::SetSlippage { wallet, bps }) => {
+            assert_eq!(wallet, "walletX");
+            assert_eq!(bps, 300);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    match parse_command("/sl walletX 12.5") {
+        Some(Ctrl::SetSl { pct, .. }) => assert_eq!(pct, 12.5),
+        other => panic!("unexpected {:?}", other),
+    }
+    match parse_command("/maxpos walletX 4") {
+        Some(Ctrl::SetMaxPos { n, .. }) => assert_eq!(n, 4),
+        other => panic!("unexpected {:?}", other),
+    }
+}
@@ -0,0 +1,75 @@
+//! Tests for deterministic tie-breaking between simultaneous copy signals.
+
+use solana_vntr_sniper::universal::tiebreak::{resolve_signals, CopySignal};
+use solana_vntr_sniper::universal::wallet_config::reload_from_str;
+
+fn signal(wallet: &str, mint: &str, slot: u64, trust: f64) -> CopySignal {
+    CopySignal {
+        wallet: wallet.into(),
+        mint: mint.into(),
+        slot,
+        trust,
+    }
+}
+
+#[test]
+fn higher_trust_wallet_wins_single_slot_regardless_of_delivery_order() {
+    let low = signal("walletLow", "mintX", 100, 0.2);
+    let high = signal("walletHigh", "mintX", 100, 0.9);
+
+    for batch in [
+        vec![low.clone(), high.clone()],
+        vec![high.clone(), low.clone()],
+    ] {
+        let (execute, dropped) = resolve_signals(batch, 1);
+        assert_eq!(execute, vec![high.clone()]);
+        assert_eq!(dropped, vec![low.clone()]);
+    }
+}
+
+#[test]
+fn equal_trust_falls_back_to_earliest_slot_then_wallet() {
+    let (execute, _) = resolve_signals(
+        vec![
+            signal("walletB", "mintA", 101, 0.5),
+            signal("walletC", "mintB", 100, 0.5),
+            signal("walletA", "mintC", 101, 0.5),
+        ],
+        2,
+    );
+    let winners: Vec<&str> = execute.iter().map(|s| s.wallet.as_str()).collect();
+    assert_eq!(winners, vec!["walletC", "walletA"]);
+}
+
+#[test]
+fn only_one_copy_per_mint_even_with_free_slots() {
+    let (execute, dropped) = resolve_signals(
+        vec![
+            signal("walletA", "mintX", 100, 0.5),
+            signal("walletB", "mintX", 100, 0.4),
+        ],
+        5,
+    );
+    assert_eq!(execute.len(), 1);
+    assert_eq!(execute[0].wallet, "walletA");
+    assert_eq!(dropped.len(), 1);
+}
+
+#[test]
+fn trust_is_read_from_wallet_config() {
+    reload_from_str(
+        r#"
+[wallets.trustedWallet]
+trust = 0.8
+"#,
+    )
+    .unwrap();
+
+    let trusted = CopySignal::from_config("trustedWallet", "mintX", 100);
+    let unknown = CopySignal::from_config("unknownWallet", "mintX", 99);
+    assert_eq!(trusted.trust, 0.8);
+    assert_eq!(unknown.trust, 0.0);
+
+    let (execute, _) = resolve_signals(vec![unknown, trusted], 1);
+    assert_eq!(execute[0].wallet, "trustedWallet");
+}
@@ -0,0 +1,190 @@
+//! Tests for the take-profit / stop-loss monitor.
+
+use std::sync::{Arc, Mutex};
+
+use futures::future::BoxFuture;
+use solana_vntr_sniper::universal::positions::{PositionStatus, PositionsRegistry, SellCommitment};
+use solana_vntr_sniper::universal::tp_sl::{
+    check_exit, ExitExecutor, ExitSignal, ExitTrigger, TpSlConfig, TpSlMonitor,
+};
+
+#[test]
+fn check_exit_compares_against_entry_multiples() {
+    assert_eq!(
+        check_exit(1.0, 1.3, 1.25, 0.7, None),
+        Some((ExitTrigger::TakeProfit, 1.25))
+    );
+    assert_eq!(
+        check_exit(2.0, 1.4, 1.25, 0.7, None),
+        Some((ExitTrigger::StopLoss, 1.4))
+    );
+    assert_eq!(check_exit(1.0, 1.1, 1.25, 0.7, None), None);
+    // No entry, no decision.
+    assert_eq!(check_exit(0.0, 5.0, 1.25, 0.7, None), None);
+}
+
+#[test]
+fn check_exit_trails_the_high_water_price() {
+    // 10% below a 1.2 peak.
+    let (trigger, level) = check_exit(1.0, 1.08, 1.5, 0.7, Some((1.2, 10.0))).unwrap();
+    assert_eq!(trigger, ExitTrigger::TrailingStop);
+    assert!((level - 1.08).abs() < 1e-9);
+    assert_eq!(check_exit(1.0, 1.1, 1.5, 0.7, Some((1.2, 10.0))), None);
+    // The fixed stop-loss still wins below it.
+    assert_eq!(
+        check_exit(1.0, 0.6, 1.5, 0.7, Some((1.2, 10.0))),
+        Some((ExitTrigger::StopLoss, 0.7))
+    );
+}
+
+#[test]
+fn high_water_only_moves_up() {
+    let registry = PositionsRegistry::new();
+    registry.record_open("hwWallet", "mintA", 0).unwrap();
+    registry
+        .record_entry("hwWallet", "mintA", 1.0, None)
+        .unwrap();
+    assert_eq!(
+        registry.update_high_water("hwWallet", "mintA", 1.5),
+        Some(1.5)
+    );
+    assert_eq!(
+        registry.update_high_water("hwWallet", "mintA", 1.2),
+        Some(1.5)
+    );
+    assert_eq!(
+        registry
+            .get_open_position("hwWallet", "mintA")
+            .unwrap()
+            .high_water_price,
+        Some(1.5)
+    );
+    assert_eq!(registry.update_high_water("hwWallet", "missing", 2.0), None);
+}
+
+/// Records every close; fails mints starting with "fail" and never lands
+/// those starting with "drop".
+#[derive(Default)]
+struct RecordingExecutor {
+    closed: Mutex<Vec<(String, ExitTrigger)>>,
+}
+
+impl ExitExecutor for RecordingExecutor {
+    fn close<'a>(
+        &'a self,
+        signal: &'a ExitSignal,
+    ) -> BoxFuture<'a, Result<Option<SellCommitment>, String>> {
+        Box::pin(async move {
+            self.closed
+                .lock()
+                .unwrap()
+                .push((signal.mint.clone(), signal.trigger));
+            if signal.mint.starts_with("fail") {
+                Err("send failed".to_string())
+            } else if signal.mint.starts_with("drop") {
+                Ok(None)
+            } else {
+                Ok(Some(SellCommitment::Finalized))
+            }
+        })
+    }
+}
+
+fn monitor(registry: &'static PositionsRegistry, executor: Arc<RecordingExecutor>) -> TpSlMonitor {
+    let prices = |mint: &str| match mint {
+        "moon" => Some(2.0),
+        "dump" => Some(0.5),
+        "flat" | "noEntry" => Some(1.0),
+        "failMoon" | "dropMoon" => Some(3.0),
+        _ => None,
+    };
+    TpSlMonitor::new(registry, Arc::new(prices), executor, TpSlConfig::default())
+}
+
+#[tokio::test]
+async fn tick_closes_breached_positions_and_retries_failures() {
+    let registry: &'static PositionsRegistry = Box::leak(Box::new(PositionsRegistry::new()));
+    for mint in ["moon", "dump", "flat", "failMoon", "dropMoon", "unpriced"] {
+        registry.record_open("tpslWallet", mint, 0).unwrap();
+        registry
+            .record_entry("tpslWallet", mint, 1.0, None)
+            .unwrap();
+    }
+    registry.record_open("tpslWallet", "noEntry", 0).unwrap();
+
+    let executor = Arc::new(RecordingExecutor::default());
+    let monitor = monitor(registry, executor.clone());
+
+    let mut fired: Vec<(String, ExitTrigger)> = monitor
+        .tick()
+        .await
+        .into_iter()
+        .map(|s| (s.mint, s.trigger))
+        .collect();
+    fired.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        fired,
+        vec![
+            ("dropMoon".to_string(), ExitTrigger::TakeProfit),
+            ("dump".to_string(), ExitTrigger::StopLoss),
+            ("failMoon".to_string(), ExitTrigger::TakeProfit),
+            ("moon".to_string(), ExitTrigger::TakeProfit),
+        ]
+    );
+    assert_eq!(executor.closed.lock().unwrap().len(), 4);
+
+    assert!(!registry.has_open_position("tpslWallet", "moon"));
+    assert!(!registry.has_open_position("tpslWallet", "dump"));
+    for mint in ["flat", "noEntry", "unpriced"] {
+        assert!(registry.has_open_position("tpslWallet", mint));
+    }
+    // Failed and dropped exits are reopened so the next poll tries again.
+    for mint in ["failMoon", "dropMoon"] {
+        let pending = registry.get_open_position("tpslWallet", mint).unwrap();
+        assert_eq!(pending.status, PositionStatus::Open);
+    }
+    let mut retried: Vec<String> = monitor.tick().await.into_iter().map(|s| s.mint).collect();
+    retried.sort();
+    assert_eq!(retried, vec!["dropMoon", "failMoon"]);
+}
+
+#[tokio::test]
+async fn price_that_rises_then_retraces_hits_the_trailing_stop() {
+    let registry: &'static PositionsRegistry = Box::leak(Box::new(PositionsRegistry::new()));
+    registry.record_open("trailWallet", "runner", 0).unwrap();
+    registry
+        .record_entry("trailWallet", "runner", 1.0, None)
+        .unwrap();
+
+    let price = Arc::new(Mutex::new(1.0));
+    let feed = price.clone();
+    let prices = move |_: &str| Some(*feed.lock().unwrap());
+    let executor = Arc::new(RecordingExecutor::default());
+    let cfg = TpSlConfig {
+        tp: 3.0,
+        trailing_stop_pct: Some(10.0),
+        ..TpSlConfig::default()
+    };
+    let monitor = TpSlMonitor::new(registry, Arc::new(prices), executor.clone(), cfg);
+
+    // Climbs to 2.0 without crossing TP; each tick raises the high-water mark.
+    for p in [1.4, 2.0, 1.9] {
+        *price.lock().unwrap() = p;
+        assert!(monitor.tick().await.is_empty());
+    }
+    assert_eq!(
+        registry
+            .get_open_position("trailWallet", "runner")
+            .unwrap()
+            .high_water_price,
+        Some(2.0)
+    );
+
+    // 10% off the 2.0 peak, though still well above entry and SL.
+    *price.lock().unwrap() = 1.75;
+    let fired = monitor.tick().await;
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].trigger, ExitTrigger::TrailingStop);
+    assert!((fired[0].threshold - 1.8).abs() < 1e-9);
+    assert!(!registry.has_open_position("trailWallet", "runner"));
+}
@@ -0,0 +1,398 @@
+//! Tests for per-wallet configuration loading and hot-reload.
+
+use solana_vntr_sniper::common::config::{landing_mode_for_wallet, TransactionLandingMode};
+use solana_vntr_sniper::universal::telegram::{format_reload, parse_command, Ctrl};
+use solana_vntr_sniper::universal::wallet_config::types::DEFAULT_RETRIES;
+use solana_vntr_sniper::universal::wallet_config::{
+    get_wallet_config_map, reload_from_str, ConfigChange, CopyMode, WalletConfig, WalletConfigMap,
+};
+
+#[test]
+fn parses_wallet_overrides_from_toml() {
+    let map = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.walletA]
+        slippage = 0.02
+        max_positions = 2
+        "#,
+    )
+    .expect("valid toml");
+
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get_slippage("walletA"), Some(0.02));
+    assert_eq!(map.get_max_positions("walletA"), Some(2));
+    assert_eq!(map.get_tp("walletA"), None);
+}
+
+#[test]
+fn reload_swaps_map_and_bad_toml_keeps_previous() {
+    let n = reload_from_str(
+        r#"
+        [wallets.reloadWallet]
+        slippage = 0.05
+        "#,
+    )
+    .expect("valid toml reloads");
+    assert_eq!(n, 1);
+    assert_eq!(
+        get_wallet_config_map().get_slippage("reloadWallet"),
+        Some(0.05)
+    );
+
+    let err = reload_from_str("[wallets.reloadWallet\nslippage = ").unwrap_err();
+    assert!(err.contains("Failed to parse wallet config"));
+    // The previous map is still in place.
+    assert_eq!(
+        get_wallet_config_map().get_slippage("reloadWallet"),
+        Some(0.05)
+    );
+}
+
+#[test]
+fn reload_config_command_is_parsed() {
+    assert!(matches!(
+        parse_command("/reload_config"),
+        Some(Ctrl::ReloadConfig)
+    ));
+}
+
+#[test]
+fn loads_from_path_and_inline_env_wins() {
+    let path = std::env::temp_dir().join(format!("wallet_cfg_{}.toml", std::process::id()));
+    std::fs::write(&path, "[wallets.fileWallet]\nslippage = 0.03\n").unwrap();
+
+    std::env::remove_var("WALLET_CONFIG_TOML");
+    std::env::set_var("WALLET_CONFIG_PATH", &path);
+    let map = WalletConfigMap::from_env_or_empty();
+    assert_eq!(map.get_slippage("fileWallet"), Some(0.03));
+
+    std::env::set_var(
+        "WALLET_CONFIG_TOML",
+        "[wallets.inlineWallet]\nslippage = 0.01\n",
+    );
+    let map = WalletConfigMap::from_env_or_empty();
+    assert_eq!(map.get_slippage("inlineWallet"), Some(0.01));
+    assert_eq!(map.get_slippage("fileWallet"), None);
+
+    // A missing file falls back to empty instead of panicking.
+    std::env::remove_var("WALLET_CONFIG_TOML");
+    std::env::set_var("WALLET_CONFIG_PATH", path.with_extension("missing"));
+    assert!(WalletConfigMap::from_env_or_empty().is_empty());
+
+    std::env::remove_var("WALLET_CONFIG_PATH");
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn validate_rejects_out_of_range_slippage() {
+    let mut c = WalletConfig::empty();
+    c.slippage = Some(-0.5);
+    assert_eq!(c.validate(), vec!["slippage -0.5 not in (0.0, 1.0)"]);
+    c.slippage = Some(1.0);
+    assert_eq!(c.validate().len(), 1);
+}
+
+#[test]
+fn validate_rejects_tp_at_or_below_one() {
+    let mut c = WalletConfig::empty();
+    c.tp = Some(0.0);
+    assert_eq!(c.validate(), vec!["tp 0 must be > 1.0"]);
+    c.tp = Some(1.0);
+    assert_eq!(c.validate().len(), 1);
+}
+
+#[test]
+fn validate_rejects_out_of_range_sl() {
+    let mut c = WalletConfig::empty();
+    c.sl = Some(2.0);
+    assert_eq!(c.validate(), vec!["sl 2 not in (0.0, 1.0)"]);
+}
+
+#[test]
+fn validate_rejects_zero_max_positions() {
+    let mut c = WalletConfig::empty();
+    c.max_positions = Some(0);
+    assert_eq!(c.validate(), vec!["max_positions must be > 0"]);
+}
+
+#[test]
+fn validate_rejects_out_of_range_trailing_stop() {
+    let mut c = WalletConfig::empty();
+    c.trailing_stop_pct = Some(100.0);
+    assert_eq!(
+        c.validate(),
+        vec!["trailing_stop_pct 100 not in (0.0, 100.0)"]
+    );
+    c.trailing_stop_pct = Some(15.0);
+    assert!(c.validate().is_empty());
+}
+
+#[test]
+fn validate_rejects_non_positive_daily_loss_limit() {
+    let mut c = WalletConfig::empty();
+    c.max_daily_loss_usd = Some(0.0);
+    assert_eq!(c.validate(), vec!["max_daily_loss_usd 0 must be > 0.0"]);
+    c.max_daily_loss_usd = Some(200.0);
+    assert!(c.validate().is_empty());
+}
+
+#[test]
+fn validate_rejects_zero_retries_and_unknown_landing_mode() {
+    let mut c = WalletConfig::empty();
+    c.retries = Some(0);
+    c.landing_mode = Some("jito".into());
+    assert_eq!(
+        c.validate(),
+        vec![
+            "retries must be > 0",
+            r#"landing_mode "jito" not one of ["zeroslot", "normal"]"#,
+        ]
+    );
+    c.retries = Some(2);
+    c.landing_mode = Some("normal".into());
+    assert!(c.validate().is_empty());
+}
+
+#[test]
+fn invalid_wallet_entries_are_skipped_on_load() {
+    let map = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.good]
+        slippage = 0.02
+        tp = 1.25
+        sl = 0.2
+
+        [wallets.bad]
+        slippage = -0.5
+        "#,
+    )
+    .expect("valid toml");
+
+    assert!(map.get("good").is_some());
+    assert!(map.get("bad").is_none());
+}
+
+#[test]
+fn notional_usd_wins_over_pct_of_leader() {
+    let map = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.both]
+        notional_usd = 50.0
+        notional_pct_of_leader = 25.0
+
+        [wallets.pct]
+        notional_pct_of_leader = 25.0
+        "#,
+    )
+    .expect("valid toml");
+
+    let both = map.resolve_params_for_wallet("both", 0.02, 1.25, 0.2, None);
+    assert_eq!(both.notional_usd, Some(50.0));
+    assert_eq!(both.notional_pct_of_leader, None);
+
+    let pct = map.resolve_params_for_wallet("pct", 0.02, 1.25, 0.2, None);
+    assert_eq!(pct.notional_usd, None);
+    assert_eq!(pct.notional_pct_of_leader, Some(25.0));
+
+    let unknown = map.resolve_params_for_wallet("unknown", 0.02, 1.25, 0.2, None);
+    assert_eq!(unknown.notional_usd, None);
+    assert_eq!(unknown.notional_pct_of_leader, None);
+}
+
+#[test]
+fn retries_and_landing_mode_resolve_per_wallet() {
+    let map = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.custom]
+        retries = 5
+        landing_mode = "normal"
+
+        [wallets.plain]
+        slippage = 0.02
+        "#,
+    )
+    .expect("valid toml");
+
+    let custom = map.resolve_params_for_wallet("custom", 0.02, 1.25, 0.2, None);
+    assert_eq!(custom.retries, 5);
+    assert_eq!(custom.landing_mode.as_deref(), Some("normal"));
+
+    let plain = map.resolve_params_for_wallet("plain", 0.02, 1.25, 0.2, None);
+    assert_eq!(plain.retries, DEFAULT_RETRIES);
+    assert_eq!(plain.landing_mode, None);
+}
+
+#[test]
+fn copy_mode_resolves_per_wallet_and_defaults_to_all() {
+    let map = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.buyer]
+        copy_mode = "buys_only"
+
+        [wallets.seller]
+        copy_mode = "sells_only"
+
+        [wallets.inverse]
+        copy_mode = "inverse_sells"
+
+        [wallets.plain]
+        slippage = 0.02
+        "#,
+    )
+    .expect("valid toml");
+
+    let mode = |w: &str| {
+        map.resolve_params_for_wallet(w, 0.02, 1.25, 0.2, None)
+            .copy_mode
+    };
+    assert_eq!(mode("buyer"), CopyMode::BuysOnly);
+    assert_eq!(mode("seller"), CopyMode::SellsOnly);
+    assert_eq!(mode("inverse"), CopyMode::InverseSells);
+    assert_eq!(mode("plain"), CopyMode::All);
+    assert_eq!(mode("unknown"), CopyMode::All);
+    assert_eq!(map.get_copy_mode("buyer"), CopyMode::BuysOnly);
+    assert_eq!(CopyMode::InverseSells.to_string(), "inverse_sells");
+
+    assert!(WalletConfigMap::from_toml_str("[wallets.x]\ncopy_mode = \"mirror\"\n").is_err());
+}
+
+#[test]
+fn min_leader_trade_usd_resolves_and_validates() {
+    let map = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.picky]
+        min_leader_trade_usd = 25.0
+
+        [wallets.negative]
+        min_leader_trade_usd = -1.0
+        "#,
+    )
+    .expect("valid toml");
+
+    let picky = map.resolve_params_for_wallet("picky", 0.02, 1.25, 0.2, None);
+    assert_eq!(picky.min_leader_trade_usd, 25.0);
+    let plain = map.resolve_params_for_wallet("plain", 0.02, 1.25, 0.2, None);
+    assert_eq!(plain.min_leader_trade_usd, 0.0);
+    // Invalid entries are skipped.
+    assert!(map.get("negative").is_none());
+}
+
+#[test]
+fn landing_mode_falls_back_to_default_without_override() {
+    assert!(matches!(
+        landing_mode_for_wallet(None, TransactionLandingMode::Zeroslot),
+        TransactionLandingMode::Zeroslot
+    ));
+    assert!(matches!(
+        landing_mode_for_wallet(
+            Some("wallet-without-config"),
+            TransactionLandingMode::Normal
+        ),
+        TransactionLandingMode::Normal
+    ));
+}
+
+#[test]
+fn enabled_flag_defaults_to_true() {
+    let map = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.off]
+        enabled = false
+        slippage = 0.02
+
+        [wallets.on]
+        tp = 1.5
+        "#,
+    )
+    .unwrap();
+    assert!(!map.is_enabled("off"));
+    assert_eq!(map.get_slippage("off"), Some(0.02));
+    assert!(map.is_enabled("on"));
+    assert!(map.is_enabled("unknown"));
+
+    let on = WalletConfigMap::from_toml_str("[wallets.off]\nslippage = 0.02\n").unwrap();
+    assert_eq!(
+        map.diff(&on),
+        vec![
+            ConfigChange::FieldChanged {
+                wallet: "off".into(),
+                field: "enabled",
+                from: Some("false".into()),
+                to: None,
+            },
+            ConfigChange::WalletRemoved {
+                wallet: "on".into()
+            },
+        ]
+    );
+}
+
+#[test]
+fn diff_lists_added_removed_and_modified_fields() {
+    let old = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.keep]
+        slippage = 0.02
+        tp = 1.5
+        max_positions = 2
+
+        [wallets.gone]
+        sl = 0.5
+        "#,
+    )
+    .unwrap();
+    let new = WalletConfigMap::from_toml_str(
+        r#"
+        [wallets.keep]
+        slippage = 0.03
+        max_positions = 2
+        trust = 0.9
+
+        [wallets.fresh]
+        tp = 2.0
+        "#,
+    )
+    .unwrap();
+
+    let changes = old.diff(&new);
+    assert_eq!(
+        changes,
+        vec![
+            ConfigChange::WalletAdded {
+                wallet: "fresh".into()
+            },
+            ConfigChange::WalletRemoved {
+                wallet: "gone".into()
+            },
+            ConfigChange::FieldChanged {
+                wallet: "keep".into(),
+                field: "slippage",
+                from: Some("0.02".into()),
+                to: Some("0.03".into()),
+            },
+            ConfigChange::FieldChanged {
+                wallet: "keep".into(),
+                field: "tp",
+                from: Some("1.5".into()),
+                to: None,
+            },
+            ConfigChange::FieldChanged {
+                wallet: "keep".into(),
+                field: "trust",
+                from: None,
+                to: Some("0.9".into()),
+            },
+        ]
+    );
+    assert!(new.diff(&new).is_empty());
+
+    let reply = format_reload(2, &changes);
+    assert!(reply.starts_with("Wallet config reloaded (2 wallets)"));
+    assert!(reply.contains("\n+ wallet fresh"));
+    assert!(reply.contains("\n- wallet gone"));
+    assert!(reply.contains("\n~ keep.tp: 1.5 -> unset"));
+    assert_eq!(
+        format_reload(1, &[]),
+        "Wallet config reloaded (1 wallets), no changes"
+    );
+}
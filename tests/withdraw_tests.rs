@@ -0,0 +1,243 @@
+//! Tests for withdrawal planning and scheduling.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_vntr_sniper::common::quote::{USDC_MINT, WSOL_MINT};
+use solana_vntr_sniper::universal::dry_run::ExecutionMode;
+use solana_vntr_sniper::universal::positions::PositionsRegistry;
+use solana_vntr_sniper::universal::withdraw::{
+    plan_withdrawal, Holding, WithdrawAsset, WithdrawConfig, WithdrawalBuilder, WithdrawalLeg,
+    WithdrawalPlan, WithdrawalScheduler,
+};
+
+fn holding(mint: &str, amount: u64, value_usd: f64) -> Holding {
+    Holding {
+        mint: mint.to_string(),
+        amount,
+        decimals: 6,
+        value_usd,
+    }
+}
+
+fn cfg() -> WithdrawConfig {
+    WithdrawConfig {
+        destination: "vault".to_string(),
+        threshold_usd: 100.0,
+        interval_secs: 60,
+        ..WithdrawConfig::default()
+    }
+}
+
+#[test]
+fn nothing_is_planned_at_or_below_threshold() {
+    assert_eq!(
+        plan_withdrawal(&cfg(), &[holding(USDC_MINT, 100, 100.0)]),
+        None
+    );
+    assert_eq!(plan_withdrawal(&cfg(), &[]), None);
+}
+
+#[test]
+fn usdc_balance_is_transferred_without_a_swap() {
+    let plan = plan_withdrawal(&cfg(), &[holding(USDC_MINT, 250_000_000, 250.0)]).unwrap();
+    assert_eq!(
+        plan.legs,
+        vec![WithdrawalLeg::Transfer {
+            mint: USDC_MINT.to_string(),
+            amount: 250_000_000,
+            decimals: 6,
+        }]
+    );
+    assert_eq!(plan.destination, "vault");
+}
+
+#[test]
+fn other_tokens_are_swapped_and_empty_balances_skipped() {
+    let cfg = WithdrawConfig {
+        output: WithdrawAsset::Usdc,
+        ..cfg()
+    };
+    let plan = plan_withdrawal(
+        &cfg,
+        &[
+            holding(WSOL_MINT, 1_000_000_000, 150.0),
+            holding("memeMint", 42, 60.0),
+            holding("dustMint", 0, 0.0),
+        ],
+    )
+    .unwrap();
+    assert_eq!(plan.output, WithdrawAsset::Usdc);
+    assert!((plan.value_usd - 210.0).abs() < 1e-9);
+    assert_eq!(plan.legs.len(), 2);
+    // Swaps go first; SOL is withdrawn last.
+    assert_eq!(
+        plan.legs[0],
+        WithdrawalLeg::Swap {
+            mint: "memeMint".to_string(),
+            amount: 42,
+        }
+    );
+}
+
+#[test]
+fn retained_value_stays_in_sol() {
+    let cfg = WithdrawConfig {
+        retain_usd: 50.0,
+        ..cfg()
+    };
+    let plan = plan_withdrawal(
+        &cfg,
+        &[
+            holding(WSOL_MINT, 1_000_000_000, 100.0),
+            holding(USDC_MINT, 80_000_000, 80.0),
+        ],
+    )
+    .unwrap();
+    // 180 held, 130 withdrawable: all the USDC and half the SOL.
+    assert!((plan.value_usd - 130.0).abs() < 1e-9);
+    assert_eq!(
+        plan.legs,
+        vec![
+            WithdrawalLeg::Transfer {
+                mint: USDC_MINT.to_string(),
+                amount: 80_000_000,
+                decimals: 6,
+            },
+            WithdrawalLeg::Transfer {
+                mint: WSOL_MINT.to_string(),
+                amount: 500_000_000,
+                decimals: 6,
+            },
+        ]
+    );
+}
+
+#[test]
+fn nothing_withdrawable_after_retention_is_a_no_op() {
+    let holdings = [holding(USDC_MINT, 150_000_000, 150.0)];
+    for retain_usd in [150.0, 500.0] {
+        let cfg = WithdrawConfig {
+            retain_usd,
+            ..cfg()
+        };
+        assert_eq!(plan_withdrawal(&cfg, &holdings), None);
+    }
+}
+
+/// One empty transaction per leg.
+#[derive(Default)]
+struct StubBuilder {
+    built: Mutex<usize>,
+}
+
+impl WithdrawalBuilder for StubBuilder {
+    fn build<'a>(
+        &'a self,
+        plan: &'a WithdrawalPlan,
+    ) -> BoxFuture<'a, Result<Vec<VersionedTransaction>>> {
+        *self.built.lock().unwrap() += 1;
+        let n = plan.legs.len();
+        Box::pin(async move { Ok(vec![VersionedTransaction::default(); n]) })
+    }
+}
+
+#[tokio::test]
+async fn tick_sends_each_transaction_and_respects_the_interval() {
+    let mut scheduler = WithdrawalScheduler::new(cfg()).with_mode(ExecutionMode::Live);
+    let builder = StubBuilder::default();
+    let holdings = [holding(USDC_MINT, 1, 80.0), holding("memeMint", 1, 80.0)];
+    let start = Instant::now();
+    let mut sent = 0;
+
+    let plan = scheduler
+        .tick(start, &holdings, &builder, |_tx| {
+            sent += 1;
+            async { Ok("sig".to_string()) }
+        })
+        .await
+        .unwrap();
+    assert!(plan.is_some());
+    assert_eq!(sent, 2);
+    assert_eq!(scheduler.last(), Some(start));
+
+    // Not due yet: nothing is built.
+    let early = start + Duration::from_secs(30);
+    let plan = scheduler
+        .tick(early, &holdings, &builder, |_tx| async {
+            Ok("sig".to_string())
+        })
+        .await
+        .unwrap();
+    assert!(plan.is_none());
+    assert_eq!(*builder.built.lock().unwrap(), 1);
+
+    // Due again, but below threshold: the timer still advances.
+    let later = start + Duration::from_secs(60);
+    let plan = scheduler
+        .tick(later, &holdings[..1], &builder, |_tx| async {
+            Ok("sig".to_string())
+        })
+        .await
+        .unwrap();
+    assert!(plan.is_none());
+    assert_eq!(scheduler.last(), Some(later));
+    assert_eq!(*builder.built.lock().unwrap(), 1);
+}
+
+#[tokio::test]
+async fn open_positions_block_withdrawals() {
+    let registry: &'static PositionsRegistry = Box::leak(Box::new(PositionsRegistry::new()));
+    registry.record_open("me", "heldMint", 0).unwrap();
+    let mut scheduler = WithdrawalScheduler::new(cfg())
+        .with_mode(ExecutionMode::Live)
+        .with_positions(registry);
+    let builder = StubBuilder::default();
+    let holdings = [holding(USDC_MINT, 1, 500.0)];
+
+    let plan = scheduler
+        .tick(Instant::now(), &holdings, &builder, |_tx| async {
+            Ok("sig".to_string())
+        })
+        .await
+        .unwrap();
+    assert!(plan.is_none());
+    assert_eq!(*builder.built.lock().unwrap(), 0);
+
+    registry.record_close("me", "heldMint").unwrap();
+    let later = Instant::now() + Duration::from_secs(60);
+    let plan = scheduler
+        .tick(later, &holdings, &builder, |_tx| async {
+            Ok("sig".to_string())
+        })
+        .await
+        .unwrap();
+    assert!(plan.is_some());
+}
+
+#[tokio::test]
+async fn dry_run_logs_without_sending_but_advances_the_timer() {
+    for mode in [ExecutionMode::DryRun, ExecutionMode::Simulate] {
+        let mut scheduler = WithdrawalScheduler::new(cfg()).with_mode(mode);
+        let builder = StubBuilder::default();
+        let holdings = [holding(USDC_MINT, 1, 500.0)];
+        let now = Instant::now();
+        let mut called = false;
+
+        let plan = scheduler
+            .tick(now, &holdings, &builder, |_tx| {
+                called = true;
+                async { Ok("sig".to_string()) }
+            })
+            .await
+            .unwrap();
+        assert!(plan.is_some(), "{:?} still reports the plan", mode);
+        assert!(!called, "{:?} must not withdraw", mode);
+        assert_eq!(*builder.built.lock().unwrap(), 0);
+        assert_eq!(scheduler.last(), Some(now));
+        assert!(!scheduler.is_due(now + Duration::from_secs(30)));
+    }
+}